@@ -0,0 +1,323 @@
+// Copyright 2019-2022 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use moonbeam_rpc_primitives_xcm::{XcAssetRegistryApi, XcmDryRunApi, XcmFeeApi};
+use parity_scale_codec::{Decode, Encode};
+use serde::Serialize;
+use sp_api::{BlockId, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_core::{Bytes, H160, H256, U256};
+use sp_runtime::traits::Block;
+use std::{marker::PhantomData, sync::Arc};
+use xcm::{VersionedMultiLocation, VersionedXcm};
+
+/// Weight and fee that xcm-transactor would use to Transact in a destination
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactFee {
+	pub weight: u64,
+	pub fee: U256,
+}
+
+/// Cost of executing an inbound message in a fee asset
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InboundFee {
+	/// SCALE-encoded VersionedMultiLocation of the fee asset
+	pub asset: Bytes,
+	pub amount: U256,
+}
+
+/// A message that would have been forwarded by a dry-run
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForwardedMessage {
+	/// SCALE-encoded VersionedMultiLocation of the destination
+	pub destination: Bytes,
+	/// SCALE-encoded VersionedXcm
+	pub message: Bytes,
+}
+
+/// Effects of executing an XCM message, none of which are persisted
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct XcmDryRunEffects {
+	pub weight_used: u64,
+	/// SCALE-encoded XCM Outcome
+	pub outcome: Bytes,
+	/// SCALE-encoded runtime events
+	pub events: Vec<Bytes>,
+	pub forwarded_messages: Vec<ForwardedMessage>,
+}
+
+/// An XC-20 registered in asset-manager
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct XcAsset {
+	pub asset_id: U256,
+	/// SCALE-encoded VersionedMultiLocation of the asset, for foreign assets
+	pub location: Option<Bytes>,
+	/// Address of the ERC-20 precompile of the asset
+	pub address: H160,
+	pub name: String,
+	pub symbol: String,
+	pub decimals: u8,
+	pub is_sufficient: bool,
+	/// Whether the asset is accepted to pay for the execution of inbound messages
+	pub pays_fees: bool,
+	pub units_per_second: Option<U256>,
+}
+
+/// Maximum number of assets returned in a single page
+const MAX_ASSETS_PER_PAGE: u32 = 1000;
+
+/// An RPC endpoint to estimate the fees and effects of XCM messages sent from and to Moonbeam,
+/// and to list the XC-20s it knows about
+#[rpc(server)]
+pub trait MoonbeamXcmApi {
+	/// Returns the weight and fee that xcm-transactor would use to Transact a call of weight
+	/// `transact_weight` in `dest`, paying the fee with `fee_asset`. Both locations are given as
+	/// SCALE-encoded VersionedMultiLocation.
+	#[method(name = "xcm_transactFee")]
+	fn transact_fee(
+		&self,
+		dest: Bytes,
+		fee_asset: Bytes,
+		transact_weight: u64,
+		signed: bool,
+		at: Option<H256>,
+	) -> RpcResult<TransactFee>;
+
+	/// Returns the cost of executing an inbound message of weight `weight` in each of the
+	/// supported fee assets.
+	#[method(name = "xcm_inboundFees")]
+	fn inbound_fees(&self, weight: u64, at: Option<H256>) -> RpcResult<Vec<InboundFee>>;
+
+	/// Executes `message` from `origin`, buying at most `weight_limit`, and returns its effects
+	/// without persisting them. The origin is given as a SCALE-encoded VersionedMultiLocation
+	/// and the message as a SCALE-encoded VersionedXcm.
+	#[method(name = "xcm_dryRun")]
+	fn dry_run(
+		&self,
+		origin: Bytes,
+		message: Bytes,
+		weight_limit: u64,
+		at: Option<H256>,
+	) -> RpcResult<XcmDryRunEffects>;
+
+	/// Returns at most `limit` foreign XC-20s, starting after the asset with id `start_after`
+	/// if given. Pages are requested by passing the id of the last asset of the previous one.
+	#[method(name = "xcm_foreignAssets")]
+	fn foreign_assets(
+		&self,
+		start_after: Option<U256>,
+		limit: u32,
+		at: Option<H256>,
+	) -> RpcResult<Vec<XcAsset>>;
+
+	/// Returns at most `limit` local XC-20s, paginated as xcm_foreignAssets.
+	#[method(name = "xcm_localAssets")]
+	fn local_assets(
+		&self,
+		start_after: Option<U256>,
+		limit: u32,
+		at: Option<H256>,
+	) -> RpcResult<Vec<XcAsset>>;
+}
+
+pub struct MoonbeamXcm<B: Block, C> {
+	pub client: Arc<C>,
+	_phdata: PhantomData<B>,
+}
+
+impl<B: Block, C> MoonbeamXcm<B, C> {
+	pub fn new(client: Arc<C>) -> Self {
+		Self {
+			client,
+			_phdata: Default::default(),
+		}
+	}
+}
+
+impl<B, C> MoonbeamXcmApiServer for MoonbeamXcm<B, C>
+where
+	B: Block<Hash = H256>,
+	C: ProvideRuntimeApi<B> + HeaderBackend<B> + Send + Sync + 'static,
+	C::Api: XcmFeeApi<B> + XcmDryRunApi<B> + XcAssetRegistryApi<B>,
+{
+	fn transact_fee(
+		&self,
+		dest: Bytes,
+		fee_asset: Bytes,
+		transact_weight: u64,
+		signed: bool,
+		at: Option<H256>,
+	) -> RpcResult<TransactFee> {
+		let dest = decode_location(dest)?;
+		let fee_asset = decode_location(fee_asset)?;
+
+		let fee = self
+			.client
+			.runtime_api()
+			.transact_fee(&self.block_id(at), dest, fee_asset, transact_weight, signed)
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
+			.map_err(|err| internal_err(format!("cannot estimate transact fee: {:?}", err)))?;
+
+		Ok(TransactFee {
+			weight: fee.weight,
+			fee: fee.fee.into(),
+		})
+	}
+
+	fn inbound_fees(&self, weight: u64, at: Option<H256>) -> RpcResult<Vec<InboundFee>> {
+		let fees = self
+			.client
+			.runtime_api()
+			.inbound_fees(&self.block_id(at), weight)
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?;
+
+		Ok(fees
+			.into_iter()
+			.map(|fee| InboundFee {
+				asset: fee.asset.encode().into(),
+				amount: fee.amount.into(),
+			})
+			.collect())
+	}
+
+	fn dry_run(
+		&self,
+		origin: Bytes,
+		message: Bytes,
+		weight_limit: u64,
+		at: Option<H256>,
+	) -> RpcResult<XcmDryRunEffects> {
+		let origin = decode_location(origin)?;
+		let message = VersionedXcm::<()>::decode(&mut &message[..])
+			.map_err(|err| internal_err(format!("invalid message: {:?}", err)))?;
+
+		let effects = self
+			.client
+			.runtime_api()
+			.dry_run_xcm(&self.block_id(at), origin, message, weight_limit)
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
+			.map_err(|err| internal_err(format!("cannot dry-run message: {:?}", err)))?;
+
+		Ok(XcmDryRunEffects {
+			weight_used: effects.weight_used,
+			outcome: effects.outcome.encode().into(),
+			events: effects.events.into_iter().map(Into::into).collect(),
+			forwarded_messages: effects
+				.forwarded_messages
+				.into_iter()
+				.map(|(destination, message)| ForwardedMessage {
+					destination: destination.encode().into(),
+					message: message.encode().into(),
+				})
+				.collect(),
+		})
+	}
+
+	fn foreign_assets(
+		&self,
+		start_after: Option<U256>,
+		limit: u32,
+		at: Option<H256>,
+	) -> RpcResult<Vec<XcAsset>> {
+		let start_after = start_after.map(decode_asset_id).transpose()?;
+
+		let assets = self
+			.client
+			.runtime_api()
+			.foreign_assets(
+				&self.block_id(at),
+				start_after,
+				limit.min(MAX_ASSETS_PER_PAGE),
+			)
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?;
+
+		Ok(assets.into_iter().map(Into::into).collect())
+	}
+
+	fn local_assets(
+		&self,
+		start_after: Option<U256>,
+		limit: u32,
+		at: Option<H256>,
+	) -> RpcResult<Vec<XcAsset>> {
+		let start_after = start_after.map(decode_asset_id).transpose()?;
+
+		let assets = self
+			.client
+			.runtime_api()
+			.local_assets(
+				&self.block_id(at),
+				start_after,
+				limit.min(MAX_ASSETS_PER_PAGE),
+			)
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?;
+
+		Ok(assets.into_iter().map(Into::into).collect())
+	}
+}
+
+impl<B, C> MoonbeamXcm<B, C>
+where
+	B: Block<Hash = H256>,
+	C: HeaderBackend<B>,
+{
+	/// The block to run the runtime api at, defaulting to the best one
+	fn block_id(&self, at: Option<H256>) -> BlockId<B> {
+		BlockId::Hash(at.unwrap_or_else(|| self.client.info().best_hash))
+	}
+}
+
+impl From<moonbeam_rpc_primitives_xcm::XcAsset> for XcAsset {
+	fn from(asset: moonbeam_rpc_primitives_xcm::XcAsset) -> Self {
+		Self {
+			asset_id: asset.asset_id.into(),
+			location: asset.location.map(|location| location.encode().into()),
+			address: asset.address,
+			name: String::from_utf8_lossy(&asset.name).into_owned(),
+			symbol: String::from_utf8_lossy(&asset.symbol).into_owned(),
+			decimals: asset.decimals,
+			is_sufficient: asset.is_sufficient,
+			pays_fees: asset.units_per_second.is_some(),
+			units_per_second: asset.units_per_second.map(Into::into),
+		}
+	}
+}
+
+fn decode_asset_id(asset_id: U256) -> RpcResult<u128> {
+	u128::try_from(asset_id).map_err(|_| internal_err("invalid asset id"))
+}
+
+fn decode_location(location: Bytes) -> RpcResult<VersionedMultiLocation> {
+	VersionedMultiLocation::decode(&mut &location[..])
+		.map_err(|err| internal_err(format!("invalid location: {:?}", err)))
+}
+
+// This bit cribbed from frontier.
+pub fn internal_err<T: AsRef<str>>(message: T) -> jsonrpsee::core::Error {
+	jsonrpsee::core::Error::Call(jsonrpsee::types::error::CallError::Custom(
+		jsonrpsee::types::error::ErrorObject::borrowed(
+			jsonrpsee::types::error::INTERNAL_ERROR_CODE,
+			&message,
+			None,
+		)
+		.into_owned(),
+	))
+}
@@ -43,6 +43,9 @@ pub trait RuntimeApiCollection:
 	+ fp_rpc::EthereumRuntimeRPCApi<Block>
 	+ moonbeam_rpc_primitives_debug::DebugRuntimeApi<Block>
 	+ moonbeam_rpc_primitives_txpool::TxPoolRuntimeApi<Block>
+	+ moonbeam_rpc_primitives_xcm::XcmFeeApi<Block>
+	+ moonbeam_rpc_primitives_xcm::XcmDryRunApi<Block>
+	+ moonbeam_rpc_primitives_xcm::XcAssetRegistryApi<Block>
 	+ nimbus_primitives::NimbusApi<Block>
 	+ nimbus_primitives::AuthorFilterAPI<Block, nimbus_primitives::NimbusId>
 	+ cumulus_primitives_core::CollectCollationInfo<Block>
@@ -66,6 +69,9 @@ where
 		+ fp_rpc::EthereumRuntimeRPCApi<Block>
 		+ moonbeam_rpc_primitives_debug::DebugRuntimeApi<Block>
 		+ moonbeam_rpc_primitives_txpool::TxPoolRuntimeApi<Block>
+		+ moonbeam_rpc_primitives_xcm::XcmFeeApi<Block>
+		+ moonbeam_rpc_primitives_xcm::XcmDryRunApi<Block>
+		+ moonbeam_rpc_primitives_xcm::XcAssetRegistryApi<Block>
 		+ nimbus_primitives::NimbusApi<Block>
 		+ nimbus_primitives::AuthorFilterAPI<Block, nimbus_primitives::NimbusId>
 		+ cumulus_primitives_core::CollectCollationInfo<Block>
@@ -158,6 +158,7 @@ where
 	use moonbeam_rpc_debug::{Debug, DebugServer};
 	use moonbeam_rpc_trace::{Trace, TraceServer};
 	use moonbeam_rpc_txpool::{TxPool, TxPoolServer};
+	use moonbeam_rpc_xcm::{MoonbeamXcm, MoonbeamXcmApiServer};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
 	use substrate_frame_rpc_system::{System, SystemApiServer};
 
@@ -257,6 +258,7 @@ where
 	}
 
 	io.merge(MoonbeamFinality::new(client.clone(), frontier_backend.clone()).into_rpc())?;
+	io.merge(MoonbeamXcm::new(client.clone()).into_rpc())?;
 
 	if let Some(command_sink) = command_sink {
 		io.merge(
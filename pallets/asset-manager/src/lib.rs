@@ -27,10 +27,17 @@
 //! AssetTypeId holds a mapping from AssetType -> AssetId. LocalAssetCounter
 //! which holds the counter of local assets that have been created so far. And LocalAssetDeposit,
 //! which holds a mapping between assetId and assetInfo, i.e., the asset creator (from which
-//! we take the deposit) and the deposit amount itself.
+//! we take the deposit) and the deposit amount itself. LocalAssetsBeingDestroyed holds the
+//! local assets whose owner started their destruction, together with the last account scanned
+//! by burn_local_asset_accounts. FeePaymentAssetPreference holds the
+//! supported asset each account chose to pay the fees of its Ethereum transactions with.
 //!
-//! This pallet has eight extrinsics: register_foreign_asset, which registers a foreign
+//! This pallet has sixteen extrinsics: register_foreign_asset, which registers a foreign
 //! asset in this pallet and creates the asset as dictated by the AssetRegistrar trait.
+//! register_sibling_foreign_asset: which registers a non-sufficient foreign asset from its
+//! reserve chain
+//! register_foreign_asset_with_deposit: which registers a non-sufficient foreign asset
+//! reserving a deposit from the caller
 //! set_asset_units_per_second: which sets the unit per second that should be charged for
 //! a particular asset.
 //! change_existing_asset_type: which allows to update the correspondence between AssetId and
@@ -40,6 +47,26 @@
 //! register_local_asset: which creates a local asset with a specific owner
 //! destroy_foreign_asset: which destroys a foreign asset and all its associated data
 //! destroy_local_asset: which destroys a local asset and all its associated data
+//! transfer_local_asset_ownership: which transfers the ownership of a local asset, together
+//! with its deposit if the owner holds it
+//! start_local_asset_destruction: which lets the owner of a local asset freeze it to destroy it
+//! burn_local_asset_accounts: which burns the balances of a batch of accounts of a local asset
+//! being destroyed
+//! finish_local_asset_destruction: which destroys a local asset being destroyed, releasing its
+//! deposit
+//! set_fee_payment_asset_preference: which lets an account pay the fees of its Ethereum
+//! transactions with one of the assets supported for fee payment
+//! promote_foreign_asset_to_sufficient: which makes a non-sufficient foreign asset sufficient
+//!
+//! Non-sufficient foreign assets can also be registered without governance, either by the
+//! reserve chain itself through register_sibling_foreign_asset, or by any account reserving
+//! ForeignAssetDeposit through register_foreign_asset_with_deposit. Governance can later make
+//! these assets fee-paying with set_asset_units_per_second or sufficient with
+//! promote_foreign_asset_to_sufficient, which return the deposit, or remove them, which slashes
+//! it. As the assetId is derived from the asset location, anyone could register the location of
+//! an asset before its reserve. Assets registered with a deposit can therefore be registered
+//! again by their reserve or by governance, which overrides their parameters and returns the
+//! deposit.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -60,13 +87,14 @@ pub mod pallet {
 	use crate::weights::WeightInfo;
 	use frame_support::{
 		pallet_prelude::*,
-		traits::{Currency, ReservableCurrency},
+		traits::{BalanceStatus, Currency, ReservableCurrency},
 		PalletId,
 	};
 	use frame_system::pallet_prelude::*;
 	use parity_scale_codec::HasCompact;
 	use sp_runtime::traits::{AccountIdConversion, AtLeast32BitUnsigned};
 	use sp_std::vec::Vec;
+	use xcm::latest::MultiLocation;
 
 	#[pallet::pallet]
 	#[pallet::without_storage_info]
@@ -111,6 +139,21 @@ pub mod pallet {
 			unimplemented!()
 		}
 
+		// How to override the parameters of an existing foreign asset
+		fn update_foreign_asset(
+			_asset: T::AssetId,
+			_min_balance: T::Balance,
+			_metadata: T::AssetRegistrarMetadata,
+			_is_sufficient: bool,
+		) -> DispatchResult {
+			unimplemented!()
+		}
+
+		// How to make an existing foreign asset sufficient
+		fn make_foreign_asset_sufficient(_asset: T::AssetId) -> DispatchResult {
+			unimplemented!()
+		}
+
 		// How to destroy a foreign asset
 		fn destroy_foreign_asset(
 			_asset: T::AssetId,
@@ -132,6 +175,46 @@ pub mod pallet {
 			_asset: T::AssetId,
 			_witness: T::AssetDestroyWitness,
 		) -> Weight;
+
+		// The current owner of a local asset
+		fn local_asset_owner(_asset: T::AssetId) -> Option<T::AccountId> {
+			unimplemented!()
+		}
+
+		// How to transfer the ownership of a local asset
+		fn transfer_local_asset_ownership(
+			_asset: T::AssetId,
+			_owner: T::AccountId,
+		) -> DispatchResult {
+			unimplemented!()
+		}
+
+		// How to freeze a local asset, so that its balances cannot be moved anymore
+		fn freeze_local_asset(_asset: T::AssetId) -> DispatchResult {
+			unimplemented!()
+		}
+
+		// How to burn the balances of the accounts of a frozen local asset, scanning at most
+		// max_accounts accounts after start_after
+		// Returns the number of accounts whose balance was burnt and the last account scanned,
+		// which is None if every remaining account was scanned
+		fn burn_local_asset_accounts(
+			_asset: T::AssetId,
+			_max_accounts: u32,
+			_start_after: Option<T::AccountId>,
+		) -> Result<(u32, Option<T::AccountId>), DispatchError> {
+			unimplemented!()
+		}
+
+		// The witness needed to destroy a local asset in its current state
+		fn local_asset_destroy_witness(_asset: T::AssetId) -> Option<T::AssetDestroyWitness> {
+			unimplemented!()
+		}
+
+		// Get burn local asset accounts weight
+		fn burn_local_asset_accounts_weight(_max_accounts: u32) -> Weight {
+			unimplemented!()
+		}
 	}
 
 	// The local asset id creator. We cannot let users choose assetIds for their assets
@@ -168,6 +251,26 @@ pub mod pallet {
 		}
 	}
 
+	impl<T: Config> xcm_primitives::UpdateFeePerSecond for Pallet<T>
+	where
+		T::ForeignAssetType: From<MultiLocation>,
+	{
+		fn update_fee_per_second(location: &MultiLocation, fee_per_second: u128) -> bool {
+			let asset_type: T::ForeignAssetType = location.clone().into();
+			if !AssetTypeUnitsPerSecond::<T>::contains_key(&asset_type) {
+				return false;
+			}
+
+			AssetTypeUnitsPerSecond::<T>::insert(&asset_type, &fee_per_second);
+
+			Self::deposit_event(Event::UnitsPerSecondChanged {
+				asset_type,
+				units_per_second: fee_per_second,
+			});
+			true
+		}
+	}
+
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
@@ -180,7 +283,13 @@ pub mod pallet {
 		type AssetRegistrarMetadata: Member + Parameter + Default;
 
 		/// The Foreign Asset Kind.
-		type ForeignAssetType: Parameter + Member + Ord + PartialOrd + Into<Self::AssetId> + Default;
+		type ForeignAssetType: Parameter
+			+ Member
+			+ Ord
+			+ PartialOrd
+			+ Into<Self::AssetId>
+			+ Into<Option<MultiLocation>>
+			+ Default;
 
 		/// The units in which we record balances.
 		type Balance: Member + Parameter + AtLeast32BitUnsigned + Default + Copy + MaxEncodedLen;
@@ -192,6 +301,10 @@ pub mod pallet {
 		/// Origin that is allowed to create and modify asset information for foreign assets
 		type ForeignAssetModifierOrigin: EnsureOrigin<Self::Origin>;
 
+		/// Origin of the reserve chains that are allowed to register their own assets.
+		/// The asset location must be contained in the one of the origin
+		type SiblingAssetRegistrarOrigin: EnsureOrigin<Self::Origin, Success = MultiLocation>;
+
 		/// Origin that is allowed to create and modify asset information for local assets
 		type LocalAssetModifierOrigin: EnsureOrigin<Self::Origin>;
 
@@ -208,6 +321,11 @@ pub mod pallet {
 		#[pallet::constant]
 		type LocalAssetDeposit: Get<DepositBalanceOf<Self>>;
 
+		/// The amount of funds that must be reserved to register a foreign asset without
+		/// governance.
+		#[pallet::constant]
+		type ForeignAssetDeposit: Get<DepositBalanceOf<Self>>;
+
 		type WeightInfo: WeightInfo;
 	}
 
@@ -222,6 +340,15 @@ pub mod pallet {
 		ErrorDestroyingAsset,
 		NotSufficientDeposit,
 		NonExistentLocalAsset,
+		NotAssetReserve,
+		NotLocalAssetOwner,
+		LocalAssetDestructionAlreadyStarted,
+		LocalAssetDestructionNotStarted,
+		ErrorTransferringAssetOwnership,
+		ErrorFreezingAsset,
+		ErrorBurningAssetAccounts,
+		FeePaymentAssetNotSupported,
+		ErrorUpdatingAsset,
 	}
 
 	#[pallet::event]
@@ -263,6 +390,39 @@ pub mod pallet {
 		},
 		/// Removed all information related to an assetId and destroyed asset
 		LocalAssetDestroyed { asset_id: T::AssetId },
+		/// The ownership of a local asset was transferred, along with the deposit if the
+		/// previous owner held it
+		LocalAssetOwnershipTransferred {
+			asset_id: T::AssetId,
+			owner: T::AccountId,
+			depositor: Option<T::AccountId>,
+		},
+		/// A local asset was frozen by its owner to be destroyed
+		LocalAssetDestructionStarted { asset_id: T::AssetId },
+		/// The balances of some accounts of a local asset being destroyed were burnt
+		LocalAssetAccountsBurnt { asset_id: T::AssetId, accounts: u32 },
+		/// The deposit of a foreign asset was returned to its creator
+		ForeignAssetDepositReleased {
+			asset_id: T::AssetId,
+			creator: T::AccountId,
+			deposit: DepositBalanceOf<T>,
+		},
+		/// The deposit of a removed foreign asset was slashed
+		ForeignAssetDepositSlashed {
+			asset_id: T::AssetId,
+			creator: T::AccountId,
+			deposit: DepositBalanceOf<T>,
+		},
+		/// An account changed the asset it pays the fees of its Ethereum transactions with
+		FeePaymentAssetPreferenceSet {
+			account: T::AccountId,
+			asset_type: Option<T::ForeignAssetType>,
+		},
+		/// A foreign asset was made sufficient
+		ForeignAssetPromotedToSufficient {
+			asset_id: T::AssetId,
+			asset_type: T::ForeignAssetType,
+		},
 	}
 
 	/// Mapping from an asset id to asset type.
@@ -308,16 +468,44 @@ pub mod pallet {
 	pub type LocalAssetDeposit<T: Config> =
 		StorageMap<_, Blake2_128Concat, T::AssetId, AssetInfo<T>>;
 
+	/// Foreign asset deposits, a mapping from assetId to a struct
+	/// holding the account that registered the asset without governance
+	/// and the deposit it reserved
+	#[pallet::storage]
+	#[pallet::getter(fn foreign_asset_deposit)]
+	pub type ForeignAssetDeposit<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AssetId, AssetInfo<T>>;
+
+	/// Local assets whose owner started their destruction. These assets are frozen,
+	/// and their accounts can be burnt in batches before destroying them. Each batch resumes
+	/// the scan of the accounts after the last account scanned by the previous one
+	#[pallet::storage]
+	#[pallet::getter(fn local_asset_being_destroyed)]
+	pub type LocalAssetsBeingDestroyed<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AssetId, Option<T::AccountId>, OptionQuery>;
+
 	// Supported fee asset payments
 	#[pallet::storage]
 	#[pallet::getter(fn supported_fee_payment_assets)]
 	pub type SupportedFeePaymentAssets<T: Config> =
 		StorageValue<_, Vec<T::ForeignAssetType>, ValueQuery>;
 
+	/// The asset an account pays the fees of its Ethereum transactions with, instead of the
+	/// native currency. It is ignored while the asset is not supported for fee payment.
+	#[pallet::storage]
+	#[pallet::getter(fn fee_payment_asset_preference)]
+	pub type FeePaymentAssetPreference<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, T::ForeignAssetType>;
+
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Register new asset with the asset manager
-		#[pallet::weight(T::WeightInfo::register_foreign_asset())]
+		/// If the asset was registered with a deposit, its parameters are overridden and the
+		/// deposit is returned to its creator
+		#[pallet::weight(
+			T::WeightInfo::register_foreign_asset()
+				.saturating_add(T::DbWeight::get().reads_writes(2, 2))
+		)]
 		pub fn register_foreign_asset(
 			origin: OriginFor<T>,
 			asset: T::ForeignAssetType,
@@ -327,37 +515,16 @@ pub mod pallet {
 		) -> DispatchResult {
 			T::ForeignAssetModifierOrigin::ensure_origin(origin)?;
 
-			// Compute assetId from asset
-			let asset_id: T::AssetId = asset.clone().into();
-
-			// Ensure such an assetId does not exist
-			ensure!(
-				AssetIdType::<T>::get(&asset_id).is_none(),
-				Error::<T>::AssetAlreadyExists
-			);
-			T::AssetRegistrar::create_foreign_asset(
-				asset_id,
-				min_amount,
-				metadata.clone(),
-				is_sufficient,
-			)
-			.map_err(|_| Error::<T>::ErrorCreatingAsset)?;
-
-			// Insert the association assetId->assetType
-			AssetIdType::<T>::insert(&asset_id, &asset);
-			AssetTypeId::<T>::insert(&asset, &asset_id);
-
-			Self::deposit_event(Event::ForeignAssetRegistered {
-				asset_id,
-				asset,
-				metadata,
-			});
-			Ok(())
+			Self::register_or_override_foreign_asset(asset, metadata, min_amount, is_sufficient)
 		}
 
 		/// Change the amount of units we are charging per execution second
 		/// for a given ForeignAssetType
-		#[pallet::weight(T::WeightInfo::set_asset_units_per_second(*num_assets_weight_hint))]
+		/// If the asset was registered with a deposit, the deposit is returned to its creator
+		#[pallet::weight(
+			T::WeightInfo::set_asset_units_per_second(*num_assets_weight_hint)
+				.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+		)]
 		pub fn set_asset_units_per_second(
 			origin: OriginFor<T>,
 			asset_type: T::ForeignAssetType,
@@ -388,6 +555,10 @@ pub mod pallet {
 
 			AssetTypeUnitsPerSecond::<T>::insert(&asset_type, &units_per_second);
 
+			if let Some(asset_id) = AssetTypeId::<T>::get(&asset_type) {
+				Self::release_foreign_asset_deposit(asset_id);
+			}
+
 			Self::deposit_event(Event::UnitsPerSecondChanged {
 				asset_type,
 				units_per_second,
@@ -484,7 +655,11 @@ pub mod pallet {
 		}
 
 		/// Remove a given assetId -> assetType association
-		#[pallet::weight(T::WeightInfo::remove_existing_asset_type(*num_assets_weight_hint))]
+		/// If the asset was registered with a deposit, the deposit is slashed
+		#[pallet::weight(
+			T::WeightInfo::remove_existing_asset_type(*num_assets_weight_hint)
+				.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+		)]
 		pub fn remove_existing_asset_type(
 			origin: OriginFor<T>,
 			asset_id: T::AssetId,
@@ -517,6 +692,8 @@ pub mod pallet {
 				SupportedFeePaymentAssets::<T>::put(supported_assets);
 			}
 
+			Self::slash_foreign_asset_deposit(asset_id);
+
 			Self::deposit_event(Event::ForeignAssetRemoved {
 				asset_id,
 				asset_type,
@@ -602,6 +779,7 @@ pub mod pallet {
 			);
 			T::WeightInfo::remove_existing_asset_type(*num_assets_weight_hint)
 			.saturating_add(dispatch_info_weight)
+			.saturating_add(T::DbWeight::get().reads_writes(1, 1))
 		})]
 		pub fn destroy_foreign_asset(
 			origin: OriginFor<T>,
@@ -639,6 +817,8 @@ pub mod pallet {
 				SupportedFeePaymentAssets::<T>::put(supported_assets);
 			}
 
+			Self::slash_foreign_asset_deposit(asset_id);
+
 			Self::deposit_event(Event::ForeignAssetDestroyed {
 				asset_id,
 				asset_type,
@@ -654,7 +834,7 @@ pub mod pallet {
 			T::AssetRegistrar::destroy_asset_dispatch_info_weight(
 				*asset_id, *destroy_asset_witness
 			)
-			.saturating_add(T::DbWeight::get().reads_writes(2, 2))
+			.saturating_add(T::DbWeight::get().reads_writes(3, 3))
 		})]
 		pub fn destroy_local_asset(
 			origin: OriginFor<T>,
@@ -663,29 +843,408 @@ pub mod pallet {
 		) -> DispatchResult {
 			T::LocalAssetModifierOrigin::ensure_origin(origin)?;
 
+			Self::do_destroy_local_asset(asset_id, destroy_asset_witness)
+		}
+
+		/// Transfer the ownership of a local asset
+		/// If the caller holds the asset deposit, the deposit is moved to the new owner,
+		/// who will get it back when the asset is destroyed
+		#[pallet::weight(T::DbWeight::get().reads_writes(4, 4))]
+		pub fn transfer_local_asset_ownership(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			owner: T::AccountId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_local_asset_owner(&who, asset_id)?;
+
+			T::AssetRegistrar::transfer_local_asset_ownership(asset_id, owner.clone())
+				.map_err(|_| Error::<T>::ErrorTransferringAssetOwnership)?;
+
+			// The deposit only follows the ownership if the owner is the one holding it
+			let depositor = match LocalAssetDeposit::<T>::get(asset_id) {
+				Some(mut asset_info) if asset_info.creator == who && who != owner => {
+					T::Currency::repatriate_reserved(
+						&who,
+						&owner,
+						asset_info.deposit,
+						BalanceStatus::Reserved,
+					)?;
+					asset_info.creator = owner.clone();
+					LocalAssetDeposit::<T>::insert(asset_id, &asset_info);
+					Some(owner.clone())
+				}
+				Some(asset_info) => Some(asset_info.creator),
+				None => None,
+			};
+
+			Self::deposit_event(Event::LocalAssetOwnershipTransferred {
+				asset_id,
+				owner,
+				depositor,
+			});
+			Ok(())
+		}
+
+		/// Start the destruction of a local asset, freezing it
+		/// Its accounts can then be burnt in batches with burn_local_asset_accounts before
+		/// finishing the destruction with finish_local_asset_destruction
+		#[pallet::weight(T::DbWeight::get().reads_writes(3, 2))]
+		pub fn start_local_asset_destruction(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_local_asset_owner(&who, asset_id)?;
+
+			ensure!(
+				LocalAssetDeposit::<T>::contains_key(asset_id),
+				Error::<T>::NonExistentLocalAsset
+			);
+			ensure!(
+				!LocalAssetsBeingDestroyed::<T>::contains_key(asset_id),
+				Error::<T>::LocalAssetDestructionAlreadyStarted
+			);
+
+			T::AssetRegistrar::freeze_local_asset(asset_id)
+				.map_err(|_| Error::<T>::ErrorFreezingAsset)?;
+
+			LocalAssetsBeingDestroyed::<T>::insert(asset_id, None::<T::AccountId>);
+
+			Self::deposit_event(Event::LocalAssetDestructionStarted { asset_id });
+			Ok(())
+		}
+
+		/// Burn the balances of the accounts of a local asset being destroyed, scanning at most
+		/// max_accounts accounts after the ones scanned by the previous call
+		/// This allows to spread the cost of destroying assets with many holders over several
+		/// blocks
+		#[pallet::weight(
+			T::AssetRegistrar::burn_local_asset_accounts_weight(*max_accounts)
+			.saturating_add(T::DbWeight::get().reads_writes(2, 1))
+		)]
+		pub fn burn_local_asset_accounts(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			max_accounts: u32,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_local_asset_owner(&who, asset_id)?;
+
+			let start_after = LocalAssetsBeingDestroyed::<T>::get(asset_id)
+				.ok_or(Error::<T>::LocalAssetDestructionNotStarted)?;
+
+			let (accounts, last_scanned) =
+				T::AssetRegistrar::burn_local_asset_accounts(asset_id, max_accounts, start_after)
+					.map_err(|_| Error::<T>::ErrorBurningAssetAccounts)?;
+			LocalAssetsBeingDestroyed::<T>::insert(asset_id, last_scanned);
+
+			Self::deposit_event(Event::LocalAssetAccountsBurnt { asset_id, accounts });
+			Ok(())
+		}
+
+		/// Finish the destruction of a local asset, destroying it and releasing the deposit
+		/// The weight in this case is the one returned by the trait for the given witness,
+		/// which is smaller the more accounts were burnt beforehand
+		#[pallet::weight({
+			T::AssetRegistrar::destroy_asset_dispatch_info_weight(
+				*asset_id, *destroy_asset_witness
+			)
+			.saturating_add(T::DbWeight::get().reads_writes(4, 4))
+		})]
+		pub fn finish_local_asset_destruction(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			destroy_asset_witness: T::AssetDestroyWitness,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_local_asset_owner(&who, asset_id)?;
+
+			ensure!(
+				LocalAssetsBeingDestroyed::<T>::contains_key(asset_id),
+				Error::<T>::LocalAssetDestructionNotStarted
+			);
+
+			Self::do_destroy_local_asset(asset_id, destroy_asset_witness)
+		}
+
+		/// Choose the asset the fees of the caller's transactions are paid with
+		/// The asset must be supported for fee payment. None restores the native currency
+		#[pallet::weight(T::DbWeight::get().reads_writes(2, 1))]
+		pub fn set_fee_payment_asset_preference(
+			origin: OriginFor<T>,
+			asset_type: Option<T::ForeignAssetType>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			match &asset_type {
+				Some(asset_type) => {
+					ensure!(
+						Self::fee_payment_asset_is_supported(asset_type),
+						Error::<T>::FeePaymentAssetNotSupported
+					);
+					FeePaymentAssetPreference::<T>::insert(&who, asset_type);
+				}
+				None => FeePaymentAssetPreference::<T>::remove(&who),
+			}
+
+			Self::deposit_event(Event::FeePaymentAssetPreferenceSet {
+				account: who,
+				asset_type,
+			});
+			Ok(())
+		}
+
+		/// Make a foreign asset sufficient, so that accounts can hold it without holding the
+		/// native token
+		/// If the asset was registered with a deposit, the deposit is returned to its creator
+		#[pallet::weight(T::DbWeight::get().reads_writes(3, 3))]
+		pub fn promote_foreign_asset_to_sufficient(
+			origin: OriginFor<T>,
+			asset_type: T::ForeignAssetType,
+		) -> DispatchResult {
+			T::ForeignAssetModifierOrigin::ensure_origin(origin)?;
+
+			let asset_id =
+				AssetTypeId::<T>::get(&asset_type).ok_or(Error::<T>::AssetDoesNotExist)?;
+
+			T::AssetRegistrar::make_foreign_asset_sufficient(asset_id)
+				.map_err(|_| Error::<T>::ErrorUpdatingAsset)?;
+
+			Self::release_foreign_asset_deposit(asset_id);
+
+			Self::deposit_event(Event::ForeignAssetPromotedToSufficient {
+				asset_id,
+				asset_type,
+			});
+			Ok(())
+		}
+
+		/// Register a new non-sufficient asset from the chain that is its reserve
+		/// The asset location must be contained in the location of the origin
+		/// If the asset was registered with a deposit, its parameters are overridden and the
+		/// deposit is returned to its creator
+		#[pallet::weight(
+			T::WeightInfo::register_foreign_asset()
+				.saturating_add(T::DbWeight::get().reads_writes(2, 2))
+		)]
+		pub fn register_sibling_foreign_asset(
+			origin: OriginFor<T>,
+			asset: T::ForeignAssetType,
+			metadata: T::AssetRegistrarMetadata,
+			min_amount: T::Balance,
+		) -> DispatchResult {
+			let reserve = T::SiblingAssetRegistrarOrigin::ensure_origin(origin)?;
+
+			let location: Option<MultiLocation> = asset.clone().into();
+			ensure!(
+				location.map_or(false, |location| {
+					location.parents == reserve.parents
+						&& location.interior.len() >= reserve.interior.len()
+						&& reserve
+							.interior
+							.iter()
+							.zip(location.interior.iter())
+							.all(|(prefix, junction)| prefix == junction)
+				}),
+				Error::<T>::NotAssetReserve
+			);
+
+			Self::register_or_override_foreign_asset(asset, metadata, min_amount, false)
+		}
+
+		/// Register a new non-sufficient asset reserving ForeignAssetDeposit from the caller
+		/// The deposit is returned when governance makes the asset fee-paying and slashed
+		/// if governance removes the asset
+		#[pallet::weight(
+			T::WeightInfo::register_foreign_asset()
+				.saturating_add(T::DbWeight::get().reads_writes(1, 2))
+		)]
+		pub fn register_foreign_asset_with_deposit(
+			origin: OriginFor<T>,
+			asset: T::ForeignAssetType,
+			metadata: T::AssetRegistrarMetadata,
+			min_amount: T::Balance,
+		) -> DispatchResult {
+			let creator = ensure_signed(origin)?;
+
+			// Get the deposit amount
+			let deposit = T::ForeignAssetDeposit::get();
+
+			// Verify we can reserve
+			T::Currency::can_reserve(&creator, deposit)
+				.then(|| true)
+				.ok_or(Error::<T>::NotSufficientDeposit)?;
+
+			let asset_id = Self::do_register_foreign_asset(asset, metadata, min_amount, false)?;
+
+			// Reserve the deposit, we verified we can do this
+			T::Currency::reserve(&creator, deposit)?;
+
+			ForeignAssetDeposit::<T>::insert(asset_id, AssetInfo { creator, deposit });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The asset `who` pays the fees of its transactions with, together with the units per
+		/// second charged for it, if it is still supported for fee payment
+		pub fn fee_payment_asset(who: &T::AccountId) -> Option<(T::AssetId, u128)> {
+			Self::supported_fee_payment_asset(&FeePaymentAssetPreference::<T>::get(who)?)
+		}
+
+		/// The id of `asset_type` and the units per second charged for it, if it is supported
+		/// for fee payment
+		pub fn supported_fee_payment_asset(
+			asset_type: &T::ForeignAssetType,
+		) -> Option<(T::AssetId, u128)> {
+			if !Self::fee_payment_asset_is_supported(asset_type) {
+				return None;
+			}
+			let units_per_second = AssetTypeUnitsPerSecond::<T>::get(asset_type)?;
+			let asset_id = AssetTypeId::<T>::get(asset_type)?;
+			Some((asset_id, units_per_second))
+		}
+
+		fn fee_payment_asset_is_supported(asset_type: &T::ForeignAssetType) -> bool {
+			SupportedFeePaymentAssets::<T>::get()
+				.binary_search(asset_type)
+				.is_ok() && AssetTypeUnitsPerSecond::<T>::contains_key(asset_type)
+		}
+
+		/// The account ID of AssetManager
+		pub fn account_id() -> T::AccountId {
+			PALLET_ID.into_account_truncating()
+		}
+
+		fn do_register_foreign_asset(
+			asset: T::ForeignAssetType,
+			metadata: T::AssetRegistrarMetadata,
+			min_amount: T::Balance,
+			is_sufficient: bool,
+		) -> Result<T::AssetId, DispatchError> {
+			// Compute assetId from asset
+			let asset_id: T::AssetId = asset.clone().into();
+
+			// Ensure such an assetId does not exist
+			ensure!(
+				AssetIdType::<T>::get(&asset_id).is_none(),
+				Error::<T>::AssetAlreadyExists
+			);
+			T::AssetRegistrar::create_foreign_asset(
+				asset_id,
+				min_amount,
+				metadata.clone(),
+				is_sufficient,
+			)
+			.map_err(|_| Error::<T>::ErrorCreatingAsset)?;
+
+			// Insert the association assetId->assetType
+			AssetIdType::<T>::insert(&asset_id, &asset);
+			AssetTypeId::<T>::insert(&asset, &asset_id);
+
+			Self::deposit_event(Event::ForeignAssetRegistered {
+				asset_id,
+				asset,
+				metadata,
+			});
+			Ok(asset_id)
+		}
+
+		fn ensure_local_asset_owner(who: &T::AccountId, asset_id: T::AssetId) -> DispatchResult {
+			let owner = T::AssetRegistrar::local_asset_owner(asset_id)
+				.ok_or(Error::<T>::NonExistentLocalAsset)?;
+			ensure!(&owner == who, Error::<T>::NotLocalAssetOwner);
+			Ok(())
+		}
+
+		fn do_destroy_local_asset(
+			asset_id: T::AssetId,
+			destroy_asset_witness: T::AssetDestroyWitness,
+		) -> DispatchResult {
 			// Get asset creator and deposit amount
 			let asset_info =
 				LocalAssetDeposit::<T>::get(asset_id).ok_or(Error::<T>::NonExistentLocalAsset)?;
+			let owner = T::AssetRegistrar::local_asset_owner(asset_id)
+				.ok_or(Error::<T>::NonExistentLocalAsset)?;
 
 			// Destroy local asset
 			T::AssetRegistrar::destroy_local_asset(asset_id, destroy_asset_witness)
 				.map_err(|_| Error::<T>::ErrorDestroyingAsset)?;
 
-			// Unreserve deposit
-			T::Currency::unreserve(&asset_info.creator, asset_info.deposit);
+			// Release the deposit to the current owner of the asset
+			if owner == asset_info.creator {
+				T::Currency::unreserve(&asset_info.creator, asset_info.deposit);
+			} else {
+				T::Currency::repatriate_reserved(
+					&asset_info.creator,
+					&owner,
+					asset_info.deposit,
+					BalanceStatus::Free,
+				)?;
+			}
 
 			// Remove asset info
 			LocalAssetDeposit::<T>::remove(asset_id);
+			LocalAssetsBeingDestroyed::<T>::remove(asset_id);
 
 			Self::deposit_event(Event::LocalAssetDestroyed { asset_id });
 			Ok(())
 		}
-	}
 
-	impl<T: Config> Pallet<T> {
-		/// The account ID of AssetManager
-		pub fn account_id() -> T::AccountId {
-			PALLET_ID.into_account_truncating()
+		/// Registers `asset`, or overrides its parameters if it was registered with a deposit
+		fn register_or_override_foreign_asset(
+			asset: T::ForeignAssetType,
+			metadata: T::AssetRegistrarMetadata,
+			min_amount: T::Balance,
+			is_sufficient: bool,
+		) -> DispatchResult {
+			let asset_id: T::AssetId = asset.clone().into();
+			if !ForeignAssetDeposit::<T>::contains_key(asset_id)
+				|| AssetIdType::<T>::get(&asset_id).as_ref() != Some(&asset)
+			{
+				Self::do_register_foreign_asset(asset, metadata, min_amount, is_sufficient)?;
+				return Ok(());
+			}
+
+			T::AssetRegistrar::update_foreign_asset(
+				asset_id,
+				min_amount,
+				metadata.clone(),
+				is_sufficient,
+			)
+			.map_err(|_| Error::<T>::ErrorUpdatingAsset)?;
+
+			Self::release_foreign_asset_deposit(asset_id);
+
+			Self::deposit_event(Event::ForeignAssetRegistered {
+				asset_id,
+				asset,
+				metadata,
+			});
+			Ok(())
+		}
+
+		fn release_foreign_asset_deposit(asset_id: T::AssetId) {
+			if let Some(AssetInfo { creator, deposit }) = ForeignAssetDeposit::<T>::take(asset_id) {
+				T::Currency::unreserve(&creator, deposit);
+				Self::deposit_event(Event::ForeignAssetDepositReleased {
+					asset_id,
+					creator,
+					deposit,
+				});
+			}
+		}
+
+		fn slash_foreign_asset_deposit(asset_id: T::AssetId) {
+			if let Some(AssetInfo { creator, deposit }) = ForeignAssetDeposit::<T>::take(asset_id) {
+				let _ = T::Currency::slash_reserved(&creator, deposit);
+				Self::deposit_event(Event::ForeignAssetDepositSlashed {
+					asset_id,
+					creator,
+					deposit,
+				});
+			}
 		}
 	}
 }
@@ -19,7 +19,10 @@ use crate as pallet_asset_manager;
 use parity_scale_codec::{Decode, Encode};
 
 use frame_support::{
-	construct_runtime, parameter_types, traits::Everything, weights::Weight, RuntimeDebug,
+	construct_runtime, parameter_types,
+	traits::{EnsureOrigin, Everything},
+	weights::Weight,
+	RuntimeDebug,
 };
 use frame_system::EnsureRoot;
 use scale_info::TypeInfo;
@@ -30,6 +33,7 @@ use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup},
 };
+use sp_std::{cell::RefCell, collections::btree_map::BTreeMap};
 use xcm::latest::prelude::*;
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
@@ -155,21 +159,52 @@ pub struct MockAssetPalletRegistrar;
 
 impl AssetRegistrar<Test> for MockAssetPalletRegistrar {
 	fn create_foreign_asset(
-		_asset: u32,
-		_min_balance: u64,
+		asset: u32,
+		min_balance: u64,
 		_metadata: u32,
-		_is_sufficient: bool,
+		is_sufficient: bool,
 	) -> Result<(), DispatchError> {
+		FOREIGN_ASSETS.with(|assets| {
+			assets
+				.borrow_mut()
+				.insert(asset, (min_balance, is_sufficient))
+		});
 		Ok(())
 	}
 
+	fn update_foreign_asset(
+		asset: u32,
+		min_balance: u64,
+		_metadata: u32,
+		is_sufficient: bool,
+	) -> sp_runtime::DispatchResult {
+		FOREIGN_ASSETS.with(|assets| match assets.borrow_mut().get_mut(&asset) {
+			Some(details) => {
+				*details = (min_balance, is_sufficient);
+				Ok(())
+			}
+			None => Err(DispatchError::CannotLookup),
+		})
+	}
+
+	fn make_foreign_asset_sufficient(asset: u32) -> sp_runtime::DispatchResult {
+		FOREIGN_ASSETS.with(|assets| match assets.borrow_mut().get_mut(&asset) {
+			Some(details) => {
+				details.1 = true;
+				Ok(())
+			}
+			None => Err(DispatchError::CannotLookup),
+		})
+	}
+
 	fn create_local_asset(
-		_asset: u32,
+		asset: u32,
 		_account: u64,
 		_min_balance: u64,
 		_is_sufficient: bool,
-		_owner: u64,
+		owner: u64,
 	) -> sp_runtime::DispatchResult {
+		LOCAL_ASSETS.with(|assets| assets.borrow_mut().insert(asset, (owner, false)));
 		Ok(())
 	}
 
@@ -177,13 +212,85 @@ impl AssetRegistrar<Test> for MockAssetPalletRegistrar {
 		Ok(())
 	}
 
-	fn destroy_local_asset(_asset: u32, _witness: u32) -> Result<(), DispatchError> {
+	fn destroy_local_asset(asset: u32, _witness: u32) -> Result<(), DispatchError> {
+		LOCAL_ASSETS.with(|assets| assets.borrow_mut().remove(&asset));
 		Ok(())
 	}
 
 	fn destroy_asset_dispatch_info_weight(_asset: u32, _witness: u32) -> Weight {
 		0
 	}
+
+	fn local_asset_owner(asset: u32) -> Option<u64> {
+		LOCAL_ASSETS.with(|assets| assets.borrow().get(&asset).map(|(owner, _)| *owner))
+	}
+
+	fn transfer_local_asset_ownership(asset: u32, owner: u64) -> sp_runtime::DispatchResult {
+		LOCAL_ASSETS.with(|assets| match assets.borrow_mut().get_mut(&asset) {
+			Some(details) => {
+				details.0 = owner;
+				Ok(())
+			}
+			None => Err(DispatchError::CannotLookup),
+		})
+	}
+
+	fn freeze_local_asset(asset: u32) -> sp_runtime::DispatchResult {
+		LOCAL_ASSETS.with(|assets| match assets.borrow_mut().get_mut(&asset) {
+			Some(details) => {
+				details.1 = true;
+				Ok(())
+			}
+			None => Err(DispatchError::CannotLookup),
+		})
+	}
+
+	fn burn_local_asset_accounts(
+		asset: u32,
+		max_accounts: u32,
+		start_after: Option<u64>,
+	) -> Result<(u32, Option<u64>), DispatchError> {
+		// Mock assets always have as many accounts left as requested, numbered from 1
+		match LOCAL_ASSETS.with(|assets| assets.borrow().get(&asset).copied()) {
+			Some((_, true)) => Ok((
+				max_accounts,
+				Some(start_after.unwrap_or(0) + max_accounts as u64),
+			)),
+			_ => Err(DispatchError::Other("asset not frozen")),
+		}
+	}
+
+	fn local_asset_destroy_witness(asset: u32) -> Option<u32> {
+		Self::local_asset_owner(asset).map(|_| 0)
+	}
+
+	fn burn_local_asset_accounts_weight(_max_accounts: u32) -> Weight {
+		0
+	}
+}
+
+thread_local! {
+	/// Owner and whether the asset is frozen, for each local asset
+	pub static LOCAL_ASSETS: RefCell<BTreeMap<AssetId, (AccountId, bool)>> =
+		RefCell::new(BTreeMap::new());
+	/// Minimum balance and whether the asset is sufficient, for each foreign asset
+	pub static FOREIGN_ASSETS: RefCell<BTreeMap<AssetId, (Balance, bool)>> =
+		RefCell::new(BTreeMap::new());
+}
+
+/// The minimum balance and sufficiency the mock registrar created or updated `asset` with
+pub(crate) fn foreign_asset_details(asset: AssetId) -> Option<(Balance, bool)> {
+	FOREIGN_ASSETS.with(|assets| assets.borrow().get(&asset).copied())
+}
+
+/// Whether the mock registrar froze `asset`
+pub(crate) fn local_asset_is_frozen(asset: AssetId) -> bool {
+	LOCAL_ASSETS.with(|assets| {
+		assets
+			.borrow()
+			.get(&asset)
+			.map_or(false, |(_, frozen)| *frozen)
+	})
 }
 
 pub struct MockLocalAssetIdCreator;
@@ -200,6 +307,27 @@ impl pallet_asset_manager::LocalAssetIdCreator<Test> for MockLocalAssetIdCreator
 
 parameter_types! {
 	pub const LocalAssetDeposit: u64 = 1;
+	pub const ForeignAssetDeposit: u64 = 10;
+}
+
+/// Signed accounts act as the sibling parachain with the same id
+pub struct EnsureSiblingParachain;
+impl EnsureOrigin<Origin> for EnsureSiblingParachain {
+	type Success = MultiLocation;
+
+	fn try_origin(o: Origin) -> Result<Self::Success, Origin> {
+		Into::<Result<frame_system::RawOrigin<AccountId>, Origin>>::into(o).and_then(|o| match o {
+			frame_system::RawOrigin::Signed(account) => {
+				Ok(MultiLocation::new(1, X1(Parachain(account as u32))))
+			}
+			r => Err(Origin::from(r)),
+		})
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> Origin {
+		Origin::signed(1000)
+	}
 }
 
 impl Config for Test {
@@ -210,11 +338,13 @@ impl Config for Test {
 	type ForeignAssetType = MockAssetType;
 	type AssetRegistrar = MockAssetPalletRegistrar;
 	type ForeignAssetModifierOrigin = EnsureRoot<u64>;
+	type SiblingAssetRegistrarOrigin = EnsureSiblingParachain;
 	type LocalAssetModifierOrigin = EnsureRoot<u64>;
 	type LocalAssetIdCreator = MockLocalAssetIdCreator;
 	type AssetDestroyWitness = u32;
 	type Currency = Balances;
 	type LocalAssetDeposit = LocalAssetDeposit;
+	type ForeignAssetDeposit = ForeignAssetDeposit;
 	type WeightInfo = ();
 }
 
@@ -244,6 +374,9 @@ impl ExtBuilder {
 		}
 		.assimilate_storage(&mut t)
 		.expect("Pallet balances storage can be assimilated");
+		LOCAL_ASSETS.with(|assets| assets.borrow_mut().clear());
+		FOREIGN_ASSETS.with(|assets| assets.borrow_mut().clear());
+
 		let mut ext = sp_io::TestExternalities::new(t);
 		ext.execute_with(|| System::set_block_number(1));
 		ext
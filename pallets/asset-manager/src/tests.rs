@@ -835,3 +835,726 @@ fn test_destroy_local_asset_works() {
 			]);
 		});
 }
+
+#[test]
+fn test_sibling_can_register_its_own_asset() {
+	ExtBuilder::default().build().execute_with(|| {
+		let asset = MockAssetType::Xcm(MultiLocation::new(1, X2(Parachain(1000), GeneralIndex(5))));
+		let asset_id: AssetId = asset.clone().into();
+
+		assert_ok!(AssetManager::register_sibling_foreign_asset(
+			Origin::signed(1000),
+			asset.clone(),
+			0u32.into(),
+			1u32.into(),
+		));
+
+		assert_eq!(AssetManager::asset_id_type(asset_id).unwrap(), asset);
+		assert_eq!(
+			AssetManager::asset_type_id(asset.clone()).unwrap(),
+			asset_id
+		);
+		assert!(AssetManager::foreign_asset_deposit(asset_id).is_none());
+		expect_events(vec![crate::Event::ForeignAssetRegistered {
+			asset_id,
+			asset,
+			metadata: 0,
+		}])
+	});
+}
+
+#[test]
+fn test_sibling_cannot_register_assets_it_is_not_reserve_of() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			AssetManager::register_sibling_foreign_asset(
+				Origin::signed(1000),
+				MockAssetType::Xcm(MultiLocation::new(1, X1(Parachain(1001)))),
+				0u32.into(),
+				1u32.into(),
+			),
+			Error::<Test>::NotAssetReserve
+		);
+		assert_noop!(
+			AssetManager::register_sibling_foreign_asset(
+				Origin::signed(1000),
+				MockAssetType::Xcm(MultiLocation::parent()),
+				0u32.into(),
+				1u32.into(),
+			),
+			Error::<Test>::NotAssetReserve
+		);
+		assert_noop!(
+			AssetManager::register_sibling_foreign_asset(
+				Origin::signed(1000),
+				MockAssetType::MockAsset(1),
+				0u32.into(),
+				1u32.into(),
+			),
+			Error::<Test>::NotAssetReserve
+		);
+	});
+}
+
+#[test]
+fn test_register_foreign_asset_with_deposit_reserves_deposit() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 20)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(AssetManager::register_foreign_asset_with_deposit(
+				Origin::signed(1),
+				MockAssetType::MockAsset(1),
+				0u32.into(),
+				1u32.into(),
+			));
+
+			assert_eq!(
+				AssetManager::asset_id_type(1).unwrap(),
+				MockAssetType::MockAsset(1)
+			);
+			assert_eq!(
+				AssetManager::foreign_asset_deposit(1),
+				Some(AssetInfo {
+					creator: 1,
+					deposit: 10
+				})
+			);
+			assert_eq!(Balances::reserved_balance(1), 10);
+		});
+}
+
+#[test]
+fn test_register_foreign_asset_with_deposit_fails_without_funds() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 5)])
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				AssetManager::register_foreign_asset_with_deposit(
+					Origin::signed(1),
+					MockAssetType::MockAsset(1),
+					0u32.into(),
+					1u32.into(),
+				),
+				Error::<Test>::NotSufficientDeposit
+			);
+		});
+}
+
+#[test]
+fn test_setting_units_per_second_releases_foreign_asset_deposit() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 20)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(AssetManager::register_foreign_asset_with_deposit(
+				Origin::signed(1),
+				MockAssetType::MockAsset(1),
+				0u32.into(),
+				1u32.into(),
+			));
+
+			assert_ok!(AssetManager::set_asset_units_per_second(
+				Origin::root(),
+				MockAssetType::MockAsset(1),
+				200u128.into(),
+				0
+			));
+
+			assert!(AssetManager::foreign_asset_deposit(1).is_none());
+			assert_eq!(Balances::reserved_balance(1), 0);
+			assert_eq!(Balances::free_balance(1), 20);
+			expect_events(vec![
+				crate::Event::ForeignAssetRegistered {
+					asset_id: 1,
+					asset: MockAssetType::MockAsset(1),
+					metadata: 0,
+				},
+				crate::Event::ForeignAssetDepositReleased {
+					asset_id: 1,
+					creator: 1,
+					deposit: 10,
+				},
+				crate::Event::UnitsPerSecondChanged {
+					asset_type: MockAssetType::MockAsset(1),
+					units_per_second: 200,
+				},
+			]);
+		});
+}
+
+#[test]
+fn test_removing_foreign_asset_slashes_deposit() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 20)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(AssetManager::register_foreign_asset_with_deposit(
+				Origin::signed(1),
+				MockAssetType::MockAsset(1),
+				0u32.into(),
+				1u32.into(),
+			));
+
+			assert_ok!(AssetManager::remove_existing_asset_type(
+				Origin::root(),
+				1,
+				0
+			));
+
+			assert!(AssetManager::foreign_asset_deposit(1).is_none());
+			assert_eq!(Balances::reserved_balance(1), 0);
+			assert_eq!(Balances::free_balance(1), 10);
+		});
+}
+
+#[test]
+fn test_destroying_foreign_asset_slashes_deposit() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 20)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(AssetManager::register_foreign_asset_with_deposit(
+				Origin::signed(1),
+				MockAssetType::MockAsset(1),
+				0u32.into(),
+				1u32.into(),
+			));
+
+			assert_ok!(AssetManager::destroy_foreign_asset(Origin::root(), 1, 0, 1));
+
+			assert!(AssetManager::foreign_asset_deposit(1).is_none());
+			assert_eq!(Balances::reserved_balance(1), 0);
+			assert_eq!(Balances::free_balance(1), 10);
+			expect_events(vec![
+				crate::Event::ForeignAssetRegistered {
+					asset_id: 1,
+					asset: MockAssetType::MockAsset(1),
+					metadata: 0,
+				},
+				crate::Event::ForeignAssetDepositSlashed {
+					asset_id: 1,
+					creator: 1,
+					deposit: 10,
+				},
+				crate::Event::ForeignAssetDestroyed {
+					asset_id: 1,
+					asset_type: MockAssetType::MockAsset(1),
+				},
+			]);
+		});
+}
+
+#[test]
+fn test_transfer_local_asset_ownership_moves_deposit() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 20)])
+		.build()
+		.execute_with(|| {
+			let asset_id = MockLocalAssetIdCreator::create_asset_id_from_metadata(0);
+
+			assert_ok!(AssetManager::register_local_asset(
+				Origin::root(),
+				1u64,
+				1u64,
+				true,
+				0u32.into(),
+			));
+
+			assert_noop!(
+				AssetManager::transfer_local_asset_ownership(Origin::signed(2), asset_id, 2),
+				Error::<Test>::NotLocalAssetOwner
+			);
+			assert_ok!(AssetManager::transfer_local_asset_ownership(
+				Origin::signed(1),
+				asset_id,
+				2
+			));
+
+			assert_eq!(
+				AssetManager::local_asset_deposit(asset_id),
+				Some(AssetInfo {
+					creator: 2,
+					deposit: 1
+				})
+			);
+			assert_eq!(Balances::reserved_balance(1), 0);
+			assert_eq!(Balances::reserved_balance(2), 1);
+			assert_eq!(
+				events().last(),
+				Some(&crate::Event::LocalAssetOwnershipTransferred {
+					asset_id,
+					owner: 2,
+					depositor: Some(2),
+				})
+			);
+		});
+}
+
+#[test]
+fn test_transfer_local_asset_ownership_keeps_creator_deposit() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 20)])
+		.build()
+		.execute_with(|| {
+			let asset_id = MockLocalAssetIdCreator::create_asset_id_from_metadata(0);
+
+			assert_ok!(AssetManager::register_local_asset(
+				Origin::root(),
+				1u64,
+				2u64,
+				true,
+				0u32.into(),
+			));
+			assert_ok!(AssetManager::transfer_local_asset_ownership(
+				Origin::signed(2),
+				asset_id,
+				3
+			));
+
+			// The deposit belongs to the creator, not to the owner
+			assert_eq!(
+				AssetManager::local_asset_deposit(asset_id),
+				Some(AssetInfo {
+					creator: 1,
+					deposit: 1
+				})
+			);
+			assert_eq!(Balances::reserved_balance(1), 1);
+			assert_eq!(Balances::reserved_balance(3), 0);
+		});
+}
+
+#[test]
+fn test_destroying_local_asset_releases_deposit_to_owner() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 20)])
+		.build()
+		.execute_with(|| {
+			let asset_id = MockLocalAssetIdCreator::create_asset_id_from_metadata(0);
+
+			assert_ok!(AssetManager::register_local_asset(
+				Origin::root(),
+				1u64,
+				2u64,
+				true,
+				0u32.into(),
+			));
+			assert_ok!(AssetManager::transfer_local_asset_ownership(
+				Origin::signed(2),
+				asset_id,
+				3
+			));
+
+			assert_ok!(AssetManager::destroy_local_asset(
+				Origin::root(),
+				asset_id,
+				0
+			));
+
+			// The deposit held by the creator goes to the owner
+			assert_eq!(AssetManager::local_asset_deposit(asset_id), None);
+			assert_eq!(Balances::reserved_balance(1), 0);
+			assert_eq!(Balances::free_balance(1), 19);
+			assert_eq!(Balances::free_balance(3), 1);
+		});
+}
+
+#[test]
+fn test_owner_can_destroy_local_asset_in_stages() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 20)])
+		.build()
+		.execute_with(|| {
+			let asset_id = MockLocalAssetIdCreator::create_asset_id_from_metadata(0);
+
+			assert_ok!(AssetManager::register_local_asset(
+				Origin::root(),
+				1u64,
+				1u64,
+				true,
+				0u32.into(),
+			));
+			assert_ok!(AssetManager::transfer_local_asset_ownership(
+				Origin::signed(1),
+				asset_id,
+				2
+			));
+
+			assert_noop!(
+				AssetManager::start_local_asset_destruction(Origin::signed(1), asset_id),
+				Error::<Test>::NotLocalAssetOwner
+			);
+			assert_ok!(AssetManager::start_local_asset_destruction(
+				Origin::signed(2),
+				asset_id
+			));
+			assert!(local_asset_is_frozen(asset_id));
+			assert!(AssetManager::local_asset_being_destroyed(asset_id).is_some());
+			assert_noop!(
+				AssetManager::start_local_asset_destruction(Origin::signed(2), asset_id),
+				Error::<Test>::LocalAssetDestructionAlreadyStarted
+			);
+
+			assert_ok!(AssetManager::burn_local_asset_accounts(
+				Origin::signed(2),
+				asset_id,
+				3
+			));
+			// The next batch resumes after the last account scanned
+			assert_eq!(
+				AssetManager::local_asset_being_destroyed(asset_id),
+				Some(Some(3))
+			);
+			assert_ok!(AssetManager::burn_local_asset_accounts(
+				Origin::signed(2),
+				asset_id,
+				2
+			));
+			assert_eq!(
+				AssetManager::local_asset_being_destroyed(asset_id),
+				Some(Some(5))
+			);
+			assert_ok!(AssetManager::finish_local_asset_destruction(
+				Origin::signed(2),
+				asset_id,
+				0
+			));
+
+			// The deposit goes back to the current owner
+			assert_eq!(AssetManager::local_asset_deposit(asset_id), None);
+			assert!(AssetManager::local_asset_being_destroyed(asset_id).is_none());
+			assert_eq!(Balances::reserved_balance(2), 0);
+			assert_eq!(Balances::free_balance(2), 1);
+			expect_events(vec![
+				crate::Event::LocalAssetRegistered {
+					asset_id,
+					creator: 1,
+					owner: 1,
+				},
+				crate::Event::LocalAssetOwnershipTransferred {
+					asset_id,
+					owner: 2,
+					depositor: Some(2),
+				},
+				crate::Event::LocalAssetDestructionStarted { asset_id },
+				crate::Event::LocalAssetAccountsBurnt {
+					asset_id,
+					accounts: 3,
+				},
+				crate::Event::LocalAssetAccountsBurnt {
+					asset_id,
+					accounts: 2,
+				},
+				crate::Event::LocalAssetDestroyed { asset_id },
+			]);
+		});
+}
+
+#[test]
+fn test_local_asset_destruction_must_be_started() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 20)])
+		.build()
+		.execute_with(|| {
+			let asset_id = MockLocalAssetIdCreator::create_asset_id_from_metadata(0);
+
+			assert_ok!(AssetManager::register_local_asset(
+				Origin::root(),
+				1u64,
+				1u64,
+				true,
+				0u32.into(),
+			));
+
+			assert_noop!(
+				AssetManager::burn_local_asset_accounts(Origin::signed(1), asset_id, 3),
+				Error::<Test>::LocalAssetDestructionNotStarted
+			);
+			assert_noop!(
+				AssetManager::finish_local_asset_destruction(Origin::signed(1), asset_id, 0),
+				Error::<Test>::LocalAssetDestructionNotStarted
+			);
+		});
+}
+
+#[test]
+fn test_fee_payment_asset_preference_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(AssetManager::register_foreign_asset(
+			Origin::root(),
+			MockAssetType::MockAsset(1),
+			0u32.into(),
+			1u32.into(),
+			true
+		));
+
+		// The asset cannot pay for fees yet
+		assert_noop!(
+			AssetManager::set_fee_payment_asset_preference(
+				Origin::signed(1),
+				Some(MockAssetType::MockAsset(1))
+			),
+			Error::<Test>::FeePaymentAssetNotSupported
+		);
+
+		assert_ok!(AssetManager::set_asset_units_per_second(
+			Origin::root(),
+			MockAssetType::MockAsset(1),
+			200u128.into(),
+			0
+		));
+		assert_ok!(AssetManager::set_fee_payment_asset_preference(
+			Origin::signed(1),
+			Some(MockAssetType::MockAsset(1))
+		));
+
+		assert_eq!(
+			AssetManager::fee_payment_asset_preference(1),
+			Some(MockAssetType::MockAsset(1))
+		);
+		assert_eq!(AssetManager::fee_payment_asset(&1), Some((1, 200)));
+
+		assert_ok!(AssetManager::set_fee_payment_asset_preference(
+			Origin::signed(1),
+			None
+		));
+
+		assert!(AssetManager::fee_payment_asset_preference(1).is_none());
+		assert!(AssetManager::fee_payment_asset(&1).is_none());
+
+		expect_events(vec![
+			crate::Event::ForeignAssetRegistered {
+				asset_id: 1,
+				asset: MockAssetType::MockAsset(1),
+				metadata: 0,
+			},
+			crate::Event::UnitsPerSecondChanged {
+				asset_type: MockAssetType::MockAsset(1),
+				units_per_second: 200,
+			},
+			crate::Event::FeePaymentAssetPreferenceSet {
+				account: 1,
+				asset_type: Some(MockAssetType::MockAsset(1)),
+			},
+			crate::Event::FeePaymentAssetPreferenceSet {
+				account: 1,
+				asset_type: None,
+			},
+		])
+	});
+}
+
+#[test]
+fn test_fee_payment_asset_preference_is_ignored_once_unsupported() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(AssetManager::register_foreign_asset(
+			Origin::root(),
+			MockAssetType::MockAsset(1),
+			0u32.into(),
+			1u32.into(),
+			true
+		));
+		assert_ok!(AssetManager::set_asset_units_per_second(
+			Origin::root(),
+			MockAssetType::MockAsset(1),
+			200u128.into(),
+			0
+		));
+		assert_ok!(AssetManager::set_fee_payment_asset_preference(
+			Origin::signed(1),
+			Some(MockAssetType::MockAsset(1))
+		));
+
+		assert_ok!(AssetManager::remove_supported_asset(
+			Origin::root(),
+			MockAssetType::MockAsset(1),
+			1,
+		));
+
+		// The preference is kept, but fees are paid with the native currency again
+		assert_eq!(
+			AssetManager::fee_payment_asset_preference(1),
+			Some(MockAssetType::MockAsset(1))
+		);
+		assert!(AssetManager::fee_payment_asset(&1).is_none());
+	});
+}
+
+#[test]
+fn test_reserve_overrides_asset_registered_with_deposit() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 20)])
+		.build()
+		.execute_with(|| {
+			let asset =
+				MockAssetType::Xcm(MultiLocation::new(1, X2(Parachain(1000), GeneralIndex(5))));
+			let asset_id: AssetId = asset.clone().into();
+
+			assert_ok!(AssetManager::register_foreign_asset_with_deposit(
+				Origin::signed(1),
+				asset.clone(),
+				0u32.into(),
+				1_000u32.into(),
+			));
+			assert_eq!(foreign_asset_details(asset_id), Some((1_000, false)));
+
+			assert_ok!(AssetManager::register_sibling_foreign_asset(
+				Origin::signed(1000),
+				asset.clone(),
+				1u32.into(),
+				1u32.into(),
+			));
+
+			assert_eq!(foreign_asset_details(asset_id), Some((1, false)));
+			assert_eq!(AssetManager::asset_id_type(asset_id), Some(asset.clone()));
+			assert!(AssetManager::foreign_asset_deposit(asset_id).is_none());
+			assert_eq!(Balances::reserved_balance(1), 0);
+			assert_eq!(Balances::free_balance(1), 20);
+			expect_events(vec![
+				crate::Event::ForeignAssetRegistered {
+					asset_id,
+					asset: asset.clone(),
+					metadata: 0,
+				},
+				crate::Event::ForeignAssetDepositReleased {
+					asset_id,
+					creator: 1,
+					deposit: 10,
+				},
+				crate::Event::ForeignAssetRegistered {
+					asset_id,
+					asset: asset.clone(),
+					metadata: 1,
+				},
+			]);
+
+			// Once overridden, the asset cannot be registered again
+			assert_noop!(
+				AssetManager::register_sibling_foreign_asset(
+					Origin::signed(1000),
+					asset,
+					1u32.into(),
+					1u32.into(),
+				),
+				Error::<Test>::AssetAlreadyExists
+			);
+		});
+}
+
+#[test]
+fn test_governance_overrides_asset_registered_with_deposit() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 20)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(AssetManager::register_foreign_asset_with_deposit(
+				Origin::signed(1),
+				MockAssetType::MockAsset(1),
+				0u32.into(),
+				1_000u32.into(),
+			));
+
+			assert_ok!(AssetManager::register_foreign_asset(
+				Origin::root(),
+				MockAssetType::MockAsset(1),
+				0u32.into(),
+				1u32.into(),
+				true
+			));
+
+			assert_eq!(foreign_asset_details(1), Some((1, true)));
+			assert!(AssetManager::foreign_asset_deposit(1).is_none());
+			assert_eq!(Balances::reserved_balance(1), 0);
+
+			assert_noop!(
+				AssetManager::register_foreign_asset(
+					Origin::root(),
+					MockAssetType::MockAsset(1),
+					0u32.into(),
+					1u32.into(),
+					true
+				),
+				Error::<Test>::AssetAlreadyExists
+			);
+		});
+}
+
+#[test]
+fn test_promote_foreign_asset_to_sufficient() {
+	ExtBuilder::default()
+		.with_balances(vec![(1, 20)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(AssetManager::register_foreign_asset_with_deposit(
+				Origin::signed(1),
+				MockAssetType::MockAsset(1),
+				0u32.into(),
+				1u32.into(),
+			));
+
+			assert_noop!(
+				AssetManager::promote_foreign_asset_to_sufficient(
+					Origin::signed(1),
+					MockAssetType::MockAsset(1)
+				),
+				sp_runtime::DispatchError::BadOrigin
+			);
+			assert_noop!(
+				AssetManager::promote_foreign_asset_to_sufficient(
+					Origin::root(),
+					MockAssetType::MockAsset(2)
+				),
+				Error::<Test>::AssetDoesNotExist
+			);
+
+			assert_ok!(AssetManager::promote_foreign_asset_to_sufficient(
+				Origin::root(),
+				MockAssetType::MockAsset(1)
+			));
+
+			assert_eq!(foreign_asset_details(1), Some((1, true)));
+			assert!(AssetManager::foreign_asset_deposit(1).is_none());
+			assert_eq!(Balances::reserved_balance(1), 0);
+			expect_events(vec![
+				crate::Event::ForeignAssetRegistered {
+					asset_id: 1,
+					asset: MockAssetType::MockAsset(1),
+					metadata: 0,
+				},
+				crate::Event::ForeignAssetDepositReleased {
+					asset_id: 1,
+					creator: 1,
+					deposit: 10,
+				},
+				crate::Event::ForeignAssetPromotedToSufficient {
+					asset_id: 1,
+					asset_type: MockAssetType::MockAsset(1),
+				},
+			]);
+		});
+}
+
+#[test]
+fn new_extrinsics_keep_the_indices_of_existing_ones() {
+	use parity_scale_codec::Encode;
+
+	assert_eq!(
+		crate::Call::<Test>::set_asset_units_per_second {
+			asset_type: MockAssetType::MockAsset(1),
+			units_per_second: 200,
+			num_assets_weight_hint: 0,
+		}
+		.encode()[0],
+		1
+	);
+	assert_eq!(
+		crate::Call::<Test>::destroy_local_asset {
+			asset_id: 1,
+			destroy_asset_witness: 0,
+		}
+		.encode()[0],
+		7
+	);
+}
@@ -0,0 +1,152 @@
+// Copyright 2019-2022 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use crate::{AssetFeeBounds, Config, FeeBounds, Pallet, PriceSubmission, Prices};
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::traits::EnsureOrigin;
+use frame_system::RawOrigin;
+use sp_runtime::FixedU128;
+use sp_std::{boxed::Box, vec::Vec};
+use xcm::latest::prelude::*;
+use xcm::VersionedMultiLocation;
+
+/// The location of the `index`th fed asset
+fn fed_asset(index: u32) -> MultiLocation {
+	MultiLocation::new(1, X1(Parachain(index)))
+}
+
+/// Starts feeding the `index`th asset
+fn feed_asset<T: Config>(index: u32) {
+	AssetFeeBounds::<T>::insert(
+		fed_asset(index),
+		FeeBounds {
+			min_fee_per_second: 1,
+			max_fee_per_second: u128::MAX,
+		},
+	);
+}
+
+/// Adds `count` feeders, returning them
+fn add_feeders<T: Config>(count: u32) -> Vec<T::AccountId> {
+	let origin = T::FeedOrigin::successful_origin();
+	(0..count)
+		.map(|index| {
+			let feeder: T::AccountId = account("feeder", index, 0u32);
+			Pallet::<T>::add_feeder(origin.clone(), feeder.clone())
+				.expect("MaxFeeders is not reached");
+			feeder
+		})
+		.collect()
+}
+
+/// Records a fresh price of the `index`th asset submitted by `feeder`
+fn insert_price<T: Config>(feeder: &T::AccountId, index: u32) {
+	Prices::<T>::insert(
+		feeder,
+		fed_asset(index),
+		PriceSubmission {
+			price: FixedU128::from(2),
+			submitted_at: frame_system::Pallet::<T>::block_number(),
+		},
+	);
+}
+
+benchmarks! {
+	add_feeder {
+		let origin = T::FeedOrigin::successful_origin();
+		// Adding the last feeder moves the largest list
+		add_feeders::<T>(T::MaxFeeders::get() - 1);
+		let feeder: T::AccountId = whitelisted_caller();
+	}: _<T::Origin>(origin, feeder.clone())
+	verify {
+		assert!(Pallet::<T>::feeders().contains(&feeder));
+	}
+
+	remove_feeder {
+		let a in 0..T::MaxFedAssets::get();
+		let origin = T::FeedOrigin::successful_origin();
+		let feeder = add_feeders::<T>(T::MaxFeeders::get())
+			.pop()
+			.expect("MaxFeeders is not zero");
+		for index in 0..a {
+			feed_asset::<T>(index);
+			insert_price::<T>(&feeder, index);
+		}
+	}: _<T::Origin>(origin, feeder.clone())
+	verify {
+		assert!(!Pallet::<T>::feeders().contains(&feeder));
+		assert_eq!(Prices::<T>::iter_prefix(&feeder).count(), 0);
+	}
+
+	set_fee_bounds {
+		let origin = T::FeedOrigin::successful_origin();
+		for index in 1..T::MaxFedAssets::get() {
+			feed_asset::<T>(index);
+		}
+		let asset = fed_asset(0);
+	}: _<T::Origin>(
+		origin,
+		Box::new(VersionedMultiLocation::V1(asset.clone())),
+		1u128,
+		u128::MAX
+	)
+	verify {
+		assert!(Pallet::<T>::fee_bounds(asset).is_some());
+	}
+
+	remove_fee_bounds {
+		let f in 0..T::MaxFeeders::get();
+		let origin = T::FeedOrigin::successful_origin();
+		feed_asset::<T>(0);
+		for feeder in add_feeders::<T>(f) {
+			insert_price::<T>(&feeder, 0);
+		}
+		let asset = fed_asset(0);
+	}: _<T::Origin>(origin, Box::new(VersionedMultiLocation::V1(asset.clone())))
+	verify {
+		assert!(Pallet::<T>::fee_bounds(&asset).is_none());
+	}
+
+	submit_price {
+		let f in 1..T::MaxFeeders::get();
+		feed_asset::<T>(0);
+		let mut feeders = add_feeders::<T>(f);
+		// All the feeders already submitted a fresh price, so all of them are aggregated
+		for feeder in &feeders {
+			insert_price::<T>(feeder, 0);
+		}
+		let feeder = feeders.pop().expect("f is not zero");
+		let asset = fed_asset(0);
+	}: _(
+		RawOrigin::Signed(feeder.clone()),
+		Box::new(VersionedMultiLocation::V1(asset.clone())),
+		FixedU128::from(3)
+	)
+	verify {
+		assert_eq!(
+			Pallet::<T>::price(&feeder, &asset).map(|submission| submission.price),
+			Some(FixedU128::from(3))
+		);
+	}
+}
+
+impl_benchmark_test_suite!(
+	Pallet,
+	crate::mock::ExtBuilder::default().build(),
+	crate::mock::Test
+);
@@ -0,0 +1,369 @@
+// Copyright 2019-2022 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+//! # Asset Price Feed Pallet
+//!
+//! This pallet keeps the fees charged in XCM fee assets in line with their market price, instead
+//! of relying on governance to update them manually.
+//!
+//! A set of feeders, managed by FeedOrigin, submit the price of fee assets relative to the
+//! native token, i.e., how many units of the asset one unit of the native token is worth. Every
+//! submission aggregates the prices of the asset that are not older than MaxPriceAge blocks.
+//! If at least MinPrices of them are available, their (lower) median is used to convert
+//! NativeFeePerSecond into a fee per second in the asset, which is then clamped within the bounds
+//! set by FeedOrigin for that asset and handed to FeePerSecondUpdater. In the runtimes the
+//! updater is AssetManager, which updates AssetTypeUnitsPerSecond. Only fees that were already
+//! set are updated, so governance still decides which assets are accepted for fee payment.
+//!
+//! The fees paid to destination chains (DestinationAssetFeePerSecond in XcmTransactor) are not
+//! fed. They are what the destination chain charges for its own weight, i.e., its native fee per
+//! weight converted into the fee asset at the destination's own rate. Neither depends on the
+//! native token of this chain, so a price relative to it says nothing about them: the same
+//! price of DOT in the native token is compatible with any fee the relay chain decides to
+//! charge. Deriving them from NativeFeePerSecond would instead make transacts fail (underpaid)
+//! or overpay whenever this chain's native token moves, so they are still set by governance
+//! through set_fee_per_second.
+//!
+//! At most MaxFedAssets assets can be fed, which bounds the prices removed with a feeder.
+//!
+//! This pallet has five extrinsics:
+//! add_feeder: which allows an account to submit prices
+//! remove_feeder: which removes an account from the feeders, together with its prices
+//! set_fee_bounds: which starts feeding an asset and sets the bounds its fee per second is
+//! clamped within
+//! remove_fee_bounds: which stops feeding an asset
+//! submit_price: which submits the price of an asset and updates its fee per second
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame_support::pallet;
+pub use pallet::*;
+#[cfg(any(test, feature = "runtime-benchmarks"))]
+mod benchmarks;
+#[cfg(test)]
+pub mod mock;
+#[cfg(test)]
+pub mod tests;
+pub mod weights;
+
+#[pallet]
+pub mod pallet {
+	use crate::weights::WeightInfo;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::{
+		traits::{Saturating, Zero},
+		FixedPointNumber, FixedU128,
+	};
+	use sp_std::{boxed::Box, vec::Vec};
+	use xcm::{latest::MultiLocation, VersionedMultiLocation};
+	use xcm_primitives::UpdateFeePerSecond;
+
+	#[pallet::pallet]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	/// Bounds the fee per second of a fed asset is clamped within
+	#[derive(Clone, Encode, Decode, RuntimeDebug, PartialEq, Eq, scale_info::TypeInfo)]
+	pub struct FeeBounds {
+		pub min_fee_per_second: u128,
+		pub max_fee_per_second: u128,
+	}
+
+	/// A price submitted by a feeder, together with the block it was submitted at
+	#[derive(Clone, Encode, Decode, RuntimeDebug, PartialEq, Eq, scale_info::TypeInfo)]
+	pub struct PriceSubmission<BlockNumber> {
+		pub price: FixedU128,
+		pub submitted_at: BlockNumber,
+	}
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Origin that is allowed to manage the feeders and the fee bounds of fed assets
+		type FeedOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The amount of native token charged per second of execution
+		type NativeFeePerSecond: Get<u128>;
+
+		/// Where the fees per second computed from the submitted prices are applied
+		type FeePerSecondUpdater: UpdateFeePerSecond;
+
+		/// The maximum number of feeders
+		#[pallet::constant]
+		type MaxFeeders: Get<u32>;
+
+		/// The maximum number of fed assets
+		#[pallet::constant]
+		type MaxFedAssets: Get<u32>;
+
+		/// The minimum number of fresh prices needed to update the fee per second of an asset
+		#[pallet::constant]
+		type MinPrices: Get<u32>;
+
+		/// The number of blocks after which a submitted price is considered stale
+		#[pallet::constant]
+		type MaxPriceAge: Get<Self::BlockNumber>;
+
+		/// Weight information for extrinsics in this pallet
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		BadVersion,
+		FeederAlreadyExists,
+		FeederNotFound,
+		TooManyFeeders,
+		NotFeeder,
+		InvalidFeeBounds,
+		TooManyFedAssets,
+		AssetNotFed,
+		ZeroPrice,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Feeder added
+		FeederAdded { feeder: T::AccountId },
+		/// Feeder removed
+		FeederRemoved { feeder: T::AccountId },
+		/// Fee bounds of an asset set
+		FeeBoundsSet {
+			asset: MultiLocation,
+			min_fee_per_second: u128,
+			max_fee_per_second: u128,
+		},
+		/// Fee bounds of an asset removed, its fees are no longer updated
+		FeeBoundsRemoved { asset: MultiLocation },
+		/// A feeder submitted the price of an asset
+		PriceSubmitted {
+			feeder: T::AccountId,
+			asset: MultiLocation,
+			price: FixedU128,
+		},
+		/// The fee per second of an asset was recomputed from its median price
+		FeePerSecondUpdated {
+			asset: MultiLocation,
+			median_price: FixedU128,
+			fee_per_second: u128,
+		},
+	}
+
+	/// The accounts allowed to submit prices, sorted
+	#[pallet::storage]
+	#[pallet::getter(fn feeders)]
+	pub type Feeders<T: Config> = StorageValue<_, Vec<T::AccountId>, ValueQuery>;
+
+	/// The fed assets and the bounds their fee per second is clamped within
+	#[pallet::storage]
+	#[pallet::getter(fn fee_bounds)]
+	pub type AssetFeeBounds<T: Config> =
+		CountedStorageMap<_, Blake2_128Concat, MultiLocation, FeeBounds>;
+
+	/// The last price submitted by each feeder for each asset
+	#[pallet::storage]
+	#[pallet::getter(fn price)]
+	pub type Prices<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		MultiLocation,
+		PriceSubmission<T::BlockNumber>,
+	>;
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Allow `feeder` to submit prices
+		#[pallet::weight(T::WeightInfo::add_feeder())]
+		pub fn add_feeder(origin: OriginFor<T>, feeder: T::AccountId) -> DispatchResult {
+			T::FeedOrigin::ensure_origin(origin)?;
+
+			let mut feeders = Feeders::<T>::get();
+			let index = feeders
+				.binary_search(&feeder)
+				.err()
+				.ok_or(Error::<T>::FeederAlreadyExists)?;
+			ensure!(
+				feeders.len() < T::MaxFeeders::get() as usize,
+				Error::<T>::TooManyFeeders
+			);
+			feeders.insert(index, feeder.clone());
+			Feeders::<T>::put(feeders);
+
+			Self::deposit_event(Event::FeederAdded { feeder });
+			Ok(())
+		}
+
+		/// Remove `feeder` together with the prices it submitted, at most one per fed asset
+		#[pallet::weight(T::WeightInfo::remove_feeder(T::MaxFedAssets::get()))]
+		pub fn remove_feeder(origin: OriginFor<T>, feeder: T::AccountId) -> DispatchResult {
+			T::FeedOrigin::ensure_origin(origin)?;
+
+			let mut feeders = Feeders::<T>::get();
+			let index = feeders
+				.binary_search(&feeder)
+				.map_err(|_| Error::<T>::FeederNotFound)?;
+			feeders.remove(index);
+			Feeders::<T>::put(feeders);
+
+			let _ = Prices::<T>::clear_prefix(&feeder, u32::MAX, None);
+
+			Self::deposit_event(Event::FeederRemoved { feeder });
+			Ok(())
+		}
+
+		/// Start feeding `asset`, clamping its fee per second within the given bounds
+		#[pallet::weight(T::WeightInfo::set_fee_bounds())]
+		pub fn set_fee_bounds(
+			origin: OriginFor<T>,
+			asset: Box<VersionedMultiLocation>,
+			min_fee_per_second: u128,
+			max_fee_per_second: u128,
+		) -> DispatchResult {
+			T::FeedOrigin::ensure_origin(origin)?;
+
+			ensure!(
+				!max_fee_per_second.is_zero() && min_fee_per_second <= max_fee_per_second,
+				Error::<T>::InvalidFeeBounds
+			);
+			let asset = MultiLocation::try_from(*asset).map_err(|()| Error::<T>::BadVersion)?;
+			ensure!(
+				AssetFeeBounds::<T>::contains_key(&asset)
+					|| AssetFeeBounds::<T>::count() < T::MaxFedAssets::get(),
+				Error::<T>::TooManyFedAssets
+			);
+
+			AssetFeeBounds::<T>::insert(
+				&asset,
+				FeeBounds {
+					min_fee_per_second,
+					max_fee_per_second,
+				},
+			);
+
+			Self::deposit_event(Event::FeeBoundsSet {
+				asset,
+				min_fee_per_second,
+				max_fee_per_second,
+			});
+			Ok(())
+		}
+
+		/// Stop feeding `asset`, removing the prices submitted for it
+		#[pallet::weight(T::WeightInfo::remove_fee_bounds(T::MaxFeeders::get()))]
+		pub fn remove_fee_bounds(
+			origin: OriginFor<T>,
+			asset: Box<VersionedMultiLocation>,
+		) -> DispatchResult {
+			T::FeedOrigin::ensure_origin(origin)?;
+
+			let asset = MultiLocation::try_from(*asset).map_err(|()| Error::<T>::BadVersion)?;
+			ensure!(
+				AssetFeeBounds::<T>::contains_key(&asset),
+				Error::<T>::AssetNotFed
+			);
+
+			AssetFeeBounds::<T>::remove(&asset);
+			for feeder in Feeders::<T>::get() {
+				Prices::<T>::remove(&feeder, &asset);
+			}
+
+			Self::deposit_event(Event::FeeBoundsRemoved { asset });
+			Ok(())
+		}
+
+		/// Submit the price of `asset` relative to the native token, i.e., how many units of
+		/// `asset` one unit of the native token is worth. The fee per second of `asset` is
+		/// recomputed if enough fresh prices are available
+		// The update made by FeePerSecondUpdater is not benchmarked, as it depends on the runtime
+		#[pallet::weight(T::WeightInfo::submit_price(T::MaxFeeders::get())
+			.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+		)]
+		pub fn submit_price(
+			origin: OriginFor<T>,
+			asset: Box<VersionedMultiLocation>,
+			price: FixedU128,
+		) -> DispatchResult {
+			let feeder = ensure_signed(origin)?;
+
+			let feeders = Feeders::<T>::get();
+			ensure!(
+				feeders.binary_search(&feeder).is_ok(),
+				Error::<T>::NotFeeder
+			);
+			ensure!(!price.is_zero(), Error::<T>::ZeroPrice);
+			let asset = MultiLocation::try_from(*asset).map_err(|()| Error::<T>::BadVersion)?;
+			let bounds = AssetFeeBounds::<T>::get(&asset).ok_or(Error::<T>::AssetNotFed)?;
+
+			Prices::<T>::insert(
+				&feeder,
+				&asset,
+				PriceSubmission {
+					price,
+					submitted_at: frame_system::Pallet::<T>::block_number(),
+				},
+			);
+
+			Self::deposit_event(Event::PriceSubmitted {
+				feeder,
+				asset: asset.clone(),
+				price,
+			});
+
+			if let Some(median_price) = Self::median_price(&feeders, &asset) {
+				let fee_per_second = median_price
+					.saturating_mul_int(T::NativeFeePerSecond::get())
+					.clamp(bounds.min_fee_per_second, bounds.max_fee_per_second);
+
+				if T::FeePerSecondUpdater::update_fee_per_second(&asset, fee_per_second) {
+					Self::deposit_event(Event::FeePerSecondUpdated {
+						asset,
+						median_price,
+						fee_per_second,
+					});
+				}
+			}
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The lower median of the fresh prices submitted for `asset` by `feeders`. Returns None
+		/// if there are fewer than MinPrices of them
+		pub fn median_price(feeders: &[T::AccountId], asset: &MultiLocation) -> Option<FixedU128> {
+			let now = frame_system::Pallet::<T>::block_number();
+			let mut prices: Vec<FixedU128> = feeders
+				.iter()
+				.filter_map(|feeder| Prices::<T>::get(feeder, asset))
+				.filter(|submission| {
+					now.saturating_sub(submission.submitted_at) <= T::MaxPriceAge::get()
+				})
+				.map(|submission| submission.price)
+				.collect();
+
+			if prices.is_empty() || prices.len() < T::MinPrices::get() as usize {
+				return None;
+			}
+
+			prices.sort();
+			Some(prices[(prices.len() - 1) / 2])
+		}
+	}
+}
@@ -0,0 +1,176 @@
+// Copyright 2019-2022 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A minimal runtime including the asset-price-feed pallet
+use super::*;
+use crate as pallet_asset_price_feed;
+use frame_support::{construct_runtime, parameter_types, traits::Everything, weights::Weight};
+use frame_system::EnsureRoot;
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+	Perbill,
+};
+use sp_std::{cell::RefCell, collections::btree_map::BTreeMap};
+use xcm::latest::prelude::*;
+use xcm_primitives::UpdateFeePerSecond;
+
+pub type AccountId = u64;
+pub type BlockNumber = u64;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime to test the pallet.
+construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		AssetPriceFeed: pallet_asset_price_feed::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const MaximumBlockWeight: Weight = 1024;
+	pub const MaximumBlockLength: u32 = 2 * 1024;
+	pub const AvailableBlockRatio: Perbill = Perbill::one();
+	pub const SS58Prefix: u8 = 42;
+}
+impl frame_system::Config for Test {
+	type BaseCallFilter = Everything;
+	type DbWeight = ();
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = BlockNumber;
+	type Call = Call;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type BlockWeights = ();
+	type BlockLength = ();
+	type SS58Prefix = SS58Prefix;
+	type OnSetCode = ();
+	type MaxConsumers = frame_support::traits::ConstU32<16>;
+}
+
+thread_local! {
+	pub static FEES_PER_SECOND: RefCell<BTreeMap<MultiLocation, u128>> =
+		RefCell::new(BTreeMap::new());
+}
+
+/// Fee per second currently charged in `asset` by the mock updater
+pub(crate) fn fee_per_second(asset: &MultiLocation) -> Option<u128> {
+	FEES_PER_SECOND.with(|f| f.borrow().get(asset).copied())
+}
+
+/// Makes the mock updater charge fees in `asset`
+pub(crate) fn set_fee_per_second(asset: MultiLocation, fee_per_second: u128) {
+	FEES_PER_SECOND.with(|f| {
+		f.borrow_mut().insert(asset, fee_per_second);
+	});
+}
+
+/// Updater keeping the fees per second in memory
+pub struct MockFeePerSecondUpdater;
+impl UpdateFeePerSecond for MockFeePerSecondUpdater {
+	fn update_fee_per_second(location: &MultiLocation, fee_per_second: u128) -> bool {
+		FEES_PER_SECOND.with(|f| match f.borrow_mut().get_mut(location) {
+			Some(fee) => {
+				*fee = fee_per_second;
+				true
+			}
+			None => false,
+		})
+	}
+}
+
+parameter_types! {
+	pub const NativeFeePerSecond: u128 = 1_000;
+	pub const MaxFeeders: u32 = 5;
+	pub const MaxFedAssets: u32 = 3;
+	pub const MinPrices: u32 = 2;
+	pub const MaxPriceAge: BlockNumber = 10;
+}
+
+impl Config for Test {
+	type Event = Event;
+	type FeedOrigin = EnsureRoot<AccountId>;
+	type NativeFeePerSecond = NativeFeePerSecond;
+	type FeePerSecondUpdater = MockFeePerSecondUpdater;
+	type MaxFeeders = MaxFeeders;
+	type MaxFedAssets = MaxFedAssets;
+	type MinPrices = MinPrices;
+	type MaxPriceAge = MaxPriceAge;
+	type WeightInfo = ();
+}
+
+#[derive(Default)]
+pub(crate) struct ExtBuilder {
+	feeders: Vec<AccountId>,
+}
+
+impl ExtBuilder {
+	pub(crate) fn with_feeders(mut self, feeders: Vec<AccountId>) -> Self {
+		self.feeders = feeders;
+		self
+	}
+
+	pub(crate) fn build(self) -> sp_io::TestExternalities {
+		let t = frame_system::GenesisConfig::default()
+			.build_storage::<Test>()
+			.expect("Frame system builds valid default genesis config");
+
+		FEES_PER_SECOND.with(|f| f.borrow_mut().clear());
+
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| {
+			System::set_block_number(1);
+			let mut feeders = self.feeders;
+			feeders.sort();
+			Feeders::<Test>::put(feeders);
+		});
+		ext
+	}
+}
+
+pub(crate) fn events() -> Vec<pallet_asset_price_feed::Event<Test>> {
+	System::events()
+		.into_iter()
+		.map(|r| r.event)
+		.filter_map(|e| {
+			if let Event::AssetPriceFeed(inner) = e {
+				Some(inner)
+			} else {
+				None
+			}
+		})
+		.collect::<Vec<_>>()
+}
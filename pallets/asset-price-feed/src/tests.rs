@@ -0,0 +1,309 @@
+// Copyright 2019-2022 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Unit testing
+use crate::mock::{
+	events, fee_per_second, set_fee_per_second, AssetPriceFeed, ExtBuilder, Origin, System, Test,
+};
+use crate::{Error, Event, FeeBounds, PriceSubmission};
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::{DispatchError, FixedU128};
+use xcm::latest::prelude::*;
+
+fn asset() -> MultiLocation {
+	MultiLocation::parent()
+}
+
+fn set_bounds(min: u128, max: u128) {
+	assert_ok!(AssetPriceFeed::set_fee_bounds(
+		Origin::root(),
+		Box::new(asset().into()),
+		min,
+		max
+	));
+}
+
+fn submit(feeder: u64, price: FixedU128) {
+	assert_ok!(AssetPriceFeed::submit_price(
+		Origin::signed(feeder),
+		Box::new(asset().into()),
+		price
+	));
+}
+
+#[test]
+fn add_and_remove_feeders_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(AssetPriceFeed::add_feeder(Origin::root(), 2));
+		assert_ok!(AssetPriceFeed::add_feeder(Origin::root(), 1));
+		assert_eq!(AssetPriceFeed::feeders(), vec![1, 2]);
+
+		assert_noop!(
+			AssetPriceFeed::add_feeder(Origin::root(), 1),
+			Error::<Test>::FeederAlreadyExists
+		);
+
+		assert_ok!(AssetPriceFeed::remove_feeder(Origin::root(), 1));
+		assert_eq!(AssetPriceFeed::feeders(), vec![2]);
+
+		assert_noop!(
+			AssetPriceFeed::remove_feeder(Origin::root(), 1),
+			Error::<Test>::FeederNotFound
+		);
+		assert_eq!(
+			events(),
+			vec![
+				Event::FeederAdded { feeder: 2 },
+				Event::FeederAdded { feeder: 1 },
+				Event::FeederRemoved { feeder: 1 },
+			]
+		);
+	});
+}
+
+#[test]
+fn feeders_are_capped() {
+	ExtBuilder::default()
+		.with_feeders(vec![1, 2, 3, 4, 5])
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				AssetPriceFeed::add_feeder(Origin::root(), 6),
+				Error::<Test>::TooManyFeeders
+			);
+		});
+}
+
+#[test]
+fn only_feed_origin_can_manage_feeds() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			AssetPriceFeed::add_feeder(Origin::signed(1), 1),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			AssetPriceFeed::set_fee_bounds(Origin::signed(1), Box::new(asset().into()), 1, 10),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn set_fee_bounds_checks_bounds() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			AssetPriceFeed::set_fee_bounds(Origin::root(), Box::new(asset().into()), 10, 1),
+			Error::<Test>::InvalidFeeBounds
+		);
+		assert_noop!(
+			AssetPriceFeed::set_fee_bounds(Origin::root(), Box::new(asset().into()), 0, 0),
+			Error::<Test>::InvalidFeeBounds
+		);
+
+		set_bounds(1, 10);
+		assert_eq!(
+			AssetPriceFeed::fee_bounds(asset()),
+			Some(FeeBounds {
+				min_fee_per_second: 1,
+				max_fee_per_second: 10,
+			})
+		);
+	});
+}
+
+#[test]
+fn fed_assets_are_capped() {
+	ExtBuilder::default().build().execute_with(|| {
+		for para_id in 0..3 {
+			assert_ok!(AssetPriceFeed::set_fee_bounds(
+				Origin::root(),
+				Box::new(MultiLocation::new(1, X1(Parachain(para_id))).into()),
+				1,
+				10
+			));
+		}
+
+		assert_noop!(
+			AssetPriceFeed::set_fee_bounds(Origin::root(), Box::new(asset().into()), 1, 10),
+			Error::<Test>::TooManyFedAssets
+		);
+		// The bounds of fed assets can still be updated
+		assert_ok!(AssetPriceFeed::set_fee_bounds(
+			Origin::root(),
+			Box::new(MultiLocation::new(1, X1(Parachain(0))).into()),
+			2,
+			20
+		));
+	});
+}
+
+#[test]
+fn only_feeders_can_submit_prices_of_fed_assets() {
+	ExtBuilder::default()
+		.with_feeders(vec![1])
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				AssetPriceFeed::submit_price(
+					Origin::signed(1),
+					Box::new(asset().into()),
+					FixedU128::from(2)
+				),
+				Error::<Test>::AssetNotFed
+			);
+
+			set_bounds(1, 10_000);
+			assert_noop!(
+				AssetPriceFeed::submit_price(
+					Origin::signed(2),
+					Box::new(asset().into()),
+					FixedU128::from(2)
+				),
+				Error::<Test>::NotFeeder
+			);
+			assert_noop!(
+				AssetPriceFeed::submit_price(
+					Origin::signed(1),
+					Box::new(asset().into()),
+					FixedU128::from(0)
+				),
+				Error::<Test>::ZeroPrice
+			);
+		});
+}
+
+#[test]
+fn fee_per_second_follows_median_price() {
+	ExtBuilder::default()
+		.with_feeders(vec![1, 2, 3])
+		.build()
+		.execute_with(|| {
+			set_fee_per_second(asset(), 1);
+			set_bounds(1, 100_000);
+
+			// A single price is not enough
+			submit(1, FixedU128::from(2));
+			assert_eq!(fee_per_second(&asset()), Some(1));
+
+			submit(2, FixedU128::from(50));
+			assert_eq!(fee_per_second(&asset()), Some(2_000));
+
+			// An outlier does not move the median
+			submit(3, FixedU128::from(3));
+			assert_eq!(fee_per_second(&asset()), Some(3_000));
+
+			assert_eq!(
+				AssetPriceFeed::price(3, asset()),
+				Some(PriceSubmission {
+					price: FixedU128::from(3),
+					submitted_at: 1,
+				})
+			);
+			assert_eq!(
+				events().last(),
+				Some(&Event::FeePerSecondUpdated {
+					asset: asset(),
+					median_price: FixedU128::from(3),
+					fee_per_second: 3_000,
+				})
+			);
+		});
+}
+
+#[test]
+fn fee_per_second_is_clamped_within_bounds() {
+	ExtBuilder::default()
+		.with_feeders(vec![1, 2])
+		.build()
+		.execute_with(|| {
+			set_fee_per_second(asset(), 1);
+			set_bounds(500, 1_500);
+
+			submit(1, FixedU128::from(5));
+			submit(2, FixedU128::from(5));
+			assert_eq!(fee_per_second(&asset()), Some(1_500));
+
+			submit(1, FixedU128::from_rational(1, 10));
+			submit(2, FixedU128::from_rational(1, 10));
+			assert_eq!(fee_per_second(&asset()), Some(500));
+		});
+}
+
+#[test]
+fn stale_prices_are_ignored() {
+	ExtBuilder::default()
+		.with_feeders(vec![1, 2])
+		.build()
+		.execute_with(|| {
+			set_fee_per_second(asset(), 1);
+			set_bounds(1, 100_000);
+
+			submit(1, FixedU128::from(2));
+			System::set_block_number(12);
+			submit(2, FixedU128::from(4));
+
+			// The first price is stale, so there are not enough prices
+			assert_eq!(fee_per_second(&asset()), Some(1));
+
+			submit(1, FixedU128::from(3));
+			assert_eq!(fee_per_second(&asset()), Some(3_000));
+		});
+}
+
+#[test]
+fn fees_not_set_are_not_updated() {
+	ExtBuilder::default()
+		.with_feeders(vec![1, 2])
+		.build()
+		.execute_with(|| {
+			set_bounds(1, 100_000);
+
+			submit(1, FixedU128::from(2));
+			submit(2, FixedU128::from(2));
+
+			assert_eq!(fee_per_second(&asset()), None);
+			assert!(!events()
+				.iter()
+				.any(|e| matches!(e, Event::FeePerSecondUpdated { .. })));
+		});
+}
+
+#[test]
+fn removing_feeders_and_assets_removes_prices() {
+	ExtBuilder::default()
+		.with_feeders(vec![1, 2])
+		.build()
+		.execute_with(|| {
+			set_bounds(1, 100_000);
+			submit(1, FixedU128::from(2));
+			submit(2, FixedU128::from(2));
+
+			assert_ok!(AssetPriceFeed::remove_feeder(Origin::root(), 1));
+			assert!(AssetPriceFeed::price(1, asset()).is_none());
+			assert!(AssetPriceFeed::price(2, asset()).is_some());
+
+			assert_ok!(AssetPriceFeed::remove_fee_bounds(
+				Origin::root(),
+				Box::new(asset().into())
+			));
+			assert!(AssetPriceFeed::fee_bounds(asset()).is_none());
+			assert!(AssetPriceFeed::price(2, asset()).is_none());
+			assert_noop!(
+				AssetPriceFeed::remove_fee_bounds(Origin::root(), Box::new(asset().into())),
+				Error::<Test>::AssetNotFed
+			);
+		});
+}
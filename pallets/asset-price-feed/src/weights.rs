@@ -0,0 +1,173 @@
+// Copyright 2019-2022 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Weights for pallet_asset_price_feed
+//!
+//! The base weights are estimates until regenerated from the benchmarks with:
+// ./target/release/moonbeam
+// benchmark
+// pallet
+// --chain
+// dev
+// --execution=wasm
+// --wasm-execution=compiled
+// --pallet
+// pallet_asset_price_feed
+// --extrinsic
+// *
+// --steps
+// 50
+// --repeat
+// 20
+// --template=./benchmarking/frame-weight-template.hbs
+// --json-file
+// raw.json
+// --output
+// weights.rs
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_asset_price_feed.
+pub trait WeightInfo {
+	#[rustfmt::skip]
+	fn add_feeder() -> Weight;
+	#[rustfmt::skip]
+	fn remove_feeder(a: u32, ) -> Weight;
+	#[rustfmt::skip]
+	fn set_fee_bounds() -> Weight;
+	#[rustfmt::skip]
+	fn remove_fee_bounds(f: u32, ) -> Weight;
+	#[rustfmt::skip]
+	fn submit_price(f: u32, ) -> Weight;
+}
+
+/// Weights for pallet_asset_price_feed using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: AssetPriceFeed Feeders (r:1 w:1)
+	#[rustfmt::skip]
+	fn add_feeder() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: AssetPriceFeed Feeders (r:1 w:1)
+	// Storage: AssetPriceFeed Prices (r:0 w:1)
+	#[rustfmt::skip]
+	fn remove_feeder(a: u32, ) -> Weight {
+		(25_000_000 as Weight)
+			// Standard Error: 0
+			.saturating_add((3_000_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(a as Weight)))
+	}
+	// Storage: AssetPriceFeed AssetFeeBounds (r:1 w:1)
+	// Storage: AssetPriceFeed CounterForAssetFeeBounds (r:1 w:1)
+	#[rustfmt::skip]
+	fn set_fee_bounds() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: AssetPriceFeed AssetFeeBounds (r:1 w:1)
+	// Storage: AssetPriceFeed CounterForAssetFeeBounds (r:1 w:1)
+	// Storage: AssetPriceFeed Feeders (r:1 w:0)
+	// Storage: AssetPriceFeed Prices (r:0 w:1)
+	#[rustfmt::skip]
+	fn remove_fee_bounds(f: u32, ) -> Weight {
+		(25_000_000 as Weight)
+			// Standard Error: 0
+			.saturating_add((2_000_000 as Weight).saturating_mul(f as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(f as Weight)))
+	}
+	// Storage: AssetPriceFeed Feeders (r:1 w:0)
+	// Storage: AssetPriceFeed AssetFeeBounds (r:1 w:0)
+	// Storage: AssetPriceFeed Prices (r:1 w:1)
+	#[rustfmt::skip]
+	fn submit_price(f: u32, ) -> Weight {
+		(40_000_000 as Weight)
+			// Standard Error: 0
+			.saturating_add((5_000_000 as Weight).saturating_mul(f as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(f as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	// Storage: AssetPriceFeed Feeders (r:1 w:1)
+	#[rustfmt::skip]
+	fn add_feeder() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: AssetPriceFeed Feeders (r:1 w:1)
+	// Storage: AssetPriceFeed Prices (r:0 w:1)
+	#[rustfmt::skip]
+	fn remove_feeder(a: u32, ) -> Weight {
+		(25_000_000 as Weight)
+			// Standard Error: 0
+			.saturating_add((3_000_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(a as Weight)))
+	}
+	// Storage: AssetPriceFeed AssetFeeBounds (r:1 w:1)
+	// Storage: AssetPriceFeed CounterForAssetFeeBounds (r:1 w:1)
+	#[rustfmt::skip]
+	fn set_fee_bounds() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: AssetPriceFeed AssetFeeBounds (r:1 w:1)
+	// Storage: AssetPriceFeed CounterForAssetFeeBounds (r:1 w:1)
+	// Storage: AssetPriceFeed Feeders (r:1 w:0)
+	// Storage: AssetPriceFeed Prices (r:0 w:1)
+	#[rustfmt::skip]
+	fn remove_fee_bounds(f: u32, ) -> Weight {
+		(25_000_000 as Weight)
+			// Standard Error: 0
+			.saturating_add((2_000_000 as Weight).saturating_mul(f as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(f as Weight)))
+	}
+	// Storage: AssetPriceFeed Feeders (r:1 w:0)
+	// Storage: AssetPriceFeed AssetFeeBounds (r:1 w:0)
+	// Storage: AssetPriceFeed Prices (r:1 w:1)
+	#[rustfmt::skip]
+	fn submit_price(f: u32, ) -> Weight {
+		(40_000_000 as Weight)
+			// Standard Error: 0
+			.saturating_add((5_000_000 as Weight).saturating_mul(f as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(f as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+}
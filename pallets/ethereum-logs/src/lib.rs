@@ -0,0 +1,178 @@
+// Copyright 2019-2022 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+//! # Ethereum Logs Pallet
+//!
+//! This pallet includes the Ethereum logs of state changes made outside of the EVM (e.g. XC-20s
+//! minted through XCM) in the Ethereum block, so that indexers relying on logs see them.
+//!
+//! Logs are recorded through the EthereumLogsRecorder implementation of the pallet, which only
+//! appends them to RecordedLogs. In on_finalize, all the logs recorded during the block are
+//! appended to the pending transactions of pallet_ethereum as a single synthetic transaction,
+//! which does not use any gas and carries the same invalid signature as the Xcm transact
+//! transactions. The weight of the emission is registered when the first logs of the block are
+//! recorded.
+//!
+//! pallet_ethereum does not expose its pending transactions for writing, so they are read and
+//! written back through its storage. The pallet must therefore be finalized before
+//! pallet_ethereum, i.e., be declared after it in construct_runtime.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame_support::pallet;
+pub use pallet::*;
+#[cfg(test)]
+pub mod mock;
+#[cfg(test)]
+pub mod tests;
+
+#[pallet]
+pub mod pallet {
+	use ethereum::{EIP658ReceiptData, LegacyTransaction, TransactionSignature};
+	use ethereum_types::{Bloom, BloomInput, H160, U256};
+	use frame_support::{
+		pallet_prelude::*, storage::migration, traits::PalletInfoAccess, weights::DispatchClass,
+	};
+	use frame_system::pallet_prelude::*;
+	use pallet_ethereum::{Log, Receipt, Transaction, TransactionAction, TransactionStatus};
+	use sp_std::vec::Vec;
+	use xcm_primitives::{rs_id, EthereumLogsRecorder};
+
+	#[pallet::pallet]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_ethereum::Config {}
+
+	/// Logs recorded during the current block, emitted in on_finalize
+	#[pallet::storage]
+	#[pallet::getter(fn recorded_logs)]
+	pub type RecordedLogs<T: Config> = StorageValue<_, Vec<Log>, ValueQuery>;
+
+	/// Nonce of the synthetic transactions carrying the recorded logs
+	#[pallet::storage]
+	#[pallet::getter(fn nonce)]
+	pub type Nonce<T: Config> = StorageValue<_, U256, ValueQuery>;
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(_n: T::BlockNumber) -> Weight {
+			// Checking for recorded logs in on_finalize
+			T::DbWeight::get().reads(1)
+		}
+
+		fn on_finalize(_n: T::BlockNumber) {
+			if RecordedLogs::<T>::exists() {
+				Self::emit_logs(RecordedLogs::<T>::take());
+			}
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Weight of emitting the recorded logs, on top of checking for them
+		pub(crate) fn emission_weight() -> Weight {
+			// Nonce and pending transactions read, recorded logs, nonce and pending transactions
+			// written
+			T::DbWeight::get().reads_writes(2, 3)
+		}
+
+		/// Appends the synthetic transaction carrying `logs` to the pending Ethereum transactions
+		fn emit_logs(logs: Vec<Log>) {
+			let nonce = Nonce::<T>::get();
+			let signature = match TransactionSignature::new(42, rs_id(), rs_id()) {
+				Some(signature) => signature,
+				None => return,
+			};
+			let transaction = Transaction::Legacy(LegacyTransaction {
+				nonce,
+				gas_price: U256::zero(),
+				gas_limit: U256::zero(),
+				action: TransactionAction::Call(H160::zero()),
+				value: U256::zero(),
+				input: Vec::new(),
+				signature,
+			});
+			// Each synthetic transaction needs its own nonce to get a unique hash
+			Nonce::<T>::put(nonce.saturating_add(U256::one()));
+
+			let mut logs_bloom = Bloom::default();
+			for log in &logs {
+				logs_bloom.accrue(BloomInput::Raw(&log.address[..]));
+				for topic in &log.topics {
+					logs_bloom.accrue(BloomInput::Raw(&topic[..]));
+				}
+			}
+
+			let mut pending = pallet_ethereum::Pallet::<T>::pending();
+			// No gas is used, so the cumulative gas used is the one of the previous transaction
+			let used_gas = pending
+				.last()
+				.map(|(_, _, receipt)| match receipt {
+					Receipt::Legacy(data) | Receipt::EIP2930(data) | Receipt::EIP1559(data) => {
+						data.used_gas
+					}
+				})
+				.unwrap_or_default();
+
+			let status = TransactionStatus {
+				transaction_hash: transaction.hash(),
+				transaction_index: pending.len() as u32,
+				from: H160::zero(),
+				to: Some(H160::zero()),
+				contract_address: None,
+				logs: logs.clone(),
+				logs_bloom,
+			};
+			let receipt = Receipt::Legacy(EIP658ReceiptData {
+				status_code: 1,
+				used_gas,
+				logs_bloom,
+				logs,
+			});
+
+			pending.push((transaction, status, receipt));
+			migration::put_storage_value(
+				<pallet_ethereum::Pallet<T> as PalletInfoAccess>::name().as_bytes(),
+				b"Pending",
+				&[],
+				pending,
+			);
+		}
+	}
+
+	impl<T: Config> EthereumLogsRecorder for Pallet<T> {
+		fn record_logs(logs: Vec<Log>) {
+			if logs.is_empty() {
+				return;
+			}
+
+			let mut weight = T::DbWeight::get().reads_writes(1, 1);
+			if !RecordedLogs::<T>::exists() {
+				// First logs of the block, which will be emitted in on_finalize
+				weight = weight.saturating_add(Self::emission_weight());
+			}
+			frame_system::Pallet::<T>::register_extra_weight_unchecked(
+				weight,
+				DispatchClass::Mandatory,
+			);
+
+			for log in logs {
+				RecordedLogs::<T>::append(log);
+			}
+		}
+	}
+}
@@ -0,0 +1,117 @@
+// Copyright 2019-2022 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Unit testing
+use crate::mock::*;
+use crate::*;
+use frame_support::{
+	traits::{Get, OnFinalize},
+	weights::DispatchClass,
+};
+use pallet_ethereum::{Log, Receipt};
+use sp_core::{H160, H256, U256};
+use xcm_primitives::EthereumLogsRecorder;
+
+fn log(byte: u8) -> Log {
+	Log {
+		address: H160::repeat_byte(byte),
+		topics: vec![H256::repeat_byte(byte)],
+		data: vec![byte],
+	}
+}
+
+fn mandatory_weight() -> u64 {
+	*System::block_weight().get(DispatchClass::Mandatory)
+}
+
+#[test]
+fn logs_are_emitted_once_per_block() {
+	new_test_ext().execute_with(|| {
+		EthereumLogs::record_logs(vec![log(1)]);
+		EthereumLogs::record_logs(vec![log(2), log(3)]);
+
+		// Nothing is emitted until the block is finalized
+		assert_eq!(EthereumLogs::recorded_logs(), vec![log(1), log(2), log(3)]);
+		assert!(Ethereum::pending().is_empty());
+
+		EthereumLogs::on_finalize(1);
+
+		let pending = Ethereum::pending();
+		assert_eq!(pending.len(), 1);
+		let (_, status, receipt) = &pending[0];
+		assert_eq!(status.transaction_index, 0);
+		assert_eq!(status.logs, vec![log(1), log(2), log(3)]);
+		match receipt {
+			Receipt::Legacy(data) => {
+				assert_eq!(data.status_code, 1);
+				assert_eq!(data.used_gas, U256::zero());
+				assert_eq!(data.logs, vec![log(1), log(2), log(3)]);
+			}
+			_ => panic!("synthetic transactions have legacy receipts"),
+		}
+		assert!(EthereumLogs::recorded_logs().is_empty());
+		assert_eq!(EthereumLogs::nonce(), U256::one());
+	});
+}
+
+#[test]
+fn synthetic_transactions_follow_the_pending_ones() {
+	new_test_ext().execute_with(|| {
+		EthereumLogs::record_logs(vec![log(1)]);
+		EthereumLogs::on_finalize(1);
+		EthereumLogs::record_logs(vec![log(2)]);
+		EthereumLogs::on_finalize(1);
+
+		let pending = Ethereum::pending();
+		assert_eq!(pending.len(), 2);
+		let (_, status, _) = &pending[1];
+		assert_eq!(status.transaction_index, 1);
+		assert_eq!(status.logs, vec![log(2)]);
+		// Different nonces give different hashes
+		assert_ne!(pending[0].1.transaction_hash, status.transaction_hash);
+	});
+}
+
+#[test]
+fn nothing_is_emitted_without_logs() {
+	new_test_ext().execute_with(|| {
+		EthereumLogs::record_logs(vec![]);
+		EthereumLogs::on_finalize(1);
+
+		assert!(Ethereum::pending().is_empty());
+		assert_eq!(EthereumLogs::nonce(), U256::zero());
+		assert_eq!(mandatory_weight(), 0);
+	});
+}
+
+#[test]
+fn emission_weight_is_registered_once_per_block() {
+	new_test_ext().execute_with(|| {
+		let record_weight = <Test as frame_system::Config>::DbWeight::get().reads_writes(1, 1);
+
+		EthereumLogs::record_logs(vec![log(1)]);
+		assert_eq!(
+			mandatory_weight(),
+			record_weight + EthereumLogs::emission_weight()
+		);
+
+		EthereumLogs::record_logs(vec![log(2)]);
+		assert_eq!(
+			mandatory_weight(),
+			2 * record_weight + EthereumLogs::emission_weight()
+		);
+	});
+}
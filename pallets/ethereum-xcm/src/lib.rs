@@ -17,6 +17,15 @@
 //! # Ethereum Xcm pallet
 //!
 //! The Xcm Ethereum pallet is a bridge for Xcm Transact to Ethereum pallet
+//!
+//! The `*_with_response` variants of the transact calls additionally report the outcome of the
+//! EVM execution back to the origin of the XCM message as an XCM `QueryResponse`, so the sender
+//! can match it with the query id it provided. The response destination must convert to the
+//! dispatching account, so nobody can send responses to other locations. A transaction that
+//! cannot be dispatched is reported as a failure as well. XCM v2 responses can only carry a
+//! success or failure, so the full execution receipt and the revert data are emitted in the
+//! `ExecutionResponseSent` event. The revert data is recorded during the execution by
+//! `RecordRevertData`, which must wrap the `pallet_evm::Config::Runner` of the runtime.
 
 // Ensure we're `no_std` when compiling for Wasm.
 #![cfg_attr(not(feature = "std"), no_std)]
@@ -27,7 +36,10 @@ mod mock;
 #[cfg(all(feature = "std", test))]
 mod tests;
 
-use ethereum_types::{H160, U256};
+mod runner;
+pub use runner::RecordRevertData;
+
+use ethereum_types::{H160, H256, U256};
 use fp_ethereum::{TransactionData, ValidatedTransaction};
 use fp_evm::{CheckEvmTransaction, CheckEvmTransactionConfig, InvalidEvmTransactionError};
 #[cfg(feature = "try-runtime")]
@@ -36,13 +48,19 @@ use frame_support::{
 	codec::{Decode, Encode, MaxEncodedLen},
 	dispatch::DispatchResultWithPostInfo,
 	scale_info::TypeInfo,
+	storage::{with_transaction, TransactionOutcome},
 	traits::{EnsureOrigin, Get},
 	weights::{Pays, PostDispatchInfo, Weight},
 };
 use frame_system::pallet_prelude::OriginFor;
 use pallet_evm::{AddressMapping, GasWeightMapping};
-use sp_runtime::{traits::UniqueSaturatedInto, DispatchErrorWithPostInfo, RuntimeDebug};
+use sp_runtime::{
+	traits::UniqueSaturatedInto, DispatchError, DispatchErrorWithPostInfo, RuntimeDebug,
+};
 use sp_std::{marker::PhantomData, prelude::*};
+use xcm::latest::prelude::*;
+use xcm::VersionedMultiLocation;
+use xcm_executor::traits::Convert;
 
 pub use ethereum::{
 	AccessListItem, BlockV2 as Block, LegacyTransactionMessage, Log, ReceiptV3 as Receipt,
@@ -51,6 +69,32 @@ pub use ethereum::{
 pub use fp_rpc::TransactionStatus;
 pub use xcm_primitives::{EnsureProxy, EthereumXcmTransaction, XcmToEthereum};
 
+/// Gives access to the Ethereum transactions applied so far in the current block.
+pub trait PendingEthereumTransactions {
+	fn pending() -> Vec<(Transaction, TransactionStatus, Receipt)>;
+}
+
+/// Where to report the outcome of an Ethereum-XCM execution.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct EthereumXcmResponseInfo {
+	/// The location the response is sent to, which must convert to the dispatching account.
+	pub destination: VersionedMultiLocation,
+	/// The identifier the sender uses to match the response.
+	pub query_id: QueryId,
+	/// The maximum weight the response can use at the destination.
+	pub max_weight: Weight,
+}
+
+/// Outcome of an EVM execution triggered through XCM.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct EthereumXcmReceipt {
+	pub transaction_hash: H256,
+	pub succeeded: bool,
+	pub used_gas: U256,
+	pub contract_address: Option<H160>,
+	pub logs: Vec<Log>,
+}
+
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub enum RawOrigin {
 	XcmEthereumTransaction(H160),
@@ -92,6 +136,8 @@ pub mod pallet {
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config + pallet_timestamp::Config + pallet_evm::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 		/// Invalid transaction error
 		type InvalidEvmTransactionError: From<InvalidEvmTransactionError>;
 		/// Handler for applying an already validated transaction
@@ -104,6 +150,13 @@ pub mod pallet {
 		type EnsureProxy: EnsureProxy<Self::AccountId>;
 		/// The origin that is allowed to resume or suspend the XCM to Ethereum executions.
 		type ControllerOrigin: EnsureOrigin<Self::Origin>;
+		/// The Ethereum transactions applied in the current block, used to build receipts
+		type PendingTransactions: PendingEthereumTransactions;
+		/// How to send the execution responses
+		type XcmSender: SendXcm;
+		/// How to convert a response destination to the account allowed to request responses
+		/// sent to it
+		type LocationToAccountId: Convert<MultiLocation, Self::AccountId>;
 	}
 
 	#[pallet::pallet]
@@ -127,6 +180,34 @@ pub mod pallet {
 	pub enum Error<T> {
 		/// Xcm to Ethereum execution is suspended
 		EthereumXcmExecutionSuspended,
+		/// The version of the response destination is not supported
+		BadVersion,
+		/// The response destination does not convert to the dispatching account
+		BadResponseDestination,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The outcome of an execution was reported to the destination.
+		/// `revert_data` is the output of a reverted execution, and is empty otherwise.
+		ExecutionResponseSent {
+			query_id: QueryId,
+			destination: MultiLocation,
+			receipt: EthereumXcmReceipt,
+			revert_data: Vec<u8>,
+		},
+		/// The transaction could not be dispatched, which was reported to the destination.
+		DispatchFailureResponseSent {
+			query_id: QueryId,
+			destination: MultiLocation,
+			error: DispatchError,
+		},
+		/// The outcome of an execution could not be reported to the destination.
+		ExecutionResponseFailed {
+			query_id: QueryId,
+			destination: MultiLocation,
+		},
 	}
 
 	#[pallet::call]
@@ -222,10 +303,208 @@ pub mod pallet {
 
 			Ok(())
 		}
+
+		/// Xcm Transact an Ethereum transaction and report its outcome to
+		/// `response.destination`, which must convert to the dispatching account.
+		/// A transaction that cannot be dispatched is reported as a failure, and does not fail
+		/// the call.
+		/// Weight: Gas limit plus the db reads and writes of `transact` and the response sending
+		#[pallet::weight(<T as pallet_evm::Config>::GasWeightMapping::gas_to_weight({
+			match xcm_transaction {
+				EthereumXcmTransaction::V1(v1_tx) =>  v1_tx.gas_limit.unique_saturated_into(),
+				EthereumXcmTransaction::V2(v2_tx) =>  v2_tx.gas_limit.unique_saturated_into()
+			}
+		}).saturating_add(T::DbWeight::get().reads_writes(2, 1)))]
+		pub fn transact_with_response(
+			origin: OriginFor<T>,
+			xcm_transaction: EthereumXcmTransaction,
+			response: EthereumXcmResponseInfo,
+		) -> DispatchResultWithPostInfo {
+			Self::transact_and_respond(origin, None, xcm_transaction, response)
+		}
+
+		/// Xcm Transact an Ethereum transaction through proxy and report its outcome to
+		/// `response.destination`, which must convert to the dispatching account.
+		/// A transaction that cannot be dispatched is reported as a failure, and does not fail
+		/// the call.
+		/// Weight: Gas limit plus the db reads and writes of `transact_through_proxy` and the
+		/// response sending
+		#[pallet::weight(<T as pallet_evm::Config>::GasWeightMapping::gas_to_weight({
+			match xcm_transaction {
+				EthereumXcmTransaction::V1(v1_tx) =>  v1_tx.gas_limit.unique_saturated_into(),
+				EthereumXcmTransaction::V2(v2_tx) =>  v2_tx.gas_limit.unique_saturated_into()
+			}
+		}).saturating_add(T::DbWeight::get().reads_writes(3, 1)))]
+		pub fn transact_through_proxy_with_response(
+			origin: OriginFor<T>,
+			transact_as: H160,
+			xcm_transaction: EthereumXcmTransaction,
+			response: EthereumXcmResponseInfo,
+		) -> DispatchResultWithPostInfo {
+			Self::transact_and_respond(origin, Some(transact_as), xcm_transaction, response)
+		}
+	}
+}
+
+impl<T: Config> Pallet<T>
+where
+	OriginFor<T>: Into<Result<RawOrigin, OriginFor<T>>>,
+{
+	/// Applies the transaction, through proxy if `transact_as` is set, and reports its outcome
+	/// to `response.destination`.
+	fn transact_and_respond(
+		origin: OriginFor<T>,
+		transact_as: Option<H160>,
+		xcm_transaction: EthereumXcmTransaction,
+		response: EthereumXcmResponseInfo,
+	) -> DispatchResultWithPostInfo {
+		let source = T::XcmEthereumOrigin::ensure_origin(origin.clone())?;
+		let EthereumXcmResponseInfo {
+			destination,
+			query_id,
+			max_weight,
+		} = response;
+		let destination =
+			MultiLocation::try_from(destination).map_err(|()| Error::<T>::BadVersion)?;
+		// Only the origin of the message can receive the responses it requests
+		frame_support::ensure!(
+			T::LocationToAccountId::convert_ref(&destination)
+				== Ok(T::AddressMapping::into_account_id(source)),
+			Error::<T>::BadResponseDestination
+		);
+
+		// Failed transactions are reported, so their changes must be reverted here
+		let (outcome, revert_data) = runner::recording_revert_data(|| {
+			with_transaction(|| {
+				let result = match transact_as {
+					Some(transact_as) => {
+						Self::transact_through_proxy(origin, transact_as, xcm_transaction)
+					}
+					None => Self::transact(origin, xcm_transaction),
+				};
+				match result {
+					Ok(post_info) => TransactionOutcome::Commit(Ok(Ok(post_info))),
+					Err(error) => TransactionOutcome::Rollback(Ok(Err(error))),
+				}
+			})
+		});
+		let outcome = outcome.map_err(|error: DispatchError| error)?;
+
+		match outcome {
+			Ok(post_info) => {
+				let receipt = match Self::last_receipt() {
+					Some(receipt) => receipt,
+					None => {
+						Self::deposit_event(Event::ExecutionResponseFailed {
+							query_id,
+							destination,
+						});
+						return Ok(Self::with_response_weight(post_info));
+					}
+				};
+				let revert_data = if receipt.succeeded {
+					Vec::new()
+				} else {
+					revert_data
+				};
+				// A reverted or failed EVM execution is reported as `Trap(0)`
+				let error = (!receipt.succeeded).then(|| XcmError::Trap(0));
+				if Self::send_response(destination.clone(), query_id, max_weight, error) {
+					Self::deposit_event(Event::ExecutionResponseSent {
+						query_id,
+						destination,
+						receipt,
+						revert_data,
+					});
+				} else {
+					Self::deposit_event(Event::ExecutionResponseFailed {
+						query_id,
+						destination,
+					});
+				}
+
+				Ok(Self::with_response_weight(post_info))
+			}
+			Err(DispatchErrorWithPostInfo { post_info, error }) => {
+				// A transaction that could not be dispatched is reported as `Trap(1)`
+				if Self::send_response(
+					destination.clone(),
+					query_id,
+					max_weight,
+					Some(XcmError::Trap(1)),
+				) {
+					Self::deposit_event(Event::DispatchFailureResponseSent {
+						query_id,
+						destination,
+						error,
+					});
+				} else {
+					Self::deposit_event(Event::ExecutionResponseFailed {
+						query_id,
+						destination,
+					});
+				}
+
+				Ok(Self::with_response_weight(post_info))
+			}
+		}
 	}
 }
 
 impl<T: Config> Pallet<T> {
+	/// Builds the receipt of the last applied Ethereum transaction.
+	pub fn last_receipt() -> Option<EthereumXcmReceipt> {
+		let pending = T::PendingTransactions::pending();
+		let mut applied = pending
+			.iter()
+			.rev()
+			.map(|(_, status, receipt)| match receipt {
+				Receipt::Legacy(data) | Receipt::EIP2930(data) | Receipt::EIP1559(data) => {
+					(status, data)
+				}
+			});
+		let (status, data) = applied.next()?;
+		// Receipts hold the gas used so far in the block
+		let previous_used_gas = applied
+			.next()
+			.map(|(_, previous)| previous.used_gas)
+			.unwrap_or_default();
+
+		Some(EthereumXcmReceipt {
+			transaction_hash: status.transaction_hash,
+			succeeded: data.status_code == 1,
+			used_gas: data.used_gas.saturating_sub(previous_used_gas),
+			contract_address: status.contract_address,
+			logs: status.logs.clone(),
+		})
+	}
+
+	/// Reports an execution to `destination`, as an error of the first instruction if `error`
+	/// is set. Returns whether the response was sent.
+	fn send_response(
+		destination: MultiLocation,
+		query_id: QueryId,
+		max_weight: Weight,
+		error: Option<XcmError>,
+	) -> bool {
+		let message = Xcm(vec![QueryResponse {
+			query_id,
+			response: Response::ExecutionResult(error.map(|error| (0, error))),
+			max_weight,
+		}]);
+
+		T::XcmSender::send_xcm(destination, message).is_ok()
+	}
+
+	fn with_response_weight(post_info: PostDispatchInfo) -> PostDispatchInfo {
+		PostDispatchInfo {
+			actual_weight: post_info
+				.actual_weight
+				.map(|weight| weight.saturating_add(T::DbWeight::get().reads_writes(1, 1))),
+			pays_fee: post_info.pays_fee,
+		}
+	}
+
 	fn validate_and_apply(
 		source: H160,
 		xcm_transaction: EthereumXcmTransaction,
@@ -32,6 +32,7 @@ use sp_runtime::{
 	traits::{BlakeTwo256, IdentityLookup},
 	AccountId32,
 };
+use std::cell::RefCell;
 
 use super::*;
 use pallet_ethereum::IntermediateStateRoot;
@@ -56,7 +57,7 @@ frame_support::construct_runtime! {
 		Timestamp: pallet_timestamp::{Pallet, Call, Storage},
 		EVM: pallet_evm::{Pallet, Call, Storage, Config, Event<T>},
 		Ethereum: pallet_ethereum::{Pallet, Call, Storage, Event, Origin},
-		EthereumXcm: crate::{Pallet, Call, Origin},
+		EthereumXcm: crate::{Pallet, Call, Origin, Event<T>},
 		Proxy: pallet_proxy::{Pallet, Call, Storage, Event<T>},
 	}
 }
@@ -168,7 +169,7 @@ impl pallet_evm::Config for Test {
 	type Event = Event;
 	type PrecompilesType = ();
 	type PrecompilesValue = ();
-	type Runner = pallet_evm::runner::stack::Runner<Self>;
+	type Runner = crate::RecordRevertData<pallet_evm::runner::stack::Runner<Self>>;
 	type ChainId = ChainId;
 	type BlockGasLimit = BlockGasLimit;
 	type OnChargeTransaction = ();
@@ -245,13 +246,55 @@ impl xcm_primitives::EnsureProxy<AccountId32> for EthereumXcmEnsureProxy {
 	}
 }
 
+pub struct EthereumPendingTransactions;
+impl crate::PendingEthereumTransactions for EthereumPendingTransactions {
+	fn pending() -> Vec<(Transaction, TransactionStatus, Receipt)> {
+		Ethereum::pending()
+	}
+}
+
+thread_local! {
+	pub static SENT_XCM: RefCell<Vec<(MultiLocation, Xcm<()>)>> = RefCell::new(Vec::new());
+}
+
+pub(crate) fn sent_xcm() -> Vec<(MultiLocation, Xcm<()>)> {
+	SENT_XCM.with(|q| (*q.borrow()).clone())
+}
+
+/// Sender that records the messages instead of routing them
+pub struct TestSendXcm;
+impl SendXcm for TestSendXcm {
+	fn send_xcm(dest: impl Into<MultiLocation>, msg: Xcm<()>) -> SendResult {
+		SENT_XCM.with(|q| q.borrow_mut().push((dest.into(), msg)));
+		Ok(())
+	}
+}
+
+/// Converts `AccountKey20` locations of the parent to the account of the key
+pub struct MockLocationToAccountId;
+impl xcm_executor::traits::Convert<MultiLocation, AccountId32> for MockLocationToAccountId {
+	fn convert(location: MultiLocation) -> Result<AccountId32, MultiLocation> {
+		match location {
+			MultiLocation {
+				parents: 1,
+				interior: X1(AccountKey20 { key, .. }),
+			} => Ok(HashedAddressMapping::into_account_id(H160(key))),
+			_ => Err(location),
+		}
+	}
+}
+
 impl crate::Config for Test {
+	type Event = Event;
 	type InvalidEvmTransactionError = pallet_ethereum::InvalidTransactionWrapper;
 	type ValidatedTransaction = pallet_ethereum::ValidatedTransaction<Self>;
 	type XcmEthereumOrigin = crate::EnsureXcmEthereumTransaction;
 	type ReservedXcmpWeight = ReservedXcmpWeight;
 	type EnsureProxy = EthereumXcmEnsureProxy;
 	type ControllerOrigin = EnsureRoot<AccountId32>;
+	type PendingTransactions = EthereumPendingTransactions;
+	type XcmSender = TestSendXcm;
+	type LocationToAccountId = MockLocationToAccountId;
 }
 
 impl fp_self_contained::SelfContainedCall for Call {
@@ -335,6 +378,7 @@ fn address_build(seed: u8) -> AccountInfo {
 // our desired mockup.
 pub fn new_test_ext(accounts_len: usize) -> (Vec<AccountInfo>, sp_io::TestExternalities) {
 	// sc_cli::init_logger("");
+	SENT_XCM.with(|q| q.borrow_mut().clear());
 	let mut ext = frame_system::GenesisConfig::default()
 		.build_storage::<Test>()
 		.unwrap();
@@ -0,0 +1,161 @@
+// Copyright 2019-2022 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The EVM runner wrapper recording the revert data of reported executions
+
+use ethereum_types::{H160, H256, U256};
+use fp_evm::{CallInfo, Config as EvmConfig, CreateInfo, ExitReason};
+use pallet_evm::{runner::RunnerError, Runner};
+use sp_std::{marker::PhantomData, vec::Vec};
+
+environmental::environmental!(revert_data: Vec<u8>);
+
+/// Runs `f`, returning the output of the last reverted call it executed through
+/// `RecordRevertData`, which is empty if none reverted
+pub(crate) fn recording_revert_data<R>(f: impl FnOnce() -> R) -> (R, Vec<u8>) {
+	let mut recorded = Vec::new();
+	let result = revert_data::using(&mut recorded, f);
+	(result, recorded)
+}
+
+/// Wraps an EVM runner, recording the output of the reverted calls of executions reported
+/// through XCM, as it is not part of the Ethereum transaction receipt. Calls made outside of
+/// these executions are not recorded.
+pub struct RecordRevertData<R>(PhantomData<R>);
+
+impl<T: pallet_evm::Config, R: Runner<T>> Runner<T> for RecordRevertData<R> {
+	type Error = R::Error;
+
+	fn validate(
+		source: H160,
+		target: Option<H160>,
+		input: Vec<u8>,
+		value: U256,
+		gas_limit: u64,
+		max_fee_per_gas: Option<U256>,
+		max_priority_fee_per_gas: Option<U256>,
+		nonce: Option<U256>,
+		access_list: Vec<(H160, Vec<H256>)>,
+		is_transactional: bool,
+		evm_config: &EvmConfig,
+	) -> Result<(), RunnerError<Self::Error>> {
+		R::validate(
+			source,
+			target,
+			input,
+			value,
+			gas_limit,
+			max_fee_per_gas,
+			max_priority_fee_per_gas,
+			nonce,
+			access_list,
+			is_transactional,
+			evm_config,
+		)
+	}
+
+	fn call(
+		source: H160,
+		target: H160,
+		input: Vec<u8>,
+		value: U256,
+		gas_limit: u64,
+		max_fee_per_gas: Option<U256>,
+		max_priority_fee_per_gas: Option<U256>,
+		nonce: Option<U256>,
+		access_list: Vec<(H160, Vec<H256>)>,
+		is_transactional: bool,
+		validate: bool,
+		config: &EvmConfig,
+	) -> Result<CallInfo, RunnerError<Self::Error>> {
+		let info = R::call(
+			source,
+			target,
+			input,
+			value,
+			gas_limit,
+			max_fee_per_gas,
+			max_priority_fee_per_gas,
+			nonce,
+			access_list,
+			is_transactional,
+			validate,
+			config,
+		)?;
+		if let ExitReason::Revert(_) = info.exit_reason {
+			revert_data::with(|recorded| *recorded = info.value.clone());
+		}
+		Ok(info)
+	}
+
+	fn create(
+		source: H160,
+		init: Vec<u8>,
+		value: U256,
+		gas_limit: u64,
+		max_fee_per_gas: Option<U256>,
+		max_priority_fee_per_gas: Option<U256>,
+		nonce: Option<U256>,
+		access_list: Vec<(H160, Vec<H256>)>,
+		is_transactional: bool,
+		validate: bool,
+		config: &EvmConfig,
+	) -> Result<CreateInfo, RunnerError<Self::Error>> {
+		R::create(
+			source,
+			init,
+			value,
+			gas_limit,
+			max_fee_per_gas,
+			max_priority_fee_per_gas,
+			nonce,
+			access_list,
+			is_transactional,
+			validate,
+			config,
+		)
+	}
+
+	fn create2(
+		source: H160,
+		init: Vec<u8>,
+		salt: H256,
+		value: U256,
+		gas_limit: u64,
+		max_fee_per_gas: Option<U256>,
+		max_priority_fee_per_gas: Option<U256>,
+		nonce: Option<U256>,
+		access_list: Vec<(H160, Vec<H256>)>,
+		is_transactional: bool,
+		validate: bool,
+		config: &EvmConfig,
+	) -> Result<CreateInfo, RunnerError<Self::Error>> {
+		R::create2(
+			source,
+			init,
+			salt,
+			value,
+			gas_limit,
+			max_fee_per_gas,
+			max_priority_fee_per_gas,
+			nonce,
+			access_list,
+			is_transactional,
+			validate,
+			config,
+		)
+	}
+}
@@ -13,7 +13,7 @@
 
 // You should have received a copy of the GNU General Public License
 // along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
-use crate::{mock::*, Error, RawOrigin};
+use crate::{mock::*, Error, EthereumXcmResponseInfo, RawOrigin};
 use ethereum_types::{H160, U256};
 use frame_support::{
 	assert_noop, assert_ok,
@@ -22,6 +22,8 @@ use frame_support::{
 	BoundedVec,
 };
 use sp_runtime::{DispatchError, DispatchErrorWithPostInfo};
+use xcm::latest::prelude::*;
+use xcm::VersionedMultiLocation;
 use xcm_primitives::{EthereumXcmTransaction, EthereumXcmTransactionV2};
 
 // 	pragma solidity ^0.6.6;
@@ -518,3 +520,223 @@ fn transact_through_proxy_after_resume_ethereum_to_xcm_works() {
 		assert_eq!(charlie_after.data.free, charlie_before.data.free + 100);
 	});
 }
+
+fn response_destination(address: H160) -> MultiLocation {
+	MultiLocation::new(
+		1,
+		X1(AccountKey20 {
+			network: Any,
+			key: address.0,
+		}),
+	)
+}
+
+fn response_info(address: H160, query_id: u64) -> EthereumXcmResponseInfo {
+	EthereumXcmResponseInfo {
+		destination: VersionedMultiLocation::V1(response_destination(address)),
+		query_id,
+		max_weight: 1_000,
+	}
+}
+
+fn response(query_id: u64, error: Option<XcmError>) -> Xcm<()> {
+	Xcm(vec![QueryResponse {
+		query_id,
+		response: Response::ExecutionResult(error.map(|error| (0, error))),
+		max_weight: 1_000,
+	}])
+}
+
+#[test]
+fn transact_with_response_reports_evm_success() {
+	let (pairs, mut ext) = new_test_ext(2);
+	let alice = &pairs[0];
+	let bob = &pairs[1];
+
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(EthereumXcm::transact_with_response(
+			RawOrigin::XcmEthereumTransaction(alice.address).into(),
+			xcm_evm_transfer_eip_1559_transaction(bob.address, U256::from(100)),
+			response_info(alice.address, 1),
+		));
+
+		assert_eq!(
+			sent_xcm(),
+			vec![(response_destination(alice.address), response(1, None))]
+		);
+
+		let receipt = EthereumXcm::last_receipt().expect("transaction was applied");
+		assert!(receipt.succeeded);
+		assert_eq!(receipt.used_gas, U256::from(21_000));
+		System::assert_last_event(
+			crate::Event::ExecutionResponseSent {
+				query_id: 1,
+				destination: response_destination(alice.address),
+				receipt,
+				revert_data: vec![],
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn transact_with_response_reports_evm_revert() {
+	let (pairs, mut ext) = new_test_ext(2);
+	let alice = &pairs[0];
+	let bob = &pairs[1];
+
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		let t = EIP1559UnsignedTransaction {
+			nonce: U256::zero(),
+			max_priority_fee_per_gas: U256::one(),
+			max_fee_per_gas: U256::one(),
+			gas_limit: U256::from(0x100000),
+			action: ethereum::TransactionAction::Create,
+			value: U256::zero(),
+			input: hex::decode(CONTRACT).unwrap(),
+		}
+		.sign(&alice.private_key, None);
+		assert_ok!(Ethereum::execute(alice.address, &t, None,));
+
+		let contract_address = hex::decode("32dcab0ef3fb2de2fce1d2e0799d36239671f04a").unwrap();
+		let bar = hex::decode("febb0f7e").unwrap();
+
+		// The reverted call is still dispatched successfully
+		assert_ok!(EthereumXcm::transact_with_response(
+			RawOrigin::XcmEthereumTransaction(bob.address).into(),
+			xcm_evm_call_eip_1559_transaction(H160::from_slice(&contract_address), bar),
+			response_info(bob.address, 2),
+		));
+
+		assert_eq!(
+			sent_xcm(),
+			vec![(
+				response_destination(bob.address),
+				response(2, Some(XcmError::Trap(0)))
+			)]
+		);
+
+		let receipt = EthereumXcm::last_receipt().expect("transaction was applied");
+		assert!(!receipt.succeeded);
+		assert_eq!(receipt.contract_address, None);
+
+		// Error(string) with the "error_msg" reason
+		let mut revert_data = hex::decode("08c379a0").unwrap();
+		revert_data.extend_from_slice(&H256::from_low_u64_be(0x20).0);
+		revert_data.extend_from_slice(&H256::from_low_u64_be(9).0);
+		let mut reason = [0u8; 32];
+		reason[..9].copy_from_slice(b"error_msg");
+		revert_data.extend_from_slice(&reason);
+		System::assert_last_event(
+			crate::Event::ExecutionResponseSent {
+				query_id: 2,
+				destination: response_destination(bob.address),
+				receipt,
+				revert_data,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn transact_through_proxy_with_response_works() {
+	let (pairs, mut ext) = new_test_ext(3);
+	let alice = &pairs[0];
+	let bob = &pairs[1];
+	let charlie = &pairs[2];
+
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		let _ =
+			Proxy::add_proxy_delegate(&bob.account_id, alice.account_id.clone(), ProxyType::Any, 0);
+
+		assert_ok!(EthereumXcm::transact_through_proxy_with_response(
+			RawOrigin::XcmEthereumTransaction(alice.address).into(),
+			bob.address,
+			xcm_evm_transfer_eip_1559_transaction(charlie.address, U256::from(100)),
+			response_info(alice.address, 3),
+		));
+
+		assert_eq!(
+			sent_xcm(),
+			vec![(response_destination(alice.address), response(3, None))]
+		);
+	});
+}
+
+#[test]
+fn transact_with_response_reports_dispatch_failures() {
+	let (pairs, mut ext) = new_test_ext(2);
+	let alice = &pairs[0];
+	let bob = &pairs[1];
+
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(EthereumXcm::suspend_ethereum_xcm_execution(Origin::root()));
+
+		assert_ok!(EthereumXcm::transact_with_response(
+			RawOrigin::XcmEthereumTransaction(alice.address).into(),
+			xcm_evm_transfer_eip_1559_transaction(bob.address, U256::from(100)),
+			response_info(alice.address, 4),
+		));
+
+		assert_eq!(
+			sent_xcm(),
+			vec![(
+				response_destination(alice.address),
+				response(4, Some(XcmError::Trap(1)))
+			)]
+		);
+		System::assert_last_event(
+			crate::Event::DispatchFailureResponseSent {
+				query_id: 4,
+				destination: response_destination(alice.address),
+				error: Error::<Test>::EthereumXcmExecutionSuspended.into(),
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn transact_with_response_to_another_location_fails() {
+	let (pairs, mut ext) = new_test_ext(2);
+	let alice = &pairs[0];
+	let bob = &pairs[1];
+
+	ext.execute_with(|| {
+		for destination in [response_destination(bob.address), MultiLocation::parent()] {
+			assert_noop!(
+				EthereumXcm::transact_with_response(
+					RawOrigin::XcmEthereumTransaction(alice.address).into(),
+					xcm_evm_transfer_eip_1559_transaction(bob.address, U256::from(100)),
+					EthereumXcmResponseInfo {
+						destination: VersionedMultiLocation::V1(destination),
+						query_id: 5,
+						max_weight: 1_000,
+					},
+				),
+				Error::<Test>::BadResponseDestination
+			);
+		}
+		assert!(sent_xcm().is_empty());
+	});
+}
+
+#[test]
+fn response_calls_keep_the_indices_of_existing_calls() {
+	use codec::Encode;
+
+	assert_eq!(
+		crate::Call::<Test>::suspend_ethereum_xcm_execution {}.encode(),
+		vec![2u8]
+	);
+	assert_eq!(
+		crate::Call::<Test>::resume_ethereum_xcm_execution {}.encode(),
+		vec![3u8]
+	);
+}
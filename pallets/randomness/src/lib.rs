@@ -49,17 +49,34 @@
 //!
 //! `Config::BabeDataGetter` is responsible for reading the epoch index and epoch randomness
 //! from the relay chain state proof. The moonbeam `GetBabeData` implementation is in the runtime.
+//!
+//! ## Randomness sources
+//! `LocalVrfRandomness` and `PreviousLocalVrfRandomness` implement
+//! `frame_support::traits::Randomness` for runtime pallets. The former uses the VRF output of
+//! the current block. The latter uses the output of the previous block, which is committed
+//! before the author of the current block is known, so it can decide who may author it.
+//!
+//! ## Fulfillment
+//! Requests due are usually fulfilled by anyone calling `fulfillRequest` on the precompile, who
+//! gets the cost of execution out of the request fee. If `Config::FulfillRequestsOnIdle` is
+//! set, the pallet also fulfills due requests itself in `on_idle`, highest fees first and as
+//! long as the remaining weight allows for their gas limit. `Config::FulfillmentExecutor` calls
+//! back the consumer contracts, and the cost of execution is paid to the block author.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use frame_support::pallet;
+use frame_support::{pallet, weights::Weight};
+use sp_runtime::DispatchError;
+use sp_std::vec::Vec;
 
 pub use pallet::*;
 
 #[cfg(any(test, feature = "runtime-benchmarks"))]
 mod benchmarks;
+pub mod randomness_source;
 pub mod types;
 pub mod vrf;
+pub use randomness_source::*;
 pub use types::*;
 pub mod weights;
 
@@ -74,12 +91,49 @@ pub trait GetBabeData<EpochIndex, Randomness> {
 	fn get_epoch_randomness() -> Randomness;
 }
 
+/// Provides the randomness of the requests fulfilled by the pallet in `on_idle`
+pub trait FulfillmentExecutor<T: Config> {
+	/// Account paid the cost of execution of the requests fulfilled in this block
+	fn fulfiller() -> sp_core::H160;
+	/// Maximum weight of fulfilling the request, callback included
+	fn fulfillment_weight(request: &Request<BalanceOf<T>, RequestInfo<T>>) -> Weight;
+	/// Calls back the contract of request `id` with its random words
+	/// Returns the weight used and the cost of execution to pay to the fulfiller. The effects of
+	/// a failed execution are reverted, and the request is charged its `fulfillment_weight`
+	/// whatever the outcome
+	fn execute(
+		id: RequestId,
+		request: &Request<BalanceOf<T>, RequestInfo<T>>,
+		randomness: Vec<[u8; 32]>,
+	) -> Result<(Weight, BalanceOf<T>), DispatchError>;
+}
+
+impl<T: Config> FulfillmentExecutor<T> for () {
+	fn fulfiller() -> sp_core::H160 {
+		Default::default()
+	}
+	fn fulfillment_weight(_request: &Request<BalanceOf<T>, RequestInfo<T>>) -> Weight {
+		Weight::MAX
+	}
+	fn execute(
+		_id: RequestId,
+		_request: &Request<BalanceOf<T>, RequestInfo<T>>,
+		_randomness: Vec<[u8; 32]>,
+	) -> Result<(Weight, BalanceOf<T>), DispatchError> {
+		Err(DispatchError::Other("No fulfillment executor"))
+	}
+}
+
 #[pallet]
 pub mod pallet {
 	use super::*;
 	use crate::weights::{SubstrateWeight, WeightInfo};
 	use frame_support::traits::{Currency, ExistenceRequirement::KeepAlive};
-	use frame_support::{pallet_prelude::*, PalletId};
+	use frame_support::{
+		pallet_prelude::*,
+		storage::{with_transaction, TransactionOutcome},
+		PalletId,
+	};
 	use frame_system::pallet_prelude::*;
 	use nimbus_primitives::NimbusId;
 	use pallet_evm::AddressMapping;
@@ -134,6 +188,11 @@ pub mod pallet {
 		/// Babe requests expire and can be purged from storage after this many blocks/epochs
 		#[pallet::constant]
 		type EpochExpirationDelay: Get<u64>;
+		/// Whether the pallet fulfills due requests itself in `on_idle`
+		#[pallet::constant]
+		type FulfillRequestsOnIdle: Get<bool>;
+		/// Calls back the contracts of the requests fulfilled in `on_idle`
+		type FulfillmentExecutor: FulfillmentExecutor<Self>;
 	}
 
 	#[pallet::error]
@@ -203,6 +262,12 @@ pub mod pallet {
 	#[pallet::getter(fn local_vrf_output)]
 	pub type LocalVrfOutput<T: Config> = StorageValue<_, Option<T::Hash>, ValueQuery>;
 
+	/// Local VRF randomness of the previous block
+	/// Set in `on_initialize`, before `LocalVrfOutput` is updated
+	#[pallet::storage]
+	#[pallet::getter(fn previous_local_vrf_output)]
+	pub type PreviousLocalVrfOutput<T: Config> = StorageValue<_, Option<T::Hash>, ValueQuery>;
+
 	/// Relay epoch
 	#[pallet::storage]
 	#[pallet::getter(fn relay_epoch)]
@@ -225,6 +290,11 @@ pub mod pallet {
 	pub(crate) type RandomnessResults<T: Config> =
 		StorageMap<_, Twox64Concat, RequestType<T>, RandomnessResult<T::Hash>>;
 
+	/// Last request scanned for fulfillment in `on_idle`, the next scan resumes after it so that
+	/// requests late in the iteration order are reached even when the scan is cut short
+	#[pallet::storage]
+	pub(crate) type FulfillmentCursor<T: Config> = StorageValue<_, RequestId>;
+
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Populates `RandomnessResults` due this epoch with BABE epoch randomness
@@ -289,16 +359,24 @@ pub mod pallet {
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
 		fn on_initialize(_now: BlockNumberFor<T>) -> Weight {
+			Self::commit_local_vrf_output();
+			let commit_weight = T::DbWeight::get().reads_writes(1, 1);
 			// Do not set the output in the first block (genesis or runtime upgrade)
 			// because we do not have any input for author to sign
 			if NotFirstBlock::<T>::get().is_none() {
 				NotFirstBlock::<T>::put(());
 				LocalVrfOutput::<T>::put(Some(T::Hash::default()));
-				return T::DbWeight::get().read + (T::DbWeight::get().write * 2);
+				return T::DbWeight::get().read + (T::DbWeight::get().write * 2) + commit_weight;
 			}
 			// Verify VRF output included by block author and set it in storage
 			vrf::verify_and_set_output::<T>();
-			SubstrateWeight::<T>::on_initialize()
+			SubstrateWeight::<T>::on_initialize().saturating_add(commit_weight)
+		}
+		fn on_idle(_now: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			if !T::FulfillRequestsOnIdle::get() {
+				return 0;
+			}
+			Self::fulfill_due_requests(remaining_weight)
 		}
 		fn on_finalize(_now: BlockNumberFor<T>) {
 			// Ensure the mandatory inherent was included in the block or the block is invalid
@@ -323,6 +401,103 @@ pub mod pallet {
 		}
 	}
 
+	impl<T: Config> Pallet<T> {
+		/// Moves the local VRF randomness of the previous block to `PreviousLocalVrfOutput`
+		/// Called in `on_initialize`, and by block authoring predictions which cannot know the
+		/// VRF output of the block yet
+		pub fn commit_local_vrf_output() {
+			<PreviousLocalVrfOutput<T>>::put(<LocalVrfOutput<T>>::get());
+		}
+
+		/// Fulfills the requests due, highest fees first, within `remaining_weight`
+		/// Returns the weight used
+		pub(crate) fn fulfill_due_requests(remaining_weight: Weight) -> Weight {
+			let db_weight = T::DbWeight::get();
+			// Read and write of the cursor
+			let mut used_weight: Weight = db_weight.reads_writes(1, 1);
+			let mut due_requests = Vec::new();
+			let after_cursor = match <FulfillmentCursor<T>>::get() {
+				Some(cursor) => <Requests<T>>::iter_from(<Requests<T>>::hashed_key_for(cursor)),
+				None => <Requests<T>>::iter(),
+			};
+			let mut first_scanned = None;
+			let mut last_scanned = None;
+			// Wraps around to the first requests once the last one is scanned
+			for (id, state) in after_cursor.chain(<Requests<T>>::iter()) {
+				if first_scanned == Some(id) {
+					break;
+				}
+				used_weight = used_weight.saturating_add(db_weight.reads(1));
+				if used_weight >= remaining_weight {
+					break;
+				}
+				first_scanned.get_or_insert(id);
+				last_scanned = Some(id);
+				if state.request.can_be_fulfilled() {
+					due_requests.push((id, state));
+				}
+			}
+			if let Some(id) = last_scanned {
+				<FulfillmentCursor<T>>::put(id);
+			}
+			due_requests.sort_by(|(_, a), (_, b)| b.request.fee.cmp(&a.request.fee));
+
+			let fulfiller = T::FulfillmentExecutor::fulfiller();
+			for (id, state) in due_requests {
+				let fulfillment_weight = T::FulfillmentExecutor::fulfillment_weight(&state.request)
+					.saturating_add(SubstrateWeight::<T>::prepare_fulfillment(
+						state.request.num_words.into(),
+					))
+					.saturating_add(SubstrateWeight::<T>::finish_fulfillment());
+				if used_weight.saturating_add(fulfillment_weight) > remaining_weight {
+					// Requests with lower fees may still fit
+					continue;
+				}
+				// The randomness of the request may not be available yet
+				let FulfillArgs {
+					request,
+					deposit,
+					randomness,
+				} = match state.prepare_fulfill() {
+					Ok(args) => args,
+					Err(_) => {
+						used_weight =
+							used_weight.saturating_add(SubstrateWeight::<T>::prepare_fulfillment(
+								state.request.num_words.into(),
+							));
+						continue;
+					}
+				};
+				// Whether the callback succeeds or not, the execution may use up to the
+				// worst case weight
+				used_weight = used_weight.saturating_add(fulfillment_weight);
+				// The effects of a failed execution are reverted, keeping the request to be
+				// fulfilled later
+				let execution = with_transaction(|| {
+					match T::FulfillmentExecutor::execute(id, &request, randomness) {
+						Ok(result) => TransactionOutcome::Commit(Ok(result)),
+						Err(error) => TransactionOutcome::Rollback(Err(error)),
+					}
+				});
+				match execution {
+					Ok((_, cost_of_execution)) => {
+						Self::finish_fulfillment(
+							id,
+							request,
+							deposit,
+							&fulfiller,
+							cost_of_execution,
+						);
+					}
+					Err(error) => {
+						log::warn!("Failed to fulfill request {:?} on idle: {:?}", id, error);
+					}
+				}
+			}
+			used_weight
+		}
+	}
+
 	// Public functions for precompile usage only
 	impl<T: Config> Pallet<T> {
 		/// Make request for future randomness
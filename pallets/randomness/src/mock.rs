@@ -20,7 +20,7 @@ use crate as pallet_randomness;
 use frame_support::{
 	construct_runtime, parameter_types,
 	traits::{Everything, GenesisBuild},
-	weights::Weight,
+	weights::{RuntimeDbWeight, Weight},
 };
 use nimbus_primitives::NimbusId;
 use pallet_evm::IdentityAddressMapping;
@@ -31,7 +31,10 @@ use sp_runtime::{
 	traits::{BlakeTwo256, IdentityLookup},
 	Perbill,
 };
-use sp_std::convert::{TryFrom, TryInto};
+use sp_std::{
+	cell::RefCell,
+	convert::{TryFrom, TryInto},
+};
 
 pub type AccountId = H160;
 pub type Balance = u128;
@@ -60,10 +63,11 @@ parameter_types! {
 	pub const MaximumBlockLength: u32 = 2 * 1024;
 	pub const AvailableBlockRatio: Perbill = Perbill::one();
 	pub const SS58Prefix: u8 = 42;
+	pub const MockDbWeight: RuntimeDbWeight = RuntimeDbWeight { read: 1, write: 1 };
 }
 impl frame_system::Config for Test {
 	type BaseCallFilter = Everything;
-	type DbWeight = ();
+	type DbWeight = MockDbWeight;
 	type Origin = Origin;
 	type Index = u64;
 	type BlockNumber = BlockNumber;
@@ -124,6 +128,64 @@ impl crate::GetBabeData<u64, Option<H256>> for BabeDataGetter {
 	}
 }
 
+thread_local! {
+	/// Whether requests are fulfilled in `on_idle`
+	pub static FULFILL_ON_IDLE: RefCell<bool> = RefCell::new(false);
+	/// Ids of the requests executed by the mock fulfillment executor, in order
+	pub static EXECUTED_REQUESTS: RefCell<Vec<RequestId>> = RefCell::new(Vec::new());
+	/// Whether the executions of the mock fulfillment executor fail after calling back
+	pub static FAIL_EXECUTIONS: RefCell<bool> = RefCell::new(false);
+}
+
+pub struct FulfillRequestsOnIdle;
+impl frame_support::traits::Get<bool> for FulfillRequestsOnIdle {
+	fn get() -> bool {
+		FULFILL_ON_IDLE.with(|enabled| *enabled.borrow())
+	}
+}
+
+pub(crate) fn set_fulfill_requests_on_idle(enabled: bool) {
+	FULFILL_ON_IDLE.with(|on_idle| *on_idle.borrow_mut() = enabled);
+}
+
+pub(crate) fn executed_requests() -> Vec<RequestId> {
+	EXECUTED_REQUESTS.with(|executed| executed.borrow().clone())
+}
+
+pub(crate) fn set_fail_executions(fail: bool) {
+	FAIL_EXECUTIONS.with(|fail_executions| *fail_executions.borrow_mut() = fail);
+}
+
+/// Storage written by the callbacks of the mock fulfillment executor
+pub(crate) const CALLBACK_STORAGE_KEY: &[u8] = b"callback";
+
+/// Cost of execution paid to the fulfiller for every request executed on idle
+pub const MOCK_COST_OF_EXECUTION: Balance = 3;
+
+/// Weighs the callback of a request as its gas limit, which it uses entirely. Callbacks write
+/// the id of their request under CALLBACK_STORAGE_KEY
+pub struct MockFulfillmentExecutor;
+impl FulfillmentExecutor<Test> for MockFulfillmentExecutor {
+	fn fulfiller() -> H160 {
+		CHARLIE
+	}
+	fn fulfillment_weight(request: &Request<BalanceOf<Test>, RequestInfo<Test>>) -> Weight {
+		request.gas_limit
+	}
+	fn execute(
+		id: RequestId,
+		request: &Request<BalanceOf<Test>, RequestInfo<Test>>,
+		_randomness: Vec<[u8; 32]>,
+	) -> Result<(Weight, BalanceOf<Test>), sp_runtime::DispatchError> {
+		EXECUTED_REQUESTS.with(|executed| executed.borrow_mut().push(id));
+		frame_support::storage::unhashed::put(CALLBACK_STORAGE_KEY, &id);
+		if FAIL_EXECUTIONS.with(|fail| *fail.borrow()) {
+			return Err(sp_runtime::DispatchError::Other("Execution failed"));
+		}
+		Ok((request.gas_limit, MOCK_COST_OF_EXECUTION))
+	}
+}
+
 parameter_types! {
 	pub const Deposit: u128 = 10;
 	pub const MaxRandomWords: u8 = 1;
@@ -142,6 +204,8 @@ impl Config for Test {
 	type MaxBlockDelay = MaxBlockDelay;
 	type BlockExpirationDelay = MaxBlockDelay;
 	type EpochExpirationDelay = MaxBlockDelay;
+	type FulfillRequestsOnIdle = FulfillRequestsOnIdle;
+	type FulfillmentExecutor = MockFulfillmentExecutor;
 }
 
 pub(crate) fn events() -> Vec<pallet::Event<Test>> {
@@ -231,6 +295,7 @@ impl ExtBuilder {
 
 pub const ALICE: H160 = H160::repeat_byte(0xAA);
 pub const BOB: H160 = H160::repeat_byte(0xBB);
+pub const CHARLIE: H160 = H160::repeat_byte(0xCC);
 
 /// Helps test same effects for all 4 variants of RequestType
 pub fn build_default_request(
@@ -0,0 +1,53 @@
+// Copyright 2019-2022 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+//! `Randomness` implementations for runtime pallets
+use crate::{Config, LocalVrfOutput, PreviousLocalVrfOutput};
+use frame_support::traits::Randomness;
+use sp_runtime::traits::{Hash, Saturating};
+use sp_std::marker::PhantomData;
+
+/// Mixes `subject` into the local VRF randomness `output`
+fn random_for_subject<T: Config>(subject: &[u8], output: Option<T::Hash>) -> T::Hash {
+	T::Hashing::hash_of(&(subject, output.unwrap_or_default()))
+}
+
+/// Randomness from the VRF output of the current block
+/// Only available after `on_initialize`, and known to the block author before the block is
+/// produced
+pub struct LocalVrfRandomness<T>(PhantomData<T>);
+
+impl<T: Config> Randomness<T::Hash, T::BlockNumber> for LocalVrfRandomness<T> {
+	fn random(subject: &[u8]) -> (T::Hash, T::BlockNumber) {
+		(
+			random_for_subject::<T>(subject, <LocalVrfOutput<T>>::get()),
+			frame_system::Pallet::<T>::block_number(),
+		)
+	}
+}
+
+/// Randomness from the VRF output of the previous block, which the author of the current
+/// block cannot influence
+pub struct PreviousLocalVrfRandomness<T>(PhantomData<T>);
+
+impl<T: Config> Randomness<T::Hash, T::BlockNumber> for PreviousLocalVrfRandomness<T> {
+	fn random(subject: &[u8]) -> (T::Hash, T::BlockNumber) {
+		(
+			random_for_subject::<T>(subject, <PreviousLocalVrfOutput<T>>::get()),
+			frame_system::Pallet::<T>::block_number().saturating_sub(1u32.into()),
+		)
+	}
+}
@@ -15,7 +15,7 @@
 // along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
 use crate::mock::*;
 use crate::*;
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{assert_noop, assert_ok, traits::Hooks, weights::Weight};
 use sp_core::H256;
 
 #[test]
@@ -532,6 +532,222 @@ fn finish_fulfillment_decrements_randomness_result_and_removes_from_storage_if_l
 		});
 }
 
+// RANDOMNESS SOURCES
+
+#[test]
+fn local_vrf_randomness_mixes_subject_into_current_output() {
+	use frame_support::traits::Randomness as RandomnessT;
+	use sp_runtime::traits::Hash;
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(5u64);
+		let output = H256::repeat_byte(1);
+		crate::pallet::LocalVrfOutput::<Test>::put(Some(output));
+		let (random, known_since) = LocalVrfRandomness::<Test>::random(b"subject");
+		assert_eq!(
+			random,
+			<Test as frame_system::Config>::Hashing::hash_of(&(&b"subject"[..], output))
+		);
+		assert_eq!(known_since, 5u64);
+		assert_ne!(
+			LocalVrfRandomness::<Test>::random(b"other subject").0,
+			random
+		);
+	});
+}
+
+#[test]
+fn previous_local_vrf_randomness_uses_output_committed_in_on_initialize() {
+	use frame_support::traits::Randomness as RandomnessT;
+	use sp_runtime::traits::Hash;
+	ExtBuilder::default().build().execute_with(|| {
+		let previous_output = H256::repeat_byte(1);
+		crate::pallet::LocalVrfOutput::<Test>::put(Some(previous_output));
+		System::set_block_number(5u64);
+		// first block, so the output of this block is not verified
+		Randomness::on_initialize(5u64);
+		assert_eq!(
+			Randomness::previous_local_vrf_output(),
+			Some(previous_output)
+		);
+		let (random, known_since) = PreviousLocalVrfRandomness::<Test>::random(b"subject");
+		assert_eq!(
+			random,
+			<Test as frame_system::Config>::Hashing::hash_of(&(&b"subject"[..], previous_output))
+		);
+		assert_eq!(known_since, 4u64);
+		assert_ne!(LocalVrfRandomness::<Test>::random(b"subject").0, random);
+	});
+}
+
+// ON IDLE FULFILLMENT
+
+fn request_local_randomness_with_fee(fee: u128) {
+	assert_ok!(Randomness::request_randomness(Request {
+		refund_address: BOB.into(),
+		contract_address: ALICE.into(),
+		fee,
+		gas_limit: 100u64,
+		num_words: 1u8,
+		salt: H256::default(),
+		info: RequestType::Local(16u64),
+	}));
+}
+
+fn set_local_randomness_result() {
+	let mut result =
+		crate::pallet::RandomnessResults::<Test>::get(RequestType::Local(16u64)).unwrap();
+	result.randomness = Some(H256::default());
+	crate::pallet::RandomnessResults::<Test>::insert(RequestType::Local(16u64), result);
+}
+
+#[test]
+fn on_idle_fulfills_due_requests_highest_fee_first() {
+	ExtBuilder::default()
+		.with_balances(vec![(ALICE, 60)])
+		.build()
+		.execute_with(|| {
+			set_fulfill_requests_on_idle(true);
+			request_local_randomness_with_fee(5);
+			request_local_randomness_with_fee(7);
+			System::set_block_number(16u64);
+			set_local_randomness_result();
+			Randomness::on_idle(16u64, Weight::MAX);
+			assert_eq!(executed_requests(), vec![1u64, 0u64]);
+			assert!(Randomness::requests(0u64).is_none());
+			assert!(Randomness::requests(1u64).is_none());
+			// cost of execution of both requests paid to the fulfiller
+			assert_eq!(Balances::free_balance(&CHARLIE), 2 * MOCK_COST_OF_EXECUTION);
+			// deposits and fees refunded minus the cost of execution
+			assert_eq!(
+				Balances::free_balance(&BOB),
+				(10 + 5 - MOCK_COST_OF_EXECUTION) + (10 + 7 - MOCK_COST_OF_EXECUTION)
+			);
+		});
+}
+
+#[test]
+fn on_idle_does_nothing_if_fulfillment_on_idle_is_disabled() {
+	ExtBuilder::default()
+		.with_balances(vec![(ALICE, 30)])
+		.build()
+		.execute_with(|| {
+			request_local_randomness_with_fee(5);
+			System::set_block_number(16u64);
+			set_local_randomness_result();
+			assert_eq!(Randomness::on_idle(16u64, Weight::MAX), 0);
+			assert!(executed_requests().is_empty());
+			assert!(Randomness::requests(0u64).is_some());
+		});
+}
+
+#[test]
+fn on_idle_does_not_fulfill_requests_before_they_are_due() {
+	ExtBuilder::default()
+		.with_balances(vec![(ALICE, 30)])
+		.build()
+		.execute_with(|| {
+			set_fulfill_requests_on_idle(true);
+			request_local_randomness_with_fee(5);
+			System::set_block_number(15u64);
+			Randomness::on_idle(15u64, Weight::MAX);
+			assert!(executed_requests().is_empty());
+			assert!(Randomness::requests(0u64).is_some());
+		});
+}
+
+#[test]
+fn on_idle_only_fulfills_requests_fitting_in_the_remaining_weight() {
+	use crate::weights::{SubstrateWeight, WeightInfo};
+	ExtBuilder::default()
+		.with_balances(vec![(ALICE, 60)])
+		.build()
+		.execute_with(|| {
+			set_fulfill_requests_on_idle(true);
+			request_local_randomness_with_fee(5);
+			request_local_randomness_with_fee(7);
+			System::set_block_number(16u64);
+			set_local_randomness_result();
+			// cursor read and write, and the scan of both requests
+			let scan_weight = 4u64;
+			let one_request_weight = 100u64
+				+ SubstrateWeight::<Test>::prepare_fulfillment(1)
+				+ SubstrateWeight::<Test>::finish_fulfillment();
+			Randomness::on_idle(16u64, scan_weight + one_request_weight);
+			assert_eq!(executed_requests(), vec![1u64]);
+			assert!(Randomness::requests(0u64).is_some());
+		});
+}
+
+#[test]
+fn on_idle_resumes_scan_of_requests_where_the_previous_one_stopped() {
+	ExtBuilder::default()
+		.with_balances(vec![(ALICE, 60)])
+		.build()
+		.execute_with(|| {
+			set_fulfill_requests_on_idle(true);
+			request_local_randomness_with_fee(5);
+			request_local_randomness_with_fee(7);
+			System::set_block_number(16u64);
+			set_local_randomness_result();
+			// only enough weight to scan a single request per block
+			Randomness::on_idle(16u64, 4u64);
+			let first_scanned = crate::pallet::FulfillmentCursor::<Test>::get().unwrap();
+			Randomness::on_idle(16u64, 4u64);
+			let second_scanned = crate::pallet::FulfillmentCursor::<Test>::get().unwrap();
+			assert_ne!(first_scanned, second_scanned);
+			// the scan wraps around once every request was scanned
+			Randomness::on_idle(16u64, 4u64);
+			assert_eq!(
+				crate::pallet::FulfillmentCursor::<Test>::get(),
+				Some(first_scanned)
+			);
+			assert!(executed_requests().is_empty());
+			Randomness::on_idle(16u64, Weight::MAX);
+			assert_eq!(executed_requests(), vec![1u64, 0u64]);
+		});
+}
+
+#[test]
+fn on_idle_reverts_failed_executions_and_charges_their_weight() {
+	use crate::weights::{SubstrateWeight, WeightInfo};
+	ExtBuilder::default()
+		.with_balances(vec![(ALICE, 30)])
+		.build()
+		.execute_with(|| {
+			set_fulfill_requests_on_idle(true);
+			set_fail_executions(true);
+			request_local_randomness_with_fee(5);
+			System::set_block_number(16u64);
+			set_local_randomness_result();
+			// cursor read and write, and the scan of the request
+			let scan_weight = 3u64;
+			let request_weight = 100u64
+				+ SubstrateWeight::<Test>::prepare_fulfillment(1)
+				+ SubstrateWeight::<Test>::finish_fulfillment();
+			assert_eq!(
+				Randomness::on_idle(16u64, Weight::MAX),
+				scan_weight + request_weight
+			);
+			assert_eq!(executed_requests(), vec![0u64]);
+			// the callback storage is reverted, and the request kept
+			assert_eq!(
+				frame_support::storage::unhashed::get::<RequestId>(CALLBACK_STORAGE_KEY),
+				None
+			);
+			assert!(Randomness::requests(0u64).is_some());
+			assert_eq!(Balances::free_balance(&CHARLIE), 0);
+			// it is fulfilled once the execution succeeds
+			set_fail_executions(false);
+			Randomness::on_idle(16u64, Weight::MAX);
+			assert_eq!(executed_requests(), vec![0u64, 0u64]);
+			assert_eq!(
+				frame_support::storage::unhashed::get::<RequestId>(CALLBACK_STORAGE_KEY),
+				Some(0u64)
+			);
+			assert!(Randomness::requests(0u64).is_none());
+		});
+}
+
 // INCREASE REQUEST FEE
 
 #[test]
@@ -0,0 +1,146 @@
+// Copyright 2019-2022 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+//! # Trapped Assets Pallet
+//!
+//! This pallet indexes the assets trapped by pallet-xcm by the origin they were trapped for, so
+//! that the trapped assets of an account can be listed, e.g., by the xcm-utils precompile.
+//!
+//! pallet-xcm only stores how many times the hash of an origin and some assets was trapped. This
+//! pallet wraps it as the AssetTrap and AssetClaims of the XCM executor, recording the hash and
+//! the assets of every trap under its origin, and removing them once they have all been claimed.
+//!
+//! At most MaxTrapsPerOrigin traps are indexed per origin. When more assets are trapped, the
+//! oldest traps are removed from the index, but they can still be claimed from pallet-xcm.
+//!
+//! Only the assets trapped after this pallet was added to the runtime are indexed. The traps
+//! stored by pallet-xcm before that can not be migrated, as pallet-xcm only keeps their hash,
+//! from which neither the origin nor the assets can be recovered. They can still be claimed from
+//! pallet-xcm by anyone who knows the origin and assets they were trapped with.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame_support::pallet;
+pub use pallet::*;
+#[cfg(test)]
+pub mod mock;
+#[cfg(test)]
+pub mod tests;
+
+#[pallet]
+pub mod pallet {
+	use frame_support::pallet_prelude::*;
+	use sp_core::H256;
+	use sp_runtime::traits::{BlakeTwo256, Hash};
+	use xcm::{
+		latest::{MultiAssets, MultiLocation},
+		VersionedMultiAssets,
+	};
+	use xcm_executor::{
+		traits::{ClaimAssets, DropAssets},
+		Assets,
+	};
+
+	#[pallet::pallet]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	/// Assets trapped for an origin, together with the hash pallet-xcm stores them under
+	#[derive(Clone, Encode, Decode, RuntimeDebug, PartialEq, Eq, scale_info::TypeInfo)]
+	pub struct Trap {
+		pub hash: H256,
+		pub assets: VersionedMultiAssets,
+	}
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_xcm::Config {
+		/// The maximum number of traps indexed for each origin
+		#[pallet::constant]
+		type MaxTrapsPerOrigin: Get<u32>;
+	}
+
+	/// The traps of each origin that have not been claimed yet, from the oldest to the newest
+	#[pallet::storage]
+	#[pallet::getter(fn traps)]
+	pub type Traps<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		MultiLocation,
+		BoundedVec<Trap, T::MaxTrapsPerOrigin>,
+		ValueQuery,
+	>;
+
+	impl<T: Config> DropAssets for Pallet<T> {
+		fn drop_assets(origin: &MultiLocation, assets: Assets) -> Weight {
+			if assets.is_empty() {
+				// Nothing is trapped
+				return pallet_xcm::Pallet::<T>::drop_assets(origin, assets);
+			}
+
+			let versioned = VersionedMultiAssets::from(MultiAssets::from(assets.clone()));
+			let hash = trap_hash(origin, &versioned);
+			let weight = pallet_xcm::Pallet::<T>::drop_assets(origin, assets);
+
+			Traps::<T>::mutate(origin, |traps| {
+				// The same assets trapped again are stored under the same hash
+				if traps.iter().any(|trap| trap.hash == hash) {
+					return;
+				}
+				// The oldest trap makes room for the new one
+				if !traps.is_empty() && traps.len() >= T::MaxTrapsPerOrigin::get() as usize {
+					traps.remove(0);
+				}
+				// Only fails if MaxTrapsPerOrigin is zero, in which case nothing is indexed
+				let _ = traps.try_push(Trap {
+					hash,
+					assets: versioned,
+				});
+			});
+
+			weight.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+		}
+	}
+
+	impl<T: Config> ClaimAssets for Pallet<T> {
+		fn claim_assets(
+			origin: &MultiLocation,
+			ticket: &MultiLocation,
+			what: &MultiAssets,
+		) -> bool {
+			if !pallet_xcm::Pallet::<T>::claim_assets(origin, ticket, what) {
+				return false;
+			}
+
+			let hash = trap_hash(origin, &VersionedMultiAssets::from(what.clone()));
+			if pallet_xcm::Pallet::<T>::asset_trap(hash) == 0 {
+				Traps::<T>::mutate_exists(origin, |maybe_traps| {
+					if let Some(traps) = maybe_traps {
+						traps.retain(|trap| trap.hash != hash);
+						if traps.is_empty() {
+							*maybe_traps = None;
+						}
+					}
+				});
+			}
+			true
+		}
+	}
+
+	/// The hash pallet-xcm stores the `assets` trapped for `origin` under
+	pub fn trap_hash(origin: &MultiLocation, assets: &VersionedMultiAssets) -> H256 {
+		BlakeTwo256::hash_of(&(origin, assets))
+	}
+}
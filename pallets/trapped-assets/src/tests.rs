@@ -0,0 +1,153 @@
+// Copyright 2019-2022 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Unit testing
+use crate::mock::*;
+use crate::*;
+use xcm::{latest::prelude::*, VersionedMultiAssets};
+use xcm_executor::{
+	traits::{ClaimAssets, DropAssets},
+	Assets,
+};
+
+fn origin() -> MultiLocation {
+	MultiLocation::new(
+		0,
+		X1(AccountIndex64 {
+			network: NetworkId::Any,
+			index: 1,
+		}),
+	)
+}
+
+fn assets(amount: u128) -> MultiAssets {
+	let asset: MultiAsset = (MultiLocation::parent(), amount).into();
+	asset.into()
+}
+
+fn trap(amount: u128) -> Trap {
+	let assets = VersionedMultiAssets::from(assets(amount));
+	Trap {
+		hash: trap_hash(&origin(), &assets),
+		assets,
+	}
+}
+
+fn trap_assets(amount: u128) {
+	TrappedAssets::drop_assets(&origin(), Assets::from(assets(amount)));
+}
+
+#[test]
+fn trapped_assets_are_indexed_by_origin() {
+	new_test_ext().execute_with(|| {
+		trap_assets(100);
+
+		assert_eq!(TrappedAssets::traps(origin()), vec![trap(100)]);
+		assert_eq!(PolkadotXcm::asset_trap(trap(100).hash), 1);
+		assert!(TrappedAssets::traps(MultiLocation::parent()).is_empty());
+	});
+}
+
+#[test]
+fn nothing_is_indexed_without_assets() {
+	new_test_ext().execute_with(|| {
+		TrappedAssets::drop_assets(&origin(), Assets::new());
+
+		assert!(!Traps::<Test>::contains_key(origin()));
+	});
+}
+
+#[test]
+fn same_assets_are_indexed_once() {
+	new_test_ext().execute_with(|| {
+		trap_assets(100);
+		trap_assets(100);
+
+		assert_eq!(TrappedAssets::traps(origin()), vec![trap(100)]);
+		assert_eq!(PolkadotXcm::asset_trap(trap(100).hash), 2);
+
+		// The trap is indexed until it is claimed as many times as it was trapped
+		assert!(TrappedAssets::claim_assets(
+			&origin(),
+			&MultiLocation::here(),
+			&assets(100)
+		));
+		assert_eq!(TrappedAssets::traps(origin()), vec![trap(100)]);
+
+		assert!(TrappedAssets::claim_assets(
+			&origin(),
+			&MultiLocation::here(),
+			&assets(100)
+		));
+		assert!(!Traps::<Test>::contains_key(origin()));
+	});
+}
+
+#[test]
+fn claimed_traps_are_removed_from_the_index() {
+	new_test_ext().execute_with(|| {
+		trap_assets(100);
+		trap_assets(200);
+
+		assert!(TrappedAssets::claim_assets(
+			&origin(),
+			&MultiLocation::here(),
+			&assets(100)
+		));
+
+		assert_eq!(TrappedAssets::traps(origin()), vec![trap(200)]);
+		assert_eq!(PolkadotXcm::asset_trap(trap(100).hash), 0);
+	});
+}
+
+#[test]
+fn failed_claims_keep_the_index() {
+	new_test_ext().execute_with(|| {
+		trap_assets(100);
+
+		assert!(!TrappedAssets::claim_assets(
+			&origin(),
+			&MultiLocation::here(),
+			&assets(200)
+		));
+		assert!(!TrappedAssets::claim_assets(
+			&MultiLocation::parent(),
+			&MultiLocation::here(),
+			&assets(100)
+		));
+
+		assert_eq!(TrappedAssets::traps(origin()), vec![trap(100)]);
+	});
+}
+
+#[test]
+fn oldest_traps_are_dropped_from_the_index() {
+	new_test_ext().execute_with(|| {
+		trap_assets(100);
+		trap_assets(200);
+		trap_assets(300);
+
+		assert_eq!(TrappedAssets::traps(origin()), vec![trap(200), trap(300)]);
+		// The oldest trap can still be claimed from pallet-xcm
+		assert_eq!(PolkadotXcm::asset_trap(trap(100).hash), 1);
+		assert!(TrappedAssets::claim_assets(
+			&origin(),
+			&MultiLocation::here(),
+			&assets(100)
+		));
+		assert_eq!(TrappedAssets::traps(origin()), vec![trap(200), trap(300)]);
+	});
+}
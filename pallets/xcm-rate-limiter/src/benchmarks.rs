@@ -0,0 +1,112 @@
+// Copyright 2019-2022 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use crate::{Config, InflowLimit, Inflows, Pallet};
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite};
+use frame_support::traits::{EnsureOrigin, Get};
+use sp_std::boxed::Box;
+use xcm::latest::prelude::*;
+use xcm::VersionedMultiLocation;
+use xcm_executor::traits::TransactAsset;
+
+/// Amount of the benchmark asset quarantined before releasing or burning it
+const QUARANTINED_AMOUNT: u128 = 1_000_000_000_000_000_000;
+
+/// Deposits the benchmark asset into the quarantine account and records it, returning its id
+fn quarantine_benchmark_asset<T: Config>() -> u64 {
+	let asset: MultiAsset = (T::BenchmarkAsset::get(), QUARANTINED_AMOUNT).into();
+	T::AssetTransactor::deposit_asset(&asset, &Pallet::<T>::quarantine_location())
+		.expect("BenchmarkAsset can be deposited");
+
+	let id = Pallet::<T>::next_quarantine_id();
+	Pallet::<T>::quarantine(
+		asset,
+		MultiLocation::parent(),
+		Pallet::<T>::quarantine_location(),
+	);
+	id
+}
+
+benchmarks! {
+	set_inflow_limit {
+		let origin = T::LimiterOrigin::successful_origin();
+		let asset = MultiLocation::parent();
+		let from = MultiLocation::new(1, X1(Parachain(1000)));
+		let amount = 1_000u128;
+		let period: T::BlockNumber = 10u32.into();
+	}: _<T::Origin>(
+		origin,
+		Box::new(VersionedMultiLocation::V1(asset.clone())),
+		Box::new(VersionedMultiLocation::V1(from.clone())),
+		amount,
+		period
+	)
+	verify {
+		assert_eq!(
+			Pallet::<T>::inflow_limit(asset, from),
+			Some(InflowLimit { amount, period })
+		);
+	}
+
+	remove_inflow_limit {
+		let origin = T::LimiterOrigin::successful_origin();
+		let asset = MultiLocation::parent();
+		let from = MultiLocation::new(1, X1(Parachain(1000)));
+		Pallet::<T>::set_inflow_limit(
+			origin.clone(),
+			Box::new(VersionedMultiLocation::V1(asset.clone())),
+			Box::new(VersionedMultiLocation::V1(from.clone())),
+			1_000u128,
+			10u32.into(),
+		)?;
+		// Some inflow is tracked, so that it has to be removed too
+		if let Some((_, inflow)) = Pallet::<T>::inflow_allowance(&asset, &from, 100u128) {
+			Inflows::<T>::insert(&asset, &from, inflow);
+		}
+	}: _<T::Origin>(
+		origin,
+		Box::new(VersionedMultiLocation::V1(asset.clone())),
+		Box::new(VersionedMultiLocation::V1(from.clone()))
+	)
+	verify {
+		assert!(Pallet::<T>::inflow_limit(&asset, &from).is_none());
+		assert!(!Inflows::<T>::contains_key(&asset, &from));
+	}
+
+	release_quarantined {
+		let origin = T::LimiterOrigin::successful_origin();
+		let id = quarantine_benchmark_asset::<T>();
+	}: _<T::Origin>(origin, id)
+	verify {
+		assert!(Pallet::<T>::quarantined(id).is_none());
+	}
+
+	burn_quarantined {
+		let origin = T::LimiterOrigin::successful_origin();
+		let id = quarantine_benchmark_asset::<T>();
+	}: _<T::Origin>(origin, id)
+	verify {
+		assert!(Pallet::<T>::quarantined(id).is_none());
+	}
+}
+
+impl_benchmark_test_suite!(
+	Pallet,
+	crate::mock::ExtBuilder::default().build(),
+	crate::mock::Test
+);
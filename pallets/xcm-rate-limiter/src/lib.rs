@@ -0,0 +1,344 @@
+// Copyright 2019-2022 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+//! # Xcm Rate Limiter Pallet
+//!
+//! This pallet caps the amount of each asset that can be deposited through XCM by a given origin,
+//! so that a compromised sibling chain (or sovereign account) cannot mint an unlimited amount of
+//! XC-20s into this chain.
+//!
+//! The pallet implements TransactAsset by wrapping another transactor (typically the runtime
+//! AssetTransactors). Deposits of concrete fungible assets made while executing a message whose
+//! origin has an inflow limit configured for that asset are capped: every origin can deposit
+//! at most InflowLimit.amount units over InflowLimit.period blocks. The capacity is replenished
+//! linearly, so the limit applies over a rolling window rather than over fixed periods.
+//! Whatever exceeds the limit is deposited into the quarantine account, derived from the pallet
+//! id, and recorded in the Quarantined storage item.
+//!
+//! The origin of the message is only known to the transactor if the executor is wrapped in
+//! TrackInboundOrigin. Deposits made outside of it (or with no limit configured) are forwarded
+//! to the inner transactor untouched.
+//!
+//! This pallet has four extrinsics, all callable only by LimiterOrigin:
+//! set_inflow_limit: which sets the inflow limit of an asset for a particular origin
+//! remove_inflow_limit: which removes the inflow limit of an asset for a particular origin
+//! release_quarantined: which moves quarantined assets to the beneficiary they were meant for
+//! burn_quarantined: which destroys quarantined assets
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame_support::pallet;
+pub use pallet::*;
+#[cfg(any(test, feature = "runtime-benchmarks"))]
+mod benchmarks;
+#[cfg(test)]
+pub mod mock;
+#[cfg(test)]
+pub mod tests;
+mod transactor;
+pub use transactor::TrackInboundOrigin;
+pub mod weights;
+
+#[pallet]
+pub mod pallet {
+	use crate::weights::WeightInfo;
+	use frame_support::{pallet_prelude::*, PalletId};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::{AccountIdConversion, Convert, Saturating, UniqueSaturatedInto, Zero};
+	use sp_std::boxed::Box;
+	use xcm::{latest::prelude::*, VersionedMultiLocation};
+	use xcm_executor::traits::TransactAsset;
+
+	#[pallet::pallet]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	/// The XcmRateLimiter's pallet id, from which the quarantine account is derived
+	pub const PALLET_ID: PalletId = PalletId(*b"xcmratel");
+
+	/// Maximum amount of an asset that can flow in from an origin every `period` blocks
+	#[derive(Clone, Encode, Decode, RuntimeDebug, PartialEq, Eq, scale_info::TypeInfo)]
+	pub struct InflowLimit<BlockNumber> {
+		pub amount: u128,
+		pub period: BlockNumber,
+	}
+
+	/// Amount of an asset that flowed in from an origin and has not been replenished yet,
+	/// as of block `updated_at`
+	#[derive(Clone, Default, Encode, Decode, RuntimeDebug, PartialEq, Eq, scale_info::TypeInfo)]
+	pub struct Inflow<BlockNumber> {
+		pub amount: u128,
+		pub updated_at: BlockNumber,
+	}
+
+	/// Assets that exceeded an inflow limit and are held in the quarantine account
+	#[derive(Clone, Encode, Decode, RuntimeDebug, PartialEq, Eq, scale_info::TypeInfo)]
+	pub struct QuarantinedAsset {
+		pub asset: MultiAsset,
+		pub origin: MultiLocation,
+		pub beneficiary: MultiLocation,
+	}
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The transactor whose deposits are rate limited
+		type AssetTransactor: TransactAsset;
+
+		/// Convert the quarantine account into a location the transactor can deposit into
+		type AccountIdToMultiLocation: Convert<Self::AccountId, MultiLocation>;
+
+		/// Origin that is allowed to set inflow limits and to release or burn quarantined assets
+		type LimiterOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Weight information for extrinsics in this pallet
+		type WeightInfo: WeightInfo;
+
+		/// An asset AssetTransactor can deposit and withdraw, used to benchmark the release and
+		/// the burn of quarantined assets
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkAsset: Get<MultiLocation>;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		BadVersion,
+		ZeroPeriod,
+		InflowLimitNotFound,
+		QuarantineNotFound,
+		ReleaseFailed,
+		BurnFailed,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Inflow limit set
+		InflowLimitSet {
+			asset: MultiLocation,
+			origin: MultiLocation,
+			amount: u128,
+			period: T::BlockNumber,
+		},
+		/// Inflow limit removed
+		InflowLimitRemoved {
+			asset: MultiLocation,
+			origin: MultiLocation,
+		},
+		/// Assets exceeding an inflow limit were deposited into the quarantine account
+		AssetsQuarantined {
+			id: u64,
+			asset: MultiAsset,
+			origin: MultiLocation,
+			beneficiary: MultiLocation,
+		},
+		/// Quarantined assets were moved to their beneficiary
+		QuarantineReleased { id: u64 },
+		/// Quarantined assets were burned
+		QuarantineBurned { id: u64 },
+	}
+
+	/// Inflow limits, indexed by asset location and by the origin the assets come from
+	#[pallet::storage]
+	#[pallet::getter(fn inflow_limit)]
+	pub type InflowLimits<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		MultiLocation,
+		Blake2_128Concat,
+		MultiLocation,
+		InflowLimit<T::BlockNumber>,
+	>;
+
+	/// Current inflow of each asset from each limited origin
+	#[pallet::storage]
+	#[pallet::getter(fn inflow)]
+	pub type Inflows<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		MultiLocation,
+		Blake2_128Concat,
+		MultiLocation,
+		Inflow<T::BlockNumber>,
+		ValueQuery,
+	>;
+
+	/// Assets held in the quarantine account, indexed by quarantine id
+	#[pallet::storage]
+	#[pallet::getter(fn quarantined)]
+	pub type Quarantined<T: Config> = StorageMap<_, Twox64Concat, u64, QuarantinedAsset>;
+
+	/// The id to be assigned to the next quarantined asset
+	#[pallet::storage]
+	#[pallet::getter(fn next_quarantine_id)]
+	pub type NextQuarantineId<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Limit the amount of `asset` that can flow in from `from` to `amount` every `period`
+		/// blocks
+		#[pallet::weight(T::WeightInfo::set_inflow_limit())]
+		pub fn set_inflow_limit(
+			origin: OriginFor<T>,
+			asset: Box<VersionedMultiLocation>,
+			from: Box<VersionedMultiLocation>,
+			amount: u128,
+			period: T::BlockNumber,
+		) -> DispatchResult {
+			T::LimiterOrigin::ensure_origin(origin)?;
+
+			ensure!(!period.is_zero(), Error::<T>::ZeroPeriod);
+			let asset = MultiLocation::try_from(*asset).map_err(|()| Error::<T>::BadVersion)?;
+			let from = MultiLocation::try_from(*from).map_err(|()| Error::<T>::BadVersion)?;
+
+			InflowLimits::<T>::insert(&asset, &from, InflowLimit { amount, period });
+
+			Self::deposit_event(Event::InflowLimitSet {
+				asset,
+				origin: from,
+				amount,
+				period,
+			});
+			Ok(())
+		}
+
+		/// Remove the inflow limit of `asset` from `from`
+		#[pallet::weight(T::WeightInfo::remove_inflow_limit())]
+		pub fn remove_inflow_limit(
+			origin: OriginFor<T>,
+			asset: Box<VersionedMultiLocation>,
+			from: Box<VersionedMultiLocation>,
+		) -> DispatchResult {
+			T::LimiterOrigin::ensure_origin(origin)?;
+
+			let asset = MultiLocation::try_from(*asset).map_err(|()| Error::<T>::BadVersion)?;
+			let from = MultiLocation::try_from(*from).map_err(|()| Error::<T>::BadVersion)?;
+			ensure!(
+				InflowLimits::<T>::contains_key(&asset, &from),
+				Error::<T>::InflowLimitNotFound
+			);
+
+			InflowLimits::<T>::remove(&asset, &from);
+			Inflows::<T>::remove(&asset, &from);
+
+			Self::deposit_event(Event::InflowLimitRemoved {
+				asset,
+				origin: from,
+			});
+			Ok(())
+		}
+
+		/// Move the quarantined assets with `id` to the beneficiary they were sent to. The
+		/// released assets do not count towards the inflow limit
+		#[pallet::weight(T::WeightInfo::release_quarantined())]
+		pub fn release_quarantined(origin: OriginFor<T>, id: u64) -> DispatchResult {
+			T::LimiterOrigin::ensure_origin(origin)?;
+
+			let quarantined = Quarantined::<T>::get(id).ok_or(Error::<T>::QuarantineNotFound)?;
+			T::AssetTransactor::withdraw_asset(&quarantined.asset, &Self::quarantine_location())
+				.map_err(|_| Error::<T>::ReleaseFailed)?;
+			T::AssetTransactor::deposit_asset(&quarantined.asset, &quarantined.beneficiary)
+				.map_err(|_| Error::<T>::ReleaseFailed)?;
+
+			Quarantined::<T>::remove(id);
+
+			Self::deposit_event(Event::QuarantineReleased { id });
+			Ok(())
+		}
+
+		/// Burn the quarantined assets with `id`
+		#[pallet::weight(T::WeightInfo::burn_quarantined())]
+		pub fn burn_quarantined(origin: OriginFor<T>, id: u64) -> DispatchResult {
+			T::LimiterOrigin::ensure_origin(origin)?;
+
+			let quarantined = Quarantined::<T>::get(id).ok_or(Error::<T>::QuarantineNotFound)?;
+			T::AssetTransactor::withdraw_asset(&quarantined.asset, &Self::quarantine_location())
+				.map_err(|_| Error::<T>::BurnFailed)?;
+
+			Quarantined::<T>::remove(id);
+
+			Self::deposit_event(Event::QuarantineBurned { id });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The account ID of XcmRateLimiter, which holds the quarantined assets
+		pub fn account_id() -> T::AccountId {
+			PALLET_ID.into_account_truncating()
+		}
+
+		/// The location of the quarantine account
+		pub fn quarantine_location() -> MultiLocation {
+			T::AccountIdToMultiLocation::convert(Self::account_id())
+		}
+
+		/// Returns how many of the `amount` units of `asset` coming from `origin` fit within its
+		/// inflow limit, together with the resulting inflow. Returns None if there is no limit
+		pub(crate) fn inflow_allowance(
+			asset: &MultiLocation,
+			origin: &MultiLocation,
+			amount: u128,
+		) -> Option<(u128, Inflow<T::BlockNumber>)> {
+			let limit = InflowLimits::<T>::get(asset, origin)?;
+			let inflow = Inflows::<T>::get(asset, origin);
+			let now = frame_system::Pallet::<T>::block_number();
+
+			// The limit is replenished linearly over its period
+			let elapsed: u128 = now
+				.saturating_sub(inflow.updated_at)
+				.unique_saturated_into();
+			let period: u128 = limit.period.unique_saturated_into();
+			let replenished = limit.amount.saturating_mul(elapsed) / period.max(1);
+			let current = inflow.amount.saturating_sub(replenished);
+
+			let allowed = amount.min(limit.amount.saturating_sub(current));
+			Some((
+				allowed,
+				Inflow {
+					amount: current.saturating_add(allowed),
+					updated_at: now,
+				},
+			))
+		}
+
+		/// Records assets deposited into the quarantine account
+		pub(crate) fn quarantine(
+			asset: MultiAsset,
+			origin: MultiLocation,
+			beneficiary: MultiLocation,
+		) {
+			let id = NextQuarantineId::<T>::get();
+			NextQuarantineId::<T>::put(id.saturating_add(1));
+			Quarantined::<T>::insert(
+				id,
+				QuarantinedAsset {
+					asset: asset.clone(),
+					origin: origin.clone(),
+					beneficiary: beneficiary.clone(),
+				},
+			);
+
+			Self::deposit_event(Event::AssetsQuarantined {
+				id,
+				asset,
+				origin,
+				beneficiary,
+			});
+		}
+	}
+}
@@ -0,0 +1,212 @@
+// Copyright 2019-2022 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A minimal runtime including the xcm-rate-limiter pallet
+use super::*;
+use crate as pallet_xcm_rate_limiter;
+use frame_support::storage::unhashed;
+use frame_support::{construct_runtime, parameter_types, traits::Everything, weights::Weight};
+use frame_system::EnsureRoot;
+use parity_scale_codec::Encode;
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, Convert, IdentityLookup},
+	Perbill,
+};
+use xcm::latest::prelude::*;
+use xcm_executor::{traits::TransactAsset, Assets};
+
+pub type AccountId = u64;
+pub type BlockNumber = u64;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime to test the pallet.
+construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		XcmRateLimiter: pallet_xcm_rate_limiter::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const MaximumBlockWeight: Weight = 1024;
+	pub const MaximumBlockLength: u32 = 2 * 1024;
+	pub const AvailableBlockRatio: Perbill = Perbill::one();
+	pub const SS58Prefix: u8 = 42;
+}
+impl frame_system::Config for Test {
+	type BaseCallFilter = Everything;
+	type DbWeight = ();
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = BlockNumber;
+	type Call = Call;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type BlockWeights = ();
+	type BlockLength = ();
+	type SS58Prefix = SS58Prefix;
+	type OnSetCode = ();
+	type MaxConsumers = frame_support::traits::ConstU32<16>;
+}
+
+fn balance_key(asset: &MultiLocation, who: &MultiLocation) -> Vec<u8> {
+	(b"balance", asset, who).encode()
+}
+
+/// Balance of `asset` held by `who` in the mock transactor
+pub(crate) fn balance(asset: &MultiLocation, who: &MultiLocation) -> u128 {
+	unhashed::get_or_default(&balance_key(asset, who))
+}
+
+/// Transactor keeping the balances of concrete fungible assets in storage, so that they are
+/// reverted together with the pallet storage
+pub struct MockTransactor;
+impl TransactAsset for MockTransactor {
+	fn deposit_asset(what: &MultiAsset, who: &MultiLocation) -> XcmResult {
+		match what {
+			MultiAsset {
+				id: Concrete(id),
+				fun: Fungible(amount),
+			} => {
+				let balance = balance(id, who)
+					.checked_add(*amount)
+					.ok_or(XcmError::FailedToTransactAsset("Balance overflow"))?;
+				unhashed::put(&balance_key(id, who), &balance);
+				Ok(())
+			}
+			_ => Err(XcmError::AssetNotFound),
+		}
+	}
+
+	fn withdraw_asset(what: &MultiAsset, who: &MultiLocation) -> Result<Assets, XcmError> {
+		match what {
+			MultiAsset {
+				id: Concrete(id),
+				fun: Fungible(amount),
+			} => {
+				let balance = balance(id, who)
+					.checked_sub(*amount)
+					.ok_or(XcmError::FailedToTransactAsset("Insufficient balance"))?;
+				unhashed::put(&balance_key(id, who), &balance);
+				Ok(what.clone().into())
+			}
+			_ => Err(XcmError::AssetNotFound),
+		}
+	}
+}
+
+/// Converts an account into a location under this chain
+pub struct AccountIdToMultiLocation;
+impl Convert<AccountId, MultiLocation> for AccountIdToMultiLocation {
+	fn convert(account: AccountId) -> MultiLocation {
+		MultiLocation::new(0, X1(GeneralIndex(account.into())))
+	}
+}
+
+/// Executor depositing the assets of every DepositAsset instruction through the pallet
+pub struct MockExecutor;
+impl ExecuteXcm<()> for MockExecutor {
+	fn execute_xcm_in_credit(
+		_origin: impl Into<MultiLocation>,
+		message: Xcm<()>,
+		weight_limit: Weight,
+		_weight_credit: Weight,
+	) -> Outcome {
+		for instruction in message.0 {
+			if let DepositAsset {
+				assets: Definite(assets),
+				beneficiary,
+				..
+			} = instruction
+			{
+				for asset in assets.drain() {
+					if let Err(e) = XcmRateLimiter::deposit_asset(&asset, &beneficiary) {
+						return Outcome::Incomplete(weight_limit, e);
+					}
+				}
+			}
+		}
+		Outcome::Complete(weight_limit)
+	}
+}
+
+parameter_types! {
+	pub BenchmarkAsset: MultiLocation = MultiLocation::parent();
+}
+
+impl Config for Test {
+	type Event = Event;
+	type AssetTransactor = MockTransactor;
+	type AccountIdToMultiLocation = AccountIdToMultiLocation;
+	type LimiterOrigin = EnsureRoot<AccountId>;
+	type WeightInfo = ();
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkAsset = BenchmarkAsset;
+}
+
+pub(crate) struct ExtBuilder;
+
+impl Default for ExtBuilder {
+	fn default() -> ExtBuilder {
+		ExtBuilder
+	}
+}
+
+impl ExtBuilder {
+	pub(crate) fn build(self) -> sp_io::TestExternalities {
+		let t = frame_system::GenesisConfig::default()
+			.build_storage::<Test>()
+			.expect("Frame system builds valid default genesis config");
+
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+}
+
+pub(crate) fn events() -> Vec<pallet_xcm_rate_limiter::Event<Test>> {
+	System::events()
+		.into_iter()
+		.map(|r| r.event)
+		.filter_map(|e| {
+			if let Event::XcmRateLimiter(inner) = e {
+				Some(inner)
+			} else {
+				None
+			}
+		})
+		.collect::<Vec<_>>()
+}
@@ -0,0 +1,348 @@
+// Copyright 2019-2022 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Unit testing
+use crate::mock::{
+	balance, events, AccountIdToMultiLocation, ExtBuilder, MockExecutor, MockTransactor, Origin,
+	System, Test, XcmRateLimiter,
+};
+use crate::{Error, Event, Inflow, InflowLimit, QuarantinedAsset, TrackInboundOrigin};
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::{traits::Convert, DispatchError};
+use xcm::latest::prelude::*;
+use xcm_executor::traits::TransactAsset;
+
+fn asset() -> MultiLocation {
+	MultiLocation::parent()
+}
+
+fn sibling() -> MultiLocation {
+	MultiLocation::new(1, X1(Parachain(2000)))
+}
+
+fn beneficiary() -> MultiLocation {
+	AccountIdToMultiLocation::convert(1)
+}
+
+fn receive(origin: MultiLocation, amount: u128) -> Outcome {
+	TrackInboundOrigin::<MockExecutor>::execute_xcm(
+		origin,
+		Xcm(vec![DepositAsset {
+			assets: Definite((asset(), amount).into()),
+			max_assets: 1,
+			beneficiary: beneficiary(),
+		}]),
+		1_000,
+	)
+}
+
+fn set_limit(amount: u128, period: u64) {
+	assert_ok!(XcmRateLimiter::set_inflow_limit(
+		Origin::root(),
+		Box::new(asset().into()),
+		Box::new(sibling().into()),
+		amount,
+		period
+	));
+}
+
+#[test]
+fn set_inflow_limit_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		set_limit(100, 10);
+
+		assert_eq!(
+			XcmRateLimiter::inflow_limit(asset(), sibling()),
+			Some(InflowLimit {
+				amount: 100,
+				period: 10
+			})
+		);
+		assert_eq!(
+			events(),
+			vec![Event::InflowLimitSet {
+				asset: asset(),
+				origin: sibling(),
+				amount: 100,
+				period: 10,
+			}]
+		);
+	})
+}
+
+#[test]
+fn set_inflow_limit_requires_limiter_origin() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			XcmRateLimiter::set_inflow_limit(
+				Origin::signed(1),
+				Box::new(asset().into()),
+				Box::new(sibling().into()),
+				100,
+				10
+			),
+			DispatchError::BadOrigin
+		);
+	})
+}
+
+#[test]
+fn set_inflow_limit_rejects_zero_period() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			XcmRateLimiter::set_inflow_limit(
+				Origin::root(),
+				Box::new(asset().into()),
+				Box::new(sibling().into()),
+				100,
+				0
+			),
+			Error::<Test>::ZeroPeriod
+		);
+	})
+}
+
+#[test]
+fn remove_inflow_limit_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		set_limit(100, 10);
+		assert_eq!(receive(sibling(), 50), Outcome::Complete(1_000));
+
+		assert_ok!(XcmRateLimiter::remove_inflow_limit(
+			Origin::root(),
+			Box::new(asset().into()),
+			Box::new(sibling().into()),
+		));
+
+		assert_eq!(XcmRateLimiter::inflow_limit(asset(), sibling()), None);
+		assert_eq!(
+			XcmRateLimiter::inflow(asset(), sibling()),
+			Inflow::default()
+		);
+		assert_eq!(
+			events().last(),
+			Some(&Event::InflowLimitRemoved {
+				asset: asset(),
+				origin: sibling(),
+			})
+		);
+
+		// No limit applies anymore
+		assert_eq!(receive(sibling(), 500), Outcome::Complete(1_000));
+		assert_eq!(balance(&asset(), &beneficiary()), 550);
+	})
+}
+
+#[test]
+fn remove_unknown_inflow_limit_fails() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			XcmRateLimiter::remove_inflow_limit(
+				Origin::root(),
+				Box::new(asset().into()),
+				Box::new(sibling().into()),
+			),
+			Error::<Test>::InflowLimitNotFound
+		);
+	})
+}
+
+#[test]
+fn deposits_within_limit_are_not_quarantined() {
+	ExtBuilder::default().build().execute_with(|| {
+		set_limit(100, 10);
+
+		assert_eq!(receive(sibling(), 60), Outcome::Complete(1_000));
+		assert_eq!(receive(sibling(), 40), Outcome::Complete(1_000));
+
+		assert_eq!(balance(&asset(), &beneficiary()), 100);
+		assert_eq!(XcmRateLimiter::inflow(asset(), sibling()).amount, 100);
+		assert_eq!(XcmRateLimiter::next_quarantine_id(), 0);
+	})
+}
+
+#[test]
+fn deposits_exceeding_limit_are_quarantined() {
+	ExtBuilder::default().build().execute_with(|| {
+		set_limit(100, 10);
+
+		assert_eq!(receive(sibling(), 123), Outcome::Complete(1_000));
+
+		assert_eq!(balance(&asset(), &beneficiary()), 100);
+		assert_eq!(
+			balance(&asset(), &XcmRateLimiter::quarantine_location()),
+			23
+		);
+		assert_eq!(
+			XcmRateLimiter::quarantined(0),
+			Some(QuarantinedAsset {
+				asset: (asset(), 23).into(),
+				origin: sibling(),
+				beneficiary: beneficiary(),
+			})
+		);
+		assert_eq!(XcmRateLimiter::next_quarantine_id(), 1);
+		assert_eq!(
+			events().last(),
+			Some(&Event::AssetsQuarantined {
+				id: 0,
+				asset: (asset(), 23).into(),
+				origin: sibling(),
+				beneficiary: beneficiary(),
+			})
+		);
+	})
+}
+
+#[test]
+fn failed_quarantine_reverts_the_deposit() {
+	ExtBuilder::default().build().execute_with(|| {
+		set_limit(100, 10);
+		// The quarantine account cannot hold any more of the asset
+		assert_ok!(MockTransactor::deposit_asset(
+			&(asset(), u128::MAX).into(),
+			&XcmRateLimiter::quarantine_location()
+		));
+
+		assert_eq!(
+			receive(sibling(), 123),
+			Outcome::Incomplete(1_000, XcmError::FailedToTransactAsset("Balance overflow"))
+		);
+
+		// Neither the allowed amount nor the inflow were recorded
+		assert_eq!(balance(&asset(), &beneficiary()), 0);
+		assert_eq!(
+			XcmRateLimiter::inflow(asset(), sibling()),
+			Inflow::default()
+		);
+		assert_eq!(XcmRateLimiter::quarantined(0), None);
+		assert_eq!(XcmRateLimiter::next_quarantine_id(), 0);
+	})
+}
+
+#[test]
+fn limit_is_replenished_over_its_period() {
+	ExtBuilder::default().build().execute_with(|| {
+		set_limit(100, 10);
+		assert_eq!(receive(sibling(), 100), Outcome::Complete(1_000));
+
+		// 4 blocks replenish 40% of the limit
+		System::set_block_number(5);
+		assert_eq!(receive(sibling(), 50), Outcome::Complete(1_000));
+		assert_eq!(balance(&asset(), &beneficiary()), 140);
+		assert_eq!(
+			balance(&asset(), &XcmRateLimiter::quarantine_location()),
+			10
+		);
+
+		// A full period replenishes the whole limit
+		System::set_block_number(15);
+		assert_eq!(receive(sibling(), 100), Outcome::Complete(1_000));
+		assert_eq!(balance(&asset(), &beneficiary()), 240);
+		assert_eq!(XcmRateLimiter::next_quarantine_id(), 1);
+	})
+}
+
+#[test]
+fn limits_apply_per_origin() {
+	ExtBuilder::default().build().execute_with(|| {
+		set_limit(100, 10);
+
+		assert_eq!(
+			receive(MultiLocation::parent(), 200),
+			Outcome::Complete(1_000)
+		);
+		assert_eq!(balance(&asset(), &beneficiary()), 200);
+		assert_eq!(XcmRateLimiter::next_quarantine_id(), 0);
+	})
+}
+
+#[test]
+fn deposits_outside_xcm_execution_are_not_limited() {
+	ExtBuilder::default().build().execute_with(|| {
+		set_limit(100, 10);
+
+		assert_ok!(XcmRateLimiter::deposit_asset(
+			&(asset(), 200).into(),
+			&beneficiary()
+		));
+		assert_eq!(balance(&asset(), &beneficiary()), 200);
+		assert_eq!(XcmRateLimiter::next_quarantine_id(), 0);
+	})
+}
+
+#[test]
+fn release_quarantined_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		set_limit(100, 10);
+		assert_eq!(receive(sibling(), 123), Outcome::Complete(1_000));
+
+		assert_ok!(XcmRateLimiter::release_quarantined(Origin::root(), 0));
+
+		assert_eq!(balance(&asset(), &beneficiary()), 123);
+		assert_eq!(balance(&asset(), &XcmRateLimiter::quarantine_location()), 0);
+		assert_eq!(XcmRateLimiter::quarantined(0), None);
+		// Released assets do not count towards the limit
+		assert_eq!(XcmRateLimiter::inflow(asset(), sibling()).amount, 100);
+		assert_eq!(events().last(), Some(&Event::QuarantineReleased { id: 0 }));
+	})
+}
+
+#[test]
+fn burn_quarantined_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		set_limit(100, 10);
+		assert_eq!(receive(sibling(), 123), Outcome::Complete(1_000));
+
+		assert_ok!(XcmRateLimiter::burn_quarantined(Origin::root(), 0));
+
+		assert_eq!(balance(&asset(), &beneficiary()), 100);
+		assert_eq!(balance(&asset(), &XcmRateLimiter::quarantine_location()), 0);
+		assert_eq!(XcmRateLimiter::quarantined(0), None);
+		assert_eq!(events().last(), Some(&Event::QuarantineBurned { id: 0 }));
+	})
+}
+
+#[test]
+fn quarantine_management_requires_limiter_origin() {
+	ExtBuilder::default().build().execute_with(|| {
+		set_limit(100, 10);
+		assert_eq!(receive(sibling(), 123), Outcome::Complete(1_000));
+
+		assert_noop!(
+			XcmRateLimiter::release_quarantined(Origin::signed(1), 0),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			XcmRateLimiter::burn_quarantined(Origin::signed(1), 0),
+			DispatchError::BadOrigin
+		);
+	})
+}
+
+#[test]
+fn unknown_quarantine_cannot_be_released_or_burned() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			XcmRateLimiter::release_quarantined(Origin::root(), 0),
+			Error::<Test>::QuarantineNotFound
+		);
+		assert_noop!(
+			XcmRateLimiter::burn_quarantined(Origin::root(), 0),
+			Error::<Test>::QuarantineNotFound
+		);
+	})
+}
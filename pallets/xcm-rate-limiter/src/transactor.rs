@@ -0,0 +1,138 @@
+// Copyright 2019-2022 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The executor wrapper and the rate limited TransactAsset implementation
+
+use crate::{Config, Inflow, Inflows, Pallet};
+use frame_support::{
+	storage::{with_transaction, TransactionOutcome},
+	weights::Weight,
+};
+use sp_runtime::DispatchError;
+use sp_std::marker::PhantomData;
+use xcm::latest::prelude::*;
+use xcm_executor::{traits::TransactAsset, Assets};
+
+environmental::environmental!(inbound_origin: MultiLocation);
+
+/// Wraps an XCM executor, making the origin of the message being executed available to the
+/// rate limiting transactor. The origin is captured before executing the message, so
+/// instructions altering it (e.g. DescendOrigin) do not change the origin limits apply to
+pub struct TrackInboundOrigin<Executor>(PhantomData<Executor>);
+
+impl<Call, Executor: ExecuteXcm<Call>> ExecuteXcm<Call> for TrackInboundOrigin<Executor> {
+	fn execute_xcm_in_credit(
+		origin: impl Into<MultiLocation>,
+		message: Xcm<Call>,
+		weight_limit: Weight,
+		weight_credit: Weight,
+	) -> Outcome {
+		let origin = origin.into();
+		inbound_origin::using(&mut origin.clone(), || {
+			Executor::execute_xcm_in_credit(origin, message, weight_limit, weight_credit)
+		})
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Deposits `allowed` units of the asset `id` into `who`, quarantining the rest of `amount`
+	fn limited_deposit(
+		id: &MultiLocation,
+		amount: u128,
+		allowed: u128,
+		who: &MultiLocation,
+		origin: MultiLocation,
+		inflow: Inflow<T::BlockNumber>,
+	) -> XcmResult {
+		if allowed > 0 {
+			T::AssetTransactor::deposit_asset(&(id.clone(), allowed).into(), who)?;
+		}
+		Inflows::<T>::insert(id, &origin, inflow);
+
+		let excess = amount.saturating_sub(allowed);
+		if excess > 0 {
+			let excess: MultiAsset = (id.clone(), excess).into();
+			T::AssetTransactor::deposit_asset(&excess, &Pallet::<T>::quarantine_location())?;
+			Pallet::<T>::quarantine(excess, origin, who.clone());
+		}
+
+		Ok(())
+	}
+}
+
+impl<T: Config> TransactAsset for Pallet<T> {
+	fn can_check_in(origin: &MultiLocation, what: &MultiAsset) -> XcmResult {
+		T::AssetTransactor::can_check_in(origin, what)
+	}
+
+	fn check_in(origin: &MultiLocation, what: &MultiAsset) {
+		T::AssetTransactor::check_in(origin, what)
+	}
+
+	fn check_out(dest: &MultiLocation, what: &MultiAsset) {
+		T::AssetTransactor::check_out(dest, what)
+	}
+
+	fn deposit_asset(what: &MultiAsset, who: &MultiLocation) -> XcmResult {
+		// Only concrete fungible assets deposited while executing a message can be limited
+		let (origin, id, amount) = match (inbound_origin::with(|origin| origin.clone()), what) {
+			(
+				Some(origin),
+				MultiAsset {
+					id: Concrete(id),
+					fun: Fungible(amount),
+				},
+			) => (origin, id, *amount),
+			_ => return T::AssetTransactor::deposit_asset(what, who),
+		};
+		let (allowed, inflow) = match Pallet::<T>::inflow_allowance(id, &origin, amount) {
+			Some(allowance) => allowance,
+			None => return T::AssetTransactor::deposit_asset(what, who),
+		};
+
+		// The deposit of the allowed amount is reverted if the excess cannot be quarantined,
+		// so that a failed deposit does not consume the inflow limit
+		with_transaction(
+			|| match Self::limited_deposit(id, amount, allowed, who, origin, inflow) {
+				Ok(()) => TransactionOutcome::Commit(Ok(Ok(()))),
+				Err(e) => TransactionOutcome::Rollback(Ok(Err(e))),
+			},
+		)
+		.map_err(|_: DispatchError| {
+			XcmError::FailedToTransactAsset("Too many nested storage transactions")
+		})?
+	}
+
+	fn withdraw_asset(what: &MultiAsset, who: &MultiLocation) -> Result<Assets, XcmError> {
+		T::AssetTransactor::withdraw_asset(what, who)
+	}
+
+	fn internal_transfer_asset(
+		asset: &MultiAsset,
+		from: &MultiLocation,
+		to: &MultiLocation,
+	) -> Result<Assets, XcmError> {
+		T::AssetTransactor::internal_transfer_asset(asset, from, to)
+	}
+
+	fn transfer_asset(
+		asset: &MultiAsset,
+		from: &MultiLocation,
+		to: &MultiLocation,
+	) -> Result<Assets, XcmError> {
+		T::AssetTransactor::transfer_asset(asset, from, to)
+	}
+}
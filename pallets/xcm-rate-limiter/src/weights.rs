@@ -0,0 +1,129 @@
+// Copyright 2019-2022 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Weights for xcm_rate_limiter
+//!
+//! The base weights are estimates until regenerated from the benchmarks with:
+// ./target/release/moonbeam
+// benchmark
+// pallet
+// --chain
+// dev
+// --execution=wasm
+// --wasm-execution=compiled
+// --pallet
+// xcm_rate_limiter
+// --extrinsic
+// *
+// --steps
+// 50
+// --repeat
+// 20
+// --template=./benchmarking/frame-weight-template.hbs
+// --json-file
+// raw.json
+// --output
+// weights.rs
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for xcm_rate_limiter.
+pub trait WeightInfo {
+	#[rustfmt::skip]
+	fn set_inflow_limit() -> Weight;
+	#[rustfmt::skip]
+	fn remove_inflow_limit() -> Weight;
+	#[rustfmt::skip]
+	fn release_quarantined() -> Weight;
+	#[rustfmt::skip]
+	fn burn_quarantined() -> Weight;
+}
+
+/// Weights for xcm_rate_limiter using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: XcmRateLimiter InflowLimits (r:0 w:1)
+	#[rustfmt::skip]
+	fn set_inflow_limit() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: XcmRateLimiter InflowLimits (r:1 w:1)
+	// Storage: XcmRateLimiter Inflows (r:0 w:1)
+	#[rustfmt::skip]
+	fn remove_inflow_limit() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: XcmRateLimiter Quarantined (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	#[rustfmt::skip]
+	fn release_quarantined() -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: XcmRateLimiter Quarantined (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	#[rustfmt::skip]
+	fn burn_quarantined() -> Weight {
+		(35_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	// Storage: XcmRateLimiter InflowLimits (r:0 w:1)
+	#[rustfmt::skip]
+	fn set_inflow_limit() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: XcmRateLimiter InflowLimits (r:1 w:1)
+	// Storage: XcmRateLimiter Inflows (r:0 w:1)
+	#[rustfmt::skip]
+	fn remove_inflow_limit() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: XcmRateLimiter Quarantined (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	#[rustfmt::skip]
+	fn release_quarantined() -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	// Storage: XcmRateLimiter Quarantined (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	#[rustfmt::skip]
+	fn burn_quarantined() -> Weight {
+		(35_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+}
@@ -16,31 +16,48 @@
 
 #![cfg(feature = "runtime-benchmarks")]
 
-use crate::{Call, Config, Currency, CurrencyPayment, Pallet, TransactWeights};
+use crate::{
+	Call, Config, Currency, CurrencyPayment, HrmpInitParams, HrmpOperation, Pallet,
+	TransactOptions, TransactStatus, TransactStatusInfo, TransactStatusQueries, TransactWeights,
+};
 use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite};
+use frame_support::traits::EnsureOrigin;
 use frame_system::RawOrigin;
 use sp_std::boxed::Box;
 use sp_std::vec;
 use xcm::latest::prelude::*;
+use xcm_primitives::XcmTransact;
 
 benchmarks! {
 	where_clause { where T::Transactor: Default, T::CurrencyId: From<MultiLocation>}
 	register {
 		let user: T::AccountId  = account("account id", 0u32, 0u32);
 
+		let dest = MultiLocation::parent();
 		let index = 1u16;
-	}: _(RawOrigin::Root, user.clone(), index)
+	}: _(
+		RawOrigin::Root,
+		user.clone(),
+		Box::new(xcm::VersionedMultiLocation::V1(dest.clone())),
+		index
+	)
 	verify {
-		assert_eq!(Pallet::<T>::index_to_account(index), Some(user));
+		assert_eq!(Pallet::<T>::index_to_account(dest, index), Some(user));
 	}
 
 	deregister {
 		let user: T::AccountId  = account("account id", 0u32, 0u32);
+		let dest = MultiLocation::parent();
 		let index = 1u16;
-		Pallet::<T>::register(RawOrigin::Root.into(), user, index).unwrap();
-	}: _(RawOrigin::Root, index)
+		Pallet::<T>::register(
+			RawOrigin::Root.into(),
+			user,
+			Box::new(xcm::VersionedMultiLocation::V1(dest.clone())),
+			index
+		).unwrap();
+	}: _(RawOrigin::Root, Box::new(xcm::VersionedMultiLocation::V1(dest.clone())), index)
 	verify {
-		assert!(Pallet::<T>::index_to_account(index).is_none());
+		assert!(Pallet::<T>::index_to_account(dest, index).is_none());
 	}
 
 	set_transact_info {
@@ -117,6 +134,7 @@ benchmarks! {
 		Pallet::<T>::register(
 			RawOrigin::Root.into(),
 			user.clone(),
+			Box::new(xcm::VersionedMultiLocation::V1(T::Transactor::default().destination())),
 			0
 		).unwrap();
 	}: {
@@ -238,6 +256,232 @@ benchmarks! {
 			overall_weight: None
 		}
 	)
+
+	notify_transact_status {
+		let origin = T::ResponseOrigin::successful_origin();
+		let dest = T::ResponseOrigin::ensure_origin(origin.clone())
+			.map_err(|_| "Response origin does not yield a location")?;
+		let query_id = 0u64;
+		TransactStatusQueries::<T>::insert(query_id, TransactStatusInfo {
+			dest,
+			status: TransactStatus::Pending
+		});
+		let response = Response::ExecutionResult(Some((3u32, XcmError::Barrier)));
+	}: _<T::Origin>(origin, query_id, response)
+	verify {
+		assert_eq!(
+			Pallet::<T>::transact_status(query_id).map(|info| info.status),
+			Some(TransactStatus::Failed { index: 3u32, error: XcmError::Barrier })
+		);
+	}
+
+	hrmp_manage {
+		let fee_per_second = 1;
+		let extra_weight = 300000000u64;
+		let max_weight = 20000000000u64;
+		let location = MultiLocation::parent();
+		let currency: T::CurrencyId = location.clone().into();
+		let dest_weight = 100u64;
+		Pallet::<T>::set_transact_info(
+			RawOrigin::Root.into(),
+			Box::new(xcm::VersionedMultiLocation::V1(location.clone())),
+			extra_weight,
+			max_weight,
+			Some(extra_weight)
+		).unwrap();
+		Pallet::<T>::set_fee_per_second(
+			RawOrigin::Root.into(),
+			Box::new(xcm::VersionedMultiLocation::V1(location.clone())),
+			fee_per_second
+		).unwrap();
+		let origin = T::HrmpManipulatorOrigin::successful_origin();
+		let action = HrmpOperation::InitOpen(HrmpInitParams {
+			para_id: 1000u32.into(),
+			proposed_max_capacity: 1000u32,
+			proposed_max_message_size: 1000u32,
+		});
+	}: _<T::Origin>(
+		origin,
+		action,
+		CurrencyPayment {
+			// This might involve a db Read when translating, therefore worst case
+			currency: Currency::AsCurrencyId(currency),
+			// This involves a db Read, hence the None is worst case
+			fee_amount: None
+		},
+		TransactWeights {
+			transact_required_weight_at_most: dest_weight,
+			// This involves a db Read, hence the None is worst case
+			overall_weight: None
+		}
+	)
+
+	transact_through_derivative_with_options {
+		let fee_per_second = 1;
+		let extra_weight = 300000000u64;
+		let max_weight = 20000000000u64;
+		let location = MultiLocation::parent();
+		let call = vec![1u8];
+		let dest_weight = 100u64;
+		let currency: T::CurrencyId = location.clone().into();
+		let user: T::AccountId  = account("account id", 0u32, 0u32);
+		Pallet::<T>::set_transact_info(
+			RawOrigin::Root.into(),
+			Box::new(xcm::VersionedMultiLocation::V1(location.clone())),
+			extra_weight,
+			max_weight,
+			Some(extra_weight)
+		).unwrap();
+		Pallet::<T>::set_fee_per_second(
+			RawOrigin::Root.into(),
+			Box::new(xcm::VersionedMultiLocation::V1(location.clone())),
+			fee_per_second
+		).unwrap();
+		Pallet::<T>::register(
+			RawOrigin::Root.into(),
+			user.clone(),
+			Box::new(xcm::VersionedMultiLocation::V1(T::Transactor::default().destination())),
+			0
+		).unwrap();
+	}: {
+
+		let result = Pallet::<T>::transact_through_derivative_with_options(
+			RawOrigin::Signed(user.clone()).into(),
+			T::Transactor::default(),
+			0,
+			CurrencyPayment {
+				// This might involve a db Read when translating, therefore worst case
+				currency: Currency::AsCurrencyId(currency),
+				// This involves a db Read, hence the None is worst case
+				fee_amount: None
+			},
+			call,
+			TransactWeights {
+				transact_required_weight_at_most: dest_weight,
+				// This involves a db Read, hence the None is worst case
+				overall_weight: None
+			},
+			// Registering the status query involves db writes, hence worst case. Refunds are
+			// not supported through the sovereign account
+			TransactOptions {
+				report_status: true,
+				refund: false,
+			}
+		);
+
+		// It's expected that the error comes from the fact that the asset is not known
+		// The weight coming withdraw asset + send is accounted by charging for the instruction per se
+		if result.is_ok() {
+			assert_eq!(result, Ok(()))
+		}
+		else {
+			assert_eq!(result, Err(crate::Error::<T>::UnableToWithdrawAsset.into()))
+		}
+	}
+
+	transact_through_sovereign_with_options {
+		let fee_per_second = 1;
+		let extra_weight = 300000000u64;
+		let max_weight = 20000000000u64;
+		let location = MultiLocation::parent();
+		let currency: T::CurrencyId = location.clone().into();
+		let call = vec![1u8];
+		let dest_weight = 100u64;
+		let user: T::AccountId  = account("account id", 0u32, 0u32);
+		Pallet::<T>::set_transact_info(
+			RawOrigin::Root.into(),
+			Box::new(xcm::VersionedMultiLocation::V1(location.clone())),
+			extra_weight,
+			max_weight,
+			Some(extra_weight)
+		).unwrap();
+		Pallet::<T>::set_fee_per_second(
+			RawOrigin::Root.into(),
+			Box::new(xcm::VersionedMultiLocation::V1(location.clone())),
+			fee_per_second
+		).unwrap();
+	}: {
+
+		let result = Pallet::<T>::transact_through_sovereign_with_options(
+			RawOrigin::Root.into(),
+			Box::new(xcm::VersionedMultiLocation::V1(location.clone())),
+			user.clone(),
+			CurrencyPayment {
+				// This might involve a db Read when translating, therefore worst case
+				currency: Currency::AsCurrencyId(currency),
+				// This involves a db Read, hence the None is worst case
+				fee_amount: None
+			},
+			call,
+			OriginKind::SovereignAccount,
+			TransactWeights {
+				transact_required_weight_at_most: dest_weight,
+				// This involves a db Read, hence the None is worst case
+				overall_weight: None
+			},
+			// Registering the status query involves db writes, hence worst case. Refunds are
+			// not supported through the sovereign account
+			TransactOptions {
+				report_status: true,
+				refund: false,
+			}
+		);
+
+		// It's expected that the error comes from the fact that the asset is not known
+		// The weight coming withdraw asset + send is accounted by charging for the instruction per se
+		if result.is_ok() {
+			assert_eq!(result, Ok(()))
+		}
+		else {
+			assert_eq!(result, Err(crate::Error::<T>::UnableToWithdrawAsset.into()))
+		}
+	}
+
+	transact_through_signed_with_options {
+		let fee_per_second = 1;
+		let extra_weight = 300000000u64;
+		let max_weight = 20000000000u64;
+		let location = MultiLocation::parent();
+		let currency: T::CurrencyId = location.clone().into();
+		let call = vec![1u8];
+		let dest_weight = 100u64;
+		let user: T::AccountId  = account("account id", 0u32, 0u32);
+		Pallet::<T>::set_transact_info(
+			RawOrigin::Root.into(),
+			Box::new(xcm::VersionedMultiLocation::V1(location.clone())),
+			extra_weight,
+			max_weight,
+			Some(extra_weight)
+		).unwrap();
+		Pallet::<T>::set_fee_per_second(
+			RawOrigin::Root.into(),
+			Box::new(xcm::VersionedMultiLocation::V1(location.clone())),
+			fee_per_second
+		).unwrap();
+	}: {
+		Pallet::<T>::transact_through_signed_with_options(
+			RawOrigin::Signed(user.clone()).into(),
+			Box::new(xcm::VersionedMultiLocation::V1(location.clone())),
+			CurrencyPayment {
+				// This might involve a db Read when translating, therefore worst case
+				currency: Currency::AsCurrencyId(currency),
+				// This involves a db Read, hence the None is worst case
+				fee_amount: None
+			},
+			call,
+			TransactWeights {
+				transact_required_weight_at_most: dest_weight,
+				// This involves a db Read, hence the None is worst case
+				overall_weight: None
+			},
+			// Registering the status query involves db writes and refunding involves reanchoring
+			// the beneficiary, hence worst case
+			TransactOptions {
+				report_status: true,
+				refund: true,
+			}
+		)?;
+	}
 }
 
 #[cfg(test)]
@@ -48,7 +48,9 @@
 //! 	the inner call is executed from the derivative account and not the sovereign
 //! 	account itself.
 //!
-//! 	Index registration happens through DerivativeAddressRegistrationOrigin.
+//! 	Index registration happens through DerivativeAddressRegistrationOrigin, and is done
+//! 	per-destination: the same index can be owned by different accounts in different
+//! 	destinations.
 //! 	This derivative account can be funded by external users to
 //! 	ensure it has enough funds to make the calls
 //!
@@ -64,6 +66,28 @@
 //! 	(and DescendOrigin + WithdrawAsset + BuyExecution + Transact messages allowed) in the
 //! 	destination chain. Additionally, a ML-based derivation mechanism needs to be implemented
 //! 	in the destination chain.
+//!
+//! All three have a `_with_options` variant taking TransactOptions. With `report_status` set, a
+//! SetAppendix(ReportError) instruction is added to the message and a query is registered through
+//! QueryNotifier. The destination chain answers with the outcome of the execution, which is
+//! stored until the query times out and notified through the
+//! RemoteTransactSucceeded/RemoteTransactFailed events. Note that in XCM v2 ReportError only
+//! reports XCM execution errors: a Transact whose inner call fails to dispatch in the destination
+//! chain is still reported as succeeded. The weight of the appended instructions is added to the
+//! weight bought in the destination chain.
+//!
+//! With `refund` set, RefundSurplus and DepositAsset instructions are appended so that the fees
+//! not spent in the destination chain are deposited into the account derived from the caller in
+//! the destination chain. As for status reporting, the weight of these instructions is added to
+//! the weight bought in the destination chain. Refunds are only supported by
+//! transact_through_signed_with_options: transact_through_derivative and
+//! transact_through_sovereign pay for the fees from the sovereign account of this chain, and the
+//! destination chain has no account for the local fee payer to refund to, so the fees would be
+//! trapped there.
+//!
+//! Finally, hrmp_manage allows HrmpManipulatorOrigin to open, accept and close HRMP channels with
+//! other chains. The corresponding relay chain call is encoded through HrmpEncoder and transacted
+//! with a Native origin through the sovereign account, which pays for the fees in the relay.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -89,6 +113,7 @@ pub mod pallet {
 
 	use crate::weights::WeightInfo;
 	use crate::CurrencyIdOf;
+	use cumulus_primitives_core::{relay_chain::HrmpChannelId, ParaId};
 	use frame_support::{pallet_prelude::*, weights::constants::WEIGHT_PER_SECOND};
 	use frame_system::{ensure_signed, pallet_prelude::*};
 	use orml_traits::location::{Parse, Reserve};
@@ -98,7 +123,10 @@ pub mod pallet {
 	use sp_std::prelude::*;
 	use xcm::{latest::prelude::*, VersionedMultiLocation};
 	use xcm_executor::traits::{InvertLocation, TransactAsset, WeightBounds};
-	use xcm_primitives::{UtilityAvailableCalls, UtilityEncodeCall, XcmTransact};
+	use xcm_primitives::{
+		HrmpAvailableCalls, HrmpEncodeCall, UtilityAvailableCalls, UtilityEncodeCall,
+		XcmQueryNotifier, XcmTransact,
+	};
 
 	#[pallet::pallet]
 	#[pallet::without_storage_info]
@@ -163,6 +191,28 @@ pub mod pallet {
 		/// configured to accept absolute or relative paths for self tokens
 		type ReserveProvider: Reserve;
 
+		/// Means of registering queries that notify the outcome of a transact message back to
+		/// this pallet through `notify_transact_status`.
+		type QueryNotifier: XcmQueryNotifier<Call<Self>, Self::BlockNumber>;
+
+		/// The origin of the responses to the transact status queries. It must yield the location
+		/// of the responder.
+		type ResponseOrigin: EnsureOrigin<Self::Origin, Success = MultiLocation>;
+
+		/// Number of blocks after which a transact status query times out.
+		#[pallet::constant]
+		type TransactStatusTimeout: Get<Self::BlockNumber>;
+
+		/// Maximum number of transact status queries timing out in the same block.
+		#[pallet::constant]
+		type MaxTransactStatusQueriesPerBlock: Get<u32>;
+
+		// The origin that is allowed to manage the HRMP channels of this chain
+		type HrmpManipulatorOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Encoder of the HRMP calls of the relay chain
+		type HrmpEncoder: HrmpEncodeCall;
+
 		type WeightInfo: WeightInfo;
 	}
 
@@ -243,12 +293,70 @@ pub mod pallet {
 		pub overall_weight: Option<Weight>,
 	}
 
+	/// Opt-in features of a transact message
+	#[derive(Default, Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, scale_info::TypeInfo)]
+	pub struct TransactOptions {
+		/// Whether the destination chain should report the outcome of the message back. In XCM
+		/// v2 only XCM execution errors are reported: a Transact whose call fails to dispatch in
+		/// the destination chain is reported as succeeded
+		pub report_status: bool,
+		/// Whether the fees not spent in the destination chain should be deposited back into
+		/// the account derived from the caller in the destination chain. Only supported by
+		/// signed transacts
+		pub refund: bool,
+	}
+
+	/// Status of a transact message sent with status reporting enabled
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, scale_info::TypeInfo)]
+	pub enum TransactStatus {
+		/// The destination chain has not answered yet
+		Pending,
+		/// The message was executed in the destination chain without errors
+		Succeeded,
+		/// The message failed in the destination chain at the instruction in position `index`
+		Failed { index: u32, error: XcmError },
+	}
+
+	/// Stores the destination and the status of a transact status query
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, scale_info::TypeInfo)]
+	pub struct TransactStatusInfo {
+		/// The chain the transact message was sent to, and the only one allowed to answer
+		pub dest: MultiLocation,
+		/// The current status of the transact message
+		pub status: TransactStatus,
+	}
+
+	/// Parameters of a request to open an HRMP channel to another chain
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, scale_info::TypeInfo)]
+	pub struct HrmpInitParams {
+		/// The chain the channel is opened to
+		pub para_id: ParaId,
+		/// The maximum number of messages that can be pending in the channel at once
+		pub proposed_max_capacity: u32,
+		/// The maximum size of a message in the channel
+		pub proposed_max_message_size: u32,
+	}
+
+	/// HRMP operations that this chain can request in the relay chain
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, scale_info::TypeInfo)]
+	pub enum HrmpOperation {
+		/// Request opening a channel from this chain to another chain
+		InitOpen(HrmpInitParams),
+		/// Accept the request of another chain to open a channel to this chain
+		Accept { para_id: ParaId },
+		/// Close a channel this chain is either the sender or the recipient of
+		Close(HrmpChannelId),
+	}
+
 	/// Since we are using pallet-utility for account derivation (through AsDerivative),
 	/// we need to provide an index for the account derivation. This storage item stores the index
-	/// assigned for a given local account. These indices are usable as derivative in the relay chain
+	/// assigned for a given local account in a given destination. Derivative accounts of different
+	/// destinations are unrelated, so the same index can be owned by different accounts as long as
+	/// the destinations differ
 	#[pallet::storage]
 	#[pallet::getter(fn index_to_account)]
-	pub type IndexToAccount<T: Config> = StorageMap<_, Blake2_128Concat, u16, T::AccountId>;
+	pub type DestinationIndexToAccount<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, MultiLocation, Blake2_128Concat, u16, T::AccountId>;
 
 	/// Stores the transact info of a MultiLocation. This defines how much extra weight we need to
 	/// add when we want to transact in the destination chain and maximum amount of weight allowed
@@ -259,12 +367,31 @@ pub mod pallet {
 		StorageMap<_, Blake2_128Concat, MultiLocation, RemoteTransactInfoWithMaxWeight>;
 
 	/// Stores the fee per second for an asset in its reserve chain. This allows us to convert
-	/// from weight to fee
+	/// from weight to fee. It is priced by the reserve chain and does not follow the native token
+	/// price, so it is not updated by the asset price feed
 	#[pallet::storage]
 	#[pallet::getter(fn dest_asset_fee_per_second)]
 	pub type DestinationAssetFeePerSecond<T: Config> =
 		StorageMap<_, Twox64Concat, MultiLocation, u128>;
 
+	/// Stores the status of the transact messages sent with status reporting enabled, indexed
+	/// by the id of the query the destination chain answers to. Entries are removed when their
+	/// query times out
+	#[pallet::storage]
+	#[pallet::getter(fn transact_status)]
+	pub type TransactStatusQueries<T: Config> =
+		StorageMap<_, Twox64Concat, QueryId, TransactStatusInfo>;
+
+	/// Stores the transact status queries timing out at each block
+	#[pallet::storage]
+	pub type TransactStatusTimeouts<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		BoundedVec<QueryId, T::MaxTransactStatusQueriesPerBlock>,
+		ValueQuery,
+	>;
+
 	/// An error that can occur while executing the mapping pallet's logic.
 	#[pallet::error]
 	pub enum Error<T> {
@@ -291,6 +418,12 @@ pub mod pallet {
 		FeePerSecondNotSet,
 		SignedTransactNotAllowedForDestination,
 		FailedMultiLocationToJunction,
+		UnknownTransactStatusQuery,
+		TransactStatusAlreadyReported,
+		InvalidResponder,
+		UnexpectedResponse,
+		TooManyTransactStatusQueries,
+		RefundNotSupported,
 	}
 
 	#[pallet::event]
@@ -315,12 +448,14 @@ pub mod pallet {
 			dest: MultiLocation,
 			call: Vec<u8>,
 		},
-		/// Registered a derivative index for an account id.
+		/// Registered a derivative index for an account id in a destination.
 		RegisteredDerivative {
 			account_id: T::AccountId,
+			dest: MultiLocation,
 			index: u16,
 		},
 		DeRegisteredDerivative {
+			dest: MultiLocation,
 			index: u16,
 		},
 		/// Transact failed
@@ -345,32 +480,77 @@ pub mod pallet {
 		DestFeePerSecondRemoved {
 			location: MultiLocation,
 		},
+		/// Registered a query to be notified of the outcome of a transact message
+		TransactStatusQueried {
+			query_id: QueryId,
+			dest: MultiLocation,
+		},
+		/// The destination chain executed a transact message without XCM errors. In XCM v2 this
+		/// does not tell whether the transacted call dispatched successfully
+		RemoteTransactSucceeded {
+			query_id: QueryId,
+			dest: MultiLocation,
+		},
+		/// The destination chain failed to execute a transact message
+		RemoteTransactFailed {
+			query_id: QueryId,
+			dest: MultiLocation,
+			index: u32,
+			error: XcmError,
+		},
+		/// Sent an HRMP operation to the relay chain
+		HrmpManagementSent {
+			action: HrmpOperation,
+		},
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		/// Remove the transact status queries timing out in this block, whether the destination
+		/// chain answered them or not
+		fn on_initialize(n: T::BlockNumber) -> Weight {
+			let queries = TransactStatusTimeouts::<T>::take(n);
+			let removed = queries.len() as Weight;
+			for query_id in queries {
+				TransactStatusQueries::<T>::remove(query_id);
+			}
+
+			T::DbWeight::get().reads_writes(1, removed.saturating_add(1))
+		}
 	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		#[pallet::weight(T::WeightInfo::register())]
-		/// Register a derivative index for an account id. Dispatchable by
+		/// Register a derivative index for an account id in a destination. Dispatchable by
 		/// DerivativeAddressRegistrationOrigin
 		///
 		/// We do not store the derivative address, but only the index. We do not need to store
 		/// the derivative address to issue calls, only the index is enough
 		///
-		/// For now an index is registered for all possible destinations and not per-destination.
-		/// We can change this in the future although it would just make things more complicated
-		pub fn register(origin: OriginFor<T>, who: T::AccountId, index: u16) -> DispatchResult {
+		/// Indices are registered per-destination, as the derivative accounts of our sovereign
+		/// account in one destination are unrelated to those in any other destination
+		pub fn register(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+			dest: Box<VersionedMultiLocation>,
+			index: u16,
+		) -> DispatchResult {
 			T::DerivativeAddressRegistrationOrigin::ensure_origin(origin)?;
 
+			let dest = MultiLocation::try_from(*dest).map_err(|()| Error::<T>::BadVersion)?;
+
 			ensure!(
-				IndexToAccount::<T>::get(&index).is_none(),
+				DestinationIndexToAccount::<T>::get(&dest, &index).is_none(),
 				Error::<T>::IndexAlreadyClaimed
 			);
 
-			IndexToAccount::<T>::insert(&index, who.clone());
+			DestinationIndexToAccount::<T>::insert(&dest, &index, who.clone());
 
 			// Deposit event
 			Self::deposit_event(Event::<T>::RegisteredDerivative {
 				account_id: who,
+				dest,
 				index: index,
 			});
 
@@ -378,16 +558,23 @@ pub mod pallet {
 		}
 
 		#[pallet::weight(T::WeightInfo::deregister())]
-		/// De-Register a derivative index. This prevents an account to use a derivative address
-		/// (represented by an index) from our of our sovereign accounts anymore
-		pub fn deregister(origin: OriginFor<T>, index: u16) -> DispatchResult {
+		/// De-Register a derivative index in a destination. This prevents an account to use a
+		/// derivative address (represented by an index) from our of our sovereign accounts in
+		/// that destination anymore
+		pub fn deregister(
+			origin: OriginFor<T>,
+			dest: Box<VersionedMultiLocation>,
+			index: u16,
+		) -> DispatchResult {
 			T::DerivativeAddressRegistrationOrigin::ensure_origin(origin)?;
 
+			let dest = MultiLocation::try_from(*dest).map_err(|()| Error::<T>::BadVersion)?;
+
 			// Remove index
-			IndexToAccount::<T>::remove(&index);
+			DestinationIndexToAccount::<T>::remove(&dest, &index);
 
 			// Deposit event
-			Self::deposit_event(Event::<T>::DeRegisteredDerivative { index });
+			Self::deposit_event(Event::<T>::DeRegisteredDerivative { dest, index });
 
 			Ok(())
 		}
@@ -395,8 +582,9 @@ pub mod pallet {
 		/// Transact the inner call through a derivative account in a destination chain,
 		/// using 'fee_location' to pay for the fees. This fee_location is given as a multilocation
 		///
-		/// The caller needs to have the index registered in this pallet. The fee multiasset needs
-		/// to be a reserve asset for the destination transactor::multilocation.
+		/// The caller needs to have the index registered in this pallet for the destination of the
+		/// transactor. The fee multiasset needs to be a reserve asset for the destination
+		/// transactor::multilocation.
 		#[pallet::weight(
 			Pallet::<T>::weight_of_initiate_reserve_withdraw()
 			.saturating_add(T::WeightInfo::transact_through_derivative())
@@ -417,42 +605,15 @@ pub mod pallet {
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
-			let fee_location = Self::currency_to_multilocation(fee.currency)
-				.ok_or(Error::<T>::NotCrossChainTransferableCurrency)?;
-
-			// The index exists
-			let account = IndexToAccount::<T>::get(index).ok_or(Error::<T>::UnclaimedIndex)?;
-			// The derivative index is owned by the origin
-			ensure!(account == who, Error::<T>::NotOwner);
-
-			// Encode call bytes
-			// We make sure the inner call is wrapped on a as_derivative dispatchable
-			let call_bytes: Vec<u8> = dest
-				.clone()
-				.encode_call(UtilityAvailableCalls::AsDerivative(index, inner_call));
-
-			// Grab the destination
-			let dest = dest.destination();
-
-			Self::transact_in_dest_chain_asset_non_signed(
-				dest.clone(),
-				who.clone(),
-				fee_location,
-				call_bytes.clone(),
-				OriginKind::SovereignAccount,
-				fee.fee_amount,
+			Self::do_transact_through_derivative(
+				who,
+				dest,
+				index,
+				fee,
+				inner_call,
 				weight_info,
-			)?;
-
-			// Deposit event
-			Self::deposit_event(Event::<T>::TransactedDerivative {
-				account_id: who,
-				dest: dest,
-				call: call_bytes,
-				index: index,
-			});
-
-			Ok(())
+				TransactOptions::default(),
+			)
 		}
 
 		/// Transact the call through the sovereign account in a destination chain,
@@ -480,29 +641,15 @@ pub mod pallet {
 		) -> DispatchResult {
 			T::SovereignAccountDispatcherOrigin::ensure_origin(origin)?;
 
-			let fee_location = Self::currency_to_multilocation(fee.currency)
-				.ok_or(Error::<T>::NotCrossChainTransferableCurrency)?;
-
-			let dest = MultiLocation::try_from(*dest).map_err(|()| Error::<T>::BadVersion)?;
-			// Grab the destination
-			Self::transact_in_dest_chain_asset_non_signed(
-				dest.clone(),
-				fee_payer.clone(),
-				fee_location,
-				call.clone(),
-				origin_kind,
-				fee.fee_amount,
-				weight_info,
-			)?;
-
-			// Deposit event
-			Self::deposit_event(Event::<T>::TransactedSovereign {
-				fee_payer,
+			Self::do_transact_through_sovereign(
 				dest,
+				fee_payer,
+				fee,
 				call,
-			});
-
-			Ok(())
+				origin_kind,
+				weight_info,
+				TransactOptions::default(),
+			)
 		}
 
 		/// Change the transact info of a location
@@ -568,30 +715,14 @@ pub mod pallet {
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
-			let dest = MultiLocation::try_from(*dest).map_err(|()| Error::<T>::BadVersion)?;
-
-			let fee_location = Self::currency_to_multilocation(fee.currency)
-				.ok_or(Error::<T>::NotCrossChainTransferableCurrency)?;
-
-			// Grab the destination
-			Self::transact_in_dest_chain_asset_signed(
-				dest.clone(),
-				who.clone(),
-				fee_location,
-				call.clone(),
-				OriginKind::SovereignAccount,
-				fee.fee_amount,
-				weight_info,
-			)?;
-
-			// Deposit event
-			Self::deposit_event(Event::<T>::TransactedSigned {
-				fee_payer: who,
+			Self::do_transact_through_signed(
+				who,
 				dest,
+				fee,
 				call,
-			});
-
-			Ok(())
+				weight_info,
+				TransactOptions::default(),
+			)
 		}
 
 		/// Set the fee per second of an asset on its reserve chain
@@ -631,40 +762,377 @@ pub mod pallet {
 			});
 			Ok(())
 		}
+
+		/// Manage the HRMP channels of this chain by transacting the corresponding call in the
+		/// relay chain through the sovereign account, which pays for the fees
+		///
+		/// HrmpManipulatorOrigin callable only
+		#[pallet::weight(
+			Pallet::<T>::weight_of_initiate_reserve_withdraw()
+			.saturating_add(T::WeightInfo::hrmp_manage())
+		)]
+		pub fn hrmp_manage(
+			origin: OriginFor<T>,
+			// operation to be performed in the relay chain
+			action: HrmpOperation,
+			// fee to be used
+			fee: CurrencyPayment<CurrencyIdOf<T>>,
+			// weight information to be used
+			weight_info: TransactWeights,
+		) -> DispatchResult {
+			T::HrmpManipulatorOrigin::ensure_origin(origin)?;
+
+			let fee_location = Self::currency_to_multilocation(fee.currency)
+				.ok_or(Error::<T>::NotCrossChainTransferableCurrency)?;
+
+			let call = match action.clone() {
+				HrmpOperation::InitOpen(params) => HrmpAvailableCalls::InitOpenChannel(
+					params.para_id,
+					params.proposed_max_capacity,
+					params.proposed_max_message_size,
+				),
+				HrmpOperation::Accept { para_id } => HrmpAvailableCalls::AcceptOpenChannel(para_id),
+				HrmpOperation::Close(channel_id) => HrmpAvailableCalls::CloseChannel(channel_id),
+			};
+			let call_bytes = T::HrmpEncoder::hrmp_encode_call(call);
+
+			// HRMP calls must be dispatched with the parachain origin of this chain
+			Self::transact_in_dest_chain_asset_non_signed(
+				MultiLocation::parent(),
+				None,
+				fee_location,
+				call_bytes,
+				OriginKind::Native,
+				fee.fee_amount,
+				weight_info,
+				TransactOptions::default(),
+			)?;
+
+			Self::deposit_event(Event::<T>::HrmpManagementSent { action });
+
+			Ok(())
+		}
+
+		/// Record the outcome of a transact message sent with status reporting enabled.
+		/// Dispatched by the response handler when the destination chain answers the query
+		#[pallet::weight(T::WeightInfo::notify_transact_status())]
+		pub fn notify_transact_status(
+			origin: OriginFor<T>,
+			query_id: QueryId,
+			response: Response,
+		) -> DispatchResult {
+			let responder = T::ResponseOrigin::ensure_origin(origin)?;
+
+			let mut info = TransactStatusQueries::<T>::get(query_id)
+				.ok_or(Error::<T>::UnknownTransactStatusQuery)?;
+			ensure!(
+				info.status == TransactStatus::Pending,
+				Error::<T>::TransactStatusAlreadyReported
+			);
+			ensure!(info.dest == responder, Error::<T>::InvalidResponder);
+
+			let event = match response {
+				Response::ExecutionResult(None) => {
+					info.status = TransactStatus::Succeeded;
+					Event::<T>::RemoteTransactSucceeded {
+						query_id,
+						dest: info.dest.clone(),
+					}
+				}
+				Response::ExecutionResult(Some((index, error))) => {
+					info.status = TransactStatus::Failed { index, error };
+					Event::<T>::RemoteTransactFailed {
+						query_id,
+						dest: info.dest.clone(),
+						index,
+						error,
+					}
+				}
+				_ => return Err(Error::<T>::UnexpectedResponse.into()),
+			};
+
+			TransactStatusQueries::<T>::insert(query_id, info);
+
+			Self::deposit_event(event);
+			Ok(())
+		}
+
+		/// Transact the inner call through a derivative account in a destination chain, like
+		/// `transact_through_derivative`, with the opt-in features given in `options`
+		///
+		/// If the status is reported, note that XCM v2 does not report whether the inner call
+		/// dispatched successfully in the destination chain, only XCM execution errors. Refunds
+		/// are not supported, as the fees are paid by the sovereign account of this chain
+		#[pallet::weight(
+			Pallet::<T>::weight_of_initiate_reserve_withdraw()
+			.saturating_add(T::WeightInfo::transact_through_derivative_with_options())
+		)]
+		pub fn transact_through_derivative_with_options(
+			origin: OriginFor<T>,
+			// destination to which the message should be sent
+			dest: T::Transactor,
+			// derivative index to be used
+			index: u16,
+			// fee to be used
+			fee: CurrencyPayment<CurrencyIdOf<T>>,
+			// inner call to be executed in destination. This wiol
+			// be wrapped into utility.as_derivative
+			inner_call: Vec<u8>,
+			// weight information to be used
+			weight_info: TransactWeights,
+			// opt-in features of the message
+			options: TransactOptions,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Self::do_transact_through_derivative(
+				who,
+				dest,
+				index,
+				fee,
+				inner_call,
+				weight_info,
+				options,
+			)
+		}
+
+		/// Transact the call through the sovereign account in a destination chain, like
+		/// `transact_through_sovereign`, with the opt-in features given in `options`
+		///
+		/// If the status is reported, note that XCM v2 does not report whether the call
+		/// dispatched successfully in the destination chain, only XCM execution errors. Refunds
+		/// are not supported, as the fees are paid by the sovereign account of this chain
+		#[pallet::weight(
+			Pallet::<T>::weight_of_initiate_reserve_withdraw()
+			.saturating_add(T::WeightInfo::transact_through_sovereign_with_options())
+		)]
+		pub fn transact_through_sovereign_with_options(
+			origin: OriginFor<T>,
+			// destination to which the message should be sent
+			dest: Box<VersionedMultiLocation>,
+			// account paying for fees
+			fee_payer: T::AccountId,
+			// fee to be used
+			fee: CurrencyPayment<CurrencyIdOf<T>>,
+			// call to be executed in destination
+			call: Vec<u8>,
+			// origin kind to be used
+			origin_kind: OriginKind,
+			// weight information to be used
+			weight_info: TransactWeights,
+			// opt-in features of the message
+			options: TransactOptions,
+		) -> DispatchResult {
+			T::SovereignAccountDispatcherOrigin::ensure_origin(origin)?;
+
+			Self::do_transact_through_sovereign(
+				dest,
+				fee_payer,
+				fee,
+				call,
+				origin_kind,
+				weight_info,
+				options,
+			)
+		}
+
+		/// Transact the call through the a signed origin in this chain, like
+		/// `transact_through_signed`, with the opt-in features given in `options`
+		///
+		/// If the status is reported, note that XCM v2 does not report whether the call
+		/// dispatched successfully in the destination chain, only XCM execution errors
+		#[pallet::weight(T::WeightInfo::transact_through_signed_with_options())]
+		pub fn transact_through_signed_with_options(
+			origin: OriginFor<T>,
+			// destination to which the message should be sent
+			dest: Box<VersionedMultiLocation>,
+			// fee to be used
+			fee: CurrencyPayment<CurrencyIdOf<T>>,
+			// call to be executed in destination
+			call: Vec<u8>,
+			// weight information to be used
+			weight_info: TransactWeights,
+			// opt-in features of the message
+			options: TransactOptions,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Self::do_transact_through_signed(who, dest, fee, call, weight_info, options)
+		}
 	}
 
 	impl<T: Config> Pallet<T> {
+		fn do_transact_through_derivative(
+			who: T::AccountId,
+			dest: T::Transactor,
+			index: u16,
+			fee: CurrencyPayment<CurrencyIdOf<T>>,
+			inner_call: Vec<u8>,
+			weight_info: TransactWeights,
+			options: TransactOptions,
+		) -> DispatchResult {
+			let fee_location = Self::currency_to_multilocation(fee.currency)
+				.ok_or(Error::<T>::NotCrossChainTransferableCurrency)?;
+
+			// Grab the destination
+			let destination = dest.clone().destination();
+
+			// The index exists in the destination
+			let account = DestinationIndexToAccount::<T>::get(&destination, index)
+				.ok_or(Error::<T>::UnclaimedIndex)?;
+			// The derivative index is owned by the origin
+			ensure!(account == who, Error::<T>::NotOwner);
+
+			// Encode call bytes
+			// We make sure the inner call is wrapped on a as_derivative dispatchable
+			let call_bytes: Vec<u8> =
+				dest.encode_call(UtilityAvailableCalls::AsDerivative(index, inner_call));
+
+			Self::transact_in_dest_chain_asset_non_signed(
+				destination.clone(),
+				Some(who.clone()),
+				fee_location,
+				call_bytes.clone(),
+				OriginKind::SovereignAccount,
+				fee.fee_amount,
+				weight_info,
+				options,
+			)?;
+
+			// Deposit event
+			Self::deposit_event(Event::<T>::TransactedDerivative {
+				account_id: who,
+				dest: destination,
+				call: call_bytes,
+				index: index,
+			});
+
+			Ok(())
+		}
+
+		fn do_transact_through_sovereign(
+			dest: Box<VersionedMultiLocation>,
+			fee_payer: T::AccountId,
+			fee: CurrencyPayment<CurrencyIdOf<T>>,
+			call: Vec<u8>,
+			origin_kind: OriginKind,
+			weight_info: TransactWeights,
+			options: TransactOptions,
+		) -> DispatchResult {
+			let fee_location = Self::currency_to_multilocation(fee.currency)
+				.ok_or(Error::<T>::NotCrossChainTransferableCurrency)?;
+
+			let dest = MultiLocation::try_from(*dest).map_err(|()| Error::<T>::BadVersion)?;
+			// Grab the destination
+			Self::transact_in_dest_chain_asset_non_signed(
+				dest.clone(),
+				Some(fee_payer.clone()),
+				fee_location,
+				call.clone(),
+				origin_kind,
+				fee.fee_amount,
+				weight_info,
+				options,
+			)?;
+
+			// Deposit event
+			Self::deposit_event(Event::<T>::TransactedSovereign {
+				fee_payer,
+				dest,
+				call,
+			});
+
+			Ok(())
+		}
+
+		fn do_transact_through_signed(
+			who: T::AccountId,
+			dest: Box<VersionedMultiLocation>,
+			fee: CurrencyPayment<CurrencyIdOf<T>>,
+			call: Vec<u8>,
+			weight_info: TransactWeights,
+			options: TransactOptions,
+		) -> DispatchResult {
+			let dest = MultiLocation::try_from(*dest).map_err(|()| Error::<T>::BadVersion)?;
+
+			let fee_location = Self::currency_to_multilocation(fee.currency)
+				.ok_or(Error::<T>::NotCrossChainTransferableCurrency)?;
+
+			// Grab the destination
+			Self::transact_in_dest_chain_asset_signed(
+				dest.clone(),
+				who.clone(),
+				fee_location,
+				call.clone(),
+				OriginKind::SovereignAccount,
+				fee.fee_amount,
+				weight_info,
+				options,
+			)?;
+
+			// Deposit event
+			Self::deposit_event(Event::<T>::TransactedSigned {
+				fee_payer: who,
+				dest,
+				call,
+			});
+
+			Ok(())
+		}
+
+		/// Transact `call` in `dest` through the sovereign account of this chain. If `fee_payer`
+		/// is given, the fees are withdrawn from it in this chain, compensating the sovereign
+		/// account for the fees it pays in the destination chain
+		#[allow(clippy::too_many_arguments)]
 		fn transact_in_dest_chain_asset_non_signed(
 			dest: MultiLocation,
-			fee_payer: T::AccountId,
+			fee_payer: Option<T::AccountId>,
 			fee_location: MultiLocation,
 			call: Vec<u8>,
 			origin_kind: OriginKind,
-
 			fee_amount: Option<u128>,
 			weight_info: TransactWeights,
+			options: TransactOptions,
 		) -> DispatchResult {
+			// The sovereign account pays for the fees in the destination chain, where the fee
+			// payer has no account the unspent fees could be deposited into
+			ensure!(!options.refund, Error::<T>::RefundNotSupported);
+
+			// Convert origin to multilocation
+			let fee_payer_as_mult = fee_payer.map(T::AccountIdToMultiLocation::convert);
+
+			// Instructions to be executed even if the message errors
+			let mut appendix = Vec::new();
+			if options.report_status {
+				appendix.push(Self::report_transact_status(&dest)?);
+			}
+			let appendix_weight = Self::appendix_weight(&appendix)?;
+
 			// Calculate the total weight that the xcm message is going to spend in the
-			// destination chain
+			// destination chain, including the appendix
 			let total_weight: u64 = weight_info.overall_weight.map_or_else(
 				|| {
 					Self::take_weight_from_transact_info(
 						dest.clone(),
-						weight_info.transact_required_weight_at_most,
+						weight_info
+							.transact_required_weight_at_most
+							.saturating_add(appendix_weight),
 					)
 				},
-				|v| Ok(v),
+				|v| {
+					v.checked_add(appendix_weight)
+						.ok_or_else(|| Error::<T>::WeightOverflow.into())
+				},
 			)?;
 			// Calculate fee based on FeePerSecond and total_weight
 			let fee = Self::calculate_fee(fee_location, fee_amount, dest.clone(), total_weight)?;
 
-			// Convert origin to multilocation
-			let origin_as_mult = T::AccountIdToMultiLocation::convert(fee_payer);
-
-			// Construct the local withdraw message with the previous calculated amount
-			// This message deducts and burns "amount" from the caller when executed
-			T::AssetTransactor::withdraw_asset(&fee.clone().into(), &origin_as_mult)
-				.map_err(|_| Error::<T>::UnableToWithdrawAsset)?;
+			if let Some(origin_as_mult) = fee_payer_as_mult {
+				// Construct the local withdraw message with the previous calculated amount
+				// This message deducts and burns "amount" from the caller when executed
+				T::AssetTransactor::withdraw_asset(&fee.clone().into(), &origin_as_mult)
+					.map_err(|_| Error::<T>::UnableToWithdrawAsset)?;
+			}
 
 			// Construct the transact message. This is composed of WithdrawAsset||BuyExecution||
 			// Transact.
@@ -672,7 +1140,7 @@ pub mod pallet {
 			// used to pay fees
 			// BuyExecution: Buys "execution power" in the destination chain
 			// Transact: Issues the transaction
-			let transact_message: Xcm<()> = Self::transact_message(
+			let mut transact_message: Xcm<()> = Self::transact_message(
 				dest.clone(),
 				fee,
 				total_weight,
@@ -680,6 +1148,7 @@ pub mod pallet {
 				weight_info.transact_required_weight_at_most,
 				origin_kind,
 			)?;
+			Self::set_appendix(&mut transact_message, appendix);
 
 			// Send to sovereign
 			T::XcmSender::send_xcm(dest, transact_message).map_err(|_| Error::<T>::ErrorSending)?;
@@ -687,6 +1156,7 @@ pub mod pallet {
 			Ok(())
 		}
 
+		#[allow(clippy::too_many_arguments)]
 		fn transact_in_dest_chain_asset_signed(
 			dest: MultiLocation,
 			fee_payer: T::AccountId,
@@ -695,25 +1165,42 @@ pub mod pallet {
 			origin_kind: OriginKind,
 			fee_amount: Option<u128>,
 			weight_info: TransactWeights,
+			options: TransactOptions,
 		) -> DispatchResult {
+			// Convert origin to multilocation
+			let origin_as_mult = T::AccountIdToMultiLocation::convert(fee_payer);
+
+			// Instructions to be executed even if the message errors
+			let mut appendix = Vec::new();
+			if options.report_status {
+				appendix.push(Self::report_transact_status(&dest)?);
+			}
+			if options.refund {
+				// Fees were withdrawn from the account derived from the caller
+				appendix.extend(Self::refund_instructions(origin_as_mult.clone(), &dest)?);
+			}
+			let appendix_weight = Self::appendix_weight(&appendix)?;
+
 			// Calculate the total weight that the xcm message is going to spend in the
-			// destination chain
+			// destination chain, including the appendix
 			let total_weight: u64 = weight_info.overall_weight.map_or_else(
 				|| {
 					Self::take_weight_from_transact_info_signed(
 						dest.clone(),
-						weight_info.transact_required_weight_at_most,
+						weight_info
+							.transact_required_weight_at_most
+							.saturating_add(appendix_weight),
 					)
 				},
-				|v| Ok(v),
+				|v| {
+					v.checked_add(appendix_weight)
+						.ok_or_else(|| Error::<T>::WeightOverflow.into())
+				},
 			)?;
 
 			// Calculate fee based on FeePerSecond and total_weight
 			let fee = Self::calculate_fee(fee_location, fee_amount, dest.clone(), total_weight)?;
 
-			// Convert origin to multilocation
-			let origin_as_mult = T::AccountIdToMultiLocation::convert(fee_payer);
-
 			// Construct the transact message. This is composed of WithdrawAsset||BuyExecution||
 			// Transact.
 			// WithdrawAsset: Withdraws "amount" from the sovereign account. These tokens will be
@@ -737,6 +1224,7 @@ pub mod pallet {
 				.try_into()
 				.map_err(|_| Error::<T>::FailedMultiLocationToJunction)?;
 			transact_message.0.insert(0, DescendOrigin(interior));
+			Self::set_appendix(&mut transact_message, appendix);
 
 			// Send to destination chain
 			T::XcmSender::send_xcm(dest, transact_message).map_err(|_| Error::<T>::ErrorSending)?;
@@ -744,6 +1232,95 @@ pub mod pallet {
 			Ok(())
 		}
 
+		/// Insert SetAppendix right before the Transact instruction, if there is anything to
+		/// append. The appendix is not placed first to keep the message passing the paid
+		/// execution barriers of the destination chain
+		fn set_appendix(transact_message: &mut Xcm<()>, appendix: Vec<Instruction<()>>) {
+			if appendix.is_empty() {
+				return;
+			}
+
+			// Transact is always the last instruction of the message
+			let transact_index = transact_message.0.len().saturating_sub(1);
+			transact_message
+				.0
+				.insert(transact_index, SetAppendix(Xcm(appendix)));
+		}
+
+		/// Weight that the SetAppendix instruction holding `appendix` adds to a message. The
+		/// destination chain is assumed to weigh these instructions like this chain does
+		fn appendix_weight(appendix: &[Instruction<()>]) -> Result<Weight, DispatchError> {
+			if appendix.is_empty() {
+				return Ok(0);
+			}
+
+			let xcm: Xcm<()> = Xcm(vec![SetAppendix(Xcm(appendix.to_vec()))]);
+			T::Weigher::weight(&mut xcm.into()).map_err(|()| Error::<T>::UnweighableMessage.into())
+		}
+
+		/// Register a query expecting the outcome of the transact message from `dest`, and
+		/// construct the ReportError instruction with which the destination chain reports
+		/// back the result of the execution
+		fn report_transact_status(dest: &MultiLocation) -> Result<Instruction<()>, DispatchError> {
+			// The location of this chain as seen by the destination
+			let response_dest = T::LocationInverter::invert_location(dest)
+				.map_err(|()| Error::<T>::DestinationNotInvertible)?;
+
+			let timeout = frame_system::Pallet::<T>::block_number()
+				.saturating_add(T::TransactStatusTimeout::get());
+			let query_id = T::QueryNotifier::new_notify_query(
+				dest.clone(),
+				Call::<T>::notify_transact_status {
+					query_id: 0,
+					response: Response::Null,
+				},
+				timeout,
+			);
+
+			// Queries are removed when they time out
+			TransactStatusTimeouts::<T>::try_mutate(timeout, |queries| queries.try_push(query_id))
+				.map_err(|_| Error::<T>::TooManyTransactStatusQueries)?;
+			TransactStatusQueries::<T>::insert(
+				query_id,
+				TransactStatusInfo {
+					dest: dest.clone(),
+					status: TransactStatus::Pending,
+				},
+			);
+
+			Self::deposit_event(Event::<T>::TransactStatusQueried {
+				query_id,
+				dest: dest.clone(),
+			});
+
+			Ok(ReportError {
+				query_id,
+				dest: response_dest,
+				max_response_weight: T::WeightInfo::notify_transact_status(),
+			})
+		}
+
+		/// Construct the instructions that refund the unspent fees and deposit them into
+		/// `beneficiary`, given as a location in this chain that `at` can convert into an account
+		fn refund_instructions(
+			beneficiary: MultiLocation,
+			at: &MultiLocation,
+		) -> Result<Vec<Instruction<()>>, DispatchError> {
+			let ancestry = T::LocationInverter::ancestry();
+			let beneficiary = beneficiary
+				.reanchored(at, &ancestry)
+				.map_err(|_| Error::<T>::CannotReanchor)?;
+
+			Ok(vec![
+				RefundSurplus,
+				DepositAsset {
+					assets: Wild(All),
+					max_assets: 1,
+					beneficiary,
+				},
+			])
+		}
+
 		/// Calculate the amount of fee based on the multilocation of the fee asset and
 		/// the total weight to be spent
 		fn calculate_fee(
@@ -960,6 +1537,13 @@ pub mod pallet {
 			Ok(Self::calculate_fee_per_second(total_weight, fee_per_second))
 		}
 
+		/// Returns the id of the last transact status query registered in the current block
+		pub fn last_transact_status_query() -> Option<QueryId> {
+			let timeout = frame_system::Pallet::<T>::block_number()
+				.saturating_add(T::TransactStatusTimeout::get());
+			TransactStatusTimeouts::<T>::get(timeout).last().copied()
+		}
+
 		/// Converts Currency to multilocation
 		pub fn currency_to_multilocation(
 			currency: Currency<CurrencyIdOf<T>>,
@@ -15,8 +15,8 @@
 // along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
 
 use crate::{
-	Config, DestinationAssetFeePerSecond, RemoteTransactInfoWithMaxWeight,
-	TransactInfoWithWeightLimit,
+	Config, DestinationAssetFeePerSecond, DestinationIndexToAccount,
+	RemoteTransactInfoWithMaxWeight, TransactInfoWithWeightLimit,
 };
 use frame_support::{
 	pallet_prelude::PhantomData,
@@ -321,3 +321,115 @@ impl<T: Config> OnRuntimeUpgrade for TransactSignedWeightAndFeePerSecond<T> {
 		Ok(())
 	}
 }
+
+/// Migrates the derivative indices registered for all destinations (the old IndexToAccount
+/// storage) to DestinationIndexToAccount under the relay chain, which was the only destination
+/// they were being used for
+pub struct IndexToAccountPerDestination<T>(PhantomData<T>);
+impl<T: Config> OnRuntimeUpgrade for IndexToAccountPerDestination<T> {
+	fn on_runtime_upgrade() -> Weight {
+		log::info!(target: "IndexToAccountPerDestination", "actually running it");
+		let pallet_prefix: &[u8] = b"XcmTransactor";
+		let storage_item_prefix: &[u8] = b"IndexToAccount";
+
+		// Read all the data into memory, removing it from the old storage.
+		// https://crates.parity.io/frame_support/storage/migration/fn.storage_key_iter.html
+		let stored_data: Vec<_> = storage_key_iter::<u16, T::AccountId, Blake2_128Concat>(
+			pallet_prefix,
+			storage_item_prefix,
+		)
+		.drain()
+		.collect();
+
+		let migrated_count: Weight = stored_data
+			.len()
+			.try_into()
+			.expect("There are between 0 and 2**64 mappings stored.");
+
+		log::info!(
+			target: "IndexToAccountPerDestination", "Migrating {:?} elements", migrated_count);
+
+		// Write to the new storage under the relay chain
+		for (index, account) in stored_data {
+			DestinationIndexToAccount::<T>::insert(MultiLocation::parent(), index, account);
+		}
+
+		log::info!(target: "IndexToAccountPerDestination", "almost done");
+
+		// Return the weight used. For each migrated mapping there is a read to get it into
+		// memory, a write to clear the old stored value, and a write to re-store it.
+		let db_weights = T::DbWeight::get();
+		migrated_count.saturating_mul(2 * db_weights.write + db_weights.read)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<(), &'static str> {
+		use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+
+		let pallet_prefix: &[u8] = b"XcmTransactor";
+		let storage_item_prefix: &[u8] = b"IndexToAccount";
+
+		// We want to test that:
+		// There are no entries in the new storage beforehand
+		// The same number of mappings exist before and after
+		// As long as there are some mappings stored, one representative key maps to the
+		// same value after the migration.
+
+		// Assert new storage is empty
+		assert!(DestinationIndexToAccount::<T>::iter().next().is_none());
+
+		// Check number of entries, and set it aside in temp storage
+		let stored_data: Vec<_> = storage_key_iter::<u16, T::AccountId, Blake2_128Concat>(
+			pallet_prefix,
+			storage_item_prefix,
+		)
+		.collect();
+		let mapping_count = stored_data.len();
+		Self::set_temp_storage(mapping_count as u32, "mapping_count");
+
+		// Read an example pair from old storage and set it aside in temp storage
+		if mapping_count > 0 {
+			let example_pair = stored_data
+				.iter()
+				.next()
+				.expect("We already confirmed that there was at least one item stored");
+
+			Self::set_temp_storage(example_pair, "example_pair");
+		}
+
+		Ok(())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade() -> Result<(), &'static str> {
+		use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+
+		let pallet_prefix: &[u8] = b"XcmTransactor";
+		let storage_item_prefix: &[u8] = b"IndexToAccount";
+
+		// Check number of entries matches what was set aside in pre_upgrade
+		let old_mapping_count: u32 = Self::get_temp_storage("mapping_count")
+			.expect("We stored a mapping count; it should be there; qed");
+		let new_mapping_count = DestinationIndexToAccount::<T>::iter().count() as u32;
+		assert_eq!(old_mapping_count, new_mapping_count);
+
+		// Check that our example pair is still well-mapped after the migration
+		if new_mapping_count > 0 {
+			let (index, original_account): (u16, T::AccountId) =
+				Self::get_temp_storage("example_pair").expect("qed");
+			let migrated_account =
+				DestinationIndexToAccount::<T>::get(MultiLocation::parent(), index).expect("qed");
+			assert_eq!(original_account, migrated_account);
+		}
+
+		// Check there are no entries left in the old storage
+		assert!(storage_key_iter::<u16, T::AccountId, Blake2_128Concat>(
+			pallet_prefix,
+			storage_item_prefix
+		)
+		.next()
+		.is_none());
+
+		Ok(())
+	}
+}
@@ -19,7 +19,10 @@
 use super::*;
 use crate as pallet_xcm_transactor;
 use frame_support::{construct_runtime, parameter_types};
-use frame_support::{traits::PalletInfo as PalletInfoTrait, weights::Weight};
+use frame_support::{
+	traits::{EnsureOrigin, PalletInfo as PalletInfoTrait},
+	weights::Weight,
+};
 use frame_system::EnsureRoot;
 use parity_scale_codec::{Decode, Encode};
 
@@ -34,7 +37,10 @@ use xcm::latest::{
 	Junction::{AccountKey20, PalletInstance, Parachain},
 	Junctions, MultiAsset, MultiLocation, NetworkId, Result as XcmResult, SendResult, SendXcm, Xcm,
 };
-use xcm_primitives::{UtilityAvailableCalls, UtilityEncodeCall, XcmTransact};
+use xcm_primitives::{
+	HrmpAvailableCalls, HrmpEncodeCall, UtilityAvailableCalls, UtilityEncodeCall, XcmQueryNotifier,
+	XcmTransact,
+};
 
 use sp_std::cell::RefCell;
 use xcm_executor::{
@@ -163,12 +169,19 @@ impl InvertLocation for InvertNothing {
 use sp_std::marker::PhantomData;
 pub struct DummyWeigher<C>(PhantomData<C>);
 
+// Only the instructions in an appendix weigh, one unit each, so that tests can check they are
+// accounted for in the weight bought in the destination
 impl<C: Decode> WeightBounds<C> for DummyWeigher<C> {
-	fn weight(_message: &mut Xcm<C>) -> Result<Weight, ()> {
-		Ok(0)
+	fn weight(message: &mut Xcm<C>) -> Result<Weight, ()> {
+		message.0.iter().try_fold(0, |total, instruction| {
+			Ok(total + Self::instr_weight(instruction)?)
+		})
 	}
-	fn instr_weight(_instruction: &Instruction<C>) -> Result<Weight, ()> {
-		Ok(0)
+	fn instr_weight(instruction: &Instruction<C>) -> Result<Weight, ()> {
+		match instruction {
+			Instruction::SetAppendix(appendix) => Ok(appendix.0.len() as Weight),
+			_ => Ok(0),
+		}
 	}
 }
 
@@ -208,6 +221,8 @@ pub enum RelayCall {
 	#[codec(index = 0u8)]
 	// the index should match the position of the module in `construct_runtime!`
 	Utility(UtilityCall),
+	#[codec(index = 1u8)]
+	Hrmp(HrmpCall),
 }
 
 #[derive(Encode, Decode)]
@@ -216,6 +231,16 @@ pub enum UtilityCall {
 	AsDerivative(u16),
 }
 
+#[derive(Encode, Decode)]
+pub enum HrmpCall {
+	#[codec(index = 0u8)]
+	InitOpenChannel(cumulus_primitives_core::ParaId, u32, u32),
+	#[codec(index = 1u8)]
+	AcceptOpenChannel(cumulus_primitives_core::ParaId),
+	#[codec(index = 2u8)]
+	CloseChannel(cumulus_primitives_core::relay_chain::HrmpChannelId),
+}
+
 // Transactors for the mock runtime. Only relay chain
 #[derive(Clone, Eq, Debug, PartialEq, Ord, PartialOrd, Encode, Decode, scale_info::TypeInfo)]
 pub enum Transactors {
@@ -252,6 +277,22 @@ impl UtilityEncodeCall for Transactors {
 	}
 }
 
+impl HrmpEncodeCall for Transactors {
+	fn hrmp_encode_call(call: HrmpAvailableCalls) -> Vec<u8> {
+		match call {
+			HrmpAvailableCalls::InitOpenChannel(a, b, c) => {
+				RelayCall::Hrmp(HrmpCall::InitOpenChannel(a, b, c)).encode()
+			}
+			HrmpAvailableCalls::AcceptOpenChannel(a) => {
+				RelayCall::Hrmp(HrmpCall::AcceptOpenChannel(a)).encode()
+			}
+			HrmpAvailableCalls::CloseChannel(a) => {
+				RelayCall::Hrmp(HrmpCall::CloseChannel(a)).encode()
+			}
+		}
+	}
+}
+
 pub type AssetId = u128;
 #[derive(Clone, Eq, Debug, PartialEq, Ord, PartialOrd, Encode, Decode, scale_info::TypeInfo)]
 pub enum CurrencyId {
@@ -308,6 +349,45 @@ impl SendXcm for TestSendXcm {
 	}
 }
 
+// Simulates registering queries in the response handler, handing out sequential ids
+thread_local! {
+	pub static QUERY_COUNTER: RefCell<u64> = RefCell::new(0);
+}
+pub struct TestQueryNotifier;
+impl XcmQueryNotifier<pallet_xcm_transactor::Call<Test>, u64> for TestQueryNotifier {
+	fn new_notify_query(
+		_responder: MultiLocation,
+		_notify: pallet_xcm_transactor::Call<Test>,
+		_timeout: u64,
+	) -> u64 {
+		QUERY_COUNTER.with(|counter| {
+			let query_id = *counter.borrow();
+			*counter.borrow_mut() += 1;
+			query_id
+		})
+	}
+}
+
+// Root plays the role of the response handler, answering on behalf of the relay chain
+pub struct EnsureRelayResponse;
+impl EnsureOrigin<Origin> for EnsureRelayResponse {
+	type Success = MultiLocation;
+
+	fn try_origin(o: Origin) -> Result<MultiLocation, Origin> {
+		EnsureRoot::<u64>::try_origin(o).map(|()| MultiLocation::parent())
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> Origin {
+		Origin::root()
+	}
+}
+
+parameter_types! {
+	pub const TransactStatusTimeout: u64 = 10;
+	pub const MaxTransactStatusQueriesPerBlock: u32 = 2;
+}
+
 impl Config for Test {
 	type Event = Event;
 	type Balance = Balance;
@@ -324,6 +404,12 @@ impl Config for Test {
 	type BaseXcmWeight = BaseXcmWeight;
 	type XcmSender = TestSendXcm;
 	type ReserveProvider = orml_traits::location::RelativeReserveProvider;
+	type QueryNotifier = TestQueryNotifier;
+	type ResponseOrigin = EnsureRelayResponse;
+	type TransactStatusTimeout = TransactStatusTimeout;
+	type MaxTransactStatusQueriesPerBlock = MaxTransactStatusQueriesPerBlock;
+	type HrmpManipulatorOrigin = EnsureRoot<u64>;
+	type HrmpEncoder = Transactors;
 	type WeightInfo = ();
 }
 
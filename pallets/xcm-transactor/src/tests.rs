@@ -15,15 +15,18 @@
 // along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
 
 use crate::mock::*;
+use crate::weights::WeightInfo;
 use crate::*;
 use frame_support::dispatch::DispatchError;
 use frame_support::{
-	assert_noop, assert_ok, storage::migration::put_storage_value,
+	assert_noop, assert_ok, storage::migration::put_storage_value, traits::Hooks,
 	weights::constants::WEIGHT_PER_SECOND, Blake2_128Concat,
 };
 use sp_std::boxed::Box;
 use xcm::latest::prelude::*;
-use xcm_primitives::{UtilityAvailableCalls, UtilityEncodeCall};
+use xcm_primitives::{
+	HrmpAvailableCalls, HrmpEncodeCall, UtilityAvailableCalls, UtilityEncodeCall,
+};
 #[test]
 fn test_register_address() {
 	ExtBuilder::default()
@@ -32,23 +35,114 @@ fn test_register_address() {
 		.execute_with(|| {
 			// Only root can do this, as specified in runtime
 			assert_noop!(
-				XcmTransactor::register(Origin::signed(1u64), 1u64, 1),
+				XcmTransactor::register(
+					Origin::signed(1u64),
+					1u64,
+					Box::new(xcm::VersionedMultiLocation::V1(MultiLocation::parent())),
+					1
+				),
 				DispatchError::BadOrigin
 			);
 
 			// Root can register
-			assert_ok!(XcmTransactor::register(Origin::root(), 1u64, 1));
+			assert_ok!(XcmTransactor::register(
+				Origin::root(),
+				1u64,
+				Box::new(xcm::VersionedMultiLocation::V1(MultiLocation::parent())),
+				1
+			));
 
-			assert_eq!(XcmTransactor::index_to_account(&1).unwrap(), 1u64);
+			assert_eq!(
+				XcmTransactor::index_to_account(MultiLocation::parent(), 1).unwrap(),
+				1u64
+			);
 
 			let expected = vec![crate::Event::RegisteredDerivative {
 				account_id: 1u64,
+				dest: MultiLocation::parent(),
 				index: 1,
 			}];
 			assert_eq!(events(), expected);
 		})
 }
 
+#[test]
+fn test_register_address_per_destination() {
+	ExtBuilder::default()
+		.with_balances(vec![])
+		.build()
+		.execute_with(|| {
+			let sibling = MultiLocation::new(1, X1(Parachain(1000)));
+
+			// The same index can be registered in different destinations
+			assert_ok!(XcmTransactor::register(
+				Origin::root(),
+				1u64,
+				Box::new(xcm::VersionedMultiLocation::V1(MultiLocation::parent())),
+				1
+			));
+			assert_ok!(XcmTransactor::register(
+				Origin::root(),
+				2u64,
+				Box::new(xcm::VersionedMultiLocation::V1(sibling.clone())),
+				1
+			));
+
+			// But only once per destination
+			assert_noop!(
+				XcmTransactor::register(
+					Origin::root(),
+					2u64,
+					Box::new(xcm::VersionedMultiLocation::V1(MultiLocation::parent())),
+					1
+				),
+				Error::<Test>::IndexAlreadyClaimed
+			);
+
+			assert_eq!(
+				XcmTransactor::index_to_account(MultiLocation::parent(), 1).unwrap(),
+				1u64
+			);
+			assert_eq!(
+				XcmTransactor::index_to_account(sibling.clone(), 1).unwrap(),
+				2u64
+			);
+
+			// The owner of the index in the sibling cannot use it to transact in the relay
+			assert_noop!(
+				XcmTransactor::transact_through_derivative(
+					Origin::signed(2u64),
+					Transactors::Relay,
+					1,
+					CurrencyPayment {
+						currency: Currency::AsMultiLocation(Box::new(
+							xcm::VersionedMultiLocation::V1(MultiLocation::parent())
+						)),
+						fee_amount: None
+					},
+					vec![0u8],
+					TransactWeights {
+						transact_required_weight_at_most: 100u64,
+						overall_weight: None
+					}
+				),
+				Error::<Test>::NotOwner
+			);
+
+			// De-registering only affects the given destination
+			assert_ok!(XcmTransactor::deregister(
+				Origin::root(),
+				Box::new(xcm::VersionedMultiLocation::V1(sibling.clone())),
+				1
+			));
+			assert!(XcmTransactor::index_to_account(sibling, 1).is_none());
+			assert_eq!(
+				XcmTransactor::index_to_account(MultiLocation::parent(), 1).unwrap(),
+				1u64
+			);
+		})
+}
+
 #[test]
 fn test_transact_through_derivative_errors() {
 	ExtBuilder::default()
@@ -77,7 +171,12 @@ fn test_transact_through_derivative_errors() {
 			);
 
 			// Root can register
-			assert_ok!(XcmTransactor::register(Origin::root(), 1u64, 1));
+			assert_ok!(XcmTransactor::register(
+				Origin::root(),
+				1u64,
+				Box::new(xcm::VersionedMultiLocation::V1(MultiLocation::parent())),
+				1
+			));
 
 			// TransactInfo not yet set
 			assert_noop!(
@@ -320,7 +419,12 @@ fn test_transact_through_derivative_multilocation_success() {
 		.build()
 		.execute_with(|| {
 			// Root can register
-			assert_ok!(XcmTransactor::register(Origin::root(), 1u64, 1));
+			assert_ok!(XcmTransactor::register(
+				Origin::root(),
+				1u64,
+				Box::new(xcm::VersionedMultiLocation::V1(MultiLocation::parent())),
+				1
+			));
 
 			// Root can set transact info
 			assert_ok!(XcmTransactor::set_transact_info(
@@ -358,6 +462,7 @@ fn test_transact_through_derivative_multilocation_success() {
 			let expected = vec![
 				crate::Event::RegisteredDerivative {
 					account_id: 1u64,
+					dest: MultiLocation::parent(),
 					index: 1,
 				},
 				crate::Event::TransactInfoChanged {
@@ -391,7 +496,12 @@ fn test_transact_through_derivative_success() {
 		.build()
 		.execute_with(|| {
 			// Root can register
-			assert_ok!(XcmTransactor::register(Origin::root(), 1u64, 1));
+			assert_ok!(XcmTransactor::register(
+				Origin::root(),
+				1u64,
+				Box::new(xcm::VersionedMultiLocation::V1(MultiLocation::parent())),
+				1
+			));
 
 			// Root can set transact info
 			assert_ok!(XcmTransactor::set_transact_info(
@@ -427,6 +537,7 @@ fn test_transact_through_derivative_success() {
 			let expected = vec![
 				crate::Event::RegisteredDerivative {
 					account_id: 1u64,
+					dest: MultiLocation::parent(),
 					index: 1,
 				},
 				crate::Event::TransactInfoChanged {
@@ -576,20 +687,36 @@ fn de_registering_works() {
 		.build()
 		.execute_with(|| {
 			// Root can register
-			assert_ok!(XcmTransactor::register(Origin::root(), 1u64, 1));
+			assert_ok!(XcmTransactor::register(
+				Origin::root(),
+				1u64,
+				Box::new(xcm::VersionedMultiLocation::V1(MultiLocation::parent())),
+				1
+			));
 
-			assert_eq!(XcmTransactor::index_to_account(&1).unwrap(), 1u64);
+			assert_eq!(
+				XcmTransactor::index_to_account(MultiLocation::parent(), 1).unwrap(),
+				1u64
+			);
 
-			assert_ok!(XcmTransactor::deregister(Origin::root(), 1));
+			assert_ok!(XcmTransactor::deregister(
+				Origin::root(),
+				Box::new(xcm::VersionedMultiLocation::V1(MultiLocation::parent())),
+				1
+			));
 
-			assert!(XcmTransactor::index_to_account(&1).is_none());
+			assert!(XcmTransactor::index_to_account(MultiLocation::parent(), 1).is_none());
 
 			let expected = vec![
 				crate::Event::RegisteredDerivative {
 					account_id: 1u64,
+					dest: MultiLocation::parent(),
+					index: 1,
+				},
+				crate::Event::DeRegisteredDerivative {
+					dest: MultiLocation::parent(),
 					index: 1,
 				},
-				crate::Event::DeRegisteredDerivative { index: 1 },
 			];
 			assert_eq!(events(), expected);
 		})
@@ -913,6 +1040,57 @@ fn test_signed_weight_and_fee_per_second_migration_works() {
 		})
 }
 
+#[test]
+fn test_index_to_account_per_destination_migration_works() {
+	ExtBuilder::default()
+		.with_balances(vec![])
+		.build()
+		.execute_with(|| {
+			let pallet_prefix: &[u8] = b"XcmTransactor";
+			let storage_item_prefix: &[u8] = b"IndexToAccount";
+			use frame_support::traits::OnRuntimeUpgrade;
+			use frame_support::StorageHasher;
+			use parity_scale_codec::Encode;
+
+			// We populate the previous storage, which registered indices for all destinations
+			put_storage_value(
+				pallet_prefix,
+				storage_item_prefix,
+				&Blake2_128Concat::hash(&1u16.encode()),
+				1u64,
+			);
+			put_storage_value(
+				pallet_prefix,
+				storage_item_prefix,
+				&Blake2_128Concat::hash(&2u16.encode()),
+				2u64,
+			);
+
+			// We run the migration
+			crate::migrations::IndexToAccountPerDestination::<Test>::on_runtime_upgrade();
+
+			// Indices are now registered in the relay chain
+			assert_eq!(
+				XcmTransactor::index_to_account(MultiLocation::parent(), 1),
+				Some(1u64)
+			);
+			assert_eq!(
+				XcmTransactor::index_to_account(MultiLocation::parent(), 2),
+				Some(2u64)
+			);
+			assert_eq!(DestinationIndexToAccount::<Test>::iter().count(), 2);
+
+			// The previous storage is empty
+			assert!(frame_support::storage::migration::storage_key_iter::<
+				u16,
+				u64,
+				Blake2_128Concat,
+			>(pallet_prefix, storage_item_prefix)
+			.next()
+			.is_none());
+		})
+}
+
 #[test]
 fn test_send_through_derivative_with_custom_weight_and_fee() {
 	ExtBuilder::default()
@@ -920,7 +1098,12 @@ fn test_send_through_derivative_with_custom_weight_and_fee() {
 		.build()
 		.execute_with(|| {
 			// Root can register
-			assert_ok!(XcmTransactor::register(Origin::root(), 1u64, 1));
+			assert_ok!(XcmTransactor::register(
+				Origin::root(),
+				1u64,
+				Box::new(xcm::VersionedMultiLocation::V1(MultiLocation::parent())),
+				1
+			));
 
 			// We are gonna use a total weight of 10_100, a tx weight of 100,
 			// and a total fee of 100
@@ -948,6 +1131,7 @@ fn test_send_through_derivative_with_custom_weight_and_fee() {
 			let expected = vec![
 				crate::Event::RegisteredDerivative {
 					account_id: 1u64,
+					dest: MultiLocation::parent(),
 					index: 1,
 				},
 				crate::Event::TransactedDerivative {
@@ -986,7 +1170,12 @@ fn test_send_through_sovereign_with_custom_weight_and_fee() {
 		.build()
 		.execute_with(|| {
 			// Root can register
-			assert_ok!(XcmTransactor::register(Origin::root(), 1u64, 1));
+			assert_ok!(XcmTransactor::register(
+				Origin::root(),
+				1u64,
+				Box::new(xcm::VersionedMultiLocation::V1(MultiLocation::parent())),
+				1
+			));
 
 			// We are gonna use a total weight of 10_100, a tx weight of 100,
 			// and a total fee of 100
@@ -1020,6 +1209,7 @@ fn test_send_through_sovereign_with_custom_weight_and_fee() {
 			let expected = vec![
 				crate::Event::RegisteredDerivative {
 					account_id: 1u64,
+					dest: MultiLocation::parent(),
 					index: 1,
 				},
 				crate::Event::TransactedSovereign {
@@ -1103,3 +1293,565 @@ fn test_send_through_signed_with_custom_weight_and_fee() {
 			}));
 		})
 }
+
+#[test]
+fn test_transact_through_signed_with_status_report() {
+	ExtBuilder::default()
+		.with_balances(vec![])
+		.build()
+		.execute_with(|| {
+			let total_weight = 10_100u64;
+			let tx_weight = 100_u64;
+			let total_fee = 100u128;
+
+			assert_ok!(XcmTransactor::transact_through_signed_with_options(
+				Origin::signed(1u64),
+				Box::new(xcm::VersionedMultiLocation::V1(MultiLocation::parent())),
+				CurrencyPayment {
+					currency: Currency::AsMultiLocation(Box::new(xcm::VersionedMultiLocation::V1(
+						MultiLocation::parent()
+					))),
+					fee_amount: Some(total_fee)
+				},
+				vec![1u8],
+				TransactWeights {
+					transact_required_weight_at_most: tx_weight,
+					overall_weight: Some(total_weight)
+				},
+				TransactOptions {
+					report_status: true,
+					refund: false,
+				},
+			));
+
+			let expected = vec![
+				crate::Event::TransactStatusQueried {
+					query_id: 0,
+					dest: MultiLocation::parent(),
+				},
+				crate::Event::TransactedSigned {
+					fee_payer: 1u64,
+					dest: MultiLocation::parent(),
+					call: vec![1u8],
+				},
+			];
+			assert_eq!(events(), expected);
+			assert_eq!(
+				XcmTransactor::transact_status(0),
+				Some(TransactStatusInfo {
+					dest: MultiLocation::parent(),
+					status: TransactStatus::Pending,
+				})
+			);
+
+			let sent_messages = mock::sent_xcm();
+			let (_, sent_message) = sent_messages.first().unwrap();
+			// The appendix goes right before the Transact instruction, which is the last one
+			let instructions = sent_message.0.len();
+			assert_eq!(
+				sent_message.0[instructions - 2],
+				SetAppendix(Xcm(vec![ReportError {
+					query_id: 0,
+					dest: MultiLocation::here(),
+					max_response_weight: <() as WeightInfo>::notify_transact_status(),
+				}]))
+			);
+			assert!(matches!(sent_message.0[instructions - 1], Transact { .. }));
+			// DescendOrigin still goes first
+			assert!(matches!(sent_message.0[0], DescendOrigin(..)));
+		})
+}
+
+#[test]
+fn test_transact_through_signed_with_refund() {
+	ExtBuilder::default()
+		.with_balances(vec![])
+		.build()
+		.execute_with(|| {
+			let total_weight = 10_100u64;
+			let tx_weight = 100_u64;
+			let total_fee = 100u128;
+
+			assert_ok!(XcmTransactor::transact_through_signed_with_options(
+				Origin::signed(1u64),
+				Box::new(xcm::VersionedMultiLocation::V1(MultiLocation::parent())),
+				CurrencyPayment {
+					currency: Currency::AsMultiLocation(Box::new(xcm::VersionedMultiLocation::V1(
+						MultiLocation::parent()
+					))),
+					fee_amount: Some(total_fee)
+				},
+				vec![1u8],
+				TransactWeights {
+					transact_required_weight_at_most: tx_weight,
+					overall_weight: Some(total_weight)
+				},
+				TransactOptions {
+					report_status: true,
+					refund: true,
+				},
+			));
+
+			let sent_messages = mock::sent_xcm();
+			let (_, sent_message) = sent_messages.first().unwrap();
+			// Status report and refund share the same appendix, the report going first
+			let instructions = sent_message.0.len();
+			assert_eq!(
+				sent_message.0[instructions - 2],
+				SetAppendix(Xcm(vec![
+					ReportError {
+						query_id: 0,
+						dest: MultiLocation::here(),
+						max_response_weight: <() as WeightInfo>::notify_transact_status(),
+					},
+					RefundSurplus,
+					DepositAsset {
+						assets: Wild(All),
+						max_assets: 1,
+						// The account derived from the caller, as seen from the destination
+						beneficiary: MultiLocation::new(
+							0,
+							X2(
+								OnlyChild,
+								AccountKey20 {
+									network: NetworkId::Any,
+									key: [0xAA; 20],
+								}
+							)
+						),
+					},
+				]))
+			);
+			assert!(matches!(sent_message.0[instructions - 1], Transact { .. }));
+		})
+}
+
+#[test]
+fn test_refund_is_not_supported_through_the_sovereign_account() {
+	ExtBuilder::default()
+		.with_balances(vec![])
+		.build()
+		.execute_with(|| {
+			// Root can set transact info
+			assert_ok!(XcmTransactor::set_transact_info(
+				Origin::root(),
+				Box::new(xcm::VersionedMultiLocation::V1(MultiLocation::parent())),
+				0,
+				10000,
+				None
+			));
+
+			// Set fee per second
+			assert_ok!(XcmTransactor::set_fee_per_second(
+				Origin::root(),
+				Box::new(xcm::VersionedMultiLocation::V1(MultiLocation::parent())),
+				1
+			));
+
+			assert_ok!(XcmTransactor::register(
+				Origin::root(),
+				1u64,
+				Box::new(xcm::VersionedMultiLocation::V1(MultiLocation::parent())),
+				1
+			));
+
+			// The fee payer has no account in the destination the unspent fees could go to
+			assert_noop!(
+				XcmTransactor::transact_through_derivative_with_options(
+					Origin::signed(1u64),
+					Transactors::Relay,
+					1,
+					CurrencyPayment {
+						currency: Currency::AsCurrencyId(CurrencyId::OtherReserve(0)),
+						fee_amount: None
+					},
+					vec![1u8],
+					TransactWeights {
+						transact_required_weight_at_most: 100u64,
+						overall_weight: None
+					},
+					TransactOptions {
+						report_status: false,
+						refund: true,
+					}
+				),
+				Error::<Test>::RefundNotSupported
+			);
+
+			assert_noop!(
+				XcmTransactor::transact_through_sovereign_with_options(
+					Origin::root(),
+					Box::new(xcm::VersionedMultiLocation::V1(MultiLocation::parent())),
+					1u64,
+					CurrencyPayment {
+						currency: Currency::AsCurrencyId(CurrencyId::OtherReserve(0)),
+						fee_amount: None
+					},
+					vec![1u8],
+					OriginKind::SovereignAccount,
+					TransactWeights {
+						transact_required_weight_at_most: 100u64,
+						overall_weight: None
+					},
+					TransactOptions {
+						report_status: true,
+						refund: true,
+					}
+				),
+				Error::<Test>::RefundNotSupported
+			);
+
+			// Nothing was sent, and no status query was registered
+			assert!(mock::sent_xcm().is_empty());
+			assert_eq!(XcmTransactor::transact_status(0), None);
+		})
+}
+
+#[test]
+fn test_notify_transact_status_works() {
+	ExtBuilder::default()
+		.with_balances(vec![])
+		.build()
+		.execute_with(|| {
+			// Root can set transact info
+			assert_ok!(XcmTransactor::set_transact_info(
+				Origin::root(),
+				Box::new(xcm::VersionedMultiLocation::V1(MultiLocation::parent())),
+				0,
+				10000,
+				None
+			));
+
+			// Set fee per second
+			assert_ok!(XcmTransactor::set_fee_per_second(
+				Origin::root(),
+				Box::new(xcm::VersionedMultiLocation::V1(MultiLocation::parent())),
+				1
+			));
+
+			// Register index
+			assert_ok!(XcmTransactor::register(
+				Origin::root(),
+				1u64,
+				Box::new(xcm::VersionedMultiLocation::V1(MultiLocation::parent())),
+				1
+			));
+
+			// Two messages, each expecting their own response
+			for _ in 0..2 {
+				assert_ok!(XcmTransactor::transact_through_derivative_with_options(
+					Origin::signed(1u64),
+					Transactors::Relay,
+					1,
+					CurrencyPayment {
+						currency: Currency::AsCurrencyId(CurrencyId::OtherReserve(0)),
+						fee_amount: None
+					},
+					vec![1u8],
+					TransactWeights {
+						transact_required_weight_at_most: 100u64,
+						overall_weight: None
+					},
+					TransactOptions {
+						report_status: true,
+						refund: false,
+					},
+				));
+			}
+
+			// Only the response handler can notify
+			assert_noop!(
+				XcmTransactor::notify_transact_status(
+					Origin::signed(1u64),
+					0,
+					Response::ExecutionResult(None)
+				),
+				DispatchError::BadOrigin
+			);
+
+			// Responses must come from a registered query
+			assert_noop!(
+				XcmTransactor::notify_transact_status(
+					Origin::root(),
+					2,
+					Response::ExecutionResult(None)
+				),
+				Error::<Test>::UnknownTransactStatusQuery
+			);
+
+			// Responses must report the execution result
+			assert_noop!(
+				XcmTransactor::notify_transact_status(Origin::root(), 0, Response::Null),
+				Error::<Test>::UnexpectedResponse
+			);
+
+			assert_ok!(XcmTransactor::notify_transact_status(
+				Origin::root(),
+				0,
+				Response::ExecutionResult(None)
+			));
+			assert_ok!(XcmTransactor::notify_transact_status(
+				Origin::root(),
+				1,
+				Response::ExecutionResult(Some((2, XcmError::TooMuchWeightRequired)))
+			));
+
+			// Each query can only be answered once
+			assert_noop!(
+				XcmTransactor::notify_transact_status(
+					Origin::root(),
+					1,
+					Response::ExecutionResult(None)
+				),
+				Error::<Test>::TransactStatusAlreadyReported
+			);
+
+			assert_eq!(
+				XcmTransactor::transact_status(0).map(|info| info.status),
+				Some(TransactStatus::Succeeded)
+			);
+			assert_eq!(
+				XcmTransactor::transact_status(1).map(|info| info.status),
+				Some(TransactStatus::Failed {
+					index: 2,
+					error: XcmError::TooMuchWeightRequired
+				})
+			);
+
+			let events = events();
+			assert_eq!(
+				events[events.len() - 2..],
+				[
+					crate::Event::RemoteTransactSucceeded {
+						query_id: 0,
+						dest: MultiLocation::parent(),
+					},
+					crate::Event::RemoteTransactFailed {
+						query_id: 1,
+						dest: MultiLocation::parent(),
+						index: 2,
+						error: XcmError::TooMuchWeightRequired,
+					},
+				]
+			);
+		})
+}
+
+#[test]
+fn test_transact_status_queries_are_capped_and_removed_on_timeout() {
+	ExtBuilder::default()
+		.with_balances(vec![])
+		.build()
+		.execute_with(|| {
+			// Root can set transact info
+			assert_ok!(XcmTransactor::set_transact_info(
+				Origin::root(),
+				Box::new(xcm::VersionedMultiLocation::V1(MultiLocation::parent())),
+				0,
+				10000,
+				None
+			));
+
+			// Set fee per second
+			assert_ok!(XcmTransactor::set_fee_per_second(
+				Origin::root(),
+				Box::new(xcm::VersionedMultiLocation::V1(MultiLocation::parent())),
+				1
+			));
+
+			// Register index
+			assert_ok!(XcmTransactor::register(
+				Origin::root(),
+				1u64,
+				Box::new(xcm::VersionedMultiLocation::V1(MultiLocation::parent())),
+				1
+			));
+
+			let transact = || {
+				XcmTransactor::transact_through_derivative_with_options(
+					Origin::signed(1u64),
+					Transactors::Relay,
+					1,
+					CurrencyPayment {
+						currency: Currency::AsCurrencyId(CurrencyId::OtherReserve(0)),
+						fee_amount: None,
+					},
+					vec![1u8],
+					TransactWeights {
+						transact_required_weight_at_most: 100u64,
+						overall_weight: None,
+					},
+					TransactOptions {
+						report_status: true,
+						refund: false,
+					},
+				)
+			};
+
+			// Only MaxTransactStatusQueriesPerBlock queries can time out in the same block
+			assert_ok!(transact());
+			assert_ok!(transact());
+			assert_noop!(transact(), Error::<Test>::TooManyTransactStatusQueries);
+
+			assert_ok!(XcmTransactor::notify_transact_status(
+				Origin::root(),
+				0,
+				Response::ExecutionResult(None)
+			));
+
+			// Queries are removed when they time out, whether they were answered or not
+			let timeout = System::block_number() + TransactStatusTimeout::get();
+			XcmTransactor::on_initialize(timeout - 1);
+			assert!(XcmTransactor::transact_status(0).is_some());
+			assert!(XcmTransactor::transact_status(1).is_some());
+
+			XcmTransactor::on_initialize(timeout);
+			assert!(XcmTransactor::transact_status(0).is_none());
+			assert!(XcmTransactor::transact_status(1).is_none());
+			assert!(TransactStatusTimeouts::<Test>::get(timeout).is_empty());
+		})
+}
+
+#[test]
+fn test_hrmp_manipulator_init() {
+	ExtBuilder::default()
+		.with_balances(vec![])
+		.build()
+		.execute_with(|| {
+			let action = HrmpOperation::InitOpen(HrmpInitParams {
+				para_id: 1u32.into(),
+				proposed_max_capacity: 1,
+				proposed_max_message_size: 1,
+			});
+
+			// Only HrmpManipulatorOrigin can do this
+			assert_noop!(
+				XcmTransactor::hrmp_manage(
+					Origin::signed(1),
+					action.clone(),
+					CurrencyPayment {
+						currency: Currency::AsMultiLocation(Box::new(
+							xcm::VersionedMultiLocation::V1(MultiLocation::parent())
+						)),
+						fee_amount: Some(10000)
+					},
+					TransactWeights {
+						transact_required_weight_at_most: 100u64,
+						overall_weight: Some(1000)
+					}
+				),
+				DispatchError::BadOrigin
+			);
+
+			assert_ok!(XcmTransactor::hrmp_manage(
+				Origin::root(),
+				action.clone(),
+				CurrencyPayment {
+					currency: Currency::AsMultiLocation(Box::new(xcm::VersionedMultiLocation::V1(
+						MultiLocation::parent()
+					))),
+					fee_amount: Some(10000)
+				},
+				TransactWeights {
+					transact_required_weight_at_most: 100u64,
+					overall_weight: Some(1000)
+				}
+			));
+
+			assert_eq!(events(), vec![crate::Event::HrmpManagementSent { action }]);
+
+			let sent_messages = mock::sent_xcm();
+			let (dest, sent_message) = sent_messages.first().unwrap();
+			assert_eq!(dest, &MultiLocation::parent());
+			assert!(sent_message
+				.0
+				.contains(&WithdrawAsset((MultiLocation::here(), 10000).into())));
+			assert!(sent_message.0.contains(&Transact {
+				origin_type: OriginKind::Native,
+				require_weight_at_most: 100u64,
+				call: Transactors::hrmp_encode_call(HrmpAvailableCalls::InitOpenChannel(
+					1u32.into(),
+					1,
+					1
+				))
+				.into(),
+			}));
+		})
+}
+
+#[test]
+fn test_hrmp_manipulator_accept_and_close() {
+	ExtBuilder::default()
+		.with_balances(vec![])
+		.build()
+		.execute_with(|| {
+			// Root can set transact info
+			assert_ok!(XcmTransactor::set_transact_info(
+				Origin::root(),
+				Box::new(xcm::VersionedMultiLocation::V1(MultiLocation::parent())),
+				0,
+				10000,
+				None
+			));
+
+			// Set fee per second
+			assert_ok!(XcmTransactor::set_fee_per_second(
+				Origin::root(),
+				Box::new(xcm::VersionedMultiLocation::V1(MultiLocation::parent())),
+				1
+			));
+
+			let channel_id = cumulus_primitives_core::relay_chain::HrmpChannelId {
+				sender: 1u32.into(),
+				recipient: 100u32.into(),
+			};
+
+			assert_ok!(XcmTransactor::hrmp_manage(
+				Origin::root(),
+				HrmpOperation::Accept {
+					para_id: 1u32.into()
+				},
+				CurrencyPayment {
+					currency: Currency::AsMultiLocation(Box::new(xcm::VersionedMultiLocation::V1(
+						MultiLocation::parent()
+					))),
+					fee_amount: None
+				},
+				TransactWeights {
+					transact_required_weight_at_most: 100u64,
+					overall_weight: None
+				}
+			));
+			assert_ok!(XcmTransactor::hrmp_manage(
+				Origin::root(),
+				HrmpOperation::Close(channel_id.clone()),
+				CurrencyPayment {
+					currency: Currency::AsMultiLocation(Box::new(xcm::VersionedMultiLocation::V1(
+						MultiLocation::parent()
+					))),
+					fee_amount: None
+				},
+				TransactWeights {
+					transact_required_weight_at_most: 100u64,
+					overall_weight: None
+				}
+			));
+
+			let sent_messages = mock::sent_xcm();
+			assert_eq!(sent_messages.len(), 2);
+			assert!(sent_messages[0].1 .0.contains(&Transact {
+				origin_type: OriginKind::Native,
+				require_weight_at_most: 100u64,
+				call: Transactors::hrmp_encode_call(HrmpAvailableCalls::AcceptOpenChannel(
+					1u32.into()
+				))
+				.into(),
+			}));
+			assert!(sent_messages[1].1 .0.contains(&Transact {
+				origin_type: OriginKind::Native,
+				require_weight_at_most: 100u64,
+				call: Transactors::hrmp_encode_call(HrmpAvailableCalls::CloseChannel(channel_id))
+					.into(),
+			}));
+		})
+}
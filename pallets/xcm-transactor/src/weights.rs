@@ -70,19 +70,29 @@ pub trait WeightInfo {
 	fn transact_through_sovereign() -> Weight;
 	#[rustfmt::skip]
 	fn transact_through_signed() -> Weight;
+	#[rustfmt::skip]
+	fn notify_transact_status() -> Weight;
+	#[rustfmt::skip]
+	fn hrmp_manage() -> Weight;
+	#[rustfmt::skip]
+	fn transact_through_derivative_with_options() -> Weight;
+	#[rustfmt::skip]
+	fn transact_through_sovereign_with_options() -> Weight;
+	#[rustfmt::skip]
+	fn transact_through_signed_with_options() -> Weight;
 }
 
 /// Weights for xcm_transactor using the Substrate node and recommended hardware.
 pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
-	// Storage: XcmTransactor IndexToAccount (r:1 w:1)
+	// Storage: XcmTransactor DestinationIndexToAccount (r:1 w:1)
 	#[rustfmt::skip]
 	fn register() -> Weight {
 		(17_998_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(1 as Weight))
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
-	// Storage: XcmTransactor IndexToAccount (r:0 w:1)
+	// Storage: XcmTransactor DestinationIndexToAccount (r:0 w:1)
 	#[rustfmt::skip]
 	fn deregister() -> Weight {
 		(14_817_000 as Weight)
@@ -107,7 +117,7 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
 	// Storage: AssetManager AssetIdType (r:1 w:0)
-	// Storage: XcmTransactor IndexToAccount (r:1 w:0)
+	// Storage: XcmTransactor DestinationIndexToAccount (r:1 w:0)
 	// Storage: XcmTransactor TransactInfoWithWeightLimit (r:1 w:0)
 	// Storage: XcmTransactor DestinationAssetFeePerSecond (r:1 w:0)
 	// Storage: AssetManager AssetTypeId (r:1 w:0)
@@ -141,18 +151,88 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(8 as Weight))
 			.saturating_add(T::DbWeight::get().writes(2 as Weight))
 	}
+	// Storage: XcmTransactor TransactStatusQueries (r:1 w:1)
+	#[rustfmt::skip]
+	fn notify_transact_status() -> Weight {
+		(19_212_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: AssetManager AssetIdType (r:1 w:0)
+	// Storage: XcmTransactor TransactInfoWithWeightLimit (r:1 w:0)
+	// Storage: XcmTransactor DestinationAssetFeePerSecond (r:1 w:0)
+	// Storage: PolkadotXcm SupportedVersion (r:1 w:0)
+	// Storage: PolkadotXcm VersionDiscoveryQueue (r:1 w:1)
+	// Storage: PolkadotXcm SafeXcmVersion (r:1 w:0)
+	// Storage: ParachainSystem HostConfiguration (r:1 w:0)
+	// Storage: ParachainSystem PendingUpwardMessages (r:1 w:1)
+	#[rustfmt::skip]
+	fn hrmp_manage() -> Weight {
+		(38_702_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: AssetManager AssetIdType (r:1 w:0)
+	// Storage: XcmTransactor DestinationIndexToAccount (r:1 w:0)
+	// Storage: XcmTransactor TransactInfoWithWeightLimit (r:1 w:0)
+	// Storage: XcmTransactor DestinationAssetFeePerSecond (r:1 w:0)
+	// Storage: AssetManager AssetTypeId (r:1 w:0)
+	// Storage: Assets Asset (r:1 w:0)
+	// Storage: PolkadotXcm QueryCounter (r:1 w:1)
+	// Storage: PolkadotXcm Queries (r:0 w:1)
+	// Storage: XcmTransactor TransactStatusTimeouts (r:1 w:1)
+	// Storage: XcmTransactor TransactStatusQueries (r:0 w:1)
+	#[rustfmt::skip]
+	fn transact_through_derivative_with_options() -> Weight {
+		(30_613_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	// Storage: AssetManager AssetIdType (r:1 w:0)
+	// Storage: XcmTransactor TransactInfoWithWeightLimit (r:1 w:0)
+	// Storage: XcmTransactor DestinationAssetFeePerSecond (r:1 w:0)
+	// Storage: AssetManager AssetTypeId (r:1 w:0)
+	// Storage: Assets Asset (r:1 w:0)
+	// Storage: PolkadotXcm QueryCounter (r:1 w:1)
+	// Storage: PolkadotXcm Queries (r:0 w:1)
+	// Storage: XcmTransactor TransactStatusTimeouts (r:1 w:1)
+	// Storage: XcmTransactor TransactStatusQueries (r:0 w:1)
+	#[rustfmt::skip]
+	fn transact_through_sovereign_with_options() -> Weight {
+		(26_933_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	// Storage: AssetManager AssetIdType (r:1 w:0)
+	// Storage: XcmTransactor TransactInfoWithWeightLimit (r:1 w:0)
+	// Storage: XcmTransactor DestinationAssetFeePerSecond (r:1 w:0)
+	// Storage: PolkadotXcm SupportedVersion (r:1 w:0)
+	// Storage: PolkadotXcm VersionDiscoveryQueue (r:1 w:1)
+	// Storage: PolkadotXcm SafeXcmVersion (r:1 w:0)
+	// Storage: ParachainSystem HostConfiguration (r:1 w:0)
+	// Storage: ParachainSystem PendingUpwardMessages (r:1 w:1)
+	// Storage: PolkadotXcm QueryCounter (r:1 w:1)
+	// Storage: PolkadotXcm Queries (r:0 w:1)
+	// Storage: XcmTransactor TransactStatusTimeouts (r:1 w:1)
+	// Storage: XcmTransactor TransactStatusQueries (r:0 w:1)
+	#[rustfmt::skip]
+	fn transact_through_signed_with_options() -> Weight {
+		(65_476_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(10 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
-	// Storage: XcmTransactor IndexToAccount (r:1 w:1)
+	// Storage: XcmTransactor DestinationIndexToAccount (r:1 w:1)
 	#[rustfmt::skip]
 	fn register() -> Weight {
 		(17_998_000 as Weight)
 			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
-	// Storage: XcmTransactor IndexToAccount (r:0 w:1)
+	// Storage: XcmTransactor DestinationIndexToAccount (r:0 w:1)
 	#[rustfmt::skip]
 	fn deregister() -> Weight {
 		(14_817_000 as Weight)
@@ -177,7 +257,7 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
 	// Storage: AssetManager AssetIdType (r:1 w:0)
-	// Storage: XcmTransactor IndexToAccount (r:1 w:0)
+	// Storage: XcmTransactor DestinationIndexToAccount (r:1 w:0)
 	// Storage: XcmTransactor TransactInfoWithWeightLimit (r:1 w:0)
 	// Storage: XcmTransactor DestinationAssetFeePerSecond (r:1 w:0)
 	// Storage: AssetManager AssetTypeId (r:1 w:0)
@@ -211,4 +291,74 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(8 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
 	}
+	// Storage: XcmTransactor TransactStatusQueries (r:1 w:1)
+	#[rustfmt::skip]
+	fn notify_transact_status() -> Weight {
+		(19_212_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: AssetManager AssetIdType (r:1 w:0)
+	// Storage: XcmTransactor TransactInfoWithWeightLimit (r:1 w:0)
+	// Storage: XcmTransactor DestinationAssetFeePerSecond (r:1 w:0)
+	// Storage: PolkadotXcm SupportedVersion (r:1 w:0)
+	// Storage: PolkadotXcm VersionDiscoveryQueue (r:1 w:1)
+	// Storage: PolkadotXcm SafeXcmVersion (r:1 w:0)
+	// Storage: ParachainSystem HostConfiguration (r:1 w:0)
+	// Storage: ParachainSystem PendingUpwardMessages (r:1 w:1)
+	#[rustfmt::skip]
+	fn hrmp_manage() -> Weight {
+		(38_702_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(8 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: AssetManager AssetIdType (r:1 w:0)
+	// Storage: XcmTransactor DestinationIndexToAccount (r:1 w:0)
+	// Storage: XcmTransactor TransactInfoWithWeightLimit (r:1 w:0)
+	// Storage: XcmTransactor DestinationAssetFeePerSecond (r:1 w:0)
+	// Storage: AssetManager AssetTypeId (r:1 w:0)
+	// Storage: Assets Asset (r:1 w:0)
+	// Storage: PolkadotXcm QueryCounter (r:1 w:1)
+	// Storage: PolkadotXcm Queries (r:0 w:1)
+	// Storage: XcmTransactor TransactStatusTimeouts (r:1 w:1)
+	// Storage: XcmTransactor TransactStatusQueries (r:0 w:1)
+	#[rustfmt::skip]
+	fn transact_through_derivative_with_options() -> Weight {
+		(30_613_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(8 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	// Storage: AssetManager AssetIdType (r:1 w:0)
+	// Storage: XcmTransactor TransactInfoWithWeightLimit (r:1 w:0)
+	// Storage: XcmTransactor DestinationAssetFeePerSecond (r:1 w:0)
+	// Storage: AssetManager AssetTypeId (r:1 w:0)
+	// Storage: Assets Asset (r:1 w:0)
+	// Storage: PolkadotXcm QueryCounter (r:1 w:1)
+	// Storage: PolkadotXcm Queries (r:0 w:1)
+	// Storage: XcmTransactor TransactStatusTimeouts (r:1 w:1)
+	// Storage: XcmTransactor TransactStatusQueries (r:0 w:1)
+	#[rustfmt::skip]
+	fn transact_through_sovereign_with_options() -> Weight {
+		(26_933_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	// Storage: AssetManager AssetIdType (r:1 w:0)
+	// Storage: XcmTransactor TransactInfoWithWeightLimit (r:1 w:0)
+	// Storage: XcmTransactor DestinationAssetFeePerSecond (r:1 w:0)
+	// Storage: PolkadotXcm SupportedVersion (r:1 w:0)
+	// Storage: PolkadotXcm VersionDiscoveryQueue (r:1 w:1)
+	// Storage: PolkadotXcm SafeXcmVersion (r:1 w:0)
+	// Storage: ParachainSystem HostConfiguration (r:1 w:0)
+	// Storage: ParachainSystem PendingUpwardMessages (r:1 w:1)
+	// Storage: PolkadotXcm QueryCounter (r:1 w:1)
+	// Storage: PolkadotXcm Queries (r:0 w:1)
+	// Storage: XcmTransactor TransactStatusTimeouts (r:1 w:1)
+	// Storage: XcmTransactor TransactStatusQueries (r:0 w:1)
+	#[rustfmt::skip]
+	fn transact_through_signed_with_options() -> Weight {
+		(65_476_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(10 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
 }
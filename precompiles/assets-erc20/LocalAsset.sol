@@ -35,7 +35,8 @@ interface LocalAsset {
     /// @custom:selector 51ec2ad7
     function thawAsset() external returns (bool);
 
-    /// @dev Transfer the ownership of an asset to a new account
+    /// @dev Transfer the ownership of an asset to a new account, along with the asset
+    /// deposit if the current owner holds it
     /// @custom:selector f2fde38b
     /// @param owner address The address of the new owner
     function transferOwnership(address owner) external returns (bool);
@@ -65,4 +66,17 @@ interface LocalAsset {
     /// @dev Clear the name, symbol and decimals of your asset
     /// @custom:selector efb6d432
     function clearMetadata() external returns (bool);
+
+    /// @dev Start the destruction of your asset, freezing it
+    /// @custom:selector 5708cde8
+    function startDestroy() external returns (bool);
+
+    /// @dev Burn the balances of a batch of accounts of an asset being destroyed
+    /// @custom:selector 82eb5ccf
+    /// @param maxAccounts uint32 The maximum number of accounts to burn
+    function burnAccounts(uint32 maxAccounts) external returns (bool);
+
+    /// @dev Finish the destruction of your asset, releasing its deposit
+    /// @custom:selector 3e70effc
+    function finishDestroy() external returns (bool);
 }
@@ -27,6 +27,7 @@ use frame_support::{
 	dispatch::{Dispatchable, GetDispatchInfo, PostDispatchInfo},
 	sp_runtime::traits::StaticLookup,
 };
+use pallet_asset_manager::AssetRegistrar;
 use pallet_evm::{AddressMapping, PrecompileSet};
 use precompile_utils::prelude::*;
 use sp_runtime::traits::Bounded;
@@ -86,6 +87,9 @@ pub enum Action {
 	SetTeam = "setTeam(address,address,address)",
 	SetMetadata = "setMetadata(string,string,uint8)",
 	ClearMetadata = "clearMetadata()",
+	StartDestroy = "startDestroy()",
+	BurnAccounts = "burnAccounts(uint32)",
+	FinishDestroy = "finishDestroy()",
 	// EIP 2612
 	Eip2612Permit = "permit(address,address,uint256,uint256,uint8,bytes32,bytes32)",
 	Eip2612Nonces = "nonces(address)",
@@ -100,6 +104,23 @@ pub enum Action {
 	DeprecatedClearMetadata = "clear_metadata()",
 }
 
+impl Action {
+	/// Modifier of the function called with the action.
+	pub fn modifier(&self) -> FunctionModifier {
+		match self {
+			Action::TotalSupply
+			| Action::BalanceOf
+			| Action::Allowance
+			| Action::Name
+			| Action::Symbol
+			| Action::Decimals
+			| Action::Eip2612Nonces
+			| Action::Eip2612DomainSeparator => FunctionModifier::View,
+			_ => FunctionModifier::NonPayable,
+		}
+	}
+}
+
 /// This trait ensure we can convert AccountIds to AssetIds
 /// We will require Runtime to have this trait implemented
 pub trait AccountIdAssetIdConversion<Account, AssetId> {
@@ -150,6 +171,8 @@ where
 		+ pallet_timestamp::Config,
 	Runtime::Call: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
 	Runtime::Call: From<pallet_assets::Call<Runtime, Instance>>,
+	Runtime: pallet_asset_manager::Config<AssetId = AssetIdOf<Runtime, Instance>>,
+	Runtime::Call: From<pallet_asset_manager::Call<Runtime>>,
 	<Runtime::Call as Dispatchable>::Origin: From<Option<Runtime::AccountId>>,
 	BalanceOf<Runtime, Instance>: TryFrom<U256> + Into<U256> + EvmData,
 	Runtime: AccountIdAssetIdConversion<Runtime::AccountId, AssetIdOf<Runtime, Instance>>,
@@ -171,12 +194,7 @@ where
 						Err(e) => return Some(Err(e.into())),
 					};
 
-					if let Err(err) = handle.check_function_modifier(match selector {
-						Action::Approve | Action::Transfer | Action::TransferFrom => {
-							FunctionModifier::NonPayable
-						}
-						_ => FunctionModifier::View,
-					}) {
+					if let Err(err) = handle.check_function_modifier(selector.modifier()) {
 						return Some(Err(err.into()));
 					}
 
@@ -214,6 +232,9 @@ where
 						Action::ClearMetadata | Action::DeprecatedClearMetadata => {
 							Self::clear_metadata(asset_id, handle)
 						}
+						Action::StartDestroy => Self::start_destroy(asset_id, handle),
+						Action::BurnAccounts => Self::burn_accounts(asset_id, handle),
+						Action::FinishDestroy => Self::finish_destroy(asset_id, handle),
 						Action::Eip2612Permit => {
 							eip2612::Eip2612::<Runtime, IsLocal, Instance>::permit(asset_id, handle)
 						}
@@ -743,37 +764,6 @@ where
 		Ok(succeed(EvmDataWriter::new().write(true).build()))
 	}
 
-	fn transfer_ownership(
-		asset_id: AssetIdOf<Runtime, Instance>,
-		handle: &mut impl PrecompileHandle,
-	) -> EvmResult<PrecompileOutput> {
-		if !IsLocal::get() {
-			return Err(RevertReason::UnknownSelector.into());
-		}
-
-		read_args!(handle, { owner: Address });
-		let owner: H160 = owner.into();
-
-		// Build call with origin.
-		{
-			let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
-			let owner = Runtime::AddressMapping::into_account_id(owner);
-
-			// Dispatch call (if enough gas).
-			RuntimeHelper::<Runtime>::try_dispatch(
-				handle,
-				Some(origin).into(),
-				pallet_assets::Call::<Runtime, Instance>::transfer_ownership {
-					id: asset_id,
-					owner: Runtime::Lookup::unlookup(owner),
-				},
-			)?;
-		}
-
-		// Build output.
-		Ok(succeed(EvmDataWriter::new().write(true).build()))
-	}
-
 	fn set_team(
 		asset_id: AssetIdOf<Runtime, Instance>,
 		handle: &mut impl PrecompileHandle,
@@ -870,3 +860,143 @@ where
 		Ok(succeed(EvmDataWriter::new().write(true).build()))
 	}
 }
+
+// Local asset management going through the asset manager, which holds the asset deposits
+impl<Runtime, IsLocal, Instance> Erc20AssetsPrecompileSet<Runtime, IsLocal, Instance>
+where
+	Instance: eip2612::InstanceToPrefix + 'static,
+	Runtime: pallet_assets::Config<Instance>
+		+ pallet_evm::Config
+		+ frame_system::Config
+		+ pallet_timestamp::Config,
+	Runtime::Call: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
+	Runtime::Call: From<pallet_assets::Call<Runtime, Instance>>,
+	Runtime: pallet_asset_manager::Config<AssetId = AssetIdOf<Runtime, Instance>>,
+	Runtime::Call: From<pallet_asset_manager::Call<Runtime>>,
+	<Runtime::Call as Dispatchable>::Origin: From<Option<Runtime::AccountId>>,
+	BalanceOf<Runtime, Instance>: TryFrom<U256> + Into<U256> + EvmData,
+	Runtime: AccountIdAssetIdConversion<Runtime::AccountId, AssetIdOf<Runtime, Instance>>,
+	<<Runtime as frame_system::Config>::Call as Dispatchable>::Origin: OriginTrait,
+	IsLocal: Get<bool>,
+	<Runtime as pallet_timestamp::Config>::Moment: Into<U256>,
+	AssetIdOf<Runtime, Instance>: Display,
+{
+	fn transfer_ownership(
+		asset_id: AssetIdOf<Runtime, Instance>,
+		handle: &mut impl PrecompileHandle,
+	) -> EvmResult<PrecompileOutput> {
+		if !IsLocal::get() {
+			return Err(RevertReason::UnknownSelector.into());
+		}
+
+		read_args!(handle, { owner: Address });
+		let owner: H160 = owner.into();
+
+		// Build call with origin.
+		{
+			let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+			let owner = Runtime::AddressMapping::into_account_id(owner);
+
+			// Dispatch call (if enough gas).
+			RuntimeHelper::<Runtime>::try_dispatch(
+				handle,
+				Some(origin).into(),
+				pallet_asset_manager::Call::<Runtime>::transfer_local_asset_ownership {
+					asset_id,
+					owner,
+				},
+			)?;
+		}
+
+		// Build output.
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
+
+	fn start_destroy(
+		asset_id: AssetIdOf<Runtime, Instance>,
+		handle: &mut impl PrecompileHandle,
+	) -> EvmResult<PrecompileOutput> {
+		if !IsLocal::get() {
+			return Err(RevertReason::UnknownSelector.into());
+		}
+
+		// Build call with origin.
+		{
+			let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+
+			// Dispatch call (if enough gas).
+			RuntimeHelper::<Runtime>::try_dispatch(
+				handle,
+				Some(origin).into(),
+				pallet_asset_manager::Call::<Runtime>::start_local_asset_destruction { asset_id },
+			)?;
+		}
+
+		// Build output.
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
+
+	fn burn_accounts(
+		asset_id: AssetIdOf<Runtime, Instance>,
+		handle: &mut impl PrecompileHandle,
+	) -> EvmResult<PrecompileOutput> {
+		if !IsLocal::get() {
+			return Err(RevertReason::UnknownSelector.into());
+		}
+
+		read_args!(handle, { max_accounts: u32 });
+
+		// Build call with origin.
+		{
+			let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+
+			// Dispatch call (if enough gas).
+			RuntimeHelper::<Runtime>::try_dispatch(
+				handle,
+				Some(origin).into(),
+				pallet_asset_manager::Call::<Runtime>::burn_local_asset_accounts {
+					asset_id,
+					max_accounts,
+				},
+			)?;
+		}
+
+		// Build output.
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
+
+	fn finish_destroy(
+		asset_id: AssetIdOf<Runtime, Instance>,
+		handle: &mut impl PrecompileHandle,
+	) -> EvmResult<PrecompileOutput> {
+		if !IsLocal::get() {
+			return Err(RevertReason::UnknownSelector.into());
+		}
+
+		// The witness is read from the asset itself, as it depends on every account left
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		let destroy_asset_witness =
+			<Runtime as pallet_asset_manager::Config>::AssetRegistrar::local_asset_destroy_witness(
+				asset_id,
+			)
+			.ok_or_else(|| revert("Asset does not exist"))?;
+
+		// Build call with origin.
+		{
+			let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+
+			// Dispatch call (if enough gas).
+			RuntimeHelper::<Runtime>::try_dispatch(
+				handle,
+				Some(origin).into(),
+				pallet_asset_manager::Call::<Runtime>::finish_local_asset_destruction {
+					asset_id,
+					destroy_asset_witness,
+				},
+			)?;
+		}
+
+		// Build output.
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
+}
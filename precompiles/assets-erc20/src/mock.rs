@@ -19,9 +19,17 @@
 use super::*;
 
 use codec::{Decode, Encode, MaxEncodedLen};
-use frame_support::{construct_runtime, parameter_types, traits::Everything};
+use frame_support::{
+	construct_runtime,
+	dispatch::DispatchResult,
+	parameter_types,
+	traits::{tokens::fungibles, Everything},
+	weights::Weight,
+};
 
-use frame_system::EnsureRoot;
+use frame_system::{EnsureNever, EnsureRoot};
+use moonbeam_runtime_common::assets;
+use pallet_assets::DestroyWitness;
 use pallet_evm::{AddressMapping, EnsureAddressNever, EnsureAddressRoot};
 use scale_info::TypeInfo;
 use serde::{Deserialize, Serialize};
@@ -29,7 +37,9 @@ use sp_core::{H160, H256};
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup},
+	DispatchError,
 };
+use xcm::latest::MultiLocation;
 
 pub type AccountId = Account;
 pub type AssetId = u128;
@@ -300,6 +310,151 @@ impl pallet_assets::Config<LocalAssetInstance> for Runtime {
 	type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
 }
 
+#[derive(Clone, Default, Eq, Debug, PartialEq, Ord, PartialOrd, Encode, Decode, TypeInfo)]
+pub struct MockAssetType(pub AssetId);
+
+impl From<MockAssetType> for AssetId {
+	fn from(asset: MockAssetType) -> AssetId {
+		asset.0
+	}
+}
+
+impl From<MockAssetType> for Option<MultiLocation> {
+	fn from(_asset: MockAssetType) -> Option<MultiLocation> {
+		None
+	}
+}
+
+fn local_asset_details(asset: AssetId) -> Option<assets::AssetDetails<AccountId, Balance>> {
+	assets::asset_details::<LocalAssets, AccountId, Balance, _>(asset)
+}
+
+fn set_local_asset_frozen(
+	asset: AssetId,
+	details: &assets::AssetDetails<AccountId, Balance>,
+	is_frozen: bool,
+) -> DispatchResult {
+	LocalAssets::force_asset_status(
+		Origin::root(),
+		asset,
+		details.owner.clone(),
+		details.issuer.clone(),
+		details.admin.clone(),
+		details.freezer.clone(),
+		details.min_balance,
+		details.is_sufficient,
+		is_frozen,
+	)
+}
+
+/// Manages local assets like the runtimes do
+pub struct MockAssetRegistrar;
+impl pallet_asset_manager::AssetRegistrar<Runtime> for MockAssetRegistrar {
+	fn create_local_asset(
+		asset: AssetId,
+		_creator: AccountId,
+		min_balance: Balance,
+		is_sufficient: bool,
+		owner: AccountId,
+	) -> DispatchResult {
+		LocalAssets::force_create(Origin::root(), asset, owner, is_sufficient, min_balance)
+	}
+
+	fn destroy_local_asset(asset: AssetId, witness: DestroyWitness) -> DispatchResult {
+		LocalAssets::destroy(Origin::root(), asset, witness).map_err(|info| info.error)?;
+		Ok(())
+	}
+
+	fn destroy_asset_dispatch_info_weight(_asset: AssetId, _witness: DestroyWitness) -> Weight {
+		0
+	}
+
+	fn local_asset_owner(asset: AssetId) -> Option<AccountId> {
+		local_asset_details(asset).map(|details| details.owner)
+	}
+
+	fn transfer_local_asset_ownership(asset: AssetId, owner: AccountId) -> DispatchResult {
+		let details = local_asset_details(asset).ok_or(DispatchError::CannotLookup)?;
+		LocalAssets::transfer_ownership(Origin::signed(details.owner), asset, owner)
+	}
+
+	fn freeze_local_asset(asset: AssetId) -> DispatchResult {
+		let details = local_asset_details(asset).ok_or(DispatchError::CannotLookup)?;
+		set_local_asset_frozen(asset, &details, true)
+	}
+
+	fn burn_local_asset_accounts(
+		asset: AssetId,
+		max_accounts: u32,
+		start_after: Option<AccountId>,
+	) -> Result<(u32, Option<AccountId>), DispatchError> {
+		let details = local_asset_details(asset).ok_or(DispatchError::CannotLookup)?;
+		set_local_asset_frozen(asset, &details, false)?;
+
+		let scanned: Vec<AccountId> =
+			assets::asset_accounts::<LocalAssets, AccountId, _>(asset, start_after.as_ref())
+				.take(max_accounts as usize)
+				.collect();
+
+		let mut burnt = 0u32;
+		for who in scanned.iter() {
+			let balance = LocalAssets::balance(asset, who);
+			if balance == 0 {
+				continue;
+			}
+			LocalAssets::thaw(Origin::signed(details.admin.clone()), asset, who.clone())?;
+			<LocalAssets as fungibles::Mutate<AccountId>>::burn_from(asset, who, balance)?;
+			burnt += 1;
+		}
+
+		set_local_asset_frozen(asset, &details, true)?;
+		let last_scanned = if scanned.len() < max_accounts as usize {
+			None
+		} else {
+			scanned.last().cloned()
+		};
+		Ok((burnt, last_scanned))
+	}
+
+	fn local_asset_destroy_witness(asset: AssetId) -> Option<DestroyWitness> {
+		assets::asset_destroy_witness::<LocalAssets, AccountId, Balance, _>(asset)
+	}
+
+	fn burn_local_asset_accounts_weight(_max_accounts: u32) -> Weight {
+		0
+	}
+}
+
+pub struct MockLocalAssetIdCreator;
+impl pallet_asset_manager::LocalAssetIdCreator<Runtime> for MockLocalAssetIdCreator {
+	fn create_asset_id_from_metadata(local_asset_counter: u128) -> AssetId {
+		local_asset_counter
+	}
+}
+
+parameter_types! {
+	pub const LocalAssetDeposit: Balance = 10;
+	pub const ForeignAssetDeposit: Balance = 0;
+}
+
+impl pallet_asset_manager::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type AssetId = AssetId;
+	type AssetRegistrarMetadata = ();
+	type ForeignAssetType = MockAssetType;
+	type AssetRegistrar = MockAssetRegistrar;
+	type ForeignAssetModifierOrigin = EnsureRoot<AccountId>;
+	type SiblingAssetRegistrarOrigin = EnsureNever<MultiLocation>;
+	type LocalAssetModifierOrigin = EnsureRoot<AccountId>;
+	type LocalAssetIdCreator = MockLocalAssetIdCreator;
+	type AssetDestroyWitness = DestroyWitness;
+	type Currency = Balances;
+	type LocalAssetDeposit = LocalAssetDeposit;
+	type ForeignAssetDeposit = ForeignAssetDeposit;
+	type WeightInfo = ();
+}
+
 // Configure a mock runtime to test the pallet.
 construct_runtime!(
 	pub enum Runtime where
@@ -312,7 +467,8 @@ construct_runtime!(
 		ForeignAssets: pallet_assets::<Instance1>::{Pallet, Call, Storage, Event<T>},
 		Evm: pallet_evm::{Pallet, Call, Storage, Event<T>},
 		Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
-		LocalAssets: pallet_assets::<Instance2>::{Pallet, Call, Storage, Event<T>}
+		LocalAssets: pallet_assets::<Instance2>::{Pallet, Call, Storage, Event<T>},
+		AssetManager: pallet_asset_manager::{Pallet, Call, Storage, Event<T>},
 	}
 );
 
@@ -1265,7 +1265,7 @@ fn transfer_ownership_local_assets() {
 						.write(Address(Account::Bob.into()))
 						.build(),
 				)
-				.expect_cost(19858000u64) // 1 weight => 1 gas in mock
+				.expect_cost(0u64) // 1 weight => 1 gas in mock
 				.expect_no_logs()
 				.execute_returns(EvmDataWriter::new().write(true).build());
 
@@ -1282,7 +1282,7 @@ fn transfer_ownership_local_assets() {
 					from_utf8(&output)
 						.unwrap()
 						.contains("Dispatched call failed with error: ")
-						&& from_utf8(&output).unwrap().contains("NoPermission")
+						&& from_utf8(&output).unwrap().contains("NotLocalAssetOwner")
 				});
 
 			precompiles()
@@ -1293,7 +1293,7 @@ fn transfer_ownership_local_assets() {
 						.write(Address(Account::Alice.into()))
 						.build(),
 				)
-				.expect_cost(19858000u64) // 1 weight => 1 gas in mock
+				.expect_cost(0u64) // 1 weight => 1 gas in mock
 				.expect_no_logs()
 				.execute_returns(EvmDataWriter::new().write(true).build());
 		});
@@ -2396,6 +2396,148 @@ fn burn_overflow() {
 		});
 }
 
+#[test]
+fn transfer_ownership_local_assets_moves_deposit() {
+	ExtBuilder::default()
+		.with_balances(vec![(Account::Alice, 1000), (Account::Bob, 2500)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(AssetManager::register_local_asset(
+				Origin::root(),
+				Account::Alice,
+				Account::Alice,
+				true,
+				1
+			));
+
+			precompiles()
+				.prepare_test(
+					Account::Alice,
+					Account::LocalAssetId(0u128),
+					EvmDataWriter::new_with_selector(Action::TransferOwnership)
+						.write(Address(Account::Bob.into()))
+						.build(),
+				)
+				.expect_no_logs()
+				.execute_returns(EvmDataWriter::new().write(true).build());
+
+			assert_eq!(Balances::reserved_balance(Account::Alice), 0);
+			assert_eq!(Balances::reserved_balance(Account::Bob), 10);
+			assert_eq!(
+				AssetManager::local_asset_deposit(0u128).map(|info| info.creator),
+				Some(Account::Bob)
+			);
+		});
+}
+
+#[test]
+fn destroy_local_assets() {
+	ExtBuilder::default()
+		.with_balances(vec![(Account::Alice, 1000), (Account::Bob, 2500)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(AssetManager::register_local_asset(
+				Origin::root(),
+				Account::Alice,
+				Account::Alice,
+				true,
+				1
+			));
+			assert_ok!(LocalAssets::mint(
+				Origin::signed(Account::Alice),
+				0u128,
+				Account::Bob,
+				400
+			));
+			assert_ok!(LocalAssets::mint(
+				Origin::signed(Account::Alice),
+				0u128,
+				Account::Charlie,
+				400
+			));
+
+			// Only the owner can destroy the asset
+			precompiles()
+				.prepare_test(
+					Account::Bob,
+					Account::LocalAssetId(0u128),
+					EvmDataWriter::new_with_selector(Action::StartDestroy).build(),
+				)
+				.execute_reverts(|output| {
+					from_utf8(&output).unwrap().contains("NotLocalAssetOwner")
+				});
+
+			precompiles()
+				.prepare_test(
+					Account::Alice,
+					Account::LocalAssetId(0u128),
+					EvmDataWriter::new_with_selector(Action::StartDestroy).build(),
+				)
+				.expect_no_logs()
+				.execute_returns(EvmDataWriter::new().write(true).build());
+
+			// The asset is frozen
+			assert!(LocalAssets::transfer(
+				Origin::signed(Account::Bob),
+				0u128,
+				Account::Alice,
+				100
+			)
+			.is_err());
+
+			precompiles()
+				.prepare_test(
+					Account::Alice,
+					Account::LocalAssetId(0u128),
+					EvmDataWriter::new_with_selector(Action::BurnAccounts)
+						.write(1u32)
+						.build(),
+				)
+				.expect_no_logs()
+				.execute_returns(EvmDataWriter::new().write(true).build());
+
+			assert_eq!(LocalAssets::total_supply(0u128), 400);
+
+			precompiles()
+				.prepare_test(
+					Account::Alice,
+					Account::LocalAssetId(0u128),
+					EvmDataWriter::new_with_selector(Action::FinishDestroy).build(),
+				)
+				.expect_no_logs()
+				.execute_returns(EvmDataWriter::new().write(true).build());
+
+			assert!(LocalAssets::maybe_total_supply(0u128).is_none());
+			assert!(AssetManager::local_asset_deposit(0u128).is_none());
+			assert_eq!(Balances::reserved_balance(Account::Alice), 0);
+			assert_eq!(Balances::free_balance(Account::Alice), 1000);
+		});
+}
+
+#[test]
+fn destroy_foreign_assets_is_not_supported() {
+	ExtBuilder::default()
+		.with_balances(vec![(Account::Alice, 1000)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(ForeignAssets::force_create(
+				Origin::root(),
+				0u128,
+				Account::Alice.into(),
+				true,
+				1
+			));
+
+			precompiles()
+				.prepare_test(
+					Account::Alice,
+					Account::ForeignAssetId(0u128),
+					EvmDataWriter::new_with_selector(Action::StartDestroy).build(),
+				)
+				.execute_reverts(|output| output == b"Unknown selector");
+		});
+}
+
 #[test]
 fn test_solidity_interface_has_all_function_selectors_documented_and_implemented() {
 	for file in ["ERC20.sol", "LocalAsset.sol", "Permit.sol"] {
@@ -2423,14 +2565,7 @@ fn test_solidity_interface_has_all_function_selectors_documented_and_implemented
 
 #[test]
 fn test_deprecated_solidity_selectors_are_supported() {
-	for deprecated_function in [
-		"freeze_asset()",
-		"thaw_asset()",
-		"transfer_ownership(address)",
-		"set_team(address,address,address)",
-		"set_metadata(string,string,uint8)",
-		"clear_metadata()",
-	] {
+	for deprecated_function in DEPRECATED_FUNCTIONS {
 		let selector = solidity::compute_selector(deprecated_function);
 		if Action::try_from(selector).is_err() {
 			panic!(
@@ -2440,3 +2575,151 @@ fn test_deprecated_solidity_selectors_are_supported() {
 		}
 	}
 }
+
+/// Deprecated functions still supported by the precompile, but not declared in the solidity
+/// files.
+const DEPRECATED_FUNCTIONS: &[&str] = &[
+	"freeze_asset()",
+	"thaw_asset()",
+	"transfer_ownership(address)",
+	"set_team(address,address,address)",
+	"set_metadata(string,string,uint8)",
+	"clear_metadata()",
+];
+
+#[test]
+fn test_solidity_interface_matches_rust_definitions() {
+	let function = |signature, args: &[(&str, String)], returns: &[String]| {
+		let action = Action::try_from(solidity::compute_selector(signature))
+			.expect("function is dispatched");
+		solidity::FunctionDeclaration::from_signature(signature, args, action.modifier(), returns)
+	};
+	let address = |name| (name, Address::solidity_type());
+	// Balances are read as the runtime balance type, but the ABI declares them as uint256.
+	let value = |name| (name, U256::solidity_type());
+	// Names and symbols are raw bytes, encoded like the strings declared in the ABI.
+	let string = |name| (name, String::solidity_type());
+	let success = [bool::solidity_type()];
+
+	let erc20 = vec![
+		function("name()", &[], &[String::solidity_type()]),
+		function("symbol()", &[], &[String::solidity_type()]),
+		function("decimals()", &[], &[u8::solidity_type()]),
+		function("totalSupply()", &[], &[U256::solidity_type()]),
+		function(
+			"balanceOf(address)",
+			&[address("who")],
+			&[U256::solidity_type()],
+		),
+		function(
+			"allowance(address,address)",
+			&[address("owner"), address("spender")],
+			&[U256::solidity_type()],
+		),
+		function(
+			"transfer(address,uint256)",
+			&[address("to"), value("value")],
+			&success,
+		),
+		function(
+			"approve(address,uint256)",
+			&[address("spender"), value("value")],
+			&success,
+		),
+		function(
+			"transferFrom(address,address,uint256)",
+			&[address("from"), address("to"), value("value")],
+			&success,
+		),
+	];
+	let local_asset = vec![
+		function(
+			"mint(address,uint256)",
+			&[address("to"), value("value")],
+			&success,
+		),
+		function(
+			"burn(address,uint256)",
+			&[address("from"), value("value")],
+			&success,
+		),
+		function("freeze(address)", &[address("account")], &success),
+		function("thaw(address)", &[address("account")], &success),
+		function("freezeAsset()", &[], &success),
+		function("thawAsset()", &[], &success),
+		function("transferOwnership(address)", &[address("owner")], &success),
+		function(
+			"setTeam(address,address,address)",
+			&[address("issuer"), address("admin"), address("freezer")],
+			&success,
+		),
+		function(
+			"setMetadata(string,string,uint8)",
+			&[
+				string("name"),
+				string("symbol"),
+				("decimals", u8::solidity_type()),
+			],
+			&success,
+		),
+		function("clearMetadata()", &[], &success),
+		function("startDestroy()", &[], &success),
+		function(
+			"burnAccounts(uint32)",
+			&[("maxAccounts", u32::solidity_type())],
+			&success,
+		),
+		function("finishDestroy()", &[], &success),
+	];
+	let permit = vec![
+		function(
+			"permit(address,address,uint256,uint256,uint8,bytes32,bytes32)",
+			&[
+				address("owner"),
+				address("spender"),
+				value("value"),
+				("deadline", U256::solidity_type()),
+				("v", u8::solidity_type()),
+				("r", H256::solidity_type()),
+				("s", H256::solidity_type()),
+			],
+			&[],
+		),
+		function(
+			"nonces(address)",
+			&[address("owner")],
+			&[U256::solidity_type()],
+		),
+		function("DOMAIN_SEPARATOR()", &[], &[H256::solidity_type()]),
+	];
+
+	// The functions are split across the solidity files, which must together declare every
+	// dispatched function.
+	solidity::SolidityInterface::from_declarations(
+		[&erc20[..], &local_asset[..], &permit[..]].concat(),
+		Action::signatures()
+			.iter()
+			.map(|(_, signature)| *signature)
+			.filter(|signature| !DEPRECATED_FUNCTIONS.contains(signature)),
+	);
+
+	solidity::SolidityInterface {
+		functions: erc20,
+		events: vec![],
+	}
+	.with_events(&[
+		"Transfer(address,address,uint256)",
+		"Approval(address,address,uint256)",
+	])
+	.assert_matches_file("ERC20.sol", "IERC20");
+	solidity::SolidityInterface {
+		functions: local_asset,
+		events: vec![],
+	}
+	.assert_matches_file("LocalAsset.sol", "LocalAsset");
+	solidity::SolidityInterface {
+		functions: permit,
+		events: vec![],
+	}
+	.assert_matches_file("Permit.sol", "Permit");
+}
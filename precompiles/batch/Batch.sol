@@ -7,6 +7,14 @@ pragma solidity >=0.8.3;
 /// Can be used by EOA to do multiple calls in a single transaction.
 /// @custom:address 0x0000000000000000000000000000000000000808
 interface Batch {
+    /// @dev Result of a subcall.
+    /// @custom:field success Whether the subcall succeeded.
+    /// @custom:field returnData Output of the subcall, which is the revert data if it failed.
+    struct SubcallResult {
+        bool success;
+        bytes returnData;
+    }
+
     /// @dev Batch multiple calls into a single transaction.
     /// All calls are performed from the address calling this precompile.
     ///
@@ -68,9 +76,81 @@ interface Batch {
         uint64[] memory gasLimit
     ) external;
 
+    /// @dev Batch multiple calls into a single transaction and return the result of each subcall.
+    /// All calls are performed from the address calling this precompile.
+    ///
+    /// In case of one subcall reverting following subcalls will still be attempted.
+    /// Failed subcalls emit a SubcallFailedWithData event containing the subcall output,
+    /// truncated to 256 bytes.
+    ///
+    /// @param to List of addresses to call.
+    /// @param value List of values for each subcall. If array is shorter than "to" then additional
+    /// calls will be performed with a value of 0.
+    /// @param callData Call data for each `to` address. If array is shorter than "to" then
+    /// additional calls will be performed with an empty call data.
+    /// @param gasLimit Gas limit for each `to` address. Use 0 to forward all the remaining gas.
+    /// If array is shorter than "to" then the remaining gas available will be used.
+    /// @return results Success and output of each attempted subcall, in order.
+    /// @custom:selector e1a62eee
+    function batchSomeWithResults(
+        address[] memory to,
+        uint256[] memory value,
+        bytes[] memory callData,
+        uint64[] memory gasLimit
+    ) external returns (SubcallResult[] memory results);
+
+    /// @dev Batch multiple calls into a single transaction and return the result of each subcall.
+    /// All calls are performed from the address calling this precompile.
+    ///
+    /// In case of one subcall reverting, no more subcalls will be executed but
+    /// the batch transaction will succeed.
+    /// Failed subcalls emit a SubcallFailedWithData event containing the subcall output,
+    /// truncated to 256 bytes.
+    ///
+    /// @param to List of addresses to call.
+    /// @param value List of values for each subcall. If array is shorter than "to" then additional
+    /// calls will be performed with a value of 0.
+    /// @param callData Call data for each `to` address. If array is shorter than "to" then
+    /// additional calls will be performed with an empty call data.
+    /// @param gasLimit Gas limit for each `to` address. Use 0 to forward all the remaining gas.
+    /// If array is shorter than "to" then the remaining gas available will be used.
+    /// @return results Success and output of each attempted subcall, in order.
+    /// @custom:selector be319805
+    function batchSomeUntilFailureWithResults(
+        address[] memory to,
+        uint256[] memory value,
+        bytes[] memory callData,
+        uint64[] memory gasLimit
+    ) external returns (SubcallResult[] memory results);
+
+    /// @dev Batch multiple calls into a single transaction and return the result of each subcall.
+    /// All calls are performed from the address calling this precompile.
+    ///
+    /// In case of one subcall reverting, the entire batch will revert.
+    ///
+    /// @param to List of addresses to call.
+    /// @param value List of values for each subcall. If array is shorter than "to" then additional
+    /// calls will be performed with a value of 0.
+    /// @param callData Call data for each `to` address. If array is shorter than "to" then
+    /// additional calls will be performed with an empty call data.
+    /// @param gasLimit Gas limit for each `to` address. Use 0 to forward all the remaining gas.
+    /// If array is shorter than "to" then the remaining gas available will be used.
+    /// @return results Success and output of each attempted subcall, in order.
+    /// @custom:selector 51d0eb23
+    function batchAllWithResults(
+        address[] memory to,
+        uint256[] memory value,
+        bytes[] memory callData,
+        uint64[] memory gasLimit
+    ) external returns (SubcallResult[] memory results);
+
     /// Emitted when a subcall succeeds.
     event SubcallSucceeded(uint256 index);
 
     /// Emitted when a subcall fails.
     event SubcallFailed(uint256 index);
+
+    /// Emitted when a subcall of a result-returning batch fails.
+    /// Contains the output of the subcall, such as its revert reason, truncated to 256 bytes.
+    event SubcallFailedWithData(uint256 index, bytes returnData);
 }
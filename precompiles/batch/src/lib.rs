@@ -25,7 +25,7 @@ use fp_evm::{
 use frame_support::traits::ConstU32;
 use precompile_utils::{costs::call_cost, prelude::*};
 use sp_core::{H160, U256};
-use sp_std::{iter::repeat, marker::PhantomData, vec};
+use sp_std::{iter::repeat, marker::PhantomData, vec, vec::Vec};
 
 #[cfg(test)]
 mod mock;
@@ -38,12 +38,42 @@ pub enum Action {
 	BatchSome = "batchSome(address[],uint256[],bytes[],uint64[])",
 	BatchSomeUntilFailure = "batchSomeUntilFailure(address[],uint256[],bytes[],uint64[])",
 	BatchAll = "batchAll(address[],uint256[],bytes[],uint64[])",
+	BatchSomeWithResults = "batchSomeWithResults(address[],uint256[],bytes[],uint64[])",
+	BatchSomeUntilFailureWithResults =
+		"batchSomeUntilFailureWithResults(address[],uint256[],bytes[],uint64[])",
+	BatchAllWithResults = "batchAllWithResults(address[],uint256[],bytes[],uint64[])",
+}
+
+/// Strategy to apply when a subcall fails.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Mode {
+	BatchSome,
+	BatchSomeUntilFailure,
+	BatchAll,
+}
+
+impl Action {
+	/// Failure strategy of the action and whether it returns the results of each subcall.
+	pub fn mode(self) -> (Mode, bool) {
+		match self {
+			Action::BatchSome => (Mode::BatchSome, false),
+			Action::BatchSomeUntilFailure => (Mode::BatchSomeUntilFailure, false),
+			Action::BatchAll => (Mode::BatchAll, false),
+			Action::BatchSomeWithResults => (Mode::BatchSome, true),
+			Action::BatchSomeUntilFailureWithResults => (Mode::BatchSomeUntilFailure, true),
+			Action::BatchAllWithResults => (Mode::BatchAll, true),
+		}
+	}
 }
 
 pub const LOG_SUBCALL_SUCCEEDED: [u8; 32] = keccak256!("SubcallSucceeded(uint256)");
 pub const LOG_SUBCALL_FAILED: [u8; 32] = keccak256!("SubcallFailed(uint256)");
+pub const LOG_SUBCALL_FAILED_WITH_DATA: [u8; 32] =
+	keccak256!("SubcallFailedWithData(uint256,bytes)");
 pub const CALL_DATA_LIMIT: u32 = 2u32.pow(16);
 pub const ARRAY_LIMIT: u32 = 2u32.pow(9);
+/// Maximum size of the subcall output included in a SubcallFailedWithData log.
+pub const LOGGED_RETURN_DATA_LIMIT: u32 = 2u32.pow(8);
 
 type GetCallDataLimit = ConstU32<CALL_DATA_LIMIT>;
 type GetArrayLimit = ConstU32<ARRAY_LIMIT>;
@@ -64,6 +94,21 @@ pub fn log_subcall_failed(address: impl Into<H160>, index: usize) -> Log {
 	)
 }
 
+pub fn log_subcall_failed_with_data(
+	address: impl Into<H160>,
+	index: usize,
+	data: impl Into<Vec<u8>>,
+) -> Log {
+	log1(
+		address,
+		LOG_SUBCALL_FAILED_WITH_DATA,
+		EvmDataWriter::new()
+			.write(U256::from(index))
+			.write(Bytes(data.into()))
+			.build(),
+	)
+}
+
 /// Batch precompile.
 #[derive(Debug, Clone)]
 pub struct BatchPrecompile<Runtime>(PhantomData<Runtime>);
@@ -126,10 +171,24 @@ where
 			).chain(repeat(None));
 		}
 
+		let (mode, with_results) = action.mode();
+
 		// Cost of batch log. (doesn't change when index changes)
-		let log_cost = log_subcall_failed(handle.code_address(), 0)
-			.compute_cost()
-			.map_err(|_| revert("Failed to compute log cost"))?;
+		// The output of a subcall is only known once executed, so logs of failures with data
+		// are reserved for the largest output they can contain.
+		let log_cost = if with_results {
+			log_subcall_failed_with_data(
+				handle.code_address(),
+				0,
+				vec![0u8; LOGGED_RETURN_DATA_LIMIT as usize],
+			)
+		} else {
+			log_subcall_failed(handle.code_address(), 0)
+		}
+		.compute_cost()
+		.map_err(|_| revert("Failed to compute log cost"))?;
+
+		let mut results: Vec<(bool, Bytes)> = vec![];
 
 		for ((i, address), (value, (call_data, gas_limit))) in
 			addresses.zip(values.zip(calls_data.zip(gas_limits)))
@@ -155,63 +214,54 @@ where
 			};
 
 			// We reserve enough gas to emit a final log and perform the subcall itself.
-			// If not enough gas we stop there according to Action strategy.
+			// If not enough gas we stop there according to Mode strategy.
 			let remaining_gas = handle.remaining_gas();
 
-			let forwarded_gas = match (remaining_gas.checked_sub(log_cost), action) {
+			let forwarded_gas = match (remaining_gas.checked_sub(log_cost), mode) {
 				(Some(remaining), _) => remaining,
-				(None, Action::BatchAll) => {
+				(None, Mode::BatchAll) => {
 					return Err(PrecompileFailure::Error {
 						exit_status: ExitError::OutOfGas,
 					})
 				}
-				(None, _) => {
-					return Ok(succeed([]));
-				}
+				(None, _) => break,
 			};
 
 			// Cost of the call itself that the batch precompile must pay.
 			let call_cost = call_cost(value, <Runtime as pallet_evm::Config>::config());
 
+			// If there is a provided gas limit we ensure there is enough gas remaining.
 			let forwarded_gas = match forwarded_gas.checked_sub(call_cost) {
-				Some(remaining) => remaining,
+				Some(remaining) => match gas_limit {
+					None => Some(remaining), // provide all gas if no gas limit,
+					Some(limit) if limit > remaining => None,
+					Some(limit) => Some(limit),
+				},
+				None => None,
+			};
+
+			let forwarded_gas = match forwarded_gas {
+				Some(forwarded_gas) => forwarded_gas,
 				None => {
-					let log = log_subcall_failed(handle.code_address(), i);
+					let log = Self::failure_log(handle.code_address(), i, vec![], with_results);
 					handle.record_log_costs(&[&log])?;
 					log.record(handle)?;
 
-					match action {
-						Action::BatchAll => {
+					match mode {
+						Mode::BatchAll => {
 							return Err(PrecompileFailure::Error {
 								exit_status: ExitError::OutOfGas,
 							})
 						}
-						Action::BatchSomeUntilFailure => return Ok(succeed([])),
-						Action::BatchSome => continue,
-					}
-				}
-			};
-
-			// If there is a provided gas limit we ensure there is enough gas remaining.
-			let forwarded_gas = match gas_limit {
-				None => forwarded_gas, // provide all gas if no gas limit,
-				Some(limit) => {
-					if limit > forwarded_gas {
-						let log = log_subcall_failed(handle.code_address(), i);
-						handle.record_log_costs(&[&log])?;
-						log.record(handle)?;
-
-						match action {
-							Action::BatchAll => {
-								return Err(PrecompileFailure::Error {
-									exit_status: ExitError::OutOfGas,
-								})
-							}
-							Action::BatchSomeUntilFailure => return Ok(succeed([])),
-							Action::BatchSome => continue,
+						Mode::BatchSomeUntilFailure => {
+							results.push((false, Bytes(vec![])));
+							break;
+						}
+						Mode::BatchSome => {
+							results.push((false, Bytes(vec![])));
+							continue;
 						}
 					}
-					limit
 				}
 			};
 
@@ -225,10 +275,11 @@ where
 			);
 
 			// Logs
-			// We reserved enough gas so this should not OOG.
+			// We reserved enough gas for the largest log so this should not OOG.
 			match reason {
 				ExitReason::Revert(_) | ExitReason::Error(_) => {
-					let log = log_subcall_failed(handle.code_address(), i);
+					let log =
+						Self::failure_log(handle.code_address(), i, output.clone(), with_results);
 					handle.record_log_costs(&[&log])?;
 					log.record(handle)?
 				}
@@ -241,34 +292,50 @@ where
 			}
 
 			// How to proceed
-			match (action, reason) {
+			match (mode, reason) {
 				// _: Fatal is always fatal
 				(_, ExitReason::Fatal(exit_status)) => {
 					return Err(PrecompileFailure::Fatal { exit_status })
 				}
 
 				// BatchAll : Reverts and errors are immediatly forwarded.
-				(Action::BatchAll, ExitReason::Revert(exit_status)) => {
+				(Mode::BatchAll, ExitReason::Revert(exit_status)) => {
 					return Err(PrecompileFailure::Revert {
 						exit_status,
 						output,
 					})
 				}
-				(Action::BatchAll, ExitReason::Error(exit_status)) => {
+				(Mode::BatchAll, ExitReason::Error(exit_status)) => {
 					return Err(PrecompileFailure::Error { exit_status })
 				}
 
 				// BatchSomeUntilFailure : Reverts and errors prevent subsequent subcalls to
 				// be executed but the precompile still succeed.
-				(Action::BatchSomeUntilFailure, ExitReason::Revert(_) | ExitReason::Error(_)) => {
-					return Ok(succeed([]))
+				(Mode::BatchSomeUntilFailure, ExitReason::Revert(_) | ExitReason::Error(_)) => {
+					results.push((false, Bytes(output)));
+					break;
 				}
 
 				// Success or ignored revert/error.
-				(_, _) => (),
+				(_, reason) => {
+					results.push((matches!(reason, ExitReason::Succeed(_)), Bytes(output)))
+				}
 			}
 		}
 
-		Ok(succeed([]))
+		if with_results {
+			Ok(succeed(EvmDataWriter::new().write(results).build()))
+		} else {
+			Ok(succeed([]))
+		}
+	}
+
+	fn failure_log(address: H160, index: usize, mut data: Vec<u8>, with_results: bool) -> Log {
+		if with_results {
+			data.truncate(LOGGED_RETURN_DATA_LIMIT as usize);
+			log_subcall_failed_with_data(address, index, data)
+		} else {
+			log_subcall_failed(address, index)
+		}
 	}
 }
@@ -20,7 +20,9 @@ use crate::mock::{
 	Call, ExtBuilder, Origin, PrecompilesValue, Runtime, TestPrecompiles,
 };
 use crate::{
-	log_subcall_failed, log_subcall_succeeded, Action, LOG_SUBCALL_FAILED, LOG_SUBCALL_SUCCEEDED,
+	log_subcall_failed, log_subcall_failed_with_data, log_subcall_succeeded, Action, GetArrayLimit,
+	GetCallDataLimit, LOGGED_RETURN_DATA_LIMIT, LOG_SUBCALL_FAILED, LOG_SUBCALL_FAILED_WITH_DATA,
+	LOG_SUBCALL_SUCCEEDED,
 };
 use evm::ExitReason;
 use fp_evm::{ExitError, ExitRevert, ExitSucceed};
@@ -56,11 +58,45 @@ fn costs() -> (u64, u64) {
 	(return_log_cost, call_cost)
 }
 
+/// Gas reserved before each subcall on top of the cost of a log without data, as
+/// result-returning variants reserve the cost of the largest failure log.
+fn extra_reserved_cost(action: Action) -> u64 {
+	if action.mode().1 {
+		let (log_cost, _) = costs();
+		log_subcall_failed_with_data(Precompile, 0, vec![0u8; LOGGED_RETURN_DATA_LIMIT as usize])
+			.compute_cost()
+			.unwrap() - log_cost
+	} else {
+		0
+	}
+}
+
+fn revert_output() -> Vec<u8> {
+	EvmDataWriter::new_with_selector(RevertSelector::Generic)
+		.write::<Bytes>(Bytes(b"Revert message".to_vec()))
+		.build()
+}
+
+/// Additional cost of logging the revert output in result-returning variants.
+fn revert_log_extra_cost(action: Action) -> u64 {
+	if action.mode().1 {
+		let (log_cost, _) = costs();
+		log_subcall_failed_with_data(Precompile, 1, revert_output())
+			.compute_cost()
+			.unwrap() - log_cost
+	} else {
+		0
+	}
+}
+
 #[test]
 fn selectors() {
 	assert_eq!(Action::BatchSome as u32, 0x79df4b9c);
 	assert_eq!(Action::BatchSomeUntilFailure as u32, 0xcf0491c7);
 	assert_eq!(Action::BatchAll as u32, 0x96e292b8);
+	assert_eq!(Action::BatchSomeWithResults as u32, 0xe1a62eee);
+	assert_eq!(Action::BatchSomeUntilFailureWithResults as u32, 0xbe319805);
+	assert_eq!(Action::BatchAllWithResults as u32, 0x51d0eb23);
 	assert_eq!(
 		LOG_SUBCALL_FAILED,
 		hex_literal::hex!("dbc5d06f4f877f959b1ff12d2161cdd693fa8e442ee53f1790b2804b24881f05")
@@ -69,6 +105,10 @@ fn selectors() {
 		LOG_SUBCALL_SUCCEEDED,
 		hex_literal::hex!("bf855484633929c3d6688eb3caf8eff910fb4bef030a8d7dbc9390d26759714d")
 	);
+	assert_eq!(
+		LOG_SUBCALL_FAILED_WITH_DATA,
+		hex_literal::hex!("a61fc6fb16c39ae331364e98f3cd227e256f50430a4d10f0fcfdf3a04f8cf178")
+	);
 }
 
 #[test]
@@ -135,6 +175,7 @@ fn batch_returns(
 	let mut counter = 0;
 
 	let (_, total_call_cost) = costs();
+	let extra_reserved_cost = extra_reserved_cost(action);
 
 	precompiles
 		.prepare_test(
@@ -172,7 +213,7 @@ fn batch_returns(
 
 					assert_eq!(
 						target_gas,
-						Some(100_000 - total_call_cost),
+						Some(100_000 - total_call_cost - extra_reserved_cost),
 						"batch forward all gas"
 					);
 					let transfer = transfer.expect("there is a transfer");
@@ -198,7 +239,7 @@ fn batch_returns(
 
 					assert_eq!(
 						target_gas,
-						Some(100_000 - 13 - total_call_cost * 2),
+						Some(100_000 - 13 - total_call_cost * 2 - extra_reserved_cost),
 						"batch forward all gas"
 					);
 					let transfer = transfer.expect("there is a transfer");
@@ -353,6 +394,8 @@ fn batch_incomplete(
 	let mut counter = 0;
 
 	let (_, total_call_cost) = costs();
+	let extra_reserved_cost = extra_reserved_cost(action);
+	let revert_log_extra_cost = revert_log_extra_cost(action);
 
 	precompiles
 		.prepare_test(
@@ -391,7 +434,7 @@ fn batch_incomplete(
 
 					assert_eq!(
 						target_gas,
-						Some(300_000 - total_call_cost),
+						Some(300_000 - total_call_cost - extra_reserved_cost),
 						"batch forward all gas"
 					);
 					let transfer = transfer.expect("there is a transfer");
@@ -417,7 +460,7 @@ fn batch_incomplete(
 
 					assert_eq!(
 						target_gas,
-						Some(300_000 - 13 - total_call_cost * 2),
+						Some(300_000 - 13 - total_call_cost * 2 - extra_reserved_cost),
 						"batch forward all gas"
 					);
 					let transfer = transfer.expect("there is a transfer");
@@ -432,9 +475,7 @@ fn batch_incomplete(
 
 					SubcallOutput {
 						reason: ExitReason::Revert(ExitRevert::Reverted),
-						output: EvmDataWriter::new_with_selector(RevertSelector::Generic)
-							.write::<Bytes>(Bytes(b"Revert message".to_vec()))
-							.build(),
+						output: revert_output(),
 						cost: 17,
 						logs: vec![],
 					}
@@ -445,7 +486,11 @@ fn batch_incomplete(
 
 					assert_eq!(
 						target_gas,
-						Some(300_000 - 13 - 17 - total_call_cost * 3),
+						Some(
+							300_000
+								- 13 - 17 - total_call_cost * 3 - revert_log_extra_cost
+								- extra_reserved_cost
+						),
 						"batch forward all gas"
 					);
 					let transfer = transfer.expect("there is a transfer");
@@ -508,6 +553,156 @@ fn batch_all_incomplete() {
 	})
 }
 
+#[test]
+fn batch_some_with_results_returns() {
+	ExtBuilder::default().build().execute_with(|| {
+		batch_returns(&precompiles(), Action::BatchSomeWithResults)
+			.expect_log(log1(Bob, H256::repeat_byte(0x11), vec![]))
+			.expect_log(log_subcall_succeeded(Precompile, 0))
+			.expect_log(log1(Charlie, H256::repeat_byte(0x22), vec![]))
+			.expect_log(log_subcall_succeeded(Precompile, 1))
+			.execute_returns(
+				EvmDataWriter::new()
+					.write(vec![(true, Bytes(vec![])), (true, Bytes(vec![]))])
+					.build(),
+			)
+	})
+}
+
+#[test]
+fn batch_all_with_results_returns() {
+	ExtBuilder::default().build().execute_with(|| {
+		batch_returns(&precompiles(), Action::BatchAllWithResults)
+			.expect_log(log1(Bob, H256::repeat_byte(0x11), vec![]))
+			.expect_log(log_subcall_succeeded(Precompile, 0))
+			.expect_log(log1(Charlie, H256::repeat_byte(0x22), vec![]))
+			.expect_log(log_subcall_succeeded(Precompile, 1))
+			.execute_returns(
+				EvmDataWriter::new()
+					.write(vec![(true, Bytes(vec![])), (true, Bytes(vec![]))])
+					.build(),
+			)
+	})
+}
+
+#[test]
+fn batch_some_with_results_incomplete() {
+	ExtBuilder::default().build().execute_with(|| {
+		let action = Action::BatchSomeWithResults;
+		let (_, total_call_cost) = costs();
+
+		batch_incomplete(&precompiles(), action)
+			.expect_log(log1(Bob, H256::repeat_byte(0x11), vec![]))
+			.expect_log(log_subcall_succeeded(Precompile, 0))
+			.expect_log(log_subcall_failed_with_data(Precompile, 1, revert_output()))
+			.expect_log(log1(Alice, H256::repeat_byte(0x33), vec![]))
+			.expect_log(log_subcall_succeeded(Precompile, 2))
+			.expect_cost(13 + 17 + 19 + total_call_cost * 3 + revert_log_extra_cost(action))
+			.execute_returns(
+				EvmDataWriter::new()
+					.write(vec![
+						(true, Bytes(vec![])),
+						(false, Bytes(revert_output())),
+						(true, Bytes(vec![])),
+					])
+					.build(),
+			)
+	})
+}
+
+#[test]
+fn batch_some_until_failure_with_results_incomplete() {
+	ExtBuilder::default().build().execute_with(|| {
+		let action = Action::BatchSomeUntilFailureWithResults;
+		let (_, total_call_cost) = costs();
+
+		batch_incomplete(&precompiles(), action)
+			.expect_log(log1(Bob, H256::repeat_byte(0x11), vec![]))
+			.expect_log(log_subcall_succeeded(Precompile, 0))
+			.expect_log(log_subcall_failed_with_data(Precompile, 1, revert_output()))
+			.expect_cost(13 + 17 + total_call_cost * 2 + revert_log_extra_cost(action))
+			.execute_returns(
+				EvmDataWriter::new()
+					.write(vec![(true, Bytes(vec![])), (false, Bytes(revert_output()))])
+					.build(),
+			)
+	})
+}
+
+#[test]
+fn batch_all_with_results_incomplete() {
+	ExtBuilder::default().build().execute_with(|| {
+		batch_incomplete(&precompiles(), Action::BatchAllWithResults)
+			.execute_reverts(|output| output == b"Revert message")
+	})
+}
+
+#[test]
+fn batch_some_with_results_out_of_gas() {
+	ExtBuilder::default().build().execute_with(|| {
+		let action = Action::BatchSomeWithResults;
+		let (_, total_call_cost) = costs();
+
+		precompiles()
+			.prepare_test(
+				Alice,
+				Precompile,
+				EvmDataWriter::new_with_selector(action)
+					.write(vec![Address(Bob.into())])
+					.write(vec![U256::from(1u8)])
+					.write(vec![Bytes::from(b"one".as_slice())])
+					.write(vec![50_000u64])
+					.build(),
+			)
+			.with_target_gas(Some(40_000 + total_call_cost))
+			.with_subcall_handle(move |_subcall| panic!("there shouldn't be any subcalls"))
+			.expect_log(log_subcall_failed_with_data(Precompile, 0, vec![]))
+			.execute_returns(
+				EvmDataWriter::new()
+					.write(vec![(false, Bytes(vec![]))])
+					.build(),
+			)
+	})
+}
+
+#[test]
+fn batch_some_with_results_truncates_logged_output() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (log_cost, total_call_cost) = costs();
+		let output = vec![0x42u8; LOGGED_RETURN_DATA_LIMIT as usize + 100];
+		let logged_output = output[..LOGGED_RETURN_DATA_LIMIT as usize].to_vec();
+		let log = log_subcall_failed_with_data(Precompile, 0, logged_output);
+		let log_extra_cost = log.compute_cost().unwrap() - log_cost;
+		let subcall_output = output.clone();
+
+		precompiles()
+			.prepare_test(
+				Alice,
+				Precompile,
+				EvmDataWriter::new_with_selector(Action::BatchSomeWithResults)
+					.write(vec![Address(Bob.into())])
+					.write(vec![U256::from(1u8)])
+					.write(vec![Bytes::from(b"one".as_slice())])
+					.write::<Vec<U256>>(vec![])
+					.build(),
+			)
+			.with_target_gas(Some(100_000))
+			.with_subcall_handle(move |_subcall| SubcallOutput {
+				reason: ExitReason::Revert(ExitRevert::Reverted),
+				output: subcall_output.clone(),
+				cost: 13,
+				logs: vec![],
+			})
+			.expect_log(log)
+			.expect_cost(13 + total_call_cost + log_extra_cost)
+			.execute_returns(
+				EvmDataWriter::new()
+					.write(vec![(false, Bytes(output))])
+					.build(),
+			)
+	})
+}
+
 fn batch_log_out_of_gas(
 	precompiles: &TestPrecompiles<Runtime>,
 	action: Action,
@@ -993,6 +1188,47 @@ fn test_solidity_interface_has_all_function_selectors_documented_and_implemented
 	}
 }
 
+#[test]
+fn test_solidity_interface_matches_rust_definitions() {
+	type Array<T> = BoundedVec<T, GetArrayLimit>;
+	let args = [
+		("to", Array::<Address>::solidity_type()),
+		("value", Array::<U256>::solidity_type()),
+		(
+			"callData",
+			Array::<BoundedBytes<GetCallDataLimit>>::solidity_type(),
+		),
+		("gasLimit", Array::<u64>::solidity_type()),
+	];
+
+	let functions = Action::signatures()
+		.iter()
+		.map(|(selector, signature)| {
+			let returns = match Action::try_from(*selector).expect("valid selector").mode() {
+				(_, true) => vec![Vec::<(bool, Bytes)>::solidity_type()],
+				(_, false) => vec![],
+			};
+			solidity::FunctionDeclaration::from_signature(
+				signature,
+				&args,
+				FunctionModifier::NonPayable,
+				&returns,
+			)
+		})
+		.collect();
+
+	solidity::SolidityInterface::from_declarations(
+		functions,
+		Action::signatures().iter().map(|(_, signature)| *signature),
+	)
+	.with_events(&[
+		"SubcallSucceeded(uint256)",
+		"SubcallFailed(uint256)",
+		"SubcallFailedWithData(uint256,bytes)",
+	])
+	.assert_matches_file("Batch.sol", "Batch");
+}
+
 #[test]
 fn batch_not_callable_by_smart_contract() {
 	ExtBuilder::default()
@@ -7,6 +7,18 @@ pragma solidity >=0.8.3;
 /// and signed messages can be dispatched by another network participant with a transaction
 /// @custom:address 0x000000000000000000000000000000000000080a
 interface CallPermit {
+    /// @dev Call authorized by a batch permit.
+    /// @custom:field to Which address the call is made to.
+    /// @custom:field value Value being transfered from the "from" account.
+    /// @custom:field data Call data
+    /// @custom:field gaslimit Gaslimit the dispatched call requires.
+    struct Call {
+        address to;
+        uint256 value;
+        bytes data;
+        uint64 gaslimit;
+    }
+
     /// @dev Dispatch a call on the behalf of an other user with a EIP712 permit.
     /// Will revert if the permit is not valid or if the dispatched call reverts or errors (such as
     /// out of gas).
@@ -35,6 +47,28 @@ interface CallPermit {
         bytes32 s
     ) external returns (bytes memory output);
 
+    /// @dev Dispatch a batch of calls on the behalf of an other user with a single EIP712 permit.
+    /// Calls are performed in order, and the batch will revert if the permit is not valid or if
+    /// any of the dispatched calls reverts or errors (such as out of gas).
+    /// If successful the EIP712 nonce is increased to prevent this permit to be replayed.
+    /// The permit shares its nonce with single call permits.
+    /// @param from Who made the permit and want its calls to be dispatched on their behalf.
+    /// @param calls Calls to dispatch, each with its own gaslimit.
+    /// @param deadline Deadline in UNIX seconds after which the permit will no longer be valid.
+    /// @param v V part of the signature.
+    /// @param r R part of the signature.
+    /// @param s S part of the signature.
+    /// @return outputs Output of each call.
+    /// @custom:selector cdf2d69f
+    function dispatchBatch(
+        address from,
+        Call[] memory calls,
+        uint256 deadline,
+        uint8 v,
+        bytes32 r,
+        bytes32 s
+    ) external returns (bytes[] memory outputs);
+
     /// @dev Returns the current nonce for given owner.
     /// A permit must have this nonce to be consumed, which will
     /// increase the nonce by one.
@@ -66,21 +66,91 @@ pub const PERMIT_TYPEHASH: [u8; 32] = keccak256!(
 ,uint256 nonce,uint256 deadline)"
 );
 
+/// EIP712 batch permit typehash.
+pub const PERMIT_BATCH_TYPEHASH: [u8; 32] = keccak256!(
+	"CallPermitBatch(address from,Call[] calls,uint256 nonce,uint256 deadline)\
+Call(address to,uint256 value,bytes data,uint64 gaslimit)"
+);
+
+/// EIP712 typehash of a call in a batch permit.
+pub const CALL_TYPEHASH: [u8; 32] =
+	keccak256!("Call(address to,uint256 value,bytes data,uint64 gaslimit)");
+
 /// EIP712 permit domain used to compute an individualized domain separator.
 const PERMIT_DOMAIN: [u8; 32] = keccak256!(
 	"EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)"
 );
 
 pub const CALL_DATA_LIMIT: u32 = 2u32.pow(16);
+pub const BATCH_LIMIT: u32 = 2u32.pow(9);
 
 #[generate_function_selector]
 #[derive(Debug, PartialEq)]
 pub enum Action {
 	Dispatch = "dispatch(address,address,uint256,bytes,uint64,uint256,uint8,bytes32,bytes32)",
+	DispatchBatch =
+		"dispatchBatch(address,(address,uint256,bytes,uint64)[],uint256,uint8,bytes32,bytes32)",
 	Nonces = "nonces(address)",
 	DomainSeparator = "DOMAIN_SEPARATOR()",
 }
 
+impl Action {
+	/// Modifier of the function called with the action.
+	pub fn modifier(&self) -> FunctionModifier {
+		match self {
+			Action::Dispatch | Action::DispatchBatch => FunctionModifier::NonPayable,
+			_ => FunctionModifier::View,
+		}
+	}
+}
+
+/// Call authorized by a batch permit.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PermitCall {
+	pub to: H160,
+	pub value: U256,
+	pub data: Vec<u8>,
+	pub gaslimit: u64,
+}
+
+impl EvmData for PermitCall {
+	fn read(reader: &mut EvmDataReader) -> MayRevert<Self> {
+		read_struct!(reader, {
+			to: Address,
+			value: U256,
+			data: BoundedBytes<ConstU32<CALL_DATA_LIMIT>>,
+			gaslimit: u64
+		});
+
+		Ok(PermitCall {
+			to: to.into(),
+			value,
+			data: data.into_vec(),
+			gaslimit,
+		})
+	}
+
+	fn write(writer: &mut EvmDataWriter, value: Self) {
+		EvmData::write(
+			writer,
+			(
+				Address(value.to),
+				value.value,
+				Bytes(value.data),
+				value.gaslimit,
+			),
+		);
+	}
+
+	fn has_static_size() -> bool {
+		<(Address, U256, Bytes, u64)>::has_static_size()
+	}
+
+	fn solidity_type() -> String {
+		<(Address, U256, Bytes, u64)>::solidity_type()
+	}
+}
+
 /// Precompile allowing to issue and dispatch call permits for gasless transactions.
 /// A user can sign a permit for a call that can be dispatched and paid by another user or
 /// smart contract.
@@ -94,13 +164,11 @@ where
 	fn execute(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
 		let selector = handle.read_selector()?;
 
-		handle.check_function_modifier(match selector {
-			Action::Dispatch => FunctionModifier::NonPayable,
-			_ => FunctionModifier::View,
-		})?;
+		handle.check_function_modifier(selector.modifier())?;
 
 		match selector {
 			Action::Dispatch => Self::dispatch(handle),
+			Action::DispatchBatch => Self::dispatch_batch(handle),
 			Action::Nonces => Self::nonces(handle),
 			Action::DomainSeparator => Self::domain_separator(handle),
 		}
@@ -151,7 +219,42 @@ where
 			.write(nonce)
 			.write(deadline)
 			.build();
-		let permit_content = keccak_256(&permit_content);
+		Self::permit_digest(domain_separator, keccak_256(&permit_content))
+	}
+
+	pub fn generate_batch_permit(
+		address: H160,
+		from: H160,
+		calls: &[PermitCall],
+		nonce: U256,
+		deadline: U256,
+	) -> [u8; 32] {
+		let domain_separator = Self::compute_domain_separator(address);
+
+		// arrays are encoded as the keccak_256 of the concatenated hashes of their items
+		let mut calls_hashes = Vec::with_capacity(calls.len() * 32);
+		for call in calls {
+			let call_content = EvmDataWriter::new()
+				.write(H256::from(CALL_TYPEHASH))
+				.write(Address(call.to))
+				.write(call.value)
+				.write(H256::from(keccak_256(&call.data)))
+				.write(call.gaslimit)
+				.build();
+			calls_hashes.extend_from_slice(&keccak_256(&call_content));
+		}
+
+		let permit_content = EvmDataWriter::new()
+			.write(H256::from(PERMIT_BATCH_TYPEHASH))
+			.write(Address(from))
+			.write(H256::from(keccak_256(&calls_hashes)))
+			.write(nonce)
+			.write(deadline)
+			.build();
+		Self::permit_digest(domain_separator, keccak_256(&permit_content))
+	}
+
+	fn permit_digest(domain_separator: [u8; 32], permit_content: [u8; 32]) -> [u8; 32] {
 		let mut pre_digest = Vec::with_capacity(2 + 32 + 32);
 		pre_digest.extend_from_slice(b"\x19\x01");
 		pre_digest.extend_from_slice(&domain_separator);
@@ -159,6 +262,43 @@ where
 		keccak_256(&pre_digest)
 	}
 
+	/// Ensures the permit has not expired and has been signed by `from`, then consumes
+	/// the nonce of `from`.
+	fn verify_and_consume_permit(
+		from: H160,
+		deadline: U256,
+		v: u8,
+		r: H256,
+		s: H256,
+		generate_permit: impl FnOnce(U256) -> [u8; 32],
+	) -> EvmResult {
+		// pallet_timestamp is in ms while Ethereum use second timestamps.
+		let timestamp: U256 = (pallet_timestamp::Pallet::<Runtime>::get()).into() / 1000;
+		ensure!(deadline >= timestamp, revert("Permit expired"));
+
+		let nonce = NoncesStorage::get(from);
+
+		let permit = generate_permit(nonce);
+
+		let mut sig = [0u8; 65];
+		sig[0..32].copy_from_slice(&r.as_bytes());
+		sig[32..64].copy_from_slice(&s.as_bytes());
+		sig[64] = v;
+
+		let signer = sp_io::crypto::secp256k1_ecdsa_recover(&sig, &permit)
+			.map_err(|_| revert("Invalid permit"))?;
+		let signer = H160::from(H256::from_slice(keccak_256(&signer).as_slice()));
+
+		ensure!(
+			signer != H160::zero() && signer == from,
+			revert("Invalid permit")
+		);
+
+		NoncesStorage::insert(from, nonce + U256::one());
+
+		Ok(())
+	}
+
 	pub fn dispatch_inherent_cost() -> u64 {
 		3_000 // cost of ECRecover precompile for reference
 			+ RuntimeHelper::<Runtime>::db_read_gas_cost() * 2 // we read nonce and timestamp
@@ -199,41 +339,99 @@ where
 		}
 
 		// VERIFY PERMIT
+		let address = handle.context().address;
+		Self::verify_and_consume_permit(from, deadline, v, r, s, |nonce| {
+			Self::generate_permit(
+				address,
+				from,
+				to,
+				value,
+				data.clone(),
+				gas_limit,
+				nonce,
+				deadline,
+			)
+		})?;
 
-		// pallet_timestamp is in ms while Ethereum use second timestamps.
-		let timestamp: U256 = (pallet_timestamp::Pallet::<Runtime>::get()).into() / 1000;
-		ensure!(deadline >= timestamp, revert("Permit expired"));
+		// DISPATCH CALL
+		let output = Self::dispatch_call(
+			handle,
+			from,
+			PermitCall {
+				to,
+				value,
+				data,
+				gaslimit: gas_limit,
+			},
+		)?;
 
-		let nonce = NoncesStorage::get(from);
+		Ok(succeed(EvmDataWriter::new().write(Bytes(output)).build()))
+	}
 
-		let permit = Self::generate_permit(
-			handle.context().address,
-			from,
-			to,
-			value,
-			data.clone(),
-			gas_limit,
-			nonce,
-			deadline,
+	fn dispatch_batch(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		handle.record_cost(Self::dispatch_inherent_cost())?;
+
+		// PARSE INPUT
+		read_args!(
+			handle,
+			{
+				from: Address,
+				calls: BoundedVec<PermitCall, ConstU32<BATCH_LIMIT>>,
+				deadline: U256,
+				v: u8,
+				r: H256,
+				s: H256
+			}
 		);
+		let from: H160 = from.into();
+		let calls = calls.into_vec();
 
-		let mut sig = [0u8; 65];
-		sig[0..32].copy_from_slice(&r.as_bytes());
-		sig[32..64].copy_from_slice(&s.as_bytes());
-		sig[64] = v;
+		// ENSURE GASLIMIT IS SUFFICIENT FOR ALL CALLS
+		let mut total_cost: u64 = 0;
+		for call in &calls {
+			let call_cost = call_cost(call.value, <Runtime as pallet_evm::Config>::config());
 
-		let signer = sp_io::crypto::secp256k1_ecdsa_recover(&sig, &permit)
-			.map_err(|_| revert("Invalid permit"))?;
-		let signer = H160::from(H256::from_slice(keccak_256(&signer).as_slice()));
+			total_cost = total_cost
+				.checked_add(call.gaslimit)
+				.and_then(|total| total.checked_add(call_cost))
+				.ok_or_else(|| revert("Calls require too much gas (uint64 overflow)"))?;
+		}
 
-		ensure!(
-			signer != H160::zero() && signer == from,
-			revert("Invalid permit")
-		);
+		if total_cost > handle.remaining_gas() {
+			return Err(revert("Gaslimit is too low to dispatch provided calls"));
+		}
 
-		NoncesStorage::insert(from, nonce + U256::one());
+		// VERIFY PERMIT
+		let address = handle.context().address;
+		Self::verify_and_consume_permit(from, deadline, v, r, s, |nonce| {
+			Self::generate_batch_permit(address, from, &calls, nonce, deadline)
+		})?;
+
+		// DISPATCH CALLS
+		// Any failing call makes the whole precompile call fail, which reverts the
+		// previous calls and the nonce increase.
+		let mut outputs = Vec::with_capacity(calls.len());
+		for call in calls {
+			let output = Self::dispatch_call(handle, from, call)?;
+			outputs.push(Bytes(output));
+		}
+
+		Ok(succeed(EvmDataWriter::new().write(outputs).build()))
+	}
+
+	/// Perform a call on the behalf of `from`, forwarding the exact gas limit of the call.
+	fn dispatch_call(
+		handle: &mut impl PrecompileHandle,
+		from: H160,
+		call: PermitCall,
+	) -> EvmResult<Vec<u8>> {
+		let PermitCall {
+			to,
+			value,
+			data,
+			gaslimit,
+		} = call;
 
-		// DISPATCH CALL
 		let sub_context = Context {
 			caller: from,
 			address: to.clone(),
@@ -250,8 +448,7 @@ where
 			})
 		};
 
-		let (reason, output) =
-			handle.call(to, transfer, data, Some(gas_limit), false, &sub_context);
+		let (reason, output) = handle.call(to, transfer, data, Some(gaslimit), false, &sub_context);
 		match reason {
 			ExitReason::Error(exit_status) => Err(PrecompileFailure::Error { exit_status }),
 			ExitReason::Fatal(exit_status) => Err(PrecompileFailure::Fatal { exit_status }),
@@ -259,9 +456,7 @@ where
 				exit_status: ExitRevert::Reverted,
 				output,
 			}),
-			ExitReason::Succeed(_) => {
-				Ok(succeed(EvmDataWriter::new().write(Bytes(output)).build()))
-			}
+			ExitReason::Succeed(_) => Ok(output),
 		}
 	}
 
@@ -19,10 +19,12 @@ use crate::{
 		Account::{Alice, Bob, Charlie, Precompile},
 		ExtBuilder, PrecompilesValue, Runtime, TestPrecompiles, ALICE_SECRET_KEY,
 	},
-	Action, CallPermitPrecompile,
+	Action, CallPermitPrecompile, PermitCall, BATCH_LIMIT, CALL_DATA_LIMIT, CALL_TYPEHASH,
+	PERMIT_BATCH_TYPEHASH,
 };
 use evm::ExitReason;
 use fp_evm::{ExitRevert, ExitSucceed};
+use frame_support::traits::ConstU32;
 use libsecp256k1::{sign, Message, SecretKey};
 use precompile_utils::{costs::call_cost, encoded_revert, prelude::*, solidity, testing::*};
 use sp_core::{H160, H256, U256};
@@ -38,10 +40,280 @@ fn dispatch_cost() -> u64 {
 #[test]
 fn selectors() {
 	assert_eq!(Action::Dispatch as u32, 0xb5ea0966);
+	assert_eq!(Action::DispatchBatch as u32, 0xcdf2d69f);
 	assert_eq!(Action::Nonces as u32, 0x7ecebe00);
 	assert_eq!(Action::DomainSeparator as u32, 0x3644e515);
 }
 
+#[test]
+fn batch_typehashes() {
+	assert_eq!(
+		PERMIT_BATCH_TYPEHASH,
+		hex_literal::hex!("ad86cefec04e147e2e3d08fb47367c97ee2f599ce5c5ad89f1c382db3c188b80")
+	);
+	assert_eq!(
+		CALL_TYPEHASH,
+		hex_literal::hex!("35da6e3cdde2b297b900755ae580402f3850e5e45d16e69e3c25200462bad9b6")
+	);
+}
+
+fn batch_calls() -> Vec<PermitCall> {
+	vec![
+		PermitCall {
+			to: Bob.into(),
+			value: 42u8.into(),
+			data: b"Test".to_vec(),
+			gaslimit: 100_000,
+		},
+		PermitCall {
+			to: Charlie.into(),
+			value: 0u8.into(),
+			data: b"Test2".to_vec(),
+			gaslimit: 50_000,
+		},
+	]
+}
+
+fn batch_calls_cost(calls: &[PermitCall]) -> u64 {
+	calls
+		.iter()
+		.map(|call| {
+			call.gaslimit + call_cost(call.value, <Runtime as pallet_evm::Config>::config())
+		})
+		.sum()
+}
+
+fn sign_batch_permit(calls: &[PermitCall], nonce: U256, deadline: U256) -> (u8, H256, H256) {
+	let permit = CallPermitPrecompile::<Runtime>::generate_batch_permit(
+		Precompile.into(),
+		Alice.into(),
+		calls,
+		nonce,
+		deadline,
+	);
+
+	let secret_key = SecretKey::parse(&ALICE_SECRET_KEY).unwrap();
+	let message = Message::parse(&permit);
+	let (rs, v) = sign(&message, &secret_key);
+
+	(
+		v.serialize(),
+		H256::from(rs.r.b32()),
+		H256::from(rs.s.b32()),
+	)
+}
+
+fn dispatch_batch_input(
+	calls: Vec<PermitCall>,
+	deadline: U256,
+	(v, r, s): (u8, H256, H256),
+) -> Vec<u8> {
+	EvmDataWriter::new_with_selector(Action::DispatchBatch)
+		.write(Address(Alice.into()))
+		.write(calls)
+		.write(deadline)
+		.write(v)
+		.write(r)
+		.write(s)
+		.build()
+}
+
+#[test]
+fn valid_batch_permit_returns() {
+	ExtBuilder::default()
+		.with_balances(vec![(Alice, 1000)])
+		.build()
+		.execute_with(|| {
+			let calls = batch_calls();
+			let deadline: U256 = 1_000u32.into();
+			let signature = sign_batch_permit(&calls, 0u8.into(), deadline);
+			let calls_cost = batch_calls_cost(&calls);
+
+			let mut counter = 0;
+
+			precompiles()
+				.prepare_test(
+					Charlie, // can be anyone
+					Precompile,
+					dispatch_batch_input(calls, deadline, signature),
+				)
+				.with_subcall_handle(move |subcall| {
+					let Subcall {
+						address,
+						transfer,
+						input,
+						target_gas,
+						is_static,
+						context,
+					} = subcall;
+
+					// Called on the behalf of the permit maker.
+					assert_eq!(context.caller, Alice.into());
+					assert_eq!(is_static, false);
+
+					counter += 1;
+					match counter {
+						1 => {
+							assert_eq!(address, Bob.into());
+							assert_eq!(target_gas, Some(100_000), "forward requested gas");
+
+							let transfer = transfer.expect("there is a transfer");
+							assert_eq!(transfer.source, Alice.into());
+							assert_eq!(transfer.target, Bob.into());
+							assert_eq!(transfer.value, 42u8.into());
+
+							assert_eq!(&input, b"Test");
+
+							SubcallOutput {
+								reason: ExitReason::Succeed(ExitSucceed::Returned),
+								output: b"TEST".to_vec(),
+								cost: 13,
+								logs: vec![log1(Bob, H256::repeat_byte(0x11), vec![])],
+							}
+						}
+						2 => {
+							assert_eq!(address, Charlie.into());
+							assert_eq!(target_gas, Some(50_000), "forward requested gas");
+							assert!(transfer.is_none(), "there is no transfer");
+
+							assert_eq!(&input, b"Test2");
+
+							SubcallOutput {
+								reason: ExitReason::Succeed(ExitSucceed::Returned),
+								output: b"TEST2".to_vec(),
+								cost: 17,
+								logs: vec![log1(Charlie, H256::repeat_byte(0x22), vec![])],
+							}
+						}
+						_ => panic!("unexpected subcall"),
+					}
+				})
+				.with_target_gas(Some(calls_cost + dispatch_cost()))
+				.expect_cost(
+					call_cost(42u8.into(), <Runtime as pallet_evm::Config>::config())
+						+ call_cost(0u8.into(), <Runtime as pallet_evm::Config>::config())
+						+ 13 + 17 + dispatch_cost(),
+				)
+				.expect_log(log1(Bob, H256::repeat_byte(0x11), vec![]))
+				.expect_log(log1(Charlie, H256::repeat_byte(0x22), vec![]))
+				.execute_returns(
+					EvmDataWriter::new()
+						.write(vec![Bytes(b"TEST".to_vec()), Bytes(b"TEST2".to_vec())])
+						.build(),
+				);
+
+			precompiles()
+				.prepare_test(
+					Alice,
+					Precompile,
+					EvmDataWriter::new_with_selector(Action::Nonces)
+						.write(Address(Alice.into()))
+						.build(),
+				)
+				.expect_cost(0) // TODO: Test db read/write costs
+				.expect_no_logs()
+				.execute_returns(EvmDataWriter::new().write(U256::from(1u8)).build());
+		})
+}
+
+#[test]
+fn valid_batch_permit_reverts() {
+	ExtBuilder::default()
+		.with_balances(vec![(Alice, 1000)])
+		.build()
+		.execute_with(|| {
+			let calls = batch_calls();
+			let deadline: U256 = 1_000u32.into();
+			let signature = sign_batch_permit(&calls, 0u8.into(), deadline);
+			let calls_cost = batch_calls_cost(&calls);
+
+			let mut counter = 0;
+
+			precompiles()
+				.prepare_test(
+					Charlie, // can be anyone
+					Precompile,
+					dispatch_batch_input(calls, deadline, signature),
+				)
+				.with_subcall_handle(move |Subcall { address, .. }| {
+					counter += 1;
+					match counter {
+						1 => {
+							assert_eq!(address, Bob.into());
+
+							SubcallOutput {
+								reason: ExitReason::Succeed(ExitSucceed::Returned),
+								output: b"TEST".to_vec(),
+								cost: 13,
+								logs: vec![],
+							}
+						}
+						2 => {
+							assert_eq!(address, Charlie.into());
+
+							SubcallOutput {
+								reason: ExitReason::Revert(ExitRevert::Reverted),
+								output: encoded_revert(b"TEST2"),
+								cost: 17,
+								logs: vec![],
+							}
+						}
+						_ => panic!("unexpected subcall"),
+					}
+				})
+				.with_target_gas(Some(calls_cost + dispatch_cost()))
+				.execute_reverts(|x| x == b"TEST2".to_vec());
+		})
+}
+
+#[test]
+fn invalid_batch_permit_nonce() {
+	ExtBuilder::default()
+		.with_balances(vec![(Alice, 1000)])
+		.build()
+		.execute_with(|| {
+			let calls = batch_calls();
+			let deadline: U256 = 1_000u32.into();
+			let signature = sign_batch_permit(&calls, 1u8.into(), deadline); // WRONG NONCE
+			let calls_cost = batch_calls_cost(&calls);
+
+			precompiles()
+				.prepare_test(
+					Charlie, // can be anyone
+					Precompile,
+					dispatch_batch_input(calls, deadline, signature),
+				)
+				.with_subcall_handle(move |_| panic!("should not perform subcall"))
+				.with_target_gas(Some(calls_cost + dispatch_cost()))
+				.expect_cost(dispatch_cost())
+				.execute_reverts(|x| x == b"Invalid permit");
+		})
+}
+
+#[test]
+fn invalid_batch_permit_gas_limit_too_low() {
+	ExtBuilder::default()
+		.with_balances(vec![(Alice, 1000)])
+		.build()
+		.execute_with(|| {
+			let calls = batch_calls();
+			let deadline: U256 = 1_000u32.into();
+			let signature = sign_batch_permit(&calls, 0u8.into(), deadline);
+			let calls_cost = batch_calls_cost(&calls);
+
+			precompiles()
+				.prepare_test(
+					Charlie, // can be anyone
+					Precompile,
+					dispatch_batch_input(calls, deadline, signature),
+				)
+				.with_subcall_handle(move |_| panic!("should not perform subcall"))
+				.with_target_gas(Some(calls_cost + dispatch_cost() - 1))
+				.expect_cost(dispatch_cost())
+				.execute_reverts(|x| x == b"Gaslimit is too low to dispatch provided calls");
+		})
+}
+
 #[test]
 fn valid_permit_returns() {
 	ExtBuilder::default()
@@ -685,3 +957,59 @@ fn test_solidity_interface_has_all_function_selectors_documented_and_implemented
 		}
 	}
 }
+
+#[test]
+fn test_solidity_interface_matches_rust_definitions() {
+	let function = |signature, args: &[(&str, String)], returns: &[String]| {
+		let action = Action::try_from(solidity::compute_selector(signature))
+			.expect("function is dispatched");
+		solidity::FunctionDeclaration::from_signature(signature, args, action.modifier(), returns)
+	};
+	let functions = vec![
+		function(
+			"dispatch(address,address,uint256,bytes,uint64,uint256,uint8,bytes32,bytes32)",
+			&[
+				("from", Address::solidity_type()),
+				("to", Address::solidity_type()),
+				("value", U256::solidity_type()),
+				(
+					"data",
+					BoundedBytes::<ConstU32<CALL_DATA_LIMIT>>::solidity_type(),
+				),
+				("gaslimit", u64::solidity_type()),
+				("deadline", U256::solidity_type()),
+				("v", u8::solidity_type()),
+				("r", H256::solidity_type()),
+				("s", H256::solidity_type()),
+			],
+			&[Bytes::solidity_type()],
+		),
+		function(
+			"dispatchBatch(address,(address,uint256,bytes,uint64)[],uint256,uint8,bytes32,bytes32)",
+			&[
+				("from", Address::solidity_type()),
+				(
+					"calls",
+					BoundedVec::<PermitCall, ConstU32<BATCH_LIMIT>>::solidity_type(),
+				),
+				("deadline", U256::solidity_type()),
+				("v", u8::solidity_type()),
+				("r", H256::solidity_type()),
+				("s", H256::solidity_type()),
+			],
+			&[Vec::<Bytes>::solidity_type()],
+		),
+		function(
+			"nonces(address)",
+			&[("owner", Address::solidity_type())],
+			&[U256::solidity_type()],
+		),
+		function("DOMAIN_SEPARATOR()", &[], &[H256::solidity_type()]),
+	];
+
+	solidity::SolidityInterface::from_declarations(
+		functions,
+		Action::signatures().iter().map(|(_, signature)| *signature),
+	)
+	.assert_matches_file("CallPermit.sol", "CallPermit");
+}
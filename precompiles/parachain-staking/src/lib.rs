@@ -106,29 +106,10 @@ enum Action {
 	DeprecatedCancelDelegationRequest = "cancel_delegation_request(address)",
 }
 
-/// A precompile to wrap the functionality from parachain_staking.
-///
-/// EXAMPLE USECASE:
-/// A simple example usecase is a contract that allows donors to donate, and stakes all the funds
-/// toward one fixed address chosen by the deployer.
-/// Such a contract could be deployed by a collator candidate, and the deploy address distributed to
-/// supporters who want to donate toward a perpetual nomination fund.
-pub struct ParachainStakingWrapper<Runtime>(PhantomData<Runtime>);
-
-// TODO: Migrate to precompile_utils::Precompile.
-impl<Runtime> pallet_evm::Precompile for ParachainStakingWrapper<Runtime>
-where
-	Runtime: pallet_parachain_staking::Config + pallet_evm::Config,
-	BalanceOf<Runtime>: EvmData,
-	Runtime::AccountId: Into<H160>,
-	Runtime::Call: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
-	<Runtime::Call as Dispatchable>::Origin: From<Option<Runtime::AccountId>>,
-	Runtime::Call: From<pallet_parachain_staking::Call<Runtime>>,
-{
-	fn execute(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
-		let selector = handle.read_selector()?;
-
-		handle.check_function_modifier(match selector {
+impl Action {
+	/// Modifier of the function called with the action.
+	fn modifier(&self) -> FunctionModifier {
+		match self {
 			// Views
 			Action::IsDelegator
 			| Action::IsCandidate
@@ -192,7 +173,33 @@ where
 			| Action::DeprecatedDelegatorBondMore
 			| Action::DeprecatedExecuteDelegationRequest
 			| Action::DeprecatedCancelDelegationRequest => FunctionModifier::NonPayable,
-		})?;
+		}
+	}
+}
+
+/// A precompile to wrap the functionality from parachain_staking.
+///
+/// EXAMPLE USECASE:
+/// A simple example usecase is a contract that allows donors to donate, and stakes all the funds
+/// toward one fixed address chosen by the deployer.
+/// Such a contract could be deployed by a collator candidate, and the deploy address distributed to
+/// supporters who want to donate toward a perpetual nomination fund.
+pub struct ParachainStakingWrapper<Runtime>(PhantomData<Runtime>);
+
+// TODO: Migrate to precompile_utils::Precompile.
+impl<Runtime> pallet_evm::Precompile for ParachainStakingWrapper<Runtime>
+where
+	Runtime: pallet_parachain_staking::Config + pallet_evm::Config,
+	BalanceOf<Runtime>: EvmData,
+	Runtime::AccountId: Into<H160>,
+	Runtime::Call: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
+	<Runtime::Call as Dispatchable>::Origin: From<Option<Runtime::AccountId>>,
+	Runtime::Call: From<pallet_parachain_staking::Call<Runtime>>,
+{
+	fn execute(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		let selector = handle.read_selector()?;
+
+		handle.check_function_modifier(selector.modifier())?;
 
 		// Return early if storage getter; return (origin, call) if dispatchable
 		let (origin, call) = match selector {
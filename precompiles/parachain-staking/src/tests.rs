@@ -1209,39 +1209,193 @@ fn test_solidity_interface_has_all_function_selectors_documented_and_implemented
 	}
 }
 
+#[test]
+fn test_solidity_interface_matches_rust_definitions() {
+	let function = |signature, args: &[(&str, String)], returns: &[String]| {
+		let action = Action::try_from(solidity::compute_selector(signature))
+			.expect("function is dispatched");
+		solidity::FunctionDeclaration::from_signature(signature, args, action.modifier(), returns)
+	};
+	// Rounds, counts and balances are read and written as narrower integers, but the ABI
+	// declares them as uint256.
+	let uint = |name| (name, U256::solidity_type());
+	let address = |name| (name, Address::solidity_type());
+
+	let functions = vec![
+		function(
+			"isDelegator(address)",
+			&[address("delegator")],
+			&[bool::solidity_type()],
+		),
+		function(
+			"isCandidate(address)",
+			&[address("candidate")],
+			&[bool::solidity_type()],
+		),
+		function(
+			"isSelectedCandidate(address)",
+			&[address("candidate")],
+			&[bool::solidity_type()],
+		),
+		function(
+			"points(uint256)",
+			&[uint("round")],
+			&[U256::solidity_type()],
+		),
+		function("minDelegation()", &[], &[U256::solidity_type()]),
+		function("candidateCount()", &[], &[U256::solidity_type()]),
+		function("round()", &[], &[U256::solidity_type()]),
+		function(
+			"candidateDelegationCount(address)",
+			&[address("candidate")],
+			&[U256::solidity_type()],
+		),
+		function(
+			"delegatorDelegationCount(address)",
+			&[address("delegator")],
+			&[U256::solidity_type()],
+		),
+		function(
+			"selectedCandidates()",
+			&[],
+			&[Vec::<Address>::solidity_type()],
+		),
+		function(
+			"delegationRequestIsPending(address,address)",
+			&[address("delegator"), address("candidate")],
+			&[bool::solidity_type()],
+		),
+		function(
+			"candidateExitIsPending(address)",
+			&[address("candidate")],
+			&[bool::solidity_type()],
+		),
+		function(
+			"candidateRequestIsPending(address)",
+			&[address("candidate")],
+			&[bool::solidity_type()],
+		),
+		function(
+			"joinCandidates(uint256,uint256)",
+			&[uint("amount"), uint("candidateCount")],
+			&[],
+		),
+		function(
+			"scheduleLeaveCandidates(uint256)",
+			&[uint("candidateCount")],
+			&[],
+		),
+		function(
+			"executeLeaveCandidates(address,uint256)",
+			&[address("candidate"), uint("candidateDelegationCount")],
+			&[],
+		),
+		function(
+			"cancelLeaveCandidates(uint256)",
+			&[uint("candidateCount")],
+			&[],
+		),
+		function("goOffline()", &[], &[]),
+		function("goOnline()", &[], &[]),
+		function("candidateBondMore(uint256)", &[uint("more")], &[]),
+		function("scheduleCandidateBondLess(uint256)", &[uint("less")], &[]),
+		function(
+			"executeCandidateBondLess(address)",
+			&[address("candidate")],
+			&[],
+		),
+		function("cancelCandidateBondLess()", &[], &[]),
+		function(
+			"delegate(address,uint256,uint256,uint256)",
+			&[
+				address("candidate"),
+				uint("amount"),
+				uint("candidateDelegationCount"),
+				uint("delegatorDelegationCount"),
+			],
+			&[],
+		),
+		function("scheduleLeaveDelegators()", &[], &[]),
+		function(
+			"executeLeaveDelegators(address,uint256)",
+			&[address("delegator"), uint("delegatorDelegationCount")],
+			&[],
+		),
+		function("cancelLeaveDelegators()", &[], &[]),
+		function(
+			"scheduleRevokeDelegation(address)",
+			&[address("candidate")],
+			&[],
+		),
+		function(
+			"delegatorBondMore(address,uint256)",
+			&[address("candidate"), uint("more")],
+			&[],
+		),
+		function(
+			"scheduleDelegatorBondLess(address,uint256)",
+			&[address("candidate"), uint("less")],
+			&[],
+		),
+		function(
+			"executeDelegationRequest(address,address)",
+			&[address("delegator"), address("candidate")],
+			&[],
+		),
+		function(
+			"cancelDelegationRequest(address)",
+			&[address("candidate")],
+			&[],
+		),
+	];
+
+	solidity::SolidityInterface::from_declarations(
+		functions,
+		Action::signatures()
+			.iter()
+			.map(|(_, signature)| *signature)
+			.filter(|signature| !DEPRECATED_FUNCTIONS.contains(signature)),
+	)
+	.assert_matches_file("StakingInterface.sol", "ParachainStaking");
+}
+
+/// Deprecated functions still supported by the precompile, but not declared in the
+/// solidity file.
+const DEPRECATED_FUNCTIONS: &[&str] = &[
+	"min_delegation()",
+	"candidate_count()",
+	"candidate_delegation_count(address)",
+	"delegator_delegation_count(address)",
+	"selected_candidates()",
+	"is_delegator(address)",
+	"is_candidate(address)",
+	"is_selected_candidate(address)",
+	"delegation_request_is_pending(address,address)",
+	"candidate_exit_is_pending(address)",
+	"candidate_request_is_pending(address)",
+	"join_candidates(uint256,uint256)",
+	"schedule_leave_candidates(uint256)",
+	"execute_leave_candidates(address,uint256)",
+	"cancel_leave_candidates(uint256)",
+	"go_offline()",
+	"go_online()",
+	"schedule_candidate_bond_less(uint256)",
+	"candidate_bond_more(uint256)",
+	"execute_candidate_bond_less(address)",
+	"cancel_candidate_bond_less()",
+	"schedule_leave_delegators()",
+	"execute_leave_delegators(address,uint256)",
+	"cancel_leave_delegators()",
+	"schedule_revoke_delegation(address)",
+	"schedule_delegator_bond_less(address,uint256)",
+	"delegator_bond_more(address,uint256)",
+	"execute_delegation_request(address,address)",
+	"cancel_delegation_request(address)",
+];
+
 #[test]
 fn test_deprecated_solidity_selectors_are_supported() {
-	for deprecated_function in [
-		"min_delegation()",
-		"candidate_count()",
-		"candidate_delegation_count(address)",
-		"delegator_delegation_count(address)",
-		"selected_candidates()",
-		"is_delegator(address)",
-		"is_candidate(address)",
-		"is_selected_candidate(address)",
-		"delegation_request_is_pending(address,address)",
-		"candidate_exit_is_pending(address)",
-		"candidate_request_is_pending(address)",
-		"join_candidates(uint256,uint256)",
-		"schedule_leave_candidates(uint256)",
-		"execute_leave_candidates(address,uint256)",
-		"cancel_leave_candidates(uint256)",
-		"go_offline()",
-		"go_online()",
-		"schedule_candidate_bond_less(uint256)",
-		"candidate_bond_more(uint256)",
-		"execute_candidate_bond_less(address)",
-		"cancel_candidate_bond_less()",
-		"schedule_leave_delegators()",
-		"execute_leave_delegators(address,uint256)",
-		"cancel_leave_delegators()",
-		"schedule_revoke_delegation(address)",
-		"schedule_delegator_bond_less(address,uint256)",
-		"delegator_bond_more(address,uint256)",
-		"execute_delegation_request(address,address)",
-		"cancel_delegation_request(address)",
-	] {
+	for deprecated_function in DEPRECATED_FUNCTIONS {
 		let selector = solidity::compute_selector(deprecated_function);
 		if Action::try_from(selector).is_err() {
 			panic!(
@@ -24,15 +24,17 @@ extern crate alloc;
 use fp_evm::{
 	Context, ExitReason, ExitSucceed, Log, Precompile, PrecompileHandle, PrecompileOutput,
 };
-use frame_support::traits::Get;
-use pallet_evm::GasWeightMapping;
+use frame_support::{dispatch::DispatchError, traits::Get, weights::Weight};
+use pallet_evm::{GasWeightMapping, Runner};
 use pallet_randomness::{
 	weights::{SubstrateWeight, WeightInfo},
-	BalanceOf, GetBabeData, Pallet, Request, RequestInfo, RequestState, RequestType,
+	BalanceOf, FulfillmentExecutor, GetBabeData, Pallet, Request, RequestId, RequestInfo,
+	RequestState, RequestType,
 };
 use precompile_utils::{costs::call_cost, prelude::*};
 use sp_core::{H160, H256, U256};
 use sp_std::{fmt::Debug, marker::PhantomData, vec, vec::Vec};
+use xcm_primitives::EthereumLogsRecorder;
 
 // #[cfg(test)]
 // mod mock;
@@ -57,6 +59,19 @@ pub enum Action {
 	PurgeExpiredRequest = "purgeExpiredRequest(uint256)",
 }
 
+impl Action {
+	/// Modifier of the function called with the action.
+	pub fn modifier(&self) -> FunctionModifier {
+		match self {
+			Action::RelayEpochIndex
+			| Action::GetRequestStatus
+			| Action::GetRequest
+			| Action::RequiredDeposit => FunctionModifier::View,
+			_ => FunctionModifier::NonPayable,
+		}
+	}
+}
+
 // Tests to verify equal to weight_to_gas(weight) in runtime integration tests
 pub const REQUEST_RANDOMNESS_ESTIMATED_COST: u64 = 26325;
 pub const INCREASE_REQUEST_FEE_ESTIMATED_COST: u64 = 16718;
@@ -118,6 +133,15 @@ where
 	Ok(())
 }
 
+/// Input of the callback providing `randomness` to the contract of request `request_id`
+fn fulfillment_callback_input(request_id: u64, randomness: Vec<H256>) -> Vec<u8> {
+	// callback function selector: keccak256("rawFulfillRandomWords(uint256,uint256[])")
+	EvmDataWriter::new_with_selector(0x1fe543e3_u32)
+		.write(request_id)
+		.write(randomness)
+		.build()
+}
+
 /// Subcall to provide randomness
 /// caller must call `ensure_can_provide_randomness` before calling this function
 fn provide_randomness(
@@ -130,11 +154,7 @@ fn provide_randomness(
 	let (reason, _) = handle.call(
 		contract,
 		None,
-		// callback function selector: keccak256("rawFulfillRandomWords(uint256,uint256[])")
-		EvmDataWriter::new_with_selector(0x1fe543e3_u32)
-			.write(request_id)
-			.write(randomness)
-			.build(),
+		fulfillment_callback_input(request_id, randomness),
 		Some(gas_limit),
 		false,
 		&Context {
@@ -175,13 +195,7 @@ where
 
 		let selector = handle.read_selector()?;
 
-		handle.check_function_modifier(match selector {
-			Action::RelayEpochIndex
-			| Action::GetRequestStatus
-			| Action::GetRequest
-			| Action::RequiredDeposit => FunctionModifier::View,
-			_ => FunctionModifier::NonPayable,
-		})?;
+		handle.check_function_modifier(selector.modifier())?;
 
 		match selector {
 			Action::RelayEpochIndex => Self::relay_epoch_index(handle),
@@ -487,3 +501,84 @@ where
 		})
 	}
 }
+
+/// Fulfills the requests due in `on_idle`, calling back their contracts from the precompile
+/// address as `fulfillRequest` would. The callbacks are not part of any Ethereum transaction,
+/// so their logs are recorded through `Recorder`.
+pub struct OnIdleFulfillment<Runtime, PrecompileAddress, Recorder>(
+	PhantomData<(Runtime, PrecompileAddress, Recorder)>,
+);
+
+impl<Runtime, PrecompileAddress, Recorder> FulfillmentExecutor<Runtime>
+	for OnIdleFulfillment<Runtime, PrecompileAddress, Recorder>
+where
+	Runtime: pallet_randomness::Config + pallet_evm::Config + pallet_base_fee::Config,
+	BalanceOf<Runtime>: TryFrom<U256> + Into<U256>,
+	PrecompileAddress: Get<H160>,
+	Recorder: EthereumLogsRecorder,
+{
+	fn fulfiller() -> H160 {
+		pallet_evm::Pallet::<Runtime>::find_author()
+	}
+	fn fulfillment_weight(request: &Request<BalanceOf<Runtime>, RequestInfo<Runtime>>) -> Weight {
+		<Runtime as pallet_evm::Config>::GasWeightMapping::gas_to_weight(request.gas_limit)
+	}
+	fn execute(
+		id: RequestId,
+		request: &Request<BalanceOf<Runtime>, RequestInfo<Runtime>>,
+		randomness: Vec<[u8; 32]>,
+	) -> Result<(Weight, BalanceOf<Runtime>), DispatchError> {
+		let base_fee = pallet_base_fee::Pallet::<Runtime>::base_fee_per_gas();
+		// assert fee > gasLimit * base_fee, as for `fulfillRequest`
+		match U256::from(request.gas_limit).checked_mul(base_fee) {
+			Some(gas_limit_times_base_fee) if gas_limit_times_base_fee < request.fee.into() => (),
+			_ => {
+				return Err(DispatchError::Other(
+					"Gas limit at current price must be less than fees allotted",
+				))
+			}
+		}
+		let precompile_address = PrecompileAddress::get();
+		let info = <Runtime as pallet_evm::Config>::Runner::call(
+			precompile_address,
+			request.contract_address,
+			fulfillment_callback_input(id, randomness.into_iter().map(|x| H256(x)).collect()),
+			U256::zero(),
+			request.gas_limit,
+			None,
+			None,
+			None,
+			Vec::new(),
+			false,
+			false,
+			<Runtime as pallet_evm::Config>::config(),
+		)
+		.map_err(|err| err.error.into())?;
+
+		// the callback cannot use more than the request gas limit
+		let gas_used = info.used_gas.low_u64();
+		let cost_of_execution: BalanceOf<Runtime> = U256::from(gas_used)
+			.checked_mul(base_fee)
+			.ok_or(DispatchError::Other(
+				"Multiply gas used by base fee overflowed",
+			))?
+			.try_into()
+			.map_err(|_| DispatchError::Other("amount is too large for provided balance type"))?;
+
+		let mut logs = info.logs;
+		match info.exit_reason {
+			ExitReason::Revert(_) | ExitReason::Error(_) => {
+				logs.push(log_fulfillment_failed(precompile_address))
+			}
+			ExitReason::Succeed(_) => logs.push(log_fulfillment_succeeded(precompile_address)),
+			_ => (),
+		}
+		// Only recorded once the execution can no longer fail
+		Recorder::record_logs(logs);
+
+		Ok((
+			<Runtime as pallet_evm::Config>::GasWeightMapping::gas_to_weight(gas_used),
+			cost_of_execution,
+		))
+	}
+}
@@ -196,6 +196,8 @@ impl Config for Runtime {
 	type MaxBlockDelay = MaxBlockDelay;
 	type BlockExpirationDelay = MaxBlockDelay;
 	type EpochExpirationDelay = MaxEpochDelay;
+	type FulfillRequestsOnIdle = frame_support::traits::ConstBool<false>;
+	type FulfillmentExecutor = ();
 }
 
 pub(crate) fn events() -> Vec<pallet::Event<Runtime>> {
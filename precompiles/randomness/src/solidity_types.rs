@@ -53,6 +53,10 @@ impl EvmData for RequestStatus {
 	fn has_static_size() -> bool {
 		true
 	}
+
+	fn solidity_type() -> String {
+		"uint8".into()
+	}
 }
 
 impl EvmData for RandomnessSource {
@@ -75,4 +79,8 @@ impl EvmData for RandomnessSource {
 	fn has_static_size() -> bool {
 		true
 	}
+
+	fn solidity_type() -> String {
+		"uint8".into()
+	}
 }
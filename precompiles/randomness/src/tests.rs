@@ -18,9 +18,13 @@
 
 // use std::collections::HashSet;
 
-use precompile_utils::solidity;
+use precompile_utils::{prelude::*, solidity};
+use sp_core::{H256, U256};
 
-use crate::Action;
+use crate::{
+	solidity_types::{RandomnessSource, RequestStatus},
+	Action,
+};
 // use crate::mock::*;
 
 // #[test]
@@ -73,3 +77,77 @@ fn test_solidity_interface_has_all_function_selectors_documented_and_implemented
 		}
 	}
 }
+
+#[test]
+fn test_solidity_interface_matches_rust_definitions() {
+	let function = |signature, args: &[(&str, String)], returns: &[String]| {
+		let action = Action::try_from(solidity::compute_selector(signature))
+			.expect("function is dispatched");
+		solidity::FunctionDeclaration::from_signature(signature, args, action.modifier(), returns)
+	};
+	// Request ids, deposits and block numbers are read and written as narrower integers, but the
+	// ABI declares them as larger ones.
+	let request_id = ("requestId", U256::solidity_type());
+	let request_args = [
+		("refundAddress", Address::solidity_type()),
+		("fee", U256::solidity_type()),
+		("gasLimit", u64::solidity_type()),
+		("salt", H256::solidity_type()),
+		("numWords", u8::solidity_type()),
+	];
+
+	let functions = vec![
+		function("relayEpochIndex()", &[], &[u64::solidity_type()]),
+		function("requiredDeposit()", &[], &[U256::solidity_type()]),
+		function(
+			"getRequestStatus(uint256)",
+			&[request_id.clone()],
+			&[RequestStatus::solidity_type()],
+		),
+		function(
+			"getRequest(uint256)",
+			&[request_id.clone()],
+			// The fields of the request are written one after the other, which encodes the same
+			// as the request struct since all of them have a static size.
+			&[<(
+				U256,
+				Address,
+				Address,
+				U256,
+				U256,
+				H256,
+				u32,
+				RandomnessSource,
+				u32,
+				u32,
+				u32,
+				u32,
+				RequestStatus,
+			)>::solidity_type()],
+		),
+		function(
+			"requestLocalVRFRandomWords(address,uint256,uint64,bytes32,uint8,uint64)",
+			&[&request_args[..], &[("delay", u64::solidity_type())][..]].concat(),
+			&[U256::solidity_type()],
+		),
+		function(
+			"requestRelayBabeEpochRandomWords(address,uint256,uint64,bytes32,uint8)",
+			&request_args,
+			&[U256::solidity_type()],
+		),
+		function("fulfillRequest(uint256)", &[request_id.clone()], &[]),
+		function(
+			"increaseRequestFee(uint256,uint256)",
+			&[request_id.clone(), ("feeIncrease", U256::solidity_type())],
+			&[],
+		),
+		function("purgeExpiredRequest(uint256)", &[request_id], &[]),
+	];
+
+	solidity::SolidityInterface::from_declarations(
+		functions,
+		Action::signatures().iter().map(|(_, signature)| *signature),
+	)
+	.with_events(&["FulFillmentSucceeded()", "FulFillmentFailed()"])
+	.assert_matches_file("Randomness.sol", "Randomness");
+}
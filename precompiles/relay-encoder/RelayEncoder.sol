@@ -97,4 +97,145 @@ interface RelayEncoder {
         external
         pure
         returns (bytes memory result);
+
+    /// @dev Encode 'payoutStakers' relay call
+    /// @custom:selector 53bc11d2
+    /// @param validatorStash: The stash account of the validator
+    /// @param era: The era to be paid out
+    /// @return result The bytes associated with the encoded call
+    function encodePayoutStakers(uint256 validatorStash, uint32 era)
+        external
+        pure
+        returns (bytes memory result);
+
+    /// @dev Encode 'utility.batchAll' relay call
+    /// @custom:selector 78477d1a
+    /// @param calls: The relay encoded calls to batch
+    /// @return result The bytes associated with the encoded call
+    function encodeBatchAll(bytes[] memory calls)
+        external
+        pure
+        returns (bytes memory result);
+
+    /// @dev Encode 'utility.asDerivative' relay call
+    /// @custom:selector 38653f14
+    /// @param index: The derivative index
+    /// @param call: The relay encoded call to dispatch from the derivative account
+    /// @return result The bytes associated with the encoded call
+    function encodeAsDerivative(uint16 index, bytes memory call)
+        external
+        pure
+        returns (bytes memory result);
+
+    /// @dev Encode 'proxy.addProxy' relay call
+    /// @custom:selector 5400bca7
+    /// @param delegate: The account that will be able to proxy
+    /// @param proxyType: The index of the relay proxy type
+    /// @param delay: The announcement period in relay blocks
+    /// @return result The bytes associated with the encoded call
+    function encodeAddProxy(
+        uint256 delegate,
+        uint8 proxyType,
+        uint32 delay
+    ) external pure returns (bytes memory result);
+
+    /// @dev Encode 'proxy.proxy' relay call
+    /// @custom:selector e450c2f0
+    /// @param real: The account on behalf of which the call is dispatched
+    /// @param call: The relay encoded call to dispatch
+    /// @return result The bytes associated with the encoded call
+    function encodeProxy(uint256 real, bytes memory call)
+        external
+        pure
+        returns (bytes memory result);
+
+    /// @dev Encode 'proxy.proxy' relay call forcing a proxy type
+    /// @custom:selector f9984872
+    /// @param real: The account on behalf of which the call is dispatched
+    /// @param forceProxyType: The index of the relay proxy type to use
+    /// @param call: The relay encoded call to dispatch
+    /// @return result The bytes associated with the encoded call
+    function encodeProxyWithType(
+        uint256 real,
+        uint8 forceProxyType,
+        bytes memory call
+    ) external pure returns (bytes memory result);
+
+    // The nomination pools calls revert if the relay chain doesn't have nomination pools
+
+    /// @dev Encode 'nominationPools.join' relay call
+    /// @custom:selector 3c5dd983
+    /// @param amount: The amount to bond into the pool
+    /// @param poolId: The pool to join
+    /// @return result The bytes associated with the encoded call
+    function encodePoolJoin(uint256 amount, uint32 poolId)
+        external
+        pure
+        returns (bytes memory result);
+
+    /// @dev Encode 'nominationPools.bondExtra' relay call bonding free balance
+    /// @custom:selector a12b5f1e
+    /// @param amount: The extra amount to bond
+    /// @return result The bytes associated with the encoded call
+    function encodePoolBondExtra(uint256 amount)
+        external
+        pure
+        returns (bytes memory result);
+
+    /// @dev Encode 'nominationPools.bondExtra' relay call bonding pending rewards
+    /// @custom:selector d5930060
+    /// @return result The bytes associated with the encoded call
+    function encodePoolBondExtraRewards()
+        external
+        pure
+        returns (bytes memory result);
+
+    /// @dev Encode 'nominationPools.unbond' relay call
+    /// @custom:selector e860dd70
+    /// @param member: The pool member account to unbond
+    /// @param unbondingPoints: The amount of points to unbond
+    /// @return result The bytes associated with the encoded call
+    function encodePoolUnbond(uint256 member, uint256 unbondingPoints)
+        external
+        pure
+        returns (bytes memory result);
+
+    /// @dev Encode 'nominationPools.claimPayout' relay call
+    /// @custom:selector d6876ceb
+    /// @return result The bytes associated with the encoded call
+    function encodePoolClaimPayout()
+        external
+        pure
+        returns (bytes memory result);
+
+    /// @dev Encode 'hrmp.hrmpInitOpenChannel' relay call
+    /// @custom:selector e5e20a64
+    /// @param recipient: The paraId of the channel recipient
+    /// @param maxCapacity: The proposed maximum number of messages in the channel
+    /// @param maxMessageSize: The proposed maximum message size
+    /// @return result The bytes associated with the encoded call
+    function encodeHrmpInitOpenChannel(
+        uint32 recipient,
+        uint32 maxCapacity,
+        uint32 maxMessageSize
+    ) external pure returns (bytes memory result);
+
+    /// @dev Encode 'hrmp.hrmpAcceptOpenChannel' relay call
+    /// @custom:selector 98a76477
+    /// @param sender: The paraId of the channel sender
+    /// @return result The bytes associated with the encoded call
+    function encodeHrmpAcceptOpenChannel(uint32 sender)
+        external
+        pure
+        returns (bytes memory result);
+
+    /// @dev Encode 'hrmp.hrmpCloseChannel' relay call
+    /// @custom:selector 9cfbdfc5
+    /// @param sender: The paraId of the channel sender
+    /// @param recipient: The paraId of the channel recipient
+    /// @return result The bytes associated with the encoded call
+    function encodeHrmpCloseChannel(uint32 sender, uint32 recipient)
+        external
+        pure
+        returns (bytes memory result);
 }
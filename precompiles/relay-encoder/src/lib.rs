@@ -14,12 +14,12 @@
 // You should have received a copy of the GNU General Public License
 // along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
 
-//! Precompile to encode relay staking calls via the EVM
+//! Precompile to encode relay calls via the EVM
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![cfg_attr(test, feature(assert_matches))]
 
-use cumulus_primitives_core::relay_chain;
+use cumulus_primitives_core::{relay_chain, relay_chain::HrmpChannelId};
 use fp_evm::{Precompile, PrecompileHandle, PrecompileOutput};
 use frame_support::{
 	dispatch::{Dispatchable, GetDispatchInfo, PostDispatchInfo},
@@ -33,6 +33,7 @@ use sp_runtime::AccountId32;
 use sp_runtime::Perbill;
 use sp_std::vec::Vec;
 use sp_std::{convert::TryInto, marker::PhantomData};
+use xcm_primitives::{HrmpAvailableCalls, HrmpEncodeCall};
 
 #[cfg(test)]
 mod mock;
@@ -55,6 +56,7 @@ pub enum AvailableStakeCalls {
 	SetPayee(pallet_staking::RewardDestination<relay_chain::AccountId>),
 	SetController(relay_chain::AccountId),
 	Rebond(relay_chain::Balance),
+	PayoutStakers(relay_chain::AccountId, u32),
 }
 
 pub trait StakeEncodeCall {
@@ -62,10 +64,49 @@ pub trait StakeEncodeCall {
 	fn encode_call(call: AvailableStakeCalls) -> Vec<u8>;
 }
 
+/// Utility calls. Inner calls are passed already encoded for the relay.
+pub enum AvailableUtilityCalls {
+	BatchAll(Vec<Vec<u8>>),
+	AsDerivative(u16, Vec<u8>),
+}
+
+pub trait UtilityEncodeCall {
+	/// Encode utility call from the relay.
+	fn utility_encode_call(call: AvailableUtilityCalls) -> Vec<u8>;
+}
+
+/// Proxy calls. The proxy type is the index of the variant in the relay `ProxyType` enum.
+pub enum AvailableProxyCalls {
+	AddProxy(relay_chain::AccountId, u8, relay_chain::BlockNumber),
+	Proxy(relay_chain::AccountId, Option<u8>, Vec<u8>),
+}
+
+pub trait ProxyEncodeCall {
+	/// Encode proxy call from the relay.
+	fn proxy_encode_call(call: AvailableProxyCalls) -> Vec<u8>;
+}
+
+pub enum AvailableNominationPoolsCalls {
+	Join(relay_chain::Balance, u32),
+	BondExtraFreeBalance(relay_chain::Balance),
+	BondExtraRewards,
+	Unbond(relay_chain::AccountId, relay_chain::Balance),
+	ClaimPayout,
+}
+
+pub trait NominationPoolsEncodeCall {
+	/// Encode nomination pools call from the relay.
+	/// Returns None if the relay chain doesn't have nomination pools.
+	fn nomination_pools_encode_call(call: AvailableNominationPoolsCalls) -> Option<Vec<u8>>;
+}
+
 pub const REWARD_DESTINATION_SIZE_LIMIT: u32 = 2u32.pow(16);
 pub const ARRAY_LIMIT: u32 = 512;
+pub const CALL_DATA_LIMIT: u32 = 2u32.pow(16);
+const NOMINATION_POOLS_UNAVAILABLE: &str = "nomination pools are not available in the relay chain";
 type GetArrayLimit = ConstU32<ARRAY_LIMIT>;
 type GetRewardDestinationSizeLimit = ConstU32<REWARD_DESTINATION_SIZE_LIMIT>;
+type GetCallDataLimit = ConstU32<CALL_DATA_LIMIT>;
 
 #[generate_function_selector]
 #[derive(Debug, PartialEq)]
@@ -80,6 +121,20 @@ enum Action {
 	EncodeSetPayee = "encodeSetPayee(bytes)",
 	EncodeSetController = "encodeSetController(uint256)",
 	EncodeRebond = "encodeRebond(uint256)",
+	EncodePayoutStakers = "encodePayoutStakers(uint256,uint32)",
+	EncodeBatchAll = "encodeBatchAll(bytes[])",
+	EncodeAsDerivative = "encodeAsDerivative(uint16,bytes)",
+	EncodeAddProxy = "encodeAddProxy(uint256,uint8,uint32)",
+	EncodeProxy = "encodeProxy(uint256,bytes)",
+	EncodeProxyWithType = "encodeProxyWithType(uint256,uint8,bytes)",
+	EncodePoolJoin = "encodePoolJoin(uint256,uint32)",
+	EncodePoolBondExtra = "encodePoolBondExtra(uint256)",
+	EncodePoolBondExtraRewards = "encodePoolBondExtraRewards()",
+	EncodePoolUnbond = "encodePoolUnbond(uint256,uint256)",
+	EncodePoolClaimPayout = "encodePoolClaimPayout()",
+	EncodeHrmpInitOpenChannel = "encodeHrmpInitOpenChannel(uint32,uint32,uint32)",
+	EncodeHrmpAcceptOpenChannel = "encodeHrmpAcceptOpenChannel(uint32)",
+	EncodeHrmpCloseChannel = "encodeHrmpCloseChannel(uint32,uint32)",
 
 	// deprecated
 	DeprecatedEncodeBond = "encode_bond(uint256,uint256,bytes)",
@@ -94,13 +149,17 @@ enum Action {
 	DeprecatedEncodeRebond = "encode_rebond(uint256)",
 }
 
-/// A precompile to provide relay stake calls encoding through evm
+/// A precompile to provide relay calls encoding through evm
 pub struct RelayEncoderWrapper<Runtime, RelayRuntime>(PhantomData<(Runtime, RelayRuntime)>);
 
 // TODO: Migrate to precompile_utils::Precompile.
 impl<Runtime, RelayRuntime> Precompile for RelayEncoderWrapper<Runtime, RelayRuntime>
 where
-	RelayRuntime: StakeEncodeCall,
+	RelayRuntime: StakeEncodeCall
+		+ UtilityEncodeCall
+		+ ProxyEncodeCall
+		+ NominationPoolsEncodeCall
+		+ HrmpEncodeCall,
 	Runtime: pallet_evm::Config,
 	Runtime::Call: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
 {
@@ -137,13 +196,31 @@ where
 				Self::encode_set_controller(handle)
 			}
 			Action::EncodeRebond | Action::DeprecatedEncodeRebond => Self::encode_rebond(handle),
+			Action::EncodePayoutStakers => Self::encode_payout_stakers(handle),
+			Action::EncodeBatchAll => Self::encode_batch_all(handle),
+			Action::EncodeAsDerivative => Self::encode_as_derivative(handle),
+			Action::EncodeAddProxy => Self::encode_add_proxy(handle),
+			Action::EncodeProxy => Self::encode_proxy(handle),
+			Action::EncodeProxyWithType => Self::encode_proxy_with_type(handle),
+			Action::EncodePoolJoin => Self::encode_pool_join(handle),
+			Action::EncodePoolBondExtra => Self::encode_pool_bond_extra(handle),
+			Action::EncodePoolBondExtraRewards => Self::encode_pool_bond_extra_rewards(handle),
+			Action::EncodePoolUnbond => Self::encode_pool_unbond(handle),
+			Action::EncodePoolClaimPayout => Self::encode_pool_claim_payout(handle),
+			Action::EncodeHrmpInitOpenChannel => Self::encode_hrmp_init_open_channel(handle),
+			Action::EncodeHrmpAcceptOpenChannel => Self::encode_hrmp_accept_open_channel(handle),
+			Action::EncodeHrmpCloseChannel => Self::encode_hrmp_close_channel(handle),
 		}
 	}
 }
 
 impl<Runtime, RelayRuntime> RelayEncoderWrapper<Runtime, RelayRuntime>
 where
-	RelayRuntime: StakeEncodeCall,
+	RelayRuntime: StakeEncodeCall
+		+ UtilityEncodeCall
+		+ ProxyEncodeCall
+		+ NominationPoolsEncodeCall
+		+ HrmpEncodeCall,
 	Runtime: pallet_evm::Config,
 	Runtime::Call: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
 {
@@ -301,6 +378,240 @@ where
 
 		Ok(succeed(EvmDataWriter::new().write(encoded).build()))
 	}
+
+	fn encode_payout_stakers(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+		read_args!(handle, { validator_stash: H256, era: u32 });
+		let validator_stash: [u8; 32] = validator_stash.into();
+
+		let encoded: Bytes = RelayRuntime::encode_call(AvailableStakeCalls::PayoutStakers(
+			validator_stash.into(),
+			era,
+		))
+		.as_slice()
+		.into();
+
+		Ok(succeed(EvmDataWriter::new().write(encoded).build()))
+	}
+
+	fn encode_batch_all(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+		read_args!(handle, { calls: BoundedVec<BoundedBytes<GetCallDataLimit>, GetArrayLimit> });
+
+		let calls: Vec<Vec<u8>> = calls
+			.into_vec()
+			.into_iter()
+			.map(|call| call.into_vec())
+			.collect();
+		let encoded: Bytes =
+			RelayRuntime::utility_encode_call(AvailableUtilityCalls::BatchAll(calls))
+				.as_slice()
+				.into();
+
+		Ok(succeed(EvmDataWriter::new().write(encoded).build()))
+	}
+
+	fn encode_as_derivative(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+		read_args!(handle, { index: u16, call: BoundedBytes<GetCallDataLimit> });
+
+		let encoded: Bytes = RelayRuntime::utility_encode_call(
+			AvailableUtilityCalls::AsDerivative(index, call.into_vec()),
+		)
+		.as_slice()
+		.into();
+
+		Ok(succeed(EvmDataWriter::new().write(encoded).build()))
+	}
+
+	fn encode_add_proxy(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+		read_args!(handle, { delegate: H256, proxy_type: u8, delay: u32 });
+		let delegate: [u8; 32] = delegate.into();
+
+		let encoded: Bytes = RelayRuntime::proxy_encode_call(AvailableProxyCalls::AddProxy(
+			delegate.into(),
+			proxy_type,
+			delay,
+		))
+		.as_slice()
+		.into();
+
+		Ok(succeed(EvmDataWriter::new().write(encoded).build()))
+	}
+
+	fn encode_proxy(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+		read_args!(handle, { real: H256, call: BoundedBytes<GetCallDataLimit> });
+		let real: [u8; 32] = real.into();
+
+		let encoded: Bytes = RelayRuntime::proxy_encode_call(AvailableProxyCalls::Proxy(
+			real.into(),
+			None,
+			call.into_vec(),
+		))
+		.as_slice()
+		.into();
+
+		Ok(succeed(EvmDataWriter::new().write(encoded).build()))
+	}
+
+	fn encode_proxy_with_type(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+		read_args!(handle, {
+			real: H256,
+			force_proxy_type: u8,
+			call: BoundedBytes<GetCallDataLimit>
+		});
+		let real: [u8; 32] = real.into();
+
+		let encoded: Bytes = RelayRuntime::proxy_encode_call(AvailableProxyCalls::Proxy(
+			real.into(),
+			Some(force_proxy_type),
+			call.into_vec(),
+		))
+		.as_slice()
+		.into();
+
+		Ok(succeed(EvmDataWriter::new().write(encoded).build()))
+	}
+
+	fn encode_pool_join(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+		read_args!(handle, { amount: U256, pool_id: u32 });
+
+		let relay_amount = u256_to_relay_amount(amount)?;
+		let encoded: Bytes = RelayRuntime::nomination_pools_encode_call(
+			AvailableNominationPoolsCalls::Join(relay_amount, pool_id),
+		)
+		.ok_or_else(|| revert(NOMINATION_POOLS_UNAVAILABLE))?
+		.as_slice()
+		.into();
+
+		Ok(succeed(EvmDataWriter::new().write(encoded).build()))
+	}
+
+	fn encode_pool_bond_extra(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+		read_args!(handle, { amount: U256 });
+
+		let relay_amount = u256_to_relay_amount(amount)?;
+		let encoded: Bytes = RelayRuntime::nomination_pools_encode_call(
+			AvailableNominationPoolsCalls::BondExtraFreeBalance(relay_amount),
+		)
+		.ok_or_else(|| revert(NOMINATION_POOLS_UNAVAILABLE))?
+		.as_slice()
+		.into();
+
+		Ok(succeed(EvmDataWriter::new().write(encoded).build()))
+	}
+
+	fn encode_pool_bond_extra_rewards(
+		handle: &mut impl PrecompileHandle,
+	) -> EvmResult<PrecompileOutput> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+		read_args!(handle, {});
+
+		let encoded: Bytes = RelayRuntime::nomination_pools_encode_call(
+			AvailableNominationPoolsCalls::BondExtraRewards,
+		)
+		.ok_or_else(|| revert(NOMINATION_POOLS_UNAVAILABLE))?
+		.as_slice()
+		.into();
+
+		Ok(succeed(EvmDataWriter::new().write(encoded).build()))
+	}
+
+	fn encode_pool_unbond(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+		read_args!(handle, { member: H256, unbonding_points: U256 });
+		let member: [u8; 32] = member.into();
+
+		let relay_points = u256_to_relay_amount(unbonding_points)?;
+		let encoded: Bytes = RelayRuntime::nomination_pools_encode_call(
+			AvailableNominationPoolsCalls::Unbond(member.into(), relay_points),
+		)
+		.ok_or_else(|| revert(NOMINATION_POOLS_UNAVAILABLE))?
+		.as_slice()
+		.into();
+
+		Ok(succeed(EvmDataWriter::new().write(encoded).build()))
+	}
+
+	fn encode_pool_claim_payout(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+		read_args!(handle, {});
+
+		let encoded: Bytes =
+			RelayRuntime::nomination_pools_encode_call(AvailableNominationPoolsCalls::ClaimPayout)
+				.ok_or_else(|| revert(NOMINATION_POOLS_UNAVAILABLE))?
+				.as_slice()
+				.into();
+
+		Ok(succeed(EvmDataWriter::new().write(encoded).build()))
+	}
+
+	fn encode_hrmp_init_open_channel(
+		handle: &mut impl PrecompileHandle,
+	) -> EvmResult<PrecompileOutput> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+		read_args!(handle, { recipient: u32, max_capacity: u32, max_message_size: u32 });
+
+		let encoded: Bytes = RelayRuntime::hrmp_encode_call(HrmpAvailableCalls::InitOpenChannel(
+			recipient.into(),
+			max_capacity,
+			max_message_size,
+		))
+		.as_slice()
+		.into();
+
+		Ok(succeed(EvmDataWriter::new().write(encoded).build()))
+	}
+
+	fn encode_hrmp_accept_open_channel(
+		handle: &mut impl PrecompileHandle,
+	) -> EvmResult<PrecompileOutput> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+		read_args!(handle, { sender: u32 });
+
+		let encoded: Bytes =
+			RelayRuntime::hrmp_encode_call(HrmpAvailableCalls::AcceptOpenChannel(sender.into()))
+				.as_slice()
+				.into();
+
+		Ok(succeed(EvmDataWriter::new().write(encoded).build()))
+	}
+
+	fn encode_hrmp_close_channel(
+		handle: &mut impl PrecompileHandle,
+	) -> EvmResult<PrecompileOutput> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+		read_args!(handle, { sender: u32, recipient: u32 });
+
+		let encoded: Bytes =
+			RelayRuntime::hrmp_encode_call(HrmpAvailableCalls::CloseChannel(HrmpChannelId {
+				sender: sender.into(),
+				recipient: recipient.into(),
+			}))
+			.as_slice()
+			.into();
+
+		Ok(succeed(EvmDataWriter::new().write(encoded).build()))
+	}
 }
 
 pub fn u256_to_relay_amount(value: U256) -> EvmResult<relay_chain::Balance> {
@@ -386,4 +697,8 @@ impl EvmData for RewardDestinationWrapper {
 	fn has_static_size() -> bool {
 		false
 	}
+
+	fn solidity_type() -> String {
+		"bytes".into()
+	}
 }
@@ -14,9 +14,15 @@
 // You should have received a copy of the GNU General Public License
 // along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
 
-use crate::AvailableStakeCalls;
-use crate::StakeEncodeCall;
-use parity_scale_codec::{Decode, Encode};
+use crate::{
+	AvailableNominationPoolsCalls, AvailableProxyCalls, AvailableStakeCalls, AvailableUtilityCalls,
+	NominationPoolsEncodeCall, ProxyEncodeCall, StakeEncodeCall, UtilityEncodeCall,
+};
+use cumulus_primitives_core::{
+	relay_chain::{Balance, HrmpChannelId},
+	ParaId,
+};
+use parity_scale_codec::{Compact, Decode, Encode};
 use sp_runtime::traits::{AccountIdLookup, StaticLookup};
 use sp_runtime::AccountId32;
 use sp_std::vec::Vec;
@@ -25,6 +31,60 @@ use sp_std::vec::Vec;
 pub enum RelayCall {
 	#[codec(index = 1u8)]
 	Stake(StakeCall),
+	#[codec(index = 2u8)]
+	Utility(UtilityCall),
+	#[codec(index = 3u8)]
+	Proxy(ProxyCall),
+	#[codec(index = 4u8)]
+	NominationPools(NominationPoolsCall),
+	#[codec(index = 5u8)]
+	Hrmp(HrmpCall),
+}
+
+#[derive(Encode, Decode)]
+pub enum UtilityCall {
+	#[codec(index = 1u8)]
+	AsDerivative(u16),
+	#[codec(index = 2u8)]
+	BatchAll(Compact<u32>),
+}
+
+#[derive(Encode, Decode)]
+pub enum ProxyCall {
+	#[codec(index = 0u8)]
+	Proxy(AccountId32, Option<u8>),
+	#[codec(index = 1u8)]
+	AddProxy(AccountId32, u8, u32),
+}
+
+#[derive(Encode, Decode)]
+pub enum PoolBondExtra {
+	#[codec(index = 0u8)]
+	FreeBalance(Balance),
+	#[codec(index = 1u8)]
+	Rewards,
+}
+
+#[derive(Encode, Decode)]
+pub enum NominationPoolsCall {
+	#[codec(index = 0u8)]
+	Join(#[codec(compact)] Balance, u32),
+	#[codec(index = 1u8)]
+	BondExtra(PoolBondExtra),
+	#[codec(index = 2u8)]
+	ClaimPayout,
+	#[codec(index = 3u8)]
+	Unbond(AccountId32, #[codec(compact)] Balance),
+}
+
+#[derive(Encode, Decode)]
+pub enum HrmpCall {
+	#[codec(index = 0u8)]
+	InitOpenChannel(ParaId, u32, u32),
+	#[codec(index = 1u8)]
+	AcceptOpenChannel(ParaId),
+	#[codec(index = 2u8)]
+	CloseChannel(HrmpChannelId),
 }
 
 #[derive(Encode, Decode)]
@@ -52,6 +112,8 @@ pub enum StakeCall {
 	SetPayee(pallet_staking::RewardDestination<AccountId32>),
 	#[codec(index = 8u16)]
 	SetController(<AccountIdLookup<AccountId32, ()> as StaticLookup>::Source),
+	#[codec(index = 18u16)]
+	PayoutStakers(AccountId32, u32),
 	#[codec(index = 19u16)]
 	Rebond(#[codec(compact)] cumulus_primitives_core::relay_chain::Balance),
 }
@@ -95,6 +157,91 @@ impl StakeEncodeCall for TestEncoder {
 
 				RelayCall::Stake(StakeCall::Nominate(nominated)).encode()
 			}
+
+			AvailableStakeCalls::PayoutStakers(a, b) => {
+				RelayCall::Stake(StakeCall::PayoutStakers(a, b)).encode()
+			}
+		}
+	}
+}
+
+impl UtilityEncodeCall for TestEncoder {
+	fn utility_encode_call(call: AvailableUtilityCalls) -> Vec<u8> {
+		match call {
+			AvailableUtilityCalls::BatchAll(calls) => {
+				let mut encoded =
+					RelayCall::Utility(UtilityCall::BatchAll((calls.len() as u32).into())).encode();
+				for mut call in calls {
+					encoded.append(&mut call);
+				}
+				encoded
+			}
+
+			AvailableUtilityCalls::AsDerivative(a, mut b) => {
+				let mut encoded = RelayCall::Utility(UtilityCall::AsDerivative(a)).encode();
+				encoded.append(&mut b);
+				encoded
+			}
+		}
+	}
+}
+
+impl ProxyEncodeCall for TestEncoder {
+	fn proxy_encode_call(call: AvailableProxyCalls) -> Vec<u8> {
+		match call {
+			AvailableProxyCalls::AddProxy(a, b, c) => {
+				RelayCall::Proxy(ProxyCall::AddProxy(a, b, c)).encode()
+			}
+
+			AvailableProxyCalls::Proxy(a, b, mut c) => {
+				let mut encoded = RelayCall::Proxy(ProxyCall::Proxy(a, b)).encode();
+				encoded.append(&mut c);
+				encoded
+			}
+		}
+	}
+}
+
+impl NominationPoolsEncodeCall for TestEncoder {
+	fn nomination_pools_encode_call(call: AvailableNominationPoolsCalls) -> Option<Vec<u8>> {
+		Some(match call {
+			AvailableNominationPoolsCalls::Join(a, b) => {
+				RelayCall::NominationPools(NominationPoolsCall::Join(a, b)).encode()
+			}
+
+			AvailableNominationPoolsCalls::BondExtraFreeBalance(a) => RelayCall::NominationPools(
+				NominationPoolsCall::BondExtra(PoolBondExtra::FreeBalance(a)),
+			)
+			.encode(),
+
+			AvailableNominationPoolsCalls::BondExtraRewards => {
+				RelayCall::NominationPools(NominationPoolsCall::BondExtra(PoolBondExtra::Rewards))
+					.encode()
+			}
+
+			AvailableNominationPoolsCalls::Unbond(a, b) => {
+				RelayCall::NominationPools(NominationPoolsCall::Unbond(a, b)).encode()
+			}
+
+			AvailableNominationPoolsCalls::ClaimPayout => {
+				RelayCall::NominationPools(NominationPoolsCall::ClaimPayout).encode()
+			}
+		})
+	}
+}
+
+impl xcm_primitives::HrmpEncodeCall for TestEncoder {
+	fn hrmp_encode_call(call: xcm_primitives::HrmpAvailableCalls) -> Vec<u8> {
+		match call {
+			xcm_primitives::HrmpAvailableCalls::InitOpenChannel(a, b, c) => {
+				RelayCall::Hrmp(HrmpCall::InitOpenChannel(a, b, c)).encode()
+			}
+			xcm_primitives::HrmpAvailableCalls::AcceptOpenChannel(a) => {
+				RelayCall::Hrmp(HrmpCall::AcceptOpenChannel(a)).encode()
+			}
+			xcm_primitives::HrmpAvailableCalls::CloseChannel(a) => {
+				RelayCall::Hrmp(HrmpCall::CloseChannel(a)).encode()
+			}
 		}
 	}
 }
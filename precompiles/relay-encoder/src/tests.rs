@@ -21,11 +21,13 @@ use crate::test_relay_runtime::TestEncoder;
 use crate::AvailableStakeCalls;
 use crate::StakeEncodeCall;
 use crate::*;
+use cumulus_primitives_core::relay_chain::HrmpChannelId;
 use pallet_staking::RewardDestination;
 use pallet_staking::ValidatorPrefs;
 use precompile_utils::{solidity, testing::*};
 use sp_core::{H256, U256};
 use sp_runtime::Perbill;
+use xcm_primitives::{HrmpAvailableCalls, HrmpEncodeCall};
 
 fn precompiles() -> TestPrecompiles<Runtime> {
 	PrecompilesValue::get()
@@ -43,6 +45,20 @@ fn test_selector_enum() {
 	assert_eq!(Action::EncodeSetPayee as u32, 0x414be337);
 	assert_eq!(Action::EncodeSetController as u32, 0x07f7c6dc);
 	assert_eq!(Action::EncodeRebond as u32, 0x0922ee17);
+	assert_eq!(Action::EncodePayoutStakers as u32, 0x53bc11d2);
+	assert_eq!(Action::EncodeBatchAll as u32, 0x78477d1a);
+	assert_eq!(Action::EncodeAsDerivative as u32, 0x38653f14);
+	assert_eq!(Action::EncodeAddProxy as u32, 0x5400bca7);
+	assert_eq!(Action::EncodeProxy as u32, 0xe450c2f0);
+	assert_eq!(Action::EncodeProxyWithType as u32, 0xf9984872);
+	assert_eq!(Action::EncodePoolJoin as u32, 0x3c5dd983);
+	assert_eq!(Action::EncodePoolBondExtra as u32, 0xa12b5f1e);
+	assert_eq!(Action::EncodePoolBondExtraRewards as u32, 0xd5930060);
+	assert_eq!(Action::EncodePoolUnbond as u32, 0xe860dd70);
+	assert_eq!(Action::EncodePoolClaimPayout as u32, 0xd6876ceb);
+	assert_eq!(Action::EncodeHrmpInitOpenChannel as u32, 0xe5e20a64);
+	assert_eq!(Action::EncodeHrmpAcceptOpenChannel as u32, 0x98a76477);
+	assert_eq!(Action::EncodeHrmpCloseChannel as u32, 0x9cfbdfc5);
 }
 
 #[test]
@@ -351,6 +367,438 @@ fn test_encode_withdraw_unbonded() {
 		});
 }
 
+#[test]
+fn test_encode_payout_stakers() {
+	ExtBuilder::default()
+		.with_balances(vec![(Alice, 1000)])
+		.build()
+		.execute_with(|| {
+			precompiles()
+				.prepare_test(
+					Alice,
+					Precompile,
+					EvmDataWriter::new_with_selector(Action::EncodePayoutStakers)
+						.write(H256::from([1u8; 32]))
+						.write(10u32)
+						.build(),
+				)
+				.expect_cost(0) // TODO: Test db read/write costs
+				.expect_no_logs()
+				.execute_returns(
+					EvmDataWriter::new()
+						.write(Bytes::from(
+							TestEncoder::encode_call(AvailableStakeCalls::PayoutStakers(
+								[1u8; 32].into(),
+								10u32,
+							))
+							.as_slice(),
+						))
+						.build(),
+				);
+		});
+}
+
+#[test]
+fn test_encode_batch_all() {
+	ExtBuilder::default()
+		.with_balances(vec![(Alice, 1000)])
+		.build()
+		.execute_with(|| {
+			precompiles()
+				.prepare_test(
+					Alice,
+					Precompile,
+					EvmDataWriter::new_with_selector(Action::EncodeBatchAll)
+						.write(vec![Bytes::from(&[1u8, 2u8][..]), Bytes::from(&[3u8][..])])
+						.build(),
+				)
+				.expect_cost(0) // TODO: Test db read/write costs
+				.expect_no_logs()
+				.execute_returns(
+					EvmDataWriter::new()
+						.write(Bytes::from(
+							TestEncoder::utility_encode_call(AvailableUtilityCalls::BatchAll(
+								vec![vec![1u8, 2u8], vec![3u8]],
+							))
+							.as_slice(),
+						))
+						.build(),
+				);
+		});
+}
+
+#[test]
+fn test_encode_as_derivative() {
+	ExtBuilder::default()
+		.with_balances(vec![(Alice, 1000)])
+		.build()
+		.execute_with(|| {
+			precompiles()
+				.prepare_test(
+					Alice,
+					Precompile,
+					EvmDataWriter::new_with_selector(Action::EncodeAsDerivative)
+						.write(1u16)
+						.write(Bytes::from(&[1u8, 2u8][..]))
+						.build(),
+				)
+				.expect_cost(0) // TODO: Test db read/write costs
+				.expect_no_logs()
+				.execute_returns(
+					EvmDataWriter::new()
+						.write(Bytes::from(
+							TestEncoder::utility_encode_call(AvailableUtilityCalls::AsDerivative(
+								1u16,
+								vec![1u8, 2u8],
+							))
+							.as_slice(),
+						))
+						.build(),
+				);
+		});
+}
+
+#[test]
+fn test_encode_add_proxy() {
+	ExtBuilder::default()
+		.with_balances(vec![(Alice, 1000)])
+		.build()
+		.execute_with(|| {
+			precompiles()
+				.prepare_test(
+					Alice,
+					Precompile,
+					EvmDataWriter::new_with_selector(Action::EncodeAddProxy)
+						.write(H256::from([1u8; 32]))
+						.write(3u8)
+						.write(0u32)
+						.build(),
+				)
+				.expect_cost(0) // TODO: Test db read/write costs
+				.expect_no_logs()
+				.execute_returns(
+					EvmDataWriter::new()
+						.write(Bytes::from(
+							TestEncoder::proxy_encode_call(AvailableProxyCalls::AddProxy(
+								[1u8; 32].into(),
+								3u8,
+								0u32,
+							))
+							.as_slice(),
+						))
+						.build(),
+				);
+		});
+}
+
+#[test]
+fn test_encode_proxy() {
+	ExtBuilder::default()
+		.with_balances(vec![(Alice, 1000)])
+		.build()
+		.execute_with(|| {
+			precompiles()
+				.prepare_test(
+					Alice,
+					Precompile,
+					EvmDataWriter::new_with_selector(Action::EncodeProxy)
+						.write(H256::from([1u8; 32]))
+						.write(Bytes::from(&[1u8, 2u8][..]))
+						.build(),
+				)
+				.expect_cost(0) // TODO: Test db read/write costs
+				.expect_no_logs()
+				.execute_returns(
+					EvmDataWriter::new()
+						.write(Bytes::from(
+							TestEncoder::proxy_encode_call(AvailableProxyCalls::Proxy(
+								[1u8; 32].into(),
+								None,
+								vec![1u8, 2u8],
+							))
+							.as_slice(),
+						))
+						.build(),
+				);
+		});
+}
+
+#[test]
+fn test_encode_proxy_with_type() {
+	ExtBuilder::default()
+		.with_balances(vec![(Alice, 1000)])
+		.build()
+		.execute_with(|| {
+			precompiles()
+				.prepare_test(
+					Alice,
+					Precompile,
+					EvmDataWriter::new_with_selector(Action::EncodeProxyWithType)
+						.write(H256::from([1u8; 32]))
+						.write(3u8)
+						.write(Bytes::from(&[1u8, 2u8][..]))
+						.build(),
+				)
+				.expect_cost(0) // TODO: Test db read/write costs
+				.expect_no_logs()
+				.execute_returns(
+					EvmDataWriter::new()
+						.write(Bytes::from(
+							TestEncoder::proxy_encode_call(AvailableProxyCalls::Proxy(
+								[1u8; 32].into(),
+								Some(3u8),
+								vec![1u8, 2u8],
+							))
+							.as_slice(),
+						))
+						.build(),
+				);
+		});
+}
+
+#[test]
+fn test_encode_pool_join() {
+	ExtBuilder::default()
+		.with_balances(vec![(Alice, 1000)])
+		.build()
+		.execute_with(|| {
+			precompiles()
+				.prepare_test(
+					Alice,
+					Precompile,
+					EvmDataWriter::new_with_selector(Action::EncodePoolJoin)
+						.write(U256::from(100))
+						.write(1u32)
+						.build(),
+				)
+				.expect_cost(0) // TODO: Test db read/write costs
+				.expect_no_logs()
+				.execute_returns(
+					EvmDataWriter::new()
+						.write(Bytes::from(
+							TestEncoder::nomination_pools_encode_call(
+								AvailableNominationPoolsCalls::Join(100u128, 1u32),
+							)
+							.unwrap()
+							.as_slice(),
+						))
+						.build(),
+				);
+		});
+}
+
+#[test]
+fn test_encode_pool_bond_extra() {
+	ExtBuilder::default()
+		.with_balances(vec![(Alice, 1000)])
+		.build()
+		.execute_with(|| {
+			precompiles()
+				.prepare_test(
+					Alice,
+					Precompile,
+					EvmDataWriter::new_with_selector(Action::EncodePoolBondExtra)
+						.write(U256::from(100))
+						.build(),
+				)
+				.expect_cost(0) // TODO: Test db read/write costs
+				.expect_no_logs()
+				.execute_returns(
+					EvmDataWriter::new()
+						.write(Bytes::from(
+							TestEncoder::nomination_pools_encode_call(
+								AvailableNominationPoolsCalls::BondExtraFreeBalance(100u128),
+							)
+							.unwrap()
+							.as_slice(),
+						))
+						.build(),
+				);
+		});
+}
+
+#[test]
+fn test_encode_pool_bond_extra_rewards() {
+	ExtBuilder::default()
+		.with_balances(vec![(Alice, 1000)])
+		.build()
+		.execute_with(|| {
+			precompiles()
+				.prepare_test(
+					Alice,
+					Precompile,
+					EvmDataWriter::new_with_selector(Action::EncodePoolBondExtraRewards).build(),
+				)
+				.expect_cost(0) // TODO: Test db read/write costs
+				.expect_no_logs()
+				.execute_returns(
+					EvmDataWriter::new()
+						.write(Bytes::from(
+							TestEncoder::nomination_pools_encode_call(
+								AvailableNominationPoolsCalls::BondExtraRewards,
+							)
+							.unwrap()
+							.as_slice(),
+						))
+						.build(),
+				);
+		});
+}
+
+#[test]
+fn test_encode_pool_unbond() {
+	ExtBuilder::default()
+		.with_balances(vec![(Alice, 1000)])
+		.build()
+		.execute_with(|| {
+			precompiles()
+				.prepare_test(
+					Alice,
+					Precompile,
+					EvmDataWriter::new_with_selector(Action::EncodePoolUnbond)
+						.write(H256::from([1u8; 32]))
+						.write(U256::from(100))
+						.build(),
+				)
+				.expect_cost(0) // TODO: Test db read/write costs
+				.expect_no_logs()
+				.execute_returns(
+					EvmDataWriter::new()
+						.write(Bytes::from(
+							TestEncoder::nomination_pools_encode_call(
+								AvailableNominationPoolsCalls::Unbond([1u8; 32].into(), 100u128),
+							)
+							.unwrap()
+							.as_slice(),
+						))
+						.build(),
+				);
+		});
+}
+
+#[test]
+fn test_encode_pool_claim_payout() {
+	ExtBuilder::default()
+		.with_balances(vec![(Alice, 1000)])
+		.build()
+		.execute_with(|| {
+			precompiles()
+				.prepare_test(
+					Alice,
+					Precompile,
+					EvmDataWriter::new_with_selector(Action::EncodePoolClaimPayout).build(),
+				)
+				.expect_cost(0) // TODO: Test db read/write costs
+				.expect_no_logs()
+				.execute_returns(
+					EvmDataWriter::new()
+						.write(Bytes::from(
+							TestEncoder::nomination_pools_encode_call(
+								AvailableNominationPoolsCalls::ClaimPayout,
+							)
+							.unwrap()
+							.as_slice(),
+						))
+						.build(),
+				);
+		});
+}
+
+#[test]
+fn test_encode_hrmp_init_open_channel() {
+	ExtBuilder::default()
+		.with_balances(vec![(Alice, 1000)])
+		.build()
+		.execute_with(|| {
+			precompiles()
+				.prepare_test(
+					Alice,
+					Precompile,
+					EvmDataWriter::new_with_selector(Action::EncodeHrmpInitOpenChannel)
+						.write(2000u32)
+						.write(8u32)
+						.write(1024u32)
+						.build(),
+				)
+				.expect_cost(0) // TODO: Test db read/write costs
+				.expect_no_logs()
+				.execute_returns(
+					EvmDataWriter::new()
+						.write(Bytes::from(
+							TestEncoder::hrmp_encode_call(HrmpAvailableCalls::InitOpenChannel(
+								2000u32.into(),
+								8u32,
+								1024u32,
+							))
+							.as_slice(),
+						))
+						.build(),
+				);
+		});
+}
+
+#[test]
+fn test_encode_hrmp_accept_open_channel() {
+	ExtBuilder::default()
+		.with_balances(vec![(Alice, 1000)])
+		.build()
+		.execute_with(|| {
+			precompiles()
+				.prepare_test(
+					Alice,
+					Precompile,
+					EvmDataWriter::new_with_selector(Action::EncodeHrmpAcceptOpenChannel)
+						.write(2000u32)
+						.build(),
+				)
+				.expect_cost(0) // TODO: Test db read/write costs
+				.expect_no_logs()
+				.execute_returns(
+					EvmDataWriter::new()
+						.write(Bytes::from(
+							TestEncoder::hrmp_encode_call(HrmpAvailableCalls::AcceptOpenChannel(
+								2000u32.into(),
+							))
+							.as_slice(),
+						))
+						.build(),
+				);
+		});
+}
+
+#[test]
+fn test_encode_hrmp_close_channel() {
+	ExtBuilder::default()
+		.with_balances(vec![(Alice, 1000)])
+		.build()
+		.execute_with(|| {
+			precompiles()
+				.prepare_test(
+					Alice,
+					Precompile,
+					EvmDataWriter::new_with_selector(Action::EncodeHrmpCloseChannel)
+						.write(2000u32)
+						.write(1000u32)
+						.build(),
+				)
+				.expect_cost(0) // TODO: Test db read/write costs
+				.expect_no_logs()
+				.execute_returns(
+					EvmDataWriter::new()
+						.write(Bytes::from(
+							TestEncoder::hrmp_encode_call(HrmpAvailableCalls::CloseChannel(
+								HrmpChannelId {
+									sender: 2000u32.into(),
+									recipient: 1000u32.into(),
+								},
+							))
+							.as_slice(),
+						))
+						.build(),
+				);
+		});
+}
+
 #[test]
 fn test_solidity_interface_has_all_function_selectors_documented_and_implemented() {
 	for file in ["RelayEncoder.sol"] {
@@ -378,18 +826,7 @@ fn test_solidity_interface_has_all_function_selectors_documented_and_implemented
 
 #[test]
 fn test_deprecated_solidity_selectors_are_supported() {
-	for deprecated_function in [
-		"encode_bond(uint256,uint256,bytes)",
-		"encode_bond_extra(uint256)",
-		"encode_unbond(uint256)",
-		"encode_withdraw_unbonded(uint32)",
-		"encode_validate(uint256,bool)",
-		"encode_nominate(uint256[])",
-		"encode_chill()",
-		"encode_set_payee(bytes)",
-		"encode_set_controller(uint256)",
-		"encode_rebond(uint256)",
-	] {
+	for deprecated_function in DEPRECATED_FUNCTIONS {
 		let selector = solidity::compute_selector(deprecated_function);
 		if Action::try_from(selector).is_err() {
 			panic!(
@@ -399,3 +836,139 @@ fn test_deprecated_solidity_selectors_are_supported() {
 		}
 	}
 }
+
+/// Deprecated functions still supported by the precompile, but not declared in the
+/// solidity file.
+const DEPRECATED_FUNCTIONS: &[&str] = &[
+	"encode_bond(uint256,uint256,bytes)",
+	"encode_bond_extra(uint256)",
+	"encode_unbond(uint256)",
+	"encode_withdraw_unbonded(uint32)",
+	"encode_validate(uint256,bool)",
+	"encode_nominate(uint256[])",
+	"encode_chill()",
+	"encode_set_payee(bytes)",
+	"encode_set_controller(uint256)",
+	"encode_rebond(uint256)",
+];
+
+#[test]
+fn test_solidity_interface_matches_rust_definitions() {
+	let function = |signature, args: &[(&str, String)]| {
+		solidity::FunctionDeclaration::from_signature(
+			signature,
+			args,
+			FunctionModifier::View,
+			&[Bytes::solidity_type()],
+		)
+	};
+	// Relay accounts are read as bytes32 and commissions as uint32, but the ABI declares them
+	// as uint256.
+	let uint = |name| (name, U256::solidity_type());
+	let call = ("call", BoundedBytes::<GetCallDataLimit>::solidity_type());
+
+	let functions = vec![
+		function(
+			"encodeBond(uint256,uint256,bytes)",
+			&[
+				uint("controllerAddress"),
+				uint("amount"),
+				(
+					"rewardDestination",
+					RewardDestinationWrapper::solidity_type(),
+				),
+			],
+		),
+		function("encodeBondExtra(uint256)", &[uint("amount")]),
+		function("encodeUnbond(uint256)", &[uint("amount")]),
+		function(
+			"encodeWithdrawUnbonded(uint32)",
+			&[("slashes", u32::solidity_type())],
+		),
+		function(
+			"encodeValidate(uint256,bool)",
+			&[uint("comission"), ("blocked", bool::solidity_type())],
+		),
+		function(
+			"encodeNominate(uint256[])",
+			&[("nominees", Vec::<U256>::solidity_type())],
+		),
+		function("encodeChill()", &[]),
+		function(
+			"encodeSetPayee(bytes)",
+			&[(
+				"rewardDestination",
+				RewardDestinationWrapper::solidity_type(),
+			)],
+		),
+		function("encodeSetController(uint256)", &[uint("controller")]),
+		function("encodeRebond(uint256)", &[uint("amount")]),
+		function(
+			"encodePayoutStakers(uint256,uint32)",
+			&[uint("validatorStash"), ("era", u32::solidity_type())],
+		),
+		function(
+			"encodeBatchAll(bytes[])",
+			&[(
+				"calls",
+				BoundedVec::<BoundedBytes<GetCallDataLimit>, GetArrayLimit>::solidity_type(),
+			)],
+		),
+		function(
+			"encodeAsDerivative(uint16,bytes)",
+			&[("index", u16::solidity_type()), call.clone()],
+		),
+		function(
+			"encodeAddProxy(uint256,uint8,uint32)",
+			&[
+				uint("delegate"),
+				("proxyType", u8::solidity_type()),
+				("delay", u32::solidity_type()),
+			],
+		),
+		function("encodeProxy(uint256,bytes)", &[uint("real"), call.clone()]),
+		function(
+			"encodeProxyWithType(uint256,uint8,bytes)",
+			&[uint("real"), ("forceProxyType", u8::solidity_type()), call],
+		),
+		function(
+			"encodePoolJoin(uint256,uint32)",
+			&[uint("amount"), ("poolId", u32::solidity_type())],
+		),
+		function("encodePoolBondExtra(uint256)", &[uint("amount")]),
+		function("encodePoolBondExtraRewards()", &[]),
+		function(
+			"encodePoolUnbond(uint256,uint256)",
+			&[uint("member"), uint("unbondingPoints")],
+		),
+		function("encodePoolClaimPayout()", &[]),
+		function(
+			"encodeHrmpInitOpenChannel(uint32,uint32,uint32)",
+			&[
+				("recipient", u32::solidity_type()),
+				("maxCapacity", u32::solidity_type()),
+				("maxMessageSize", u32::solidity_type()),
+			],
+		),
+		function(
+			"encodeHrmpAcceptOpenChannel(uint32)",
+			&[("sender", u32::solidity_type())],
+		),
+		function(
+			"encodeHrmpCloseChannel(uint32,uint32)",
+			&[
+				("sender", u32::solidity_type()),
+				("recipient", u32::solidity_type()),
+			],
+		),
+	];
+
+	solidity::SolidityInterface::from_declarations(
+		functions,
+		Action::signatures()
+			.iter()
+			.map(|(_, signature)| *signature)
+			.filter(|signature| !DEPRECATED_FUNCTIONS.contains(signature)),
+	)
+	.assert_matches_file("RelayEncoder.sol", "RelayEncoder");
+}
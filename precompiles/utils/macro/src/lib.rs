@@ -22,6 +22,8 @@ use syn::{
 	parse_macro_input, spanned::Spanned, Attribute, Expr, ExprLit, Ident, ItemEnum, Lit, LitStr,
 };
 
+mod precompile;
+
 struct Bytes(Vec<u8>);
 
 impl ::std::fmt::Debug for Bytes {
@@ -78,6 +80,12 @@ pub fn keccak256(input: TokenStream) -> TokenStream {
 /// 	Toto = 119097542u32,
 /// 	Tata = 1414311903u32,
 /// }
+///
+/// impl Action {
+/// 	pub fn signatures() -> &'static [(u32, &'static str)] {
+/// 		&[(119097542u32, "toto()"), (1414311903u32, "tata()")]
+/// 	}
+/// }
 /// ```
 ///
 #[proc_macro_attribute]
@@ -96,6 +104,7 @@ pub fn generate_function_selector(_: TokenStream, input: TokenStream) -> TokenSt
 	let mut ident_expressions: Vec<Ident> = vec![];
 	let mut variant_expressions: Vec<Expr> = vec![];
 	let mut variant_attrs: Vec<Vec<Attribute>> = vec![];
+	let mut signatures: Vec<(u32, LitStr)> = vec![];
 	for variant in variants {
 		match variant.discriminant {
 			Some((_, Expr::Lit(ExprLit { lit, .. }))) => {
@@ -108,6 +117,7 @@ pub fn generate_function_selector(_: TokenStream, input: TokenStream) -> TokenSt
 						attrs: Default::default(),
 					}));
 					variant_attrs.push(variant.attrs);
+					signatures.push((selector, lit_str));
 				} else {
 					return quote_spanned! {
 						lit.span() => compile_error("Expected literal string");
@@ -130,6 +140,10 @@ pub fn generate_function_selector(_: TokenStream, input: TokenStream) -> TokenSt
 		}
 	}
 
+	let signatures = signatures
+		.iter()
+		.map(|(selector, signature)| quote!((#selector, #signature)));
+
 	(quote! {
 		#(#attrs)*
 		#[derive(num_enum::TryFromPrimitive, num_enum::IntoPrimitive)]
@@ -140,6 +154,60 @@ pub fn generate_function_selector(_: TokenStream, input: TokenStream) -> TokenSt
 				#ident_expressions = #variant_expressions,
 			)*
 		}
+
+		impl #ident {
+			/// Solidity signature of each variant, along with its selector.
+			#[allow(dead_code)]
+			#vis fn signatures() -> &'static [(u32, &'static str)] {
+				&[#(#signatures),*]
+			}
+		}
 	})
 	.into()
 }
+
+/// Generates the dispatch logic of a precompile from an `impl` block of the precompile type.
+/// Each public method declares its Solidity signature and modifier, and the macro generates
+/// the selector matching, arguments parsing, modifier checks and output encoding.
+///
+/// Usage:
+///
+/// ```ignore
+/// #[precompile_utils::precompile]
+/// impl<Runtime> ExamplePrecompile<Runtime> {
+/// 	#[precompile::pre_check]
+/// 	fn pre_check(handle: &mut impl PrecompileHandle) -> EvmResult {
+/// 		// Called before any public or fallback method.
+/// 		Ok(())
+/// 	}
+///
+/// 	#[precompile::public("balanceOf(address)")]
+/// 	#[precompile::view]
+/// 	fn balance_of(handle: &mut impl PrecompileHandle, owner: Address) -> EvmResult<U256> {
+/// 		// ...
+/// 	}
+///
+/// 	#[precompile::public("deposit()")]
+/// 	#[precompile::public("depositAlias()")]
+/// 	#[precompile::payable]
+/// 	#[precompile::emits("Deposit(address,uint256)")]
+/// 	fn deposit(handle: &mut impl PrecompileHandle) -> EvmResult {
+/// 		// ...
+/// 	}
+///
+/// 	#[precompile::fallback]
+/// 	#[precompile::payable]
+/// 	fn fallback(handle: &mut impl PrecompileHandle) -> EvmResult {
+/// 		// Called when the selector is unknown or the input is too short.
+/// 	}
+/// }
+/// ```
+///
+/// Generates an enum `ExamplePrecompileCall<Runtime>` with variants `balance_of { owner }`,
+/// `deposit {}` and `fallback {}`, which can be parsed from a handle, executed, and encoded
+/// into call data (`Vec<u8>: From<ExamplePrecompileCall<Runtime>>`), and implements
+/// `fp_evm::Precompile` for `ExamplePrecompile<Runtime>`.
+#[proc_macro_attribute]
+pub fn precompile(attr: TokenStream, input: TokenStream) -> TokenStream {
+	precompile::main(attr, input)
+}
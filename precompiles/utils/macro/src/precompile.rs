@@ -0,0 +1,1165 @@
+// Copyright 2019-2022 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Implementation of the `#[precompile]` attribute macro.
+//!
+//! The macro is applied on an `impl` block of a precompile type. Methods of this block can be
+//! annotated with the following attributes:
+//! - `#[precompile::public("fnName(type1,type2)")]`: the method is callable with the selector
+//!   of the provided Solidity signature. Can be used multiple times to provide aliases.
+//! - `#[precompile::view]` / `#[precompile::payable]`: modifier of the function. Functions
+//!   without any of those attributes are non-payable.
+//! - `#[precompile::fallback]`: method called when the selector is unknown or the input is
+//!   too short to contain one.
+//! - `#[precompile::pre_check]`: method called before any public method or fallback.
+//! - `#[precompile::emits("EventName(type1,type2)")]`: event the public or fallback method can
+//!   emit, included in the Solidity interface. Can be used multiple times.
+//!
+//! It generates an enum `{Precompile}Call` with one variant per method, containing its parsed
+//! arguments, and implements `fp_evm::Precompile` for the precompile type. In tests, the
+//! Solidity interface built from the Rust types is available with `solidity_interface()`.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use sha3::{Digest, Keccak256};
+use std::collections::BTreeMap;
+use syn::{
+	parse_macro_input, spanned::Spanned, Attribute, FnArg, GenericArgument, GenericParam, Ident,
+	ImplItem, ImplItemMethod, ItemImpl, LitStr, Pat, PathArguments, ReturnType, Type,
+};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Modifier {
+	NonPayable,
+	View,
+	Payable,
+}
+
+enum Kind {
+	Public { signatures: Vec<LitStr> },
+	Fallback,
+}
+
+struct Method {
+	ident: Ident,
+	kind: Kind,
+	modifier: Modifier,
+	args: Vec<(Ident, Type)>,
+	/// Type `T` of the returned `EvmResult<T>`.
+	output: Type,
+}
+
+struct Precompile {
+	methods: Vec<Method>,
+	pre_check: Option<Ident>,
+	/// Signatures of the events emitted by the methods, in declaration order.
+	events: Vec<String>,
+	/// Selector to (method index, signature).
+	selectors: BTreeMap<u32, (usize, String)>,
+}
+
+/// Entry point of the `#[precompile]` attribute macro.
+pub fn main(_attr: TokenStream, item: TokenStream) -> TokenStream {
+	let mut item = parse_macro_input!(item as ItemImpl);
+
+	match Precompile::parse(&mut item) {
+		Ok(precompile) => precompile.expand(&item).into(),
+		Err(err) => {
+			let err = err.to_compile_error();
+			quote!(#item #err).into()
+		}
+	}
+}
+
+/// Computes the selector of a Solidity signature.
+fn selector(signature: &str) -> u32 {
+	let digest = Keccak256::digest(signature.as_ref());
+	u32::from_be_bytes([digest[0], digest[1], digest[2], digest[3]])
+}
+
+/// Converts a snake_case Rust identifier into a camelCase Solidity one.
+fn camel_case(ident: &str) -> String {
+	let mut output = String::with_capacity(ident.len());
+	let mut upper = false;
+
+	for c in ident.trim_start_matches('_').chars() {
+		if c == '_' {
+			upper = true;
+		} else if upper {
+			output.extend(c.to_uppercase());
+			upper = false;
+		} else {
+			output.push(c);
+		}
+	}
+
+	output
+}
+
+/// Counts the top-level arguments of a Solidity signature `name(type1,(type2,type3),...)`.
+fn signature_arguments_count(signature: &LitStr) -> syn::Result<usize> {
+	let value = signature.value();
+	let err = || {
+		syn::Error::new(
+			signature.span(),
+			"Expected signature `fnName(type1,type2,...)`",
+		)
+	};
+
+	let open = value.find('(').ok_or_else(err)?;
+	if open == 0 || !value.ends_with(')') || value.contains(' ') {
+		return Err(err());
+	}
+
+	let args = &value[open + 1..value.len() - 1];
+	if args.is_empty() {
+		return Ok(0);
+	}
+
+	let mut depth = 0usize;
+	let mut count = 1;
+	for c in args.chars() {
+		match c {
+			'(' => depth += 1,
+			')' => depth = depth.checked_sub(1).ok_or_else(err)?,
+			',' if depth == 0 => count += 1,
+			_ => (),
+		}
+	}
+
+	if depth != 0 {
+		return Err(err());
+	}
+
+	Ok(count)
+}
+
+/// Extracts `T` from a return type `EvmResult<T>`, `EvmResult` alone being `EvmResult<()>`.
+fn evm_result_type(output: &ReturnType) -> syn::Result<Type> {
+	let err = |span| syn::Error::new(span, "Expected return type `EvmResult<T>`");
+
+	let path = match output {
+		ReturnType::Type(_, ty) => match &**ty {
+			Type::Path(path) => path,
+			ty => return Err(err(ty.span())),
+		},
+		ReturnType::Default => return Err(err(output.span())),
+	};
+
+	let segment = path.path.segments.last().ok_or_else(|| err(path.span()))?;
+	if segment.ident != "EvmResult" {
+		return Err(err(segment.span()));
+	}
+
+	match &segment.arguments {
+		PathArguments::None => Ok(syn::parse_quote!(())),
+		PathArguments::AngleBracketed(args) => match args.args.first() {
+			Some(GenericArgument::Type(ty)) if args.args.len() == 1 => Ok(ty.clone()),
+			_ => Err(err(args.span())),
+		},
+		PathArguments::Parenthesized(args) => Err(err(args.span())),
+	}
+}
+
+/// Returns the name of the attribute if it is in the `precompile::` namespace.
+fn precompile_attribute(attr: &Attribute) -> Option<String> {
+	let segments: Vec<_> = attr.path.segments.iter().collect();
+	match segments.as_slice() {
+		[namespace, name] if namespace.ident == "precompile" => Some(name.ident.to_string()),
+		_ => None,
+	}
+}
+
+impl Precompile {
+	fn parse(item: &mut ItemImpl) -> syn::Result<Self> {
+		let mut precompile = Precompile {
+			methods: vec![],
+			pre_check: None,
+			events: vec![],
+			selectors: BTreeMap::new(),
+		};
+
+		for impl_item in &mut item.items {
+			if let ImplItem::Method(method) = impl_item {
+				precompile.parse_method(method)?;
+			}
+		}
+
+		if precompile.methods.is_empty() {
+			return Err(syn::Error::new(
+				item.span(),
+				"A precompile must have at least one public or fallback method",
+			));
+		}
+
+		Ok(precompile)
+	}
+
+	fn parse_method(&mut self, method: &mut ImplItemMethod) -> syn::Result<()> {
+		let mut signatures = vec![];
+		let mut modifier = None;
+		let mut fallback = false;
+		let mut pre_check = false;
+		let mut events = vec![];
+
+		let mut attrs = vec![];
+		for attr in method.attrs.drain(..) {
+			let name = match precompile_attribute(&attr) {
+				Some(name) => name,
+				None => {
+					attrs.push(attr);
+					continue;
+				}
+			};
+
+			match name.as_str() {
+				"public" => signatures.push(attr.parse_args::<LitStr>()?),
+				"view" | "payable" => {
+					if modifier.is_some() {
+						return Err(syn::Error::new(
+							attr.span(),
+							"A method can only have one modifier",
+						));
+					}
+					modifier = Some(if name == "view" {
+						Modifier::View
+					} else {
+						Modifier::Payable
+					});
+				}
+				"fallback" => fallback = true,
+				"pre_check" => pre_check = true,
+				"emits" => {
+					let event = attr.parse_args::<LitStr>()?;
+					signature_arguments_count(&event)?;
+					events.push(event);
+				}
+				_ => {
+					return Err(syn::Error::new(
+						attr.span(),
+						"Unknown precompile attribute, expected one of `public`, `view`, \
+						`payable`, `fallback`, `pre_check` or `emits`",
+					))
+				}
+			}
+		}
+		method.attrs = attrs;
+
+		if (signatures.is_empty() && !fallback) || pre_check {
+			if let Some(event) = events.first() {
+				return Err(syn::Error::new(
+					event.span(),
+					"Events can only be declared on public or fallback methods",
+				));
+			}
+		}
+
+		if signatures.is_empty() && !fallback && !pre_check {
+			if modifier.is_some() {
+				return Err(syn::Error::new(
+					method.sig.ident.span(),
+					"Modifiers can only be used on public or fallback methods",
+				));
+			}
+			return Ok(());
+		}
+
+		if [!signatures.is_empty(), fallback, pre_check]
+			.iter()
+			.filter(|x| **x)
+			.count() > 1
+		{
+			return Err(syn::Error::new(
+				method.sig.ident.span(),
+				"A method can only be one of public, fallback or pre_check",
+			));
+		}
+
+		let mut inputs = method.sig.inputs.iter();
+
+		match inputs.next() {
+			Some(FnArg::Typed(_)) => (),
+			_ => {
+				return Err(syn::Error::new(
+					method.sig.span(),
+					"Expected a first argument `handle: &mut impl PrecompileHandle`",
+				))
+			}
+		}
+
+		let mut args = vec![];
+		for input in inputs {
+			match input {
+				FnArg::Typed(typed) => match &*typed.pat {
+					Pat::Ident(pat) => args.push((pat.ident.clone(), (*typed.ty).clone())),
+					_ => {
+						return Err(syn::Error::new(
+							typed.pat.span(),
+							"Expected an identifier pattern",
+						))
+					}
+				},
+				FnArg::Receiver(receiver) => {
+					return Err(syn::Error::new(
+						receiver.span(),
+						"Precompile methods cannot take `self`",
+					))
+				}
+			}
+		}
+
+		if pre_check {
+			if modifier.is_some() || !args.is_empty() {
+				return Err(syn::Error::new(
+					method.sig.ident.span(),
+					"The pre_check method only takes the handle and have no modifier",
+				));
+			}
+			if self.pre_check.is_some() {
+				return Err(syn::Error::new(
+					method.sig.ident.span(),
+					"Only one pre_check method is allowed",
+				));
+			}
+			self.pre_check = Some(method.sig.ident.clone());
+			return Ok(());
+		}
+
+		let index = self.methods.len();
+
+		let kind = if fallback {
+			if !args.is_empty() {
+				return Err(syn::Error::new(
+					method.sig.ident.span(),
+					"The fallback method only takes the handle",
+				));
+			}
+			if self
+				.methods
+				.iter()
+				.any(|m| matches!(m.kind, Kind::Fallback))
+			{
+				return Err(syn::Error::new(
+					method.sig.ident.span(),
+					"Only one fallback method is allowed",
+				));
+			}
+			Kind::Fallback
+		} else {
+			for signature in &signatures {
+				let count = signature_arguments_count(signature)?;
+				if count != args.len() {
+					return Err(syn::Error::new(
+						signature.span(),
+						format!(
+							"Signature has {} arguments while the method takes {}",
+							count,
+							args.len()
+						),
+					));
+				}
+
+				let value = signature.value();
+				let selector = selector(&value);
+				if let Some((_, other)) = self.selectors.insert(selector, (index, value.clone())) {
+					return Err(syn::Error::new(
+						signature.span(),
+						format!(
+							"Selector of `{}` collides with the one of `{}`",
+							value, other
+						),
+					));
+				}
+			}
+			Kind::Public { signatures }
+		};
+
+		for event in events {
+			let event = event.value();
+			if !self.events.contains(&event) {
+				self.events.push(event);
+			}
+		}
+
+		self.methods.push(Method {
+			ident: method.sig.ident.clone(),
+			kind,
+			modifier: modifier.unwrap_or(Modifier::NonPayable),
+			args,
+			output: evm_result_type(&method.sig.output)?,
+		});
+
+		Ok(())
+	}
+
+	fn expand(&self, item: &ItemImpl) -> TokenStream2 {
+		let self_ty = &item.self_ty;
+		let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+
+		let precompile_ident = match &**self_ty {
+			Type::Path(path) => path
+				.path
+				.segments
+				.last()
+				.map(|s| s.ident.clone())
+				.unwrap_or_else(|| Ident::new("Precompile", Span::call_site())),
+			_ => Ident::new("Precompile", Span::call_site()),
+		};
+		let enum_ident = format_ident!("{}Call", precompile_ident);
+
+		let type_params: Vec<_> = item
+			.generics
+			.params
+			.iter()
+			.filter_map(|param| match param {
+				GenericParam::Type(param) => Some(&param.ident),
+				_ => None,
+			})
+			.collect();
+
+		let variants = self.methods.iter().map(|method| {
+			let ident = &method.ident;
+			let fields = method.args.iter().map(|(ident, ty)| quote!(#ident: #ty));
+			quote!(#ident { #(#fields),* })
+		});
+
+		let parse_fns = self
+			.methods
+			.iter()
+			.map(|method| self.expand_parse_fn(method));
+
+		let parse_match_arms = self.selectors.iter().map(|(selector, (index, _))| {
+			let parse_fn = format_ident!("_parse_{}", self.methods[*index].ident);
+			quote!(#selector => Self::#parse_fn(handle),)
+		});
+
+		let fallback = self
+			.methods
+			.iter()
+			.find(|method| matches!(method.kind, Kind::Fallback));
+
+		let (short_input_arm, unknown_selector_arm) = match fallback {
+			Some(method) => {
+				let parse_fn = format_ident!("_parse_{}", method.ident);
+				(
+					quote!(Self::#parse_fn(handle)),
+					quote!(Self::#parse_fn(handle)),
+				)
+			}
+			None => (
+				quote!(Err(
+					::precompile_utils::revert::RevertReason::read_out_of_bounds("selector").into()
+				)),
+				quote!(Err(
+					::precompile_utils::revert::RevertReason::UnknownSelector.into()
+				)),
+			),
+		};
+
+		let execute_arms = self.methods.iter().map(|method| {
+			let ident = &method.ident;
+			let args: Vec<_> = method.args.iter().map(|(ident, _)| ident).collect();
+			quote!(
+				Self::#ident { #(#args),* } => {
+					let output = <#self_ty>::#ident(handle, #(#args),*)?;
+					::precompile_utils::data::EvmDataWriter::new().write(output).build()
+				}
+			)
+		});
+
+		let encode_arms = self.methods.iter().map(|method| {
+			let ident = &method.ident;
+			let args: Vec<_> = method.args.iter().map(|(ident, _)| ident).collect();
+			match &method.kind {
+				Kind::Public { signatures } => {
+					let selector = selector(&signatures[0].value());
+					quote!(
+						#enum_ident::#ident { #(#args),* } => {
+							::precompile_utils::data::EvmDataWriter::new_with_selector(#selector)
+								#(.write(#args))*
+								.build()
+						}
+					)
+				}
+				Kind::Fallback => quote!(#enum_ident::#ident {} => Default::default(),),
+			}
+		});
+
+		let selectors_fns = self.methods.iter().filter_map(|method| {
+			let signatures = match &method.kind {
+				Kind::Public { signatures } => signatures,
+				Kind::Fallback => return None,
+			};
+			let fn_ident = format_ident!("{}_selectors", method.ident);
+			let selectors = signatures.iter().map(|s| selector(&s.value()));
+			let doc = format!(
+				"Selectors of `{}` (first one is used when encoding the call).",
+				method.ident
+			);
+			Some(quote!(
+				#[doc = #doc]
+				pub fn #fn_ident() -> &'static [u32] {
+					&[#(#selectors),*]
+				}
+			))
+		});
+
+		let all_selectors = self.selectors.keys();
+		let all_signatures = self
+			.selectors
+			.iter()
+			.map(|(selector, (_, signature))| quote!((#selector, #signature)));
+
+		let declarations = self.methods.iter().flat_map(|method| {
+			let signatures: &[LitStr] = match &method.kind {
+				Kind::Public { signatures } => signatures,
+				Kind::Fallback => &[],
+			};
+			let modifier = modifier_ident(method.modifier);
+			let args: Vec<_> = method
+				.args
+				.iter()
+				.map(|(ident, ty)| {
+					let name = camel_case(&ident.to_string());
+					quote!((#name.into(), <#ty as ::precompile_utils::data::EvmData>::solidity_type()))
+				})
+				.collect();
+			let output = &method.output;
+
+			signatures.iter().map(move |signature| {
+				let value = signature.value();
+				let name = &value[..value.find('(').unwrap_or(value.len())];
+				quote!({
+					let declaration = ::precompile_utils::solidity::FunctionDeclaration::new(
+						#name,
+						vec![#(#args),*],
+						::precompile_utils::modifier::FunctionModifier::#modifier,
+						<#output as ::precompile_utils::data::EvmData>::solidity_type(),
+					);
+					assert_eq!(
+						declaration.signature(),
+						#value,
+						"signature doesn't match the Rust types of the arguments",
+					);
+					declaration
+				})
+			})
+		});
+
+		let events = &self.events;
+
+		let pre_check = self
+			.pre_check
+			.as_ref()
+			.map(|ident| quote!(<#self_ty>::#ident(handle)?;));
+
+		let enum_doc = format!(
+			"Parsed calls to `{}`, generated by `#[precompile]`.",
+			precompile_ident
+		);
+
+		quote!(
+			#item
+
+			#[doc = #enum_doc]
+			#[allow(non_camel_case_types)]
+			pub enum #enum_ident #impl_generics #where_clause {
+				#(#variants,)*
+
+				#[doc(hidden)]
+				__phantom(
+					::core::marker::PhantomData<(#(#type_params),*)>,
+					::core::convert::Infallible
+				),
+			}
+
+			impl #impl_generics #enum_ident #ty_generics #where_clause {
+				/// Parse the input of the handle into a call, checking the function modifier.
+				pub fn parse_call_data(
+					handle: &mut impl ::precompile_utils::__private::fp_evm::PrecompileHandle
+				) -> ::precompile_utils::EvmResult<Self> {
+					let input = handle.input();
+
+					if input.len() < 4 {
+						return #short_input_arm;
+					}
+
+					let selector = u32::from_be_bytes([input[0], input[1], input[2], input[3]]);
+
+					match selector {
+						#(#parse_match_arms)*
+						_ => #unknown_selector_arm,
+					}
+				}
+
+				#(#parse_fns)*
+
+				/// Execute the call, performing the pre-check first if any.
+				pub fn execute(
+					self,
+					handle: &mut impl ::precompile_utils::__private::fp_evm::PrecompileHandle
+				) -> ::precompile_utils::EvmResult<
+					::precompile_utils::__private::fp_evm::PrecompileOutput
+				> {
+					#pre_check
+
+					let output = match self {
+						#(#execute_arms)*
+						Self::__phantom(_, never) => match never {},
+					};
+
+					Ok(::precompile_utils::succeed(output))
+				}
+
+				/// Check if the selector is supported by the precompile.
+				pub fn supports_selector(selector: u32) -> bool {
+					Self::selectors().contains(&selector)
+				}
+
+				/// All selectors supported by the precompile.
+				pub fn selectors() -> &'static [u32] {
+					&[#(#all_selectors),*]
+				}
+
+				/// All supported selectors with their Solidity signature.
+				pub fn signatures() -> &'static [(u32, &'static str)] {
+					&[#(#all_signatures),*]
+				}
+
+				#(#selectors_fns)*
+
+				/// Solidity interface of the public functions, built from the Rust types of
+				/// their arguments and output, and of the events they emit.
+				/// Panics if a declared signature doesn't match the Rust types.
+				#[cfg(test)]
+				pub fn solidity_interface() -> ::precompile_utils::solidity::SolidityInterface {
+					::precompile_utils::solidity::SolidityInterface {
+						functions: vec![#(#declarations),*],
+						events: vec![#(#events.into()),*],
+					}
+				}
+			}
+
+			impl #impl_generics From<#enum_ident #ty_generics> for ::precompile_utils::__private::Vec<u8>
+			#where_clause
+			{
+				fn from(call: #enum_ident #ty_generics) -> Self {
+					match call {
+						#(#encode_arms)*
+						#enum_ident::__phantom(_, never) => match never {},
+					}
+				}
+			}
+
+			impl #impl_generics ::precompile_utils::__private::fp_evm::Precompile for #self_ty
+			#where_clause
+			{
+				fn execute(
+					handle: &mut impl ::precompile_utils::__private::fp_evm::PrecompileHandle
+				) -> ::precompile_utils::EvmResult<
+					::precompile_utils::__private::fp_evm::PrecompileOutput
+				> {
+					<#enum_ident #ty_generics>::parse_call_data(handle)?.execute(handle)
+				}
+			}
+		)
+	}
+
+	fn expand_parse_fn(&self, method: &Method) -> TokenStream2 {
+		let ident = &method.ident;
+		let parse_fn = format_ident!("_parse_{}", ident);
+
+		let modifier = modifier_ident(method.modifier);
+
+		let check_modifier = quote!(
+			::precompile_utils::modifier::check_function_modifier(
+				handle.context(),
+				handle.is_static(),
+				::precompile_utils::modifier::FunctionModifier::#modifier,
+			)?;
+		);
+
+		if let Kind::Fallback = method.kind {
+			return quote!(
+				fn #parse_fn(
+					handle: &mut impl ::precompile_utils::__private::fp_evm::PrecompileHandle
+				) -> ::precompile_utils::EvmResult<Self> {
+					#check_modifier
+					Ok(Self::#ident {})
+				}
+			);
+		}
+
+		let args_count = method.args.len();
+		let args = method.args.iter().map(|(ident, ty)| {
+			let name = camel_case(&ident.to_string());
+			quote!(
+				let #ident: #ty = input.read().in_field(#name)?;
+			)
+		});
+		let args_idents = method.args.iter().map(|(ident, _)| ident);
+
+		quote!(
+			fn #parse_fn(
+				handle: &mut impl ::precompile_utils::__private::fp_evm::PrecompileHandle
+			) -> ::precompile_utils::EvmResult<Self> {
+				use ::precompile_utils::revert::InjectBacktrace as _;
+
+				#check_modifier
+
+				let mut input =
+					::precompile_utils::data::EvmDataReader::new_skip_selector(handle.input())?;
+				input.expect_arguments(#args_count)?;
+
+				#(#args)*
+
+				Ok(Self::#ident { #(#args_idents),* })
+			}
+		)
+	}
+}
+
+fn modifier_ident(modifier: Modifier) -> TokenStream2 {
+	match modifier {
+		Modifier::NonPayable => quote!(NonPayable),
+		Modifier::View => quote!(View),
+		Modifier::Payable => quote!(Payable),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn camel_case_works() {
+		assert_eq!(camel_case("owner"), "owner");
+		assert_eq!(camel_case("account_index"), "accountIndex");
+		assert_eq!(camel_case("_call_data"), "callData");
+	}
+
+	#[test]
+	fn signature_arguments_count_works() {
+		let count = |s| signature_arguments_count(&LitStr::new(s, Span::call_site()));
+
+		assert_eq!(count("f()").unwrap(), 0);
+		assert_eq!(count("f(address)").unwrap(), 1);
+		assert_eq!(count("f(address[],uint256)").unwrap(), 2);
+		assert_eq!(count("f((uint8,bytes[]),uint256)").unwrap(), 2);
+		assert!(count("(address)").is_err());
+		assert!(count("f(address").is_err());
+		assert!(count("f(address, uint256)").is_err());
+		assert!(count("f((address)").is_err());
+	}
+
+	fn precompile_item() -> ItemImpl {
+		syn::parse_quote! {
+			impl<Runtime: Config> Erc20Precompile<Runtime> {
+				#[precompile::public("balanceOf(address)")]
+				#[precompile::public("balance_of(address)")]
+				#[precompile::view]
+				fn balance_of(handle: &mut impl PrecompileHandle, owner: Address) -> EvmResult<U256> {
+					todo!()
+				}
+
+				#[precompile::public("deposit()")]
+				#[precompile::payable]
+				#[precompile::emits("Deposit(address,uint256)")]
+				fn deposit(handle: &mut impl PrecompileHandle) -> EvmResult {
+					todo!()
+				}
+
+				#[precompile::public("transfer(address,uint256)")]
+				#[precompile::emits("Transfer(address,address,uint256)")]
+				fn transfer(
+					handle: &mut impl PrecompileHandle,
+					to: Address,
+					value: U256,
+				) -> EvmResult<bool> {
+					todo!()
+				}
+
+				fn helper() {}
+			}
+		}
+	}
+
+	fn expand(mut item: ItemImpl) -> syn::File {
+		let precompile = Precompile::parse(&mut item).expect("precompile is valid");
+		syn::parse2(precompile.expand(&item)).expect("expansion is valid Rust")
+	}
+
+	fn parse_error(mut item: ItemImpl) -> String {
+		match Precompile::parse(&mut item) {
+			Ok(_) => panic!("precompile should be rejected"),
+			Err(err) => err.to_string(),
+		}
+	}
+
+	/// Methods of the `impl` block generated for the call enum.
+	fn call_enum_methods(file: &syn::File) -> BTreeMap<String, ImplItemMethod> {
+		file.items
+			.iter()
+			.filter_map(|item| match item {
+				syn::Item::Impl(item) if item.trait_.is_none() => match &*item.self_ty {
+					Type::Path(path) if path.path.segments[0].ident == "Erc20PrecompileCall" => {
+						Some(item)
+					}
+					_ => None,
+				},
+				_ => None,
+			})
+			.flat_map(|item| &item.items)
+			.filter_map(|item| match item {
+				ImplItem::Method(method) => Some((method.sig.ident.to_string(), method.clone())),
+				_ => None,
+			})
+			.collect()
+	}
+
+	#[test]
+	fn selector_works() {
+		assert_eq!(selector("balanceOf(address)"), 0x70a08231);
+		assert_eq!(selector("transfer(address,uint256)"), 0xa9059cbb);
+		assert_eq!(selector("deposit()"), 0xd0e30db0);
+	}
+
+	#[test]
+	fn parse_collects_methods_selectors_and_events() {
+		let mut item = precompile_item();
+		let precompile = Precompile::parse(&mut item).expect("precompile is valid");
+
+		let methods: Vec<_> = precompile
+			.methods
+			.iter()
+			.map(|method| method.ident.to_string())
+			.collect();
+		assert_eq!(methods, ["balance_of", "deposit", "transfer"]);
+		assert!(precompile.pre_check.is_none());
+
+		let selectors: Vec<_> = precompile
+			.selectors
+			.iter()
+			.map(|(selector, (index, signature))| (*selector, *index, signature.as_str()))
+			.collect();
+		let mut expected = vec![
+			(selector("balanceOf(address)"), 0, "balanceOf(address)"),
+			(selector("balance_of(address)"), 0, "balance_of(address)"),
+			(selector("deposit()"), 1, "deposit()"),
+			(
+				selector("transfer(address,uint256)"),
+				2,
+				"transfer(address,uint256)",
+			),
+		];
+		expected.sort();
+		assert_eq!(selectors, expected);
+
+		assert_eq!(
+			precompile.events,
+			[
+				"Deposit(address,uint256)",
+				"Transfer(address,address,uint256)"
+			]
+		);
+
+		// precompile attributes are consumed by the macro
+		for impl_item in &item.items {
+			if let ImplItem::Method(method) = impl_item {
+				assert!(method
+					.attrs
+					.iter()
+					.all(|attr| precompile_attribute(attr).is_none()));
+			}
+		}
+	}
+
+	#[test]
+	fn parse_collects_modifiers() {
+		let mut item = precompile_item();
+		let precompile = Precompile::parse(&mut item).expect("precompile is valid");
+
+		assert!(precompile.methods[0].modifier == Modifier::View);
+		assert!(precompile.methods[1].modifier == Modifier::Payable);
+		assert!(precompile.methods[2].modifier == Modifier::NonPayable);
+	}
+
+	#[test]
+	fn expansion_generates_call_enum_with_phantom_variant() {
+		let file = expand(precompile_item());
+
+		let call_enum = file
+			.items
+			.iter()
+			.find_map(|item| match item {
+				syn::Item::Enum(item) if item.ident == "Erc20PrecompileCall" => Some(item),
+				_ => None,
+			})
+			.expect("call enum is generated");
+
+		let variants: Vec<_> = call_enum
+			.variants
+			.iter()
+			.map(|variant| variant.ident.to_string())
+			.collect();
+		assert_eq!(variants, ["balance_of", "deposit", "transfer", "__phantom"]);
+
+		let transfer_fields: Vec<_> = call_enum.variants[2]
+			.fields
+			.iter()
+			.map(|field| field.ident.as_ref().expect("named field").to_string())
+			.collect();
+		assert_eq!(transfer_fields, ["to", "value"]);
+
+		// the phantom variant uses the type parameters and can't be constructed
+		let phantom_fields: Vec<_> = call_enum.variants[3]
+			.fields
+			.iter()
+			.map(|field| {
+				let ty = &field.ty;
+				quote!(#ty).to_string()
+			})
+			.collect();
+		assert_eq!(
+			phantom_fields,
+			[
+				quote!(::core::marker::PhantomData<(Runtime)>).to_string(),
+				quote!(::core::convert::Infallible).to_string(),
+			]
+		);
+	}
+
+	#[test]
+	fn expansion_generates_selectors() {
+		let methods = call_enum_methods(&expand(precompile_item()));
+		let body = |name: &str| {
+			let block = &methods[name].block;
+			quote!(#block).to_string()
+		};
+
+		let mut selectors = vec![
+			selector("balanceOf(address)"),
+			selector("balance_of(address)"),
+			selector("deposit()"),
+			selector("transfer(address,uint256)"),
+		];
+		selectors.sort_unstable();
+		assert_eq!(
+			body("selectors"),
+			quote!({ &[#(#selectors),*] }).to_string()
+		);
+
+		// the first signature is used when encoding the call
+		let balance_of_selectors = [
+			selector("balanceOf(address)"),
+			selector("balance_of(address)"),
+		];
+		assert_eq!(
+			body("balance_of_selectors"),
+			quote!({ &[#(#balance_of_selectors),*] }).to_string()
+		);
+
+		assert!(methods.contains_key("deposit_selectors"));
+		assert!(methods.contains_key("transfer_selectors"));
+		assert!(!methods.contains_key("helper_selectors"));
+	}
+
+	#[test]
+	fn expansion_checks_function_modifiers() {
+		let methods = call_enum_methods(&expand(precompile_item()));
+		let checks_modifier = |name: &str, modifier: TokenStream2| {
+			let method = &methods[name];
+			let modifier = quote!(::precompile_utils::modifier::FunctionModifier::#modifier);
+			quote!(#method).to_string().contains(&modifier.to_string())
+		};
+
+		assert!(checks_modifier("_parse_balance_of", quote!(View)));
+		assert!(checks_modifier("_parse_deposit", quote!(Payable)));
+		assert!(checks_modifier("_parse_transfer", quote!(NonPayable)));
+		assert!(!checks_modifier("_parse_transfer", quote!(View)));
+	}
+
+	#[test]
+	fn rejects_malformed_signatures() {
+		for signature in [
+			"balanceOf",
+			"(address)",
+			"balanceOf(address",
+			"balanceOf(address, bool)",
+		] {
+			let item = syn::parse_quote! {
+				impl Precompile {
+					#[precompile::public(#signature)]
+					fn balance_of(handle: &mut impl PrecompileHandle, owner: Address) -> EvmResult {
+						todo!()
+					}
+				}
+			};
+			assert!(
+				parse_error(item).contains("Expected signature"),
+				"signature '{}' should be rejected",
+				signature
+			);
+		}
+
+		let item = syn::parse_quote! {
+			impl Precompile {
+				#[precompile::public("deposit()")]
+				#[precompile::emits("Deposit(address")]
+				fn deposit(handle: &mut impl PrecompileHandle) -> EvmResult {
+					todo!()
+				}
+			}
+		};
+		assert!(parse_error(item).contains("Expected signature"));
+	}
+
+	#[test]
+	fn rejects_signatures_not_matching_the_arguments() {
+		let item = syn::parse_quote! {
+			impl Precompile {
+				#[precompile::public("transfer(address)")]
+				fn transfer(handle: &mut impl PrecompileHandle, to: Address, value: U256) -> EvmResult {
+					todo!()
+				}
+			}
+		};
+		assert_eq!(
+			parse_error(item),
+			"Signature has 1 arguments while the method takes 2"
+		);
+	}
+
+	#[test]
+	fn rejects_colliding_selectors() {
+		let item = syn::parse_quote! {
+			impl Precompile {
+				#[precompile::public("deposit()")]
+				fn deposit(handle: &mut impl PrecompileHandle) -> EvmResult {
+					todo!()
+				}
+
+				#[precompile::public("deposit()")]
+				fn deposit_again(handle: &mut impl PrecompileHandle) -> EvmResult {
+					todo!()
+				}
+			}
+		};
+		assert_eq!(
+			parse_error(item),
+			"Selector of `deposit()` collides with the one of `deposit()`"
+		);
+	}
+
+	#[test]
+	fn rejects_invalid_methods() {
+		let cases: Vec<(ItemImpl, &str)> = vec![
+			(
+				syn::parse_quote! {
+					impl Precompile {
+						#[precompile::public("deposit()")]
+						#[precompile::view]
+						#[precompile::payable]
+						fn deposit(handle: &mut impl PrecompileHandle) -> EvmResult {
+							todo!()
+						}
+					}
+				},
+				"A method can only have one modifier",
+			),
+			(
+				syn::parse_quote! {
+					impl Precompile {
+						#[precompile::public("deposit()")]
+						#[precompile::nonpayable]
+						fn deposit(handle: &mut impl PrecompileHandle) -> EvmResult {
+							todo!()
+						}
+					}
+				},
+				"Unknown precompile attribute",
+			),
+			(
+				syn::parse_quote! {
+					impl Precompile {
+						#[precompile::public("deposit()")]
+						fn deposit(handle: &mut impl PrecompileHandle) -> EvmResult {
+							todo!()
+						}
+
+						#[precompile::view]
+						fn helper(handle: &mut impl PrecompileHandle) -> EvmResult {
+							todo!()
+						}
+					}
+				},
+				"Modifiers can only be used on public or fallback methods",
+			),
+			(
+				syn::parse_quote! {
+					impl Precompile {
+						#[precompile::public("deposit()")]
+						fn deposit(handle: &mut impl PrecompileHandle) -> U256 {
+							todo!()
+						}
+					}
+				},
+				"Expected return type `EvmResult<T>`",
+			),
+			(
+				syn::parse_quote! {
+					impl Precompile {
+						#[precompile::public("deposit()")]
+						fn deposit(&self, handle: &mut impl PrecompileHandle) -> EvmResult {
+							todo!()
+						}
+					}
+				},
+				"Expected a first argument `handle: &mut impl PrecompileHandle`",
+			),
+			(
+				syn::parse_quote! {
+					impl Precompile {
+						#[precompile::fallback]
+						fn fallback(handle: &mut impl PrecompileHandle, value: U256) -> EvmResult {
+							todo!()
+						}
+					}
+				},
+				"The fallback method only takes the handle",
+			),
+			(
+				syn::parse_quote! {
+					impl Precompile {
+						fn helper() {}
+					}
+				},
+				"A precompile must have at least one public or fallback method",
+			),
+		];
+
+		for (item, error) in cases {
+			let actual = parse_error(item);
+			assert!(
+				actual.contains(error),
+				"expected error '{}', got '{}'",
+				error,
+				actual
+			);
+		}
+	}
+}
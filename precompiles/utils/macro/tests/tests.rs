@@ -47,3 +47,14 @@ fn test_generate_function_selector() {
 	);
 	assert_ne!(Action::Toto as u32, Action::Tata as u32);
 }
+
+#[test]
+fn test_generate_function_selector_signatures() {
+	assert_eq!(
+		Action::signatures(),
+		&[
+			(Action::Toto as u32, "toto()"),
+			(Action::Tata as u32, "tata()"),
+		]
+	);
+}
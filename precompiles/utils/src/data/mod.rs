@@ -19,7 +19,7 @@ pub use affix::paste;
 
 use {
 	crate::revert::{InjectBacktrace, MayRevert, RevertReason},
-	alloc::borrow::ToOwned,
+	alloc::{borrow::ToOwned, string::String},
 	core::{any::type_name, marker::PhantomData, ops::Range},
 	frame_support::traits::{ConstU32, Get},
 	impl_trait_for_tuples::impl_for_tuples,
@@ -97,6 +97,84 @@ impl<S> BoundedBytes<S> {
 	}
 }
 
+/// The `string` type of Solidity with an additional length bound on read.
+/// Reading fails if the data is not valid UTF-8.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BoundedString<S> {
+	inner: String,
+	_phantom: PhantomData<S>,
+}
+
+impl<S> BoundedString<S> {
+	pub fn as_str(&self) -> &str {
+		&self.inner
+	}
+
+	pub fn into_string(self) -> String {
+		self.inner
+	}
+}
+
+impl<S> From<String> for BoundedString<S> {
+	fn from(inner: String) -> Self {
+		Self {
+			inner,
+			_phantom: PhantomData,
+		}
+	}
+}
+
+impl<S> From<&str> for BoundedString<S> {
+	fn from(a: &str) -> Self {
+		a.to_owned().into()
+	}
+}
+
+/// The `int256` type of Solidity, stored as its two's complement representation.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct I256(pub U256);
+
+impl I256 {
+	/// Is the value strictly negative.
+	pub fn is_negative(&self) -> bool {
+		self.0.bit(255)
+	}
+}
+
+impl From<i128> for I256 {
+	fn from(value: i128) -> Self {
+		let mut buffer = if value < 0 { [0xffu8; 32] } else { [0u8; 32] };
+		buffer[16..].copy_from_slice(&value.to_be_bytes());
+		I256(U256::from_big_endian(&buffer))
+	}
+}
+
+impl TryFrom<I256> for i128 {
+	type Error = ();
+
+	fn try_from(value: I256) -> Result<Self, ()> {
+		let mut buffer = [0u8; 32];
+		value.0.to_big_endian(&mut buffer);
+		read_signed::<16>(&buffer).ok_or(())
+	}
+}
+
+/// Reads a `N` bytes signed integer from its 32 bytes big endian two's complement
+/// representation, returning `None` if it doesn't fit in `N` bytes.
+fn read_signed<const N: usize>(buffer: &[u8; 32]) -> Option<i128> {
+	let (padding, data) = buffer.split_at(32 - N);
+
+	// Value must be sign extended.
+	let sign_byte = if data[0] & 0x80 == 0 { 0x00 } else { 0xff };
+	if padding.iter().any(|byte| *byte != sign_byte) {
+		return None;
+	}
+
+	let mut value = [sign_byte; 16];
+	value[16 - N..].copy_from_slice(data);
+	Some(i128::from_be_bytes(value))
+}
+
 /// Wrapper around an EVM input slice, helping to parse it.
 /// Provide functions to parse common types.
 #[derive(Clone, Copy, Debug)]
@@ -339,6 +417,15 @@ pub trait EvmData: Sized {
 	fn read(reader: &mut EvmDataReader) -> MayRevert<Self>;
 	fn write(writer: &mut EvmDataWriter, value: Self);
 	fn has_static_size() -> bool;
+	/// Solidity ABI type of the data, with structs represented as tuples.
+	/// It is only used to check the Solidity interface of precompiles in tests, thus it panics
+	/// by default for types that don't provide it.
+	fn solidity_type() -> String {
+		panic!(
+			"solidity_type is not implemented for {}",
+			core::any::type_name::<Self>()
+		)
+	}
 }
 
 #[impl_for_tuples(1, 18)]
@@ -347,6 +434,12 @@ impl EvmData for Tuple {
 		for_tuples!(#( Tuple::has_static_size() )&*)
 	}
 
+	fn solidity_type() -> String {
+		let mut types: Vec<String> = vec![];
+		for_tuples!( #( types.push(Tuple::solidity_type()); )* );
+		alloc::format!("({})", types.join(","))
+	}
+
 	fn read(reader: &mut EvmDataReader) -> MayRevert<Self> {
 		if !Self::has_static_size() {
 			let reader = &mut reader.read_pointer()?;
@@ -377,6 +470,23 @@ impl EvmData for Tuple {
 	}
 }
 
+// Allows functions without return values to be encoded as an empty output.
+impl EvmData for () {
+	fn read(_reader: &mut EvmDataReader) -> MayRevert<Self> {
+		Ok(())
+	}
+
+	fn write(_writer: &mut EvmDataWriter, _value: Self) {}
+
+	fn has_static_size() -> bool {
+		true
+	}
+
+	fn solidity_type() -> String {
+		"()".into()
+	}
+}
+
 impl EvmData for H256 {
 	fn read(reader: &mut EvmDataReader) -> MayRevert<Self> {
 		let range = reader.move_cursor(32)?;
@@ -396,6 +506,10 @@ impl EvmData for H256 {
 	fn has_static_size() -> bool {
 		true
 	}
+
+	fn solidity_type() -> String {
+		"bytes32".into()
+	}
 }
 
 impl EvmData for Address {
@@ -417,6 +531,10 @@ impl EvmData for Address {
 	fn has_static_size() -> bool {
 		true
 	}
+
+	fn solidity_type() -> String {
+		"address".into()
+	}
 }
 
 impl EvmData for U256 {
@@ -440,6 +558,10 @@ impl EvmData for U256 {
 	fn has_static_size() -> bool {
 		true
 	}
+
+	fn solidity_type() -> String {
+		"uint256".into()
+	}
 }
 
 macro_rules! impl_evmdata_for_uints {
@@ -466,6 +588,10 @@ macro_rules! impl_evmdata_for_uints {
 				fn has_static_size() -> bool {
 					true
 				}
+
+				fn solidity_type() -> String {
+					alloc::format!("uint{}", core::mem::size_of::<Self>() * 8)
+				}
 			}
 		)*
 	};
@@ -473,6 +599,66 @@ macro_rules! impl_evmdata_for_uints {
 
 impl_evmdata_for_uints!(u8, u16, u32, u64, u128,);
 
+macro_rules! impl_evmdata_for_ints {
+	($($int:ty, )*) => {
+		$(
+			impl EvmData for $int {
+				fn read(reader: &mut EvmDataReader) -> MayRevert<Self> {
+					const SIZE: usize = core::mem::size_of::<$int>();
+
+					let value: H256 = reader.read()
+					.map_err(|_| RevertReason::read_out_of_bounds(
+						alloc::format!("int{}", SIZE * 8)
+					))?;
+
+					read_signed::<SIZE>(value.as_fixed_bytes())
+						.map(|value| value as Self)
+						.ok_or_else(|| RevertReason::value_is_too_large(
+							alloc::format!("int{}", SIZE * 8)
+						).into())
+				}
+
+				fn write(writer: &mut EvmDataWriter, value: Self) {
+					let mut buffer = if value < 0 { [0xffu8; 32] } else { [0u8; 32] };
+					buffer[32 - core::mem::size_of::<Self>()..]
+						.copy_from_slice(&value.to_be_bytes());
+					writer.data.extend_from_slice(&buffer);
+				}
+
+				fn has_static_size() -> bool {
+					true
+				}
+
+				fn solidity_type() -> String {
+					alloc::format!("int{}", core::mem::size_of::<Self>() * 8)
+				}
+			}
+		)*
+	};
+}
+
+impl_evmdata_for_ints!(i8, i16, i32, i64, i128,);
+
+impl EvmData for I256 {
+	fn read(reader: &mut EvmDataReader) -> MayRevert<Self> {
+		let value = U256::read(reader).map_err(|_| RevertReason::read_out_of_bounds("int256"))?;
+
+		Ok(I256(value))
+	}
+
+	fn write(writer: &mut EvmDataWriter, value: Self) {
+		U256::write(writer, value.0);
+	}
+
+	fn has_static_size() -> bool {
+		true
+	}
+
+	fn solidity_type() -> String {
+		"int256".into()
+	}
+}
+
 impl EvmData for bool {
 	fn read(reader: &mut EvmDataReader) -> MayRevert<Self> {
 		let h256 = H256::read(reader).map_err(|_| RevertReason::read_out_of_bounds("bool"))?;
@@ -492,6 +678,10 @@ impl EvmData for bool {
 	fn has_static_size() -> bool {
 		true
 	}
+
+	fn solidity_type() -> String {
+		"bool".into()
+	}
 }
 
 impl EvmData for Bytes {
@@ -512,6 +702,10 @@ impl EvmData for Bytes {
 	fn has_static_size() -> bool {
 		false
 	}
+
+	fn solidity_type() -> String {
+		"bytes".into()
+	}
 }
 
 impl<S: Get<u32>> EvmData for BoundedBytes<S> {
@@ -572,6 +766,102 @@ impl<S: Get<u32>> EvmData for BoundedBytes<S> {
 	fn has_static_size() -> bool {
 		false
 	}
+
+	fn solidity_type() -> String {
+		"bytes".into()
+	}
+}
+
+impl EvmData for String {
+	fn read(reader: &mut EvmDataReader) -> MayRevert<Self> {
+		Ok(BoundedString::<ConstU32Max>::read(reader)?.into_string())
+	}
+
+	fn write(writer: &mut EvmDataWriter, value: Self) {
+		BoundedString::<ConstU32Max>::write(writer, value.into());
+	}
+
+	fn has_static_size() -> bool {
+		false
+	}
+
+	fn solidity_type() -> String {
+		"string".into()
+	}
+}
+
+impl<S: Get<u32>> EvmData for BoundedString<S> {
+	fn read(reader: &mut EvmDataReader) -> MayRevert<Self> {
+		let bytes = BoundedBytes::<S>::read(reader)?.into_vec();
+
+		let inner = String::from_utf8(bytes)
+			.map_err(|_| RevertReason::custom("Value is not a valid UTF-8 string"))?;
+
+		Ok(inner.into())
+	}
+
+	fn write(writer: &mut EvmDataWriter, value: Self) {
+		BoundedBytes::<S>::write(
+			writer,
+			BoundedBytes {
+				inner: value.into_string().into_bytes(),
+				_phantom: PhantomData,
+			},
+		);
+	}
+
+	fn has_static_size() -> bool {
+		false
+	}
+
+	fn solidity_type() -> String {
+		"string".into()
+	}
+}
+
+/// Fixed-size arrays `T[N]` are encoded like tuples: inline if `T` has a static size,
+/// behind a pointer otherwise.
+impl<T: EvmData, const N: usize> EvmData for [T; N] {
+	fn read(reader: &mut EvmDataReader) -> MayRevert<Self> {
+		let mut inner_reader = if Self::has_static_size() {
+			None
+		} else {
+			Some(reader.read_pointer()?)
+		};
+		let reader = inner_reader.as_mut().unwrap_or(reader);
+
+		let mut array = Vec::with_capacity(N);
+		for i in 0..N {
+			array.push(reader.read().in_array(i)?);
+		}
+
+		// Cannot fail as exactly N items are read.
+		array
+			.try_into()
+			.map_err(|_| RevertReason::read_out_of_bounds("array content").into())
+	}
+
+	fn write(writer: &mut EvmDataWriter, value: Self) {
+		if Self::has_static_size() {
+			for item in value {
+				T::write(writer, item);
+			}
+		} else {
+			let mut inner_writer = EvmDataWriter::new();
+			for item in value {
+				T::write(&mut inner_writer, item);
+			}
+			writer.write_pointer(inner_writer.build());
+		}
+	}
+
+	fn has_static_size() -> bool {
+		T::has_static_size()
+	}
+
+	fn solidity_type() -> String {
+		alloc::format!("{}[{}]", T::solidity_type(), N)
+	}
 }
 
 impl<T: EvmData> EvmData for Vec<T> {
@@ -592,6 +882,10 @@ impl<T: EvmData> EvmData for Vec<T> {
 	fn has_static_size() -> bool {
 		false
 	}
+
+	fn solidity_type() -> String {
+		alloc::format!("{}[]", T::solidity_type())
+	}
 }
 
 /// Wrapper around a Vec that provides a max length bound on read.
@@ -667,6 +961,10 @@ impl<T: EvmData, S: Get<u32>> EvmData for BoundedVec<T, S> {
 	fn has_static_size() -> bool {
 		false
 	}
+
+	fn solidity_type() -> String {
+		alloc::format!("{}[]", T::solidity_type())
+	}
 }
 
 /// Helper to write `EvmData` impl for Solidity structs.
@@ -688,6 +986,10 @@ impl<T: EvmData, S: Get<u32>> EvmData for BoundedVec<T, S> {
 /// 	fn has_static_size() -> bool {
 /// 		<(Address, U256)>::has_static_size()
 /// 	}
+///
+/// 	fn solidity_type() -> String {
+/// 		<(Address, U256)>::solidity_type()
+/// 	}
 /// }
 /// ```
 #[macro_export]
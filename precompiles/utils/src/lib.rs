@@ -41,9 +41,15 @@ use fp_evm::{ExitRevert, ExitSucceed, PrecompileFailure, PrecompileHandle, Preco
 
 pub mod data;
 
-pub use data::{Address, Bytes, EvmData, EvmDataReader, EvmDataWriter};
+pub use data::{Address, Bytes, EvmData, EvmDataReader, EvmDataWriter, I256};
 pub use fp_evm::Precompile;
-pub use precompile_utils_macro::{generate_function_selector, keccak256};
+pub use precompile_utils_macro::{generate_function_selector, keccak256, precompile};
+
+// Allows code generated by `#[precompile]` to refer to dependencies of this crate.
+#[doc(hidden)]
+pub mod __private {
+	pub use {fp_evm, sp_std::vec::Vec};
+}
 
 /// Generated a `PrecompileFailure::Revert` with proper encoding for the output.
 /// If the revert needs improved formatting such as backtraces, `Revert` type should
@@ -87,9 +93,11 @@ pub trait StatefulPrecompile {
 
 pub mod prelude {
 	pub use {
+		alloc::string::String,
 		crate::{
 			data::{
-				Address, BoundedBytes, BoundedVec, Bytes, EvmData, EvmDataReader, EvmDataWriter,
+				Address, BoundedBytes, BoundedString, BoundedVec, Bytes, EvmData, EvmDataReader,
+				EvmDataWriter, I256,
 			},
 			handle::PrecompileHandleExt,
 			logs::{log0, log1, log2, log3, log4, LogExt},
@@ -100,6 +108,6 @@ pub mod prelude {
 			succeed, EvmResult, StatefulPrecompile,
 		},
 		pallet_evm::PrecompileHandle,
-		precompile_utils_macro::{generate_function_selector, keccak256},
+		precompile_utils_macro::{generate_function_selector, keccak256, precompile},
 	};
 }
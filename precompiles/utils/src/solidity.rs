@@ -16,9 +16,10 @@
 
 //! Utility module to interact with solidity file.
 
+use crate::modifier::FunctionModifier;
 use sp_io::hashing::keccak_256;
 use std::{
-	collections::HashMap,
+	collections::{BTreeMap, BTreeSet, HashMap},
 	fs::File,
 	io::{BufRead, BufReader, Read},
 };
@@ -236,6 +237,575 @@ fn get_selectors_from_reader<R: Read>(reader: R) -> Vec<SolidityFunction> {
 	functions
 }
 
+/// Declaration of a function in a Solidity interface.
+/// Custom types (structs and enums) are represented by their ABI type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FunctionDeclaration {
+	/// Function name
+	pub name: String,
+	/// List of function parameter names and types
+	pub args: Vec<(String, String)>,
+	/// Function modifier
+	pub modifier: FunctionModifier,
+	/// List of returned types
+	pub returns: Vec<String>,
+}
+
+impl FunctionDeclaration {
+	/// Creates a declaration from the ABI type of the returned data (`()` if none).
+	pub fn new(
+		name: impl Into<String>,
+		args: Vec<(String, String)>,
+		modifier: FunctionModifier,
+		output: String,
+	) -> Self {
+		Self {
+			name: name.into(),
+			args,
+			modifier,
+			returns: match output.as_str() {
+				"()" => vec![],
+				_ => vec![output],
+			},
+		}
+	}
+
+	/// Creates the declaration of a function from its signature and the ABI types of its
+	/// arguments and of the values it returns, panicking if the arguments don't match the
+	/// signature. Used by precompiles dispatching on a `#[generate_function_selector]` enum, whose
+	/// Rust types are not known to the macro.
+	pub fn from_signature(
+		signature: &str,
+		args: &[(&str, String)],
+		modifier: FunctionModifier,
+		returns: &[String],
+	) -> Self {
+		let (name, _) = split_signature(signature);
+		let declaration = Self {
+			name: name.to_string(),
+			args: args
+				.iter()
+				.map(|(arg, ty)| (arg.to_string(), ty.clone()))
+				.collect(),
+			modifier,
+			returns: returns.to_vec(),
+		};
+
+		assert_eq!(
+			declaration.signature(),
+			signature,
+			"signature doesn't match the Rust types of the arguments",
+		);
+		declaration
+	}
+
+	/// Returns the representative signature for the solidity function
+	pub fn signature(&self) -> String {
+		let types: Vec<_> = self.args.iter().map(|(_, ty)| ty.as_str()).collect();
+		format!("{}({})", self.name, types.join(","))
+	}
+
+	/// Computes the selector code for the solidity function
+	pub fn compute_selector(&self) -> u32 {
+		compute_selector(&self.signature())
+	}
+}
+
+/// Functions and events of a Solidity interface.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SolidityInterface {
+	/// List of declared functions
+	pub functions: Vec<FunctionDeclaration>,
+	/// List of declared event signatures
+	pub events: Vec<String>,
+}
+
+impl SolidityInterface {
+	/// Parses the functions and events declared in a solidity file.
+	pub fn from_file(filename: &str) -> Self {
+		let mut file = File::open(filename)
+			.unwrap_or_else(|e| panic!("failed opening file '{}': {}", filename, e));
+		Self::from_reader(&mut file)
+	}
+
+	/// Parses the functions and events declared in a solidity source.
+	pub fn from_reader<R: Read>(reader: &mut R) -> Self {
+		let mut source = String::new();
+		reader
+			.read_to_string(&mut source)
+			.expect("failed reading solidity source");
+
+		InterfaceParser {
+			tokens: tokenize(&source),
+			cursor: 0,
+			custom_types: HashMap::new(),
+		}
+		.parse()
+	}
+
+	/// Creates the interface of a precompile dispatching on a `#[generate_function_selector]`
+	/// enum from the declarations of its functions, panicking if they are not declared for
+	/// exactly the given signatures.
+	pub fn from_declarations<'a>(
+		functions: Vec<FunctionDeclaration>,
+		signatures: impl IntoIterator<Item = &'a str>,
+	) -> Self {
+		let declared: BTreeSet<_> = functions.iter().map(|f| f.signature()).collect();
+		let signatures: BTreeSet<_> = signatures.into_iter().map(|s| s.to_string()).collect();
+
+		let mut errors: Vec<_> = signatures
+			.difference(&declared)
+			.map(|signature| format!("function '{}' has no declaration", signature))
+			.collect();
+		errors.extend(
+			declared.difference(&signatures).map(|signature| {
+				format!("function '{}' is declared but not dispatched", signature)
+			}),
+		);
+
+		if !errors.is_empty() {
+			panic!(
+				"declarations don't match the dispatched functions:\n- {}",
+				errors.join("\n- ")
+			);
+		}
+
+		Self {
+			functions,
+			events: vec![],
+		}
+	}
+
+	/// Adds events to the interface from their signatures.
+	pub fn with_events(mut self, events: &[&str]) -> Self {
+		self.events.extend(events.iter().map(|e| e.to_string()));
+		self
+	}
+
+	/// Renders the interface as a solidity source file.
+	/// Tuples are rendered as structs named after the parameter they are used in.
+	pub fn render(&self, name: &str) -> String {
+		let mut structs = StructsRenderer::default();
+		let mut body = String::new();
+
+		for function in &self.functions {
+			let args: Vec<_> = function
+				.args
+				.iter()
+				.map(|(arg, ty)| {
+					let ty = structs.type_name(ty, arg);
+					format!("{}{} {}", ty, data_location(&ty), arg)
+				})
+				.collect();
+
+			let modifier = match function.modifier {
+				FunctionModifier::View => " view",
+				FunctionModifier::Payable => " payable",
+				FunctionModifier::NonPayable => "",
+			};
+
+			let returns = if function.returns.is_empty() {
+				String::new()
+			} else {
+				let returns: Vec<_> = function
+					.returns
+					.iter()
+					.map(|ty| {
+						let ty = structs.type_name(ty, &format!("{}Output", function.name));
+						format!("{}{}", ty, data_location(&ty))
+					})
+					.collect();
+				format!(" returns ({})", returns.join(", "))
+			};
+
+			body += &format!(
+				"\n    /// @custom:selector {:0>8x}\n    function {}({}) external{}{};\n",
+				function.compute_selector(),
+				function.name,
+				args.join(", "),
+				modifier,
+				returns,
+			);
+		}
+
+		for event in &self.events {
+			let (event_name, args) = split_signature(event);
+			let args: Vec<_> = args
+				.iter()
+				.enumerate()
+				.map(|(i, ty)| {
+					let arg = format!("arg{}", i);
+					format!("{} {}", structs.type_name(ty, &arg), arg)
+				})
+				.collect();
+			body += &format!("\n    event {}({});\n", event_name, args.join(", "));
+		}
+
+		format!(
+			"// SPDX-License-Identifier: GPL-3.0-only\n\
+			pragma solidity >=0.8.3;\n\n\
+			/// @author The Moonbeam Team\n\
+			/// @title {}\n\
+			interface {} {{{}{}}}\n",
+			name, name, structs.output, body,
+		)
+	}
+
+	/// Panics if the functions and events declared in the solidity file don't match this
+	/// interface, printing the interface rendered as solidity.
+	pub fn assert_matches_file(&self, filename: &str, name: &str) {
+		let file = Self::from_file(filename);
+		let mut errors = vec![];
+
+		let expected: BTreeMap<_, _> = self.functions.iter().map(|f| (f.signature(), f)).collect();
+		let actual: BTreeMap<_, _> = file.functions.iter().map(|f| (f.signature(), f)).collect();
+
+		for (signature, expected_fn) in &expected {
+			match actual.get(signature) {
+				None => errors.push(format!("function '{}' is not declared", signature)),
+				Some(actual_fn) => {
+					if actual_fn.modifier != expected_fn.modifier {
+						errors.push(format!(
+							"function '{}' is declared {:?} but implemented {:?}",
+							signature, actual_fn.modifier, expected_fn.modifier
+						));
+					}
+					if actual_fn.returns != expected_fn.returns {
+						errors.push(format!(
+							"function '{}' is declared returning ({}) but implemented \
+							returning ({})",
+							signature,
+							actual_fn.returns.join(","),
+							expected_fn.returns.join(",")
+						));
+					}
+				}
+			}
+		}
+
+		for signature in actual.keys() {
+			if !expected.contains_key(signature) {
+				errors.push(format!("function '{}' is not implemented", signature));
+			}
+		}
+
+		for event in &self.events {
+			if !file.events.contains(event) {
+				errors.push(format!("event '{}' is not declared", event));
+			}
+		}
+
+		for event in &file.events {
+			if !self.events.contains(event) {
+				errors.push(format!("event '{}' is not emitted", event));
+			}
+		}
+
+		if !errors.is_empty() {
+			panic!(
+				"solidity file '{}' doesn't match the Rust definitions:\n- {}\n\n\
+				Expected interface:\n\n{}",
+				filename,
+				errors.join("\n- "),
+				self.render(name),
+			);
+		}
+	}
+}
+
+/// Splits a signature `name(type1,type2)` into its name and top-level argument types.
+fn split_signature(signature: &str) -> (&str, Vec<&str>) {
+	let open = signature.find('(').unwrap_or(signature.len());
+	let name = &signature[..open];
+	let inner = signature
+		.get(open + 1..signature.len() - 1)
+		.unwrap_or_default();
+
+	(name, split_tuple(inner))
+}
+
+/// Splits the inner part of a tuple type at its top-level commas.
+fn split_tuple(inner: &str) -> Vec<&str> {
+	let mut types = vec![];
+	let mut depth = 0usize;
+	let mut start = 0;
+
+	for (i, c) in inner.char_indices() {
+		match c {
+			'(' => depth += 1,
+			')' => depth = depth.saturating_sub(1),
+			',' if depth == 0 => {
+				types.push(&inner[start..i]);
+				start = i + 1;
+			}
+			_ => (),
+		}
+	}
+
+	if !inner.is_empty() {
+		types.push(&inner[start..]);
+	}
+
+	types
+}
+
+/// Data location required for a parameter of the given type.
+fn data_location(ty: &str) -> &'static str {
+	let is_struct = ty.starts_with(|c: char| c.is_ascii_uppercase());
+	if is_struct || ty.ends_with(']') || ty == "bytes" || ty == "string" {
+		" memory"
+	} else {
+		""
+	}
+}
+
+/// Renders tuples as solidity structs, reusing the same struct for identical tuples.
+#[derive(Default)]
+struct StructsRenderer {
+	names: HashMap<String, String>,
+	output: String,
+}
+
+impl StructsRenderer {
+	/// Returns the solidity name of an ABI type, declaring structs for tuples.
+	fn type_name(&mut self, ty: &str, hint: &str) -> String {
+		if !ty.starts_with('(') {
+			return ty.to_string();
+		}
+
+		let close = ty.rfind(')').expect("unbalanced tuple type");
+		let (tuple, suffix) = ty.split_at(close + 1);
+
+		if let Some(name) = self.names.get(tuple) {
+			return format!("{}{}", name, suffix);
+		}
+
+		let mut name: String = hint
+			.chars()
+			.take(1)
+			.flat_map(|c| c.to_uppercase())
+			.chain(hint.chars().skip(1))
+			.collect();
+		while self.names.values().any(|n| n == &name) {
+			name.push('_');
+		}
+
+		let members: Vec<_> = split_tuple(&tuple[1..tuple.len() - 1])
+			.into_iter()
+			.enumerate()
+			.map(|(i, member)| {
+				let member = self.type_name(member, &format!("{}{}", name, i));
+				format!("        {} p{};\n", member, i)
+			})
+			.collect();
+
+		self.output += &format!("\n    struct {} {{\n{}    }}\n", name, members.concat());
+		self.names.insert(tuple.to_string(), name.clone());
+
+		format!("{}{}", name, suffix)
+	}
+}
+
+/// Splits a solidity source in tokens, removing comments.
+fn tokenize(source: &str) -> Vec<String> {
+	let mut tokens = vec![];
+	let mut current = String::new();
+	let mut chars = source.chars().peekable();
+
+	let flush = |current: &mut String, tokens: &mut Vec<String>| {
+		if !current.is_empty() {
+			tokens.push(std::mem::take(current));
+		}
+	};
+
+	while let Some(c) = chars.next() {
+		match c {
+			'/' if chars.peek() == Some(&'/') => {
+				flush(&mut current, &mut tokens);
+				while matches!(chars.next(), Some(c) if c != '\n') {}
+			}
+			'/' if chars.peek() == Some(&'*') => {
+				flush(&mut current, &mut tokens);
+				chars.next();
+				let mut previous = ' ';
+				for c in chars.by_ref() {
+					if previous == '*' && c == '/' {
+						break;
+					}
+					previous = c;
+				}
+			}
+			'(' | ')' | '{' | '}' | ';' | ',' | '=' => {
+				flush(&mut current, &mut tokens);
+				tokens.push(c.to_string());
+			}
+			c if c.is_whitespace() => flush(&mut current, &mut tokens),
+			c => current.push(c),
+		}
+	}
+	flush(&mut current, &mut tokens);
+
+	tokens
+}
+
+/// Parses the declarations of a tokenized solidity source.
+struct InterfaceParser {
+	tokens: Vec<String>,
+	cursor: usize,
+	/// Custom types names to their ABI type.
+	custom_types: HashMap<String, String>,
+}
+
+impl InterfaceParser {
+	fn next(&mut self) -> String {
+		let token = self
+			.tokens
+			.get(self.cursor)
+			.cloned()
+			.expect("unexpected end of solidity source");
+		self.cursor += 1;
+		token
+	}
+
+	fn peek(&self) -> Option<&str> {
+		self.tokens.get(self.cursor).map(|t| t.as_str())
+	}
+
+	fn expect(&mut self, expected: &str) {
+		let token = self.next();
+		assert_eq!(token, expected, "unexpected token in solidity source");
+	}
+
+	fn parse(mut self) -> SolidityInterface {
+		let mut interface = SolidityInterface::default();
+
+		while self.cursor < self.tokens.len() {
+			match self.next().as_str() {
+				"struct" => self.parse_struct(),
+				"enum" => self.parse_enum(),
+				"function" => interface.functions.push(self.parse_function()),
+				"event" => interface.events.push(self.parse_event()),
+				_ => (),
+			}
+		}
+
+		interface
+	}
+
+	/// Resolves custom types (including arrays of custom types) to their ABI type.
+	fn resolve(&self, ty: &str) -> String {
+		let (base, suffix) = ty.split_at(ty.find('[').unwrap_or(ty.len()));
+		let base = match base {
+			"uint" => "uint256",
+			"int" => "int256",
+			base => self
+				.custom_types
+				.get(base)
+				.map(|t| t.as_str())
+				.unwrap_or(base),
+		};
+		format!("{}{}", base, suffix)
+	}
+
+	fn parse_struct(&mut self) {
+		let name = self.next();
+		self.expect("{");
+
+		let mut members = vec![];
+		loop {
+			let ty = self.next();
+			if ty == "}" {
+				break;
+			}
+			members.push(self.resolve(&ty));
+			let _member_name = self.next();
+			self.expect(";");
+		}
+
+		self.custom_types
+			.insert(name, format!("({})", members.join(",")));
+	}
+
+	fn parse_enum(&mut self) {
+		let name = self.next();
+		while self.next() != "}" {}
+		self.custom_types.insert(name, "uint8".to_string());
+	}
+
+	/// Parses a list of parameters, returning their names, types and if they are indexed.
+	fn parse_params(&mut self) -> Vec<(String, String, bool)> {
+		self.expect("(");
+
+		let mut params = vec![];
+		loop {
+			let ty = match self.next().as_str() {
+				")" => break,
+				"," => continue,
+				ty => self.resolve(ty),
+			};
+
+			let mut name = String::new();
+			let mut indexed = false;
+			while !matches!(self.peek(), Some(")" | ",") | None) {
+				match self.next().as_str() {
+					"memory" | "calldata" | "storage" => (),
+					"indexed" => indexed = true,
+					word => name = word.to_string(),
+				}
+			}
+
+			params.push((name, ty, indexed));
+		}
+
+		params
+	}
+
+	fn parse_function(&mut self) -> FunctionDeclaration {
+		let name = self.next();
+		let args = self
+			.parse_params()
+			.into_iter()
+			.map(|(name, ty, _)| (name, ty))
+			.collect();
+
+		let mut modifier = FunctionModifier::NonPayable;
+		let mut returns = vec![];
+		loop {
+			match self.next().as_str() {
+				";" | "{" => break,
+				"view" | "pure" => modifier = FunctionModifier::View,
+				"payable" => modifier = FunctionModifier::Payable,
+				"returns" => {
+					returns = self
+						.parse_params()
+						.into_iter()
+						.map(|(_, ty, _)| ty)
+						.collect()
+				}
+				_ => (),
+			}
+		}
+
+		FunctionDeclaration {
+			name,
+			args,
+			modifier,
+			returns,
+		}
+	}
+
+	fn parse_event(&mut self) -> String {
+		let name = self.next();
+		let types: Vec<_> = self
+			.parse_params()
+			.into_iter()
+			.map(|(_, ty, _)| ty)
+			.collect();
+
+		format!("{}({})", name, types.join(","))
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -337,8 +907,64 @@ mod tests {
 				(address[],uint256[],bytes[])[])[],uint64)",
 				),
 			),
+			(
+				String::from("7d5c282b"),
+				String::from("7d5c282b"),
+				String::from("fnViewReturns(uint64)"),
+			),
+			(
+				String::from("3a403377"),
+				String::from("3a403377"),
+				String::from("fnPayable()"),
+			),
 		];
 
 		assert_eq!(expected, actual);
 	}
+
+	#[test]
+	fn test_interface_is_parsed() {
+		let interface = SolidityInterface::from_file("tests/solidity_test.sol");
+		let legacy: Vec<_> = get_selectors("tests/solidity_test.sol")
+			.into_iter()
+			.map(|sol_fn| sol_fn.signature())
+			.collect();
+
+		let signatures: Vec<_> = interface.functions.iter().map(|f| f.signature()).collect();
+		assert_eq!(signatures, legacy);
+
+		let view = interface
+			.functions
+			.iter()
+			.find(|f| f.name == "fnViewReturns")
+			.unwrap();
+		assert_eq!(view.modifier, FunctionModifier::View);
+		assert_eq!(view.returns, vec![String::from("(uint8,bytes[])")]);
+
+		let payable = interface
+			.functions
+			.iter()
+			.find(|f| f.name == "fnPayable")
+			.unwrap();
+		assert_eq!(payable.modifier, FunctionModifier::Payable);
+		assert!(payable.returns.is_empty());
+
+		assert_eq!(
+			interface.events,
+			vec![String::from(
+				"EventCustomArgs(address,(address[],uint256[],bytes[]))"
+			)]
+		);
+	}
+
+	#[test]
+	fn test_rendered_interface_is_parsed_back() {
+		let interface = SolidityInterface::from_file("tests/solidity_test.sol");
+		let rendered = interface.render("SolidityTest");
+
+		assert_eq!(
+			SolidityInterface::from_reader(&mut rendered.as_bytes()),
+			interface
+		);
+	}
 }
@@ -20,6 +20,7 @@ use {
 		prelude::*,
 		revert::Backtrace,
 	},
+	frame_support::traits::ConstU32,
 	hex_literal::hex,
 	pallet_evm::Context,
 	sp_core::{H160, H256, U256},
@@ -135,6 +136,75 @@ fn read_u256() {
 	assert_eq!(value, parsed);
 }
 
+#[test]
+fn write_i64() {
+	let value = -42i64;
+
+	let writer_output = EvmDataWriter::new().write(value).build();
+
+	let mut expected_output = [0xffu8; 32];
+	expected_output[24..].copy_from_slice(&value.to_be_bytes());
+
+	assert_eq!(writer_output, expected_output);
+}
+
+#[test]
+fn read_i64() {
+	for value in [-42i64, 42i64, i64::MIN, i64::MAX] {
+		let writer_output = EvmDataWriter::new().write(value).build();
+
+		let mut reader = EvmDataReader::new(&writer_output);
+		let parsed: i64 = reader.read().expect("to correctly parse i64");
+
+		assert_eq!(value, parsed);
+	}
+}
+
+#[test]
+fn read_i8_too_large() {
+	// Valid int16 but doesn't fit in an int8.
+	let writer_output = EvmDataWriter::new().write(-129i16).build();
+
+	let mut reader = EvmDataReader::new(&writer_output);
+	assert_eq!(
+		reader.read::<i8>(),
+		Err(RevertReason::value_is_too_large("int8").into())
+	);
+
+	// Not sign extended.
+	let mut writer_output = EvmDataWriter::new().write(-1i8).build();
+	writer_output[0] = 0;
+
+	let mut reader = EvmDataReader::new(&writer_output);
+	assert_eq!(
+		reader.read::<i8>(),
+		Err(RevertReason::value_is_too_large("int8").into())
+	);
+}
+
+#[test]
+fn write_i256() {
+	let writer_output = EvmDataWriter::new().write(I256::from(-1i128)).build();
+	assert_eq!(writer_output, [0xffu8; 32]);
+
+	let writer_output = EvmDataWriter::new().write(I256::from(42i128)).build();
+	assert_eq!(writer_output, EvmDataWriter::new().write(42u8).build());
+}
+
+#[test]
+fn read_i256() {
+	let value = I256::from(i128::MIN);
+	let writer_output = EvmDataWriter::new().write(value).build();
+
+	let mut reader = EvmDataReader::new(&writer_output);
+	let parsed: I256 = reader.read().expect("to correctly parse I256");
+
+	assert_eq!(value, parsed);
+	assert!(parsed.is_negative());
+	assert_eq!(i128::try_from(parsed), Ok(i128::MIN));
+	assert_eq!(i128::try_from(I256(U256::MAX >> 1)), Err(()));
+}
+
 #[test]
 fn read_selector() {
 	use sha3::{Digest, Keccak256};
@@ -571,6 +641,153 @@ fn write_string() {
 	assert_eq!(read("read part 4"), H256::from_slice(&padded[0x60..0x80]));
 }
 
+#[test]
+fn read_native_string() {
+	let data = "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod\
+	tempor incididunt ut labore et dolore magna aliqua.";
+	let writer_output = EvmDataWriter::new().write(Bytes::from(data)).build();
+
+	let mut reader = EvmDataReader::new(&writer_output);
+	let parsed: String = reader.read().expect("to correctly parse String");
+
+	assert_eq!(data, parsed);
+}
+
+#[test]
+fn write_native_string() {
+	let data = "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod\
+	tempor incididunt ut labore et dolore magna aliqua.";
+
+	assert_eq!(
+		EvmDataWriter::new().write(String::from(data)).build(),
+		EvmDataWriter::new().write(Bytes::from(data)).build()
+	);
+}
+
+#[test]
+fn read_string_invalid_utf8() {
+	let writer_output = EvmDataWriter::new()
+		.write(Bytes(vec![0x61, 0xff, 0x62]))
+		.build();
+
+	let mut reader = EvmDataReader::new(&writer_output);
+	assert_eq!(
+		reader.read::<String>(),
+		Err(RevertReason::custom("Value is not a valid UTF-8 string").into())
+	);
+}
+
+#[test]
+fn read_bounded_string() {
+	let writer_output = EvmDataWriter::new()
+		.write(BoundedString::<ConstU32<5>>::from("Hello"))
+		.build();
+
+	let mut reader = EvmDataReader::new(&writer_output);
+	let parsed: BoundedString<ConstU32<5>> =
+		reader.read().expect("to correctly parse BoundedString");
+	assert_eq!(parsed.as_str(), "Hello");
+
+	let mut reader = EvmDataReader::new(&writer_output);
+	assert_eq!(
+		reader
+			.read::<BoundedString<ConstU32<4>>>()
+			.map(|s| s.into_string()),
+		Err(RevertReason::value_is_too_large("length").into())
+	);
+}
+
+#[test]
+fn write_static_array_of_static_type() {
+	let output = EvmDataWriter::new()
+		.write([Address(H160::repeat_byte(0x11)), Address(H160::repeat_byte(0x22))])
+		.build();
+
+	// address[2] encoded by web3
+	let data = hex!(
+		"0000000000000000000000001111111111111111111111111111111111111111
+		0000000000000000000000002222222222222222222222222222222222222222"
+	);
+
+	assert_eq!(output, data);
+	assert!(<[Address; 2]>::has_static_size());
+	assert_eq!(<[Address; 2]>::solidity_type(), "address[2]");
+}
+
+#[test]
+fn read_static_array_of_static_type() {
+	// (address[2], uint256) encoded by web3
+	let data = hex!(
+		"0000000000000000000000001111111111111111111111111111111111111111
+		0000000000000000000000002222222222222222222222222222222222222222
+		0000000000000000000000000000000000000000000000000000000000000001"
+	);
+
+	let mut reader = EvmDataReader::new(&data);
+
+	assert_eq!(
+		reader.read::<[Address; 2]>().unwrap(),
+		[Address(H160::repeat_byte(0x11)), Address(H160::repeat_byte(0x22))]
+	);
+	assert_eq!(reader.read::<U256>().unwrap(), U256::from(1u8));
+}
+
+#[test]
+fn write_static_array_of_dynamic_type() {
+	let output = EvmDataWriter::new()
+		.write([Bytes(vec![0x01]), Bytes(vec![0x02])])
+		.build();
+
+	// bytes[2] encoded by web3
+	let data = hex!(
+		"0000000000000000000000000000000000000000000000000000000000000020
+		0000000000000000000000000000000000000000000000000000000000000040
+		0000000000000000000000000000000000000000000000000000000000000080
+		0000000000000000000000000000000000000000000000000000000000000001
+		0100000000000000000000000000000000000000000000000000000000000000
+		0000000000000000000000000000000000000000000000000000000000000001
+		0200000000000000000000000000000000000000000000000000000000000000"
+	);
+
+	assert_eq!(output, data);
+	assert!(!<[Bytes; 2]>::has_static_size());
+}
+
+#[test]
+fn read_static_array_of_dynamic_type() {
+	// bytes[2] encoded by web3
+	let data = hex!(
+		"0000000000000000000000000000000000000000000000000000000000000020
+		0000000000000000000000000000000000000000000000000000000000000040
+		0000000000000000000000000000000000000000000000000000000000000080
+		0000000000000000000000000000000000000000000000000000000000000001
+		0100000000000000000000000000000000000000000000000000000000000000
+		0000000000000000000000000000000000000000000000000000000000000001
+		0200000000000000000000000000000000000000000000000000000000000000"
+	);
+
+	let mut reader = EvmDataReader::new(&data);
+
+	assert_eq!(
+		reader.read::<[Bytes; 2]>().unwrap(),
+		[Bytes(vec![0x01]), Bytes(vec![0x02])]
+	);
+}
+
+#[test]
+fn read_static_array_too_short() {
+	let data = EvmDataWriter::new()
+		.write(Address(H160::repeat_byte(0x11)))
+		.build();
+
+	let mut reader = EvmDataReader::new(&data);
+
+	assert_eq!(
+		reader.read::<[Address; 2]>().map_err(|e| e.to_string()),
+		Err("[1]: Tried to read address out of bounds".to_string())
+	);
+}
+
 #[test]
 fn write_vec_bytes() {
 	let data = b"Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod\
@@ -684,6 +901,10 @@ impl EvmData for MultiLocation {
 	fn has_static_size() -> bool {
 		<(u8, Vec<Bytes>)>::has_static_size()
 	}
+
+	fn solidity_type() -> String {
+		<(u8, Vec<Bytes>)>::solidity_type()
+	}
 }
 
 #[generate_function_selector]
@@ -120,4 +120,19 @@ interface SolidityTest {
     /// @custom:selector 97baa040
     function fnCustomComposedArrayArg(CustomArg2[] memory arg0, uint64 arg1)
         external;
+
+    /// @param arg0 Arg0 Description
+    /// @return Return Description
+    /// @custom:selector 7d5c282b
+    function fnViewReturns(uint64 arg0)
+        external
+        view
+        returns (CustomArg0 memory);
+
+    /// @custom:selector 3a403377
+    function fnPayable() external payable;
+
+    /// @param arg0 Arg0 Description
+    /// @param arg1 Arg1 Description
+    event EventCustomArgs(address indexed arg0, CustomArg1 arg1);
 }
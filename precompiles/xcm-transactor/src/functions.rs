@@ -16,6 +16,7 @@
 
 //! Common functions to access xcm-transactor pallet dispatchables
 
+use codec::Encode;
 use fp_evm::PrecompileHandle;
 use frame_support::{
 	dispatch::{Dispatchable, GetDispatchInfo, PostDispatchInfo},
@@ -23,7 +24,8 @@ use frame_support::{
 };
 use pallet_evm::{AddressMapping, PrecompileOutput};
 use pallet_xcm_transactor::{
-	Currency, CurrencyPayment, RemoteTransactInfoWithMaxWeight, TransactWeights,
+	Currency, CurrencyPayment, RemoteTransactInfoWithMaxWeight, TransactOptions, TransactStatus,
+	TransactWeights,
 };
 use precompile_utils::prelude::*;
 use sp_core::H160;
@@ -31,6 +33,7 @@ use sp_std::{
 	boxed::Box,
 	convert::{TryFrom, TryInto},
 	marker::PhantomData,
+	vec::Vec,
 };
 use xcm::latest::MultiLocation;
 use xcm_primitives::AccountIdToCurrencyId;
@@ -59,8 +62,24 @@ where
 
 		read_args!(handle, { index: u16 });
 
+		// Indices used to be registered for all destinations, which only made sense for the
+		// relay chain. Keep returning those for backwards compatibility
+		Self::destination_account_index(MultiLocation::parent(), index)
+	}
+
+	pub(crate) fn account_index_in_destination(
+		handle: &mut impl PrecompileHandle,
+	) -> EvmResult<PrecompileOutput> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+		read_args!(handle, { dest: MultiLocation, index: u16 });
+
+		Self::destination_account_index(dest, index)
+	}
+
+	fn destination_account_index(dest: MultiLocation, index: u16) -> EvmResult<PrecompileOutput> {
 		// fetch data from pallet
-		let account: H160 = pallet_xcm_transactor::Pallet::<Runtime>::index_to_account(index)
+		let account: H160 = pallet_xcm_transactor::Pallet::<Runtime>::index_to_account(dest, index)
 			.ok_or(revert("No index assigned"))?
 			.into();
 
@@ -132,6 +151,56 @@ where
 		Ok(succeed(EvmDataWriter::new().write(fee_per_second).build()))
 	}
 
+	pub(crate) fn transact_status(
+		handle: &mut impl PrecompileHandle,
+	) -> EvmResult<PrecompileOutput> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+		read_args!(handle, { query_id: u64 });
+
+		// fetch data from pallet
+		// The status is returned as 0 (unknown query), 1 (pending), 2 (succeeded) or
+		// 3 (failed), along with the failing instruction and the SCALE-encoded XCM error
+		let (status, index, error) =
+			match pallet_xcm_transactor::Pallet::<Runtime>::transact_status(query_id) {
+				None => (0u8, 0u32, Vec::new()),
+				Some(info) => match info.status {
+					TransactStatus::Pending => (1u8, 0u32, Vec::new()),
+					TransactStatus::Succeeded => (2u8, 0u32, Vec::new()),
+					TransactStatus::Failed { index, error } => (3u8, index, error.encode()),
+				},
+			};
+
+		Ok(succeed(
+			EvmDataWriter::new()
+				.write(status)
+				.write(index)
+				.write(Bytes(error))
+				.build(),
+		))
+	}
+
+	/// The variants with options return the id of the query the destination chain reports the
+	/// status of the message to, which is 0 if no status was requested
+	fn transact_status_query_output(
+		handle: &mut impl PrecompileHandle,
+		with_options: bool,
+		report_status: bool,
+	) -> EvmResult<PrecompileOutput> {
+		if !with_options {
+			return Ok(succeed([]));
+		}
+		if !report_status {
+			return Ok(succeed(EvmDataWriter::new().write(0u64).build()));
+		}
+
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		let query_id = pallet_xcm_transactor::Pallet::<Runtime>::last_transact_status_query()
+			.ok_or(revert("transact status query not registered"))?;
+
+		Ok(succeed(EvmDataWriter::new().write(query_id).build()))
+	}
+
 	pub(crate) fn transact_through_derivative_multilocation(
 		handle: &mut impl PrecompileHandle,
 	) -> EvmResult<PrecompileOutput> {
@@ -174,16 +243,27 @@ where
 
 	pub(crate) fn transact_through_derivative_multilocation_fee_weight(
 		handle: &mut impl PrecompileHandle,
+		with_options: bool,
 	) -> EvmResult<PrecompileOutput> {
-		read_args!(handle, {
-			transactor: u8,
-			index: u16,
-			fee_asset: MultiLocation,
-			weight: u64,
-			inner_call: BoundedBytes<GetDataLimit>,
-			fee_amount: u128,
-			overall_weight: u64
-		});
+		// The variants with options take whether to report the status and whether to refund
+		// the unspent fees as extra last arguments
+		let mut input = handle.read_after_selector()?;
+		input.expect_arguments(if with_options { 9 } else { 7 })?;
+		let transactor: u8 = input.read().in_field("transactor")?;
+		let index: u16 = input.read().in_field("index")?;
+		let fee_asset: MultiLocation = input.read().in_field("feeAsset")?;
+		let weight: u64 = input.read().in_field("weight")?;
+		let inner_call: BoundedBytes<GetDataLimit> = input.read().in_field("innerCall")?;
+		let fee_amount: u128 = input.read().in_field("feeAmount")?;
+		let overall_weight: u64 = input.read().in_field("overallWeight")?;
+		let (report_status, refund): (bool, bool) = if with_options {
+			(
+				input.read().in_field("reportStatus")?,
+				input.read().in_field("refund")?,
+			)
+		} else {
+			(false, false)
+		};
 
 		let transactor = transactor
 			.try_into()
@@ -194,25 +274,41 @@ where
 		// Depending on the Runtime, this might involve a DB read. This is not the case in
 		// moonbeam, as we are using IdentityMapping
 		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
-		let call = pallet_xcm_transactor::Call::<Runtime>::transact_through_derivative {
-			dest: transactor,
-			index,
-			fee: CurrencyPayment {
-				currency: Currency::AsMultiLocation(Box::new(xcm::VersionedMultiLocation::V1(
-					fee_asset,
-				))),
-				fee_amount: Some(fee_amount),
-			},
-			inner_call,
-			weight_info: TransactWeights {
-				transact_required_weight_at_most: weight,
-				overall_weight: Some(overall_weight),
-			},
+		let fee = CurrencyPayment {
+			currency: Currency::AsMultiLocation(Box::new(xcm::VersionedMultiLocation::V1(
+				fee_asset,
+			))),
+			fee_amount: Some(fee_amount),
+		};
+		let weight_info = TransactWeights {
+			transact_required_weight_at_most: weight,
+			overall_weight: Some(overall_weight),
+		};
+		let call = if with_options {
+			pallet_xcm_transactor::Call::<Runtime>::transact_through_derivative_with_options {
+				dest: transactor,
+				index,
+				inner_call,
+				fee,
+				weight_info,
+				options: TransactOptions {
+					report_status,
+					refund,
+				},
+			}
+		} else {
+			pallet_xcm_transactor::Call::<Runtime>::transact_through_derivative {
+				dest: transactor,
+				index,
+				inner_call,
+				fee,
+				weight_info,
+			}
 		};
 
 		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call)?;
 
-		Ok(succeed([]))
+		Self::transact_status_query_output(handle, with_options, report_status)
 	}
 
 	pub(crate) fn transact_through_derivative(
@@ -264,16 +360,27 @@ where
 
 	pub(crate) fn transact_through_derivative_fee_weight(
 		handle: &mut impl PrecompileHandle,
+		with_options: bool,
 	) -> EvmResult<PrecompileOutput> {
-		read_args!(handle, {
-			transactor: u8,
-			index: u16,
-			fee_asset: Address,
-			weight: u64,
-			inner_call: BoundedBytes<GetDataLimit>,
-			fee_amount: u128,
-			overall_weight: u64
-		});
+		// The variants with options take whether to report the status and whether to refund
+		// the unspent fees as extra last arguments
+		let mut input = handle.read_after_selector()?;
+		input.expect_arguments(if with_options { 9 } else { 7 })?;
+		let transactor: u8 = input.read().in_field("transactor")?;
+		let index: u16 = input.read().in_field("index")?;
+		let fee_asset: Address = input.read().in_field("feeAsset")?;
+		let weight: u64 = input.read().in_field("weight")?;
+		let inner_call: BoundedBytes<GetDataLimit> = input.read().in_field("innerCall")?;
+		let fee_amount: u128 = input.read().in_field("feeAmount")?;
+		let overall_weight: u64 = input.read().in_field("overallWeight")?;
+		let (report_status, refund): (bool, bool) = if with_options {
+			(
+				input.read().in_field("reportStatus")?,
+				input.read().in_field("refund")?,
+			)
+		} else {
+			(false, false)
+		};
 
 		let transactor = transactor
 			.try_into()
@@ -293,23 +400,39 @@ where
 		// Depending on the Runtime, this might involve a DB read. This is not the case in
 		// moonbeam, as we are using IdentityMapping
 		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
-		let call = pallet_xcm_transactor::Call::<Runtime>::transact_through_derivative {
-			dest: transactor,
-			index,
-			fee: CurrencyPayment {
-				currency: Currency::AsCurrencyId(currency_id),
-				fee_amount: Some(fee_amount),
-			},
-			weight_info: TransactWeights {
-				transact_required_weight_at_most: weight,
-				overall_weight: Some(overall_weight),
-			},
-			inner_call,
+		let fee = CurrencyPayment {
+			currency: Currency::AsCurrencyId(currency_id),
+			fee_amount: Some(fee_amount),
+		};
+		let weight_info = TransactWeights {
+			transact_required_weight_at_most: weight,
+			overall_weight: Some(overall_weight),
+		};
+		let call = if with_options {
+			pallet_xcm_transactor::Call::<Runtime>::transact_through_derivative_with_options {
+				dest: transactor,
+				index,
+				inner_call,
+				fee,
+				weight_info,
+				options: TransactOptions {
+					report_status,
+					refund,
+				},
+			}
+		} else {
+			pallet_xcm_transactor::Call::<Runtime>::transact_through_derivative {
+				dest: transactor,
+				index,
+				inner_call,
+				fee,
+				weight_info,
+			}
 		};
 
 		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call)?;
 
-		Ok(succeed([]))
+		Self::transact_status_query_output(handle, with_options, report_status)
 	}
 
 	pub(crate) fn transact_through_signed_multilocation(
@@ -348,38 +471,65 @@ where
 
 	pub(crate) fn transact_through_signed_multilocation_fee_weight(
 		handle: &mut impl PrecompileHandle,
+		with_options: bool,
 	) -> EvmResult<PrecompileOutput> {
-		read_args!(handle, {
-			dest: MultiLocation,
-			fee_asset: MultiLocation,
-			weight: u64,
-			call: BoundedBytes<GetDataLimit>,
-			fee_amount: u128,
-			overall_weight: u64
-		});
+		// The variants with options take whether to report the status and whether to refund
+		// the unspent fees as extra last arguments
+		let mut input = handle.read_after_selector()?;
+		input.expect_arguments(if with_options { 8 } else { 6 })?;
+		let dest: MultiLocation = input.read().in_field("dest")?;
+		let fee_asset: MultiLocation = input.read().in_field("feeAsset")?;
+		let weight: u64 = input.read().in_field("weight")?;
+		let call: BoundedBytes<GetDataLimit> = input.read().in_field("call")?;
+		let fee_amount: u128 = input.read().in_field("feeAmount")?;
+		let overall_weight: u64 = input.read().in_field("overallWeight")?;
+		let (report_status, refund): (bool, bool) = if with_options {
+			(
+				input.read().in_field("reportStatus")?,
+				input.read().in_field("refund")?,
+			)
+		} else {
+			(false, false)
+		};
 		let call = call.into_vec();
 
 		// Depending on the Runtime, this might involve a DB read. This is not the case in
 		// moonbeam, as we are using IdentityMapping
 		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
-		let call = pallet_xcm_transactor::Call::<Runtime>::transact_through_signed {
-			dest: Box::new(xcm::VersionedMultiLocation::V1(dest)),
-			fee: CurrencyPayment {
-				currency: Currency::AsMultiLocation(Box::new(xcm::VersionedMultiLocation::V1(
-					fee_asset,
-				))),
-				fee_amount: Some(fee_amount),
-			},
-			weight_info: TransactWeights {
-				transact_required_weight_at_most: weight,
-				overall_weight: Some(overall_weight),
-			},
-			call,
+		let fee = CurrencyPayment {
+			currency: Currency::AsMultiLocation(Box::new(xcm::VersionedMultiLocation::V1(
+				fee_asset,
+			))),
+			fee_amount: Some(fee_amount),
+		};
+		let weight_info = TransactWeights {
+			transact_required_weight_at_most: weight,
+			overall_weight: Some(overall_weight),
+		};
+		let dest = Box::new(xcm::VersionedMultiLocation::V1(dest));
+		let call = if with_options {
+			pallet_xcm_transactor::Call::<Runtime>::transact_through_signed_with_options {
+				dest,
+				call,
+				fee,
+				weight_info,
+				options: TransactOptions {
+					report_status,
+					refund,
+				},
+			}
+		} else {
+			pallet_xcm_transactor::Call::<Runtime>::transact_through_signed {
+				dest,
+				call,
+				fee,
+				weight_info,
+			}
 		};
 
 		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call)?;
 
-		Ok(succeed([]))
+		Self::transact_status_query_output(handle, with_options, report_status)
 	}
 
 	pub(crate) fn transact_through_signed(
@@ -427,15 +577,26 @@ where
 
 	pub(crate) fn transact_through_signed_fee_weight(
 		handle: &mut impl PrecompileHandle,
+		with_options: bool,
 	) -> EvmResult<PrecompileOutput> {
-		read_args!(handle, {
-			dest: MultiLocation,
-			fee_asset: Address,
-			weight: u64,
-			call: BoundedBytes<GetDataLimit>,
-			fee_amount: u128,
-			overall_weight: u64
-		});
+		// The variants with options take whether to report the status and whether to refund
+		// the unspent fees as extra last arguments
+		let mut input = handle.read_after_selector()?;
+		input.expect_arguments(if with_options { 8 } else { 6 })?;
+		let dest: MultiLocation = input.read().in_field("dest")?;
+		let fee_asset: Address = input.read().in_field("feeAsset")?;
+		let weight: u64 = input.read().in_field("weight")?;
+		let call: BoundedBytes<GetDataLimit> = input.read().in_field("call")?;
+		let fee_amount: u128 = input.read().in_field("feeAmount")?;
+		let overall_weight: u64 = input.read().in_field("overallWeight")?;
+		let (report_status, refund): (bool, bool) = if with_options {
+			(
+				input.read().in_field("reportStatus")?,
+				input.read().in_field("refund")?,
+			)
+		} else {
+			(false, false)
+		};
 
 		let to_address: H160 = fee_asset.into();
 		let to_account = Runtime::AddressMapping::into_account_id(to_address);
@@ -452,21 +613,37 @@ where
 		// Depending on the Runtime, this might involve a DB read. This is not the case in
 		// moonbeam, as we are using IdentityMapping
 		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
-		let call = pallet_xcm_transactor::Call::<Runtime>::transact_through_signed {
-			dest: Box::new(xcm::VersionedMultiLocation::V1(dest)),
-			fee: CurrencyPayment {
-				currency: Currency::AsCurrencyId(currency_id),
-				fee_amount: Some(fee_amount),
-			},
-			weight_info: TransactWeights {
-				transact_required_weight_at_most: weight,
-				overall_weight: Some(overall_weight),
-			},
-			call,
+		let fee = CurrencyPayment {
+			currency: Currency::AsCurrencyId(currency_id),
+			fee_amount: Some(fee_amount),
+		};
+		let weight_info = TransactWeights {
+			transact_required_weight_at_most: weight,
+			overall_weight: Some(overall_weight),
+		};
+		let dest = Box::new(xcm::VersionedMultiLocation::V1(dest));
+		let call = if with_options {
+			pallet_xcm_transactor::Call::<Runtime>::transact_through_signed_with_options {
+				dest,
+				call,
+				fee,
+				weight_info,
+				options: TransactOptions {
+					report_status,
+					refund,
+				},
+			}
+		} else {
+			pallet_xcm_transactor::Call::<Runtime>::transact_through_signed {
+				dest,
+				call,
+				fee,
+				weight_info,
+			}
 		};
 
 		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call)?;
 
-		Ok(succeed([]))
+		Self::transact_status_query_output(handle, with_options, report_status)
 	}
 }
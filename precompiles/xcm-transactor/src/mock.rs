@@ -37,7 +37,7 @@ use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup},
 };
-use sp_std::marker::PhantomData;
+use sp_std::{cell::RefCell, marker::PhantomData};
 use xcm::latest::{
 	Error as XcmError,
 	Junction::{AccountKey20, GeneralIndex, PalletInstance, Parachain},
@@ -48,7 +48,7 @@ use xcm_executor::{
 	traits::{InvertLocation, TransactAsset, WeightTrader},
 	Assets,
 };
-use xcm_primitives::AccountIdToCurrencyId;
+use xcm_primitives::{AccountIdToCurrencyId, XcmQueryNotifier};
 
 pub type AccountId = TestAccount;
 pub type Balance = u128;
@@ -377,6 +377,30 @@ parameter_types! {
 	pub MaxInstructions: u32 = 100;
 }
 
+// Hands out sequential query ids, as the response handler would
+thread_local! {
+	pub static QUERY_COUNTER: RefCell<u64> = RefCell::new(0);
+}
+pub struct TestQueryNotifier;
+impl XcmQueryNotifier<pallet_xcm_transactor::Call<Runtime>, BlockNumber> for TestQueryNotifier {
+	fn new_notify_query(
+		_responder: MultiLocation,
+		_notify: pallet_xcm_transactor::Call<Runtime>,
+		_timeout: BlockNumber,
+	) -> u64 {
+		QUERY_COUNTER.with(|counter| {
+			let query_id = *counter.borrow();
+			*counter.borrow_mut() += 1;
+			query_id
+		})
+	}
+}
+
+parameter_types! {
+	pub const TransactStatusTimeout: BlockNumber = 10;
+	pub const MaxTransactStatusQueriesPerBlock: u32 = 100;
+}
+
 impl pallet_xcm_transactor::Config for Runtime {
 	type Event = Event;
 	type Balance = Balance;
@@ -393,6 +417,12 @@ impl pallet_xcm_transactor::Config for Runtime {
 	type XcmSender = DoNothingRouter;
 	type AssetTransactor = DummyAssetTransactor;
 	type ReserveProvider = orml_traits::location::RelativeReserveProvider;
+	type QueryNotifier = TestQueryNotifier;
+	type ResponseOrigin = ConvertOriginToLocal;
+	type TransactStatusTimeout = TransactStatusTimeout;
+	type MaxTransactStatusQueriesPerBlock = MaxTransactStatusQueriesPerBlock;
+	type HrmpManipulatorOrigin = frame_system::EnsureRoot<AccountId>;
+	type HrmpEncoder = MockTransactors;
 	type WeightInfo = ();
 }
 
@@ -402,6 +432,8 @@ pub enum RelayCall {
 	#[codec(index = 5u8)]
 	// the index should match the position of the module in `construct_runtime!`
 	Utility(UtilityCall),
+	#[codec(index = 6u8)]
+	Hrmp(HrmpCall),
 }
 
 #[derive(Encode, Decode)]
@@ -410,6 +442,16 @@ pub enum UtilityCall {
 	AsDerivative(u16),
 }
 
+#[derive(Encode, Decode)]
+pub enum HrmpCall {
+	#[codec(index = 0u8)]
+	InitOpenChannel(cumulus_primitives_core::ParaId, u32, u32),
+	#[codec(index = 1u8)]
+	AcceptOpenChannel(cumulus_primitives_core::ParaId),
+	#[codec(index = 2u8)]
+	CloseChannel(cumulus_primitives_core::relay_chain::HrmpChannelId),
+}
+
 #[derive(Clone, Eq, Debug, PartialEq, Ord, PartialOrd, Encode, Decode, TypeInfo)]
 pub enum MockTransactors {
 	Relay,
@@ -449,6 +491,22 @@ impl xcm_primitives::UtilityEncodeCall for MockTransactors {
 	}
 }
 
+impl xcm_primitives::HrmpEncodeCall for MockTransactors {
+	fn hrmp_encode_call(call: xcm_primitives::HrmpAvailableCalls) -> Vec<u8> {
+		match call {
+			xcm_primitives::HrmpAvailableCalls::InitOpenChannel(a, b, c) => {
+				RelayCall::Hrmp(HrmpCall::InitOpenChannel(a, b, c)).encode()
+			}
+			xcm_primitives::HrmpAvailableCalls::AcceptOpenChannel(a) => {
+				RelayCall::Hrmp(HrmpCall::AcceptOpenChannel(a)).encode()
+			}
+			xcm_primitives::HrmpAvailableCalls::CloseChannel(a) => {
+				RelayCall::Hrmp(HrmpCall::CloseChannel(a)).encode()
+			}
+		}
+	}
+}
+
 impl Into<Option<CurrencyId>> for TestAccount {
 	fn into(self) -> Option<CurrencyId> {
 		match self {
@@ -13,6 +13,7 @@
 
 // You should have received a copy of the GNU General Public License
 // along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+use crate::functions::CALL_DATA_LIMIT;
 use crate::mock::{
 	precompile_address_v1, precompile_address_v2, ExtBuilder, Origin, PrecompilesValue, Runtime,
 	TestAccount::*, TestPrecompiles, XcmTransactor,
@@ -20,11 +21,16 @@ use crate::mock::{
 use crate::v1::Action as ActionV1;
 use crate::v2::Action as ActionV2;
 
-use frame_support::assert_ok;
+use codec::Encode;
+use frame_support::{assert_ok, traits::ConstU32};
+use pallet_xcm_transactor::{TransactStatus, TransactStatusInfo, TransactStatusQueries};
 use precompile_utils::{prelude::*, solidity, testing::*};
 use sp_core::{H160, U256};
 use sp_std::boxed::Box;
-use xcm::v1::MultiLocation;
+use xcm::{
+	latest::Error as XcmError,
+	v1::{Junction, Junctions, MultiLocation},
+};
 
 fn precompiles() -> TestPrecompiles<Runtime> {
 	PrecompilesValue::get()
@@ -75,7 +81,12 @@ fn take_index_for_account() {
 				.execute_reverts(|output| output == b"No index assigned");
 
 			// register index
-			assert_ok!(XcmTransactor::register(Origin::root(), Alice.into(), 0));
+			assert_ok!(XcmTransactor::register(
+				Origin::root(),
+				Alice.into(),
+				Box::new(xcm::VersionedMultiLocation::V1(MultiLocation::parent())),
+				0
+			));
 
 			// Expected result is zero
 			precompiles()
@@ -90,6 +101,65 @@ fn take_index_for_account() {
 		});
 }
 
+#[test]
+fn take_index_for_account_in_destination() {
+	ExtBuilder::default()
+		.with_balances(vec![(Alice, 1000)])
+		.build()
+		.execute_with(|| {
+			let sibling = MultiLocation::new(1, Junctions::X1(Junction::Parachain(1000)));
+
+			let input = EvmDataWriter::new_with_selector(ActionV2::IndexToAccountInDestination)
+				.write(sibling.clone())
+				.write(0u16)
+				.build();
+
+			// register index for the relay
+			assert_ok!(XcmTransactor::register(
+				Origin::root(),
+				Alice.into(),
+				Box::new(xcm::VersionedMultiLocation::V1(MultiLocation::parent())),
+				0
+			));
+
+			// Assert that errors since no index is assigned in the sibling
+			precompiles()
+				.prepare_test(Alice, precompile_address_v2(), input.clone())
+				.execute_reverts(|output| output == b"No index assigned");
+
+			// register index for the sibling
+			assert_ok!(XcmTransactor::register(
+				Origin::root(),
+				Bob.into(),
+				Box::new(xcm::VersionedMultiLocation::V1(sibling)),
+				0
+			));
+
+			precompiles()
+				.prepare_test(Alice, precompile_address_v2(), input)
+				.expect_cost(1)
+				.expect_no_logs()
+				.execute_returns(EvmDataWriter::new().write(Address(H160::from(Bob))).build());
+
+			// The relay one is still returned by the single argument version
+			precompiles()
+				.prepare_test(
+					Alice,
+					precompile_address_v2(),
+					EvmDataWriter::new_with_selector(ActionV2::IndexToAccount)
+						.write(0u16)
+						.build(),
+				)
+				.expect_cost(1)
+				.expect_no_logs()
+				.execute_returns(
+					EvmDataWriter::new()
+						.write(Address(H160::from(Alice)))
+						.build(),
+				);
+		});
+}
+
 #[test]
 fn take_transact_info() {
 	ExtBuilder::default()
@@ -215,7 +285,12 @@ fn test_transact_derivative_multilocation_v2() {
 		.build()
 		.execute_with(|| {
 			// register index
-			assert_ok!(XcmTransactor::register(Origin::root(), Alice.into(), 0));
+			assert_ok!(XcmTransactor::register(
+				Origin::root(),
+				Alice.into(),
+				Box::new(xcm::VersionedMultiLocation::V1(MultiLocation::parent())),
+				0
+			));
 
 			// we pay with our current self reserve.
 			let fee_payer_asset = MultiLocation::parent();
@@ -253,7 +328,12 @@ fn test_transact_derivative_multilocation() {
 		.build()
 		.execute_with(|| {
 			// register index
-			assert_ok!(XcmTransactor::register(Origin::root(), Alice.into(), 0));
+			assert_ok!(XcmTransactor::register(
+				Origin::root(),
+				Alice.into(),
+				Box::new(xcm::VersionedMultiLocation::V1(MultiLocation::parent())),
+				0
+			));
 
 			// Root can set transact info
 			assert_ok!(XcmTransactor::set_transact_info(
@@ -304,7 +384,12 @@ fn test_transact_derivative() {
 		.build()
 		.execute_with(|| {
 			// register index
-			assert_ok!(XcmTransactor::register(Origin::root(), Alice.into(), 0));
+			assert_ok!(XcmTransactor::register(
+				Origin::root(),
+				Alice.into(),
+				Box::new(xcm::VersionedMultiLocation::V1(MultiLocation::parent())),
+				0
+			));
 
 			// Root can set transact info
 			assert_ok!(XcmTransactor::set_transact_info(
@@ -350,7 +435,12 @@ fn test_transact_derivative_v2() {
 		.build()
 		.execute_with(|| {
 			// register index
-			assert_ok!(XcmTransactor::register(Origin::root(), Alice.into(), 0));
+			assert_ok!(XcmTransactor::register(
+				Origin::root(),
+				Alice.into(),
+				Box::new(xcm::VersionedMultiLocation::V1(MultiLocation::parent())),
+				0
+			));
 
 			let bytes = Bytes(vec![1u8, 2u8, 3u8]);
 
@@ -541,6 +631,102 @@ fn test_transact_signed_multilocation_v2() {
 		});
 }
 
+#[test]
+fn test_transact_signed_with_options_v2() {
+	ExtBuilder::default()
+		.with_balances(vec![(Alice, 1000)])
+		.build()
+		.execute_with(|| {
+			// Destination
+			let dest = MultiLocation::parent();
+
+			let bytes: Bytes = vec![1u8, 2u8, 3u8].as_slice().into();
+
+			let total_weight = 1_000_000_000u64;
+
+			// We are transferring asset 0, which we have instructed to be the relay asset
+			// Each message returns the id of its own status query
+			for query_id in 0u64..2 {
+				precompiles()
+					.prepare_test(
+						Alice,
+						precompile_address_v2(),
+						EvmDataWriter::new_with_selector(
+							ActionV2::TransactThroughSignedWithOptions,
+						)
+						.write(dest.clone())
+						.write(Address(AssetId(0).into()))
+						.write(U256::from(4000000))
+						.write(bytes.clone())
+						.write(total_weight as u128)
+						.write(total_weight)
+						.write(true)
+						.write(true)
+						.build(),
+					)
+					.expect_cost(790476002)
+					.expect_no_logs()
+					.execute_returns(EvmDataWriter::new().write(query_id).build());
+
+				assert_eq!(
+					XcmTransactor::transact_status(query_id),
+					Some(TransactStatusInfo {
+						dest: dest.clone(),
+						status: TransactStatus::Pending
+					})
+				);
+			}
+		});
+}
+
+#[test]
+fn test_transact_status() {
+	ExtBuilder::default()
+		.with_balances(vec![(Alice, 1000)])
+		.build()
+		.execute_with(|| {
+			let error = XcmError::TooMuchWeightRequired;
+			for (query_id, status) in [
+				(1u64, TransactStatus::Pending),
+				(2u64, TransactStatus::Succeeded),
+				(3u64, TransactStatus::Failed { index: 3, error }),
+			] {
+				TransactStatusQueries::<Runtime>::insert(
+					query_id,
+					TransactStatusInfo {
+						dest: MultiLocation::parent(),
+						status,
+					},
+				);
+			}
+
+			for (query_id, status, index, error) in [
+				(0u64, 0u8, 0u32, vec![]),
+				(1u64, 1u8, 0u32, vec![]),
+				(2u64, 2u8, 0u32, vec![]),
+				(3u64, 3u8, 3u32, error.encode()),
+			] {
+				precompiles()
+					.prepare_test(
+						Alice,
+						precompile_address_v2(),
+						EvmDataWriter::new_with_selector(ActionV2::TransactStatus)
+							.write(query_id)
+							.build(),
+					)
+					.expect_cost(1)
+					.expect_no_logs()
+					.execute_returns(
+						EvmDataWriter::new()
+							.write(status)
+							.write(index)
+							.write(Bytes(error))
+							.build(),
+					);
+			}
+		});
+}
+
 #[test]
 fn test_solidity_interface_has_all_function_selectors_documented_and_implemented_v1() {
 	for file in ["src/v1/XcmTransactorV1.sol"] {
@@ -593,16 +779,7 @@ fn test_solidity_interface_has_all_function_selectors_documented_and_implemented
 
 #[test]
 fn test_deprecated_solidity_selectors_are_supported() {
-	for deprecated_function in [
-		"index_to_account(uint16)",
-		"transact_info((uint8,bytes[]))",
-		"transact_through_derivative_multilocation(uint8,uint16,(uint8,bytes[]),uint64,bytes)",
-		"transact_through_derivative(uint8,uint16,address,uint64,bytes)",
-		"transact_info_with_signed((uint8,bytes[]))",
-		"fee_per_second((uint8,bytes[]))",
-		"transact_through_signed_multilocation((uint8,bytes[]),(uint8,bytes[]),uint64,bytes)",
-		"transact_through_signed((uint8,bytes[]),address,uint64,bytes)",
-	] {
+	for deprecated_function in DEPRECATED_FUNCTIONS_V1 {
 		let selector = solidity::compute_selector(deprecated_function);
 		if ActionV1::try_from(selector).is_err() {
 			panic!(
@@ -612,3 +789,268 @@ fn test_deprecated_solidity_selectors_are_supported() {
 		}
 	}
 }
+
+/// Deprecated functions still supported by the V1 precompile, but not declared in the
+/// solidity file.
+const DEPRECATED_FUNCTIONS_V1: &[&str] = &[
+	"index_to_account(uint16)",
+	"transact_info((uint8,bytes[]))",
+	"transact_through_derivative_multilocation(uint8,uint16,(uint8,bytes[]),uint64,bytes)",
+	"transact_through_derivative(uint8,uint16,address,uint64,bytes)",
+	"transact_info_with_signed((uint8,bytes[]))",
+	"fee_per_second((uint8,bytes[]))",
+	"transact_through_signed_multilocation((uint8,bytes[]),(uint8,bytes[]),uint64,bytes)",
+	"transact_through_signed((uint8,bytes[]),address,uint64,bytes)",
+];
+
+#[test]
+fn test_solidity_interface_matches_rust_definitions_v1() {
+	let function = |signature, args: &[(&str, String)], returns: &[String]| {
+		let action = ActionV1::try_from(solidity::compute_selector(signature))
+			.expect("function is dispatched");
+		solidity::FunctionDeclaration::from_signature(signature, args, action.modifier(), returns)
+	};
+	let call = |name| {
+		(
+			name,
+			BoundedBytes::<ConstU32<CALL_DATA_LIMIT>>::solidity_type(),
+		)
+	};
+	let location = |name| (name, MultiLocation::solidity_type());
+
+	let functions = vec![
+		function(
+			"indexToAccount(uint16)",
+			&[("index", u16::solidity_type())],
+			&[Address::solidity_type()],
+		),
+		// The fee per second is written as a narrower integer, but the ABI declares it as
+		// uint256.
+		function(
+			"transactInfo((uint8,bytes[]))",
+			&[location("multilocation")],
+			&[
+				u64::solidity_type(),
+				U256::solidity_type(),
+				u64::solidity_type(),
+			],
+		),
+		function(
+			"transactInfoWithSigned((uint8,bytes[]))",
+			&[location("multilocation")],
+			&[
+				u64::solidity_type(),
+				u64::solidity_type(),
+				u64::solidity_type(),
+			],
+		),
+		function(
+			"feePerSecond((uint8,bytes[]))",
+			&[location("multilocation")],
+			&[U256::solidity_type()],
+		),
+		function(
+			"transactThroughDerivativeMultilocation(uint8,uint16,(uint8,bytes[]),uint64,bytes)",
+			&[
+				("transactor", u8::solidity_type()),
+				("index", u16::solidity_type()),
+				location("feeAsset"),
+				("weight", u64::solidity_type()),
+				call("innerCall"),
+			],
+			&[],
+		),
+		function(
+			"transactThroughDerivative(uint8,uint16,address,uint64,bytes)",
+			&[
+				("transactor", u8::solidity_type()),
+				("index", u16::solidity_type()),
+				("currencyId", Address::solidity_type()),
+				("weight", u64::solidity_type()),
+				call("innerCall"),
+			],
+			&[],
+		),
+		function(
+			"transactThroughSignedMultilocation((uint8,bytes[]),(uint8,bytes[]),uint64,bytes)",
+			&[
+				location("dest"),
+				location("feeLocation"),
+				("weight", u64::solidity_type()),
+				call("call"),
+			],
+			&[],
+		),
+		function(
+			"transactThroughSigned((uint8,bytes[]),address,uint64,bytes)",
+			&[
+				location("dest"),
+				("feeLocationAddress", Address::solidity_type()),
+				("weight", u64::solidity_type()),
+				call("call"),
+			],
+			&[],
+		),
+	];
+
+	solidity::SolidityInterface::from_declarations(
+		functions,
+		ActionV1::signatures()
+			.iter()
+			.map(|(_, signature)| *signature)
+			.filter(|signature| !DEPRECATED_FUNCTIONS_V1.contains(signature)),
+	)
+	.assert_matches_file("src/v1/XcmTransactorV1.sol", "XcmTransactorV1");
+}
+
+#[test]
+fn test_solidity_interface_matches_rust_definitions_v2() {
+	let function = |signature, args: &[(&str, String)], returns: &[String]| {
+		let action = ActionV2::try_from(solidity::compute_selector(signature))
+			.expect("function is dispatched");
+		solidity::FunctionDeclaration::from_signature(signature, args, action.modifier(), returns)
+	};
+	let call = |name| {
+		(
+			name,
+			BoundedBytes::<ConstU32<CALL_DATA_LIMIT>>::solidity_type(),
+		)
+	};
+	let location = |name| (name, MultiLocation::solidity_type());
+	// The fee amount is read as a narrower integer, but the ABI declares it as uint256.
+	let fee_args = [
+		("feeAmount", U256::solidity_type()),
+		("overallWeight", u64::solidity_type()),
+	];
+	let option_args = [
+		("reportStatus", bool::solidity_type()),
+		("refund", bool::solidity_type()),
+	];
+	let derivative_multilocation_args = [
+		("transactor", u8::solidity_type()),
+		("index", u16::solidity_type()),
+		location("feeAsset"),
+		("transactRequiredWeightAtMost", u64::solidity_type()),
+		call("innerCall"),
+	];
+	let derivative_args = [
+		("transactor", u8::solidity_type()),
+		("index", u16::solidity_type()),
+		("currencyId", Address::solidity_type()),
+		("transactRequiredWeightAtMost", u64::solidity_type()),
+		call("innerCall"),
+	];
+	let signed_multilocation_args = [
+		location("dest"),
+		location("feeLocation"),
+		("transactRequiredWeightAtMost", u64::solidity_type()),
+		call("call"),
+	];
+	let signed_args = [
+		location("dest"),
+		("feeLocationAddress", Address::solidity_type()),
+		("transactRequiredWeightAtMost", u64::solidity_type()),
+		call("call"),
+	];
+	let query_id = [u64::solidity_type()];
+
+	let functions = vec![
+		function(
+			"indexToAccount(uint16)",
+			&[("index", u16::solidity_type())],
+			&[Address::solidity_type()],
+		),
+		function(
+			"indexToAccount((uint8,bytes[]),uint16)",
+			&[location("destination"), ("index", u16::solidity_type())],
+			&[Address::solidity_type()],
+		),
+		function(
+			"transactInfoWithSigned((uint8,bytes[]))",
+			&[location("multilocation")],
+			&[
+				u64::solidity_type(),
+				u64::solidity_type(),
+				u64::solidity_type(),
+			],
+		),
+		function(
+			"feePerSecond((uint8,bytes[]))",
+			&[location("multilocation")],
+			&[U256::solidity_type()],
+		),
+		function(
+			"transactStatus(uint64)",
+			&[("queryId", u64::solidity_type())],
+			&[
+				u8::solidity_type(),
+				u32::solidity_type(),
+				Bytes::solidity_type(),
+			],
+		),
+		function(
+			"transactThroughDerivativeMultilocation(\
+				uint8,uint16,(uint8,bytes[]),uint64,bytes,uint256,uint64)",
+			&[&derivative_multilocation_args[..], &fee_args[..]].concat(),
+			&[],
+		),
+		function(
+			"transactThroughDerivative(uint8,uint16,address,uint64,bytes,uint256,uint64)",
+			&[&derivative_args[..], &fee_args[..]].concat(),
+			&[],
+		),
+		function(
+			"transactThroughSignedMultilocation(\
+				(uint8,bytes[]),(uint8,bytes[]),uint64,bytes,uint256,uint64)",
+			&[&signed_multilocation_args[..], &fee_args[..]].concat(),
+			&[],
+		),
+		function(
+			"transactThroughSigned((uint8,bytes[]),address,uint64,bytes,uint256,uint64)",
+			&[&signed_args[..], &fee_args[..]].concat(),
+			&[],
+		),
+		function(
+			"transactThroughDerivativeMultilocation(\
+				uint8,uint16,(uint8,bytes[]),uint64,bytes,uint256,uint64,bool,bool)",
+			&[
+				&derivative_multilocation_args[..],
+				&fee_args[..],
+				&option_args[..],
+			]
+			.concat(),
+			&query_id,
+		),
+		function(
+			"transactThroughDerivative(\
+				uint8,uint16,address,uint64,bytes,uint256,uint64,bool,bool)",
+			&[&derivative_args[..], &fee_args[..], &option_args[..]].concat(),
+			&query_id,
+		),
+		function(
+			"transactThroughSignedMultilocation(\
+				(uint8,bytes[]),(uint8,bytes[]),uint64,bytes,uint256,uint64,bool,bool)",
+			&[
+				&signed_multilocation_args[..],
+				&fee_args[..],
+				&option_args[..],
+			]
+			.concat(),
+			&query_id,
+		),
+		function(
+			"transactThroughSigned(\
+				(uint8,bytes[]),address,uint64,bytes,uint256,uint64,bool,bool)",
+			&[&signed_args[..], &fee_args[..], &option_args[..]].concat(),
+			&query_id,
+		),
+	];
+
+	solidity::SolidityInterface::from_declarations(
+		functions,
+		ActionV2::signatures()
+			.iter()
+			.map(|(_, signature)| *signature),
+	)
+	.assert_matches_file("src/v2/XcmTransactorV2.sol", "XcmTransactorV2");
+}
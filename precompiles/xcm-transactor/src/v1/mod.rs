@@ -58,6 +58,23 @@ pub enum Action {
 		"transact_through_signed((uint8,bytes[]),address,uint64,bytes)",
 }
 
+impl Action {
+	/// Modifier of the function called with the action.
+	pub fn modifier(&self) -> FunctionModifier {
+		match self {
+			Action::TransactThroughDerivativeMultiLocation
+			| Action::TransactThroughDerivative
+			| Action::TransactThroughSignedMultiLocation
+			| Action::TransactThroughSigned
+			| Action::DeprecatedTransactThroughDerivativeMultiLocation
+			| Action::DeprecatedTransactThroughDerivative
+			| Action::DeprecatedTransactThroughSignedMultiLocation
+			| Action::DeprecatedTransactThroughSigned => FunctionModifier::NonPayable,
+			_ => FunctionModifier::View,
+		}
+	}
+}
+
 /// A precompile to wrap the functionality from xcm transactor
 pub struct XcmTransactorWrapperV1<Runtime>(PhantomData<Runtime>);
 
@@ -74,17 +91,7 @@ where
 	fn execute(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
 		let selector = handle.read_selector()?;
 
-		handle.check_function_modifier(match selector {
-			Action::TransactThroughDerivativeMultiLocation
-			| Action::TransactThroughDerivative
-			| Action::TransactThroughSignedMultiLocation
-			| Action::TransactThroughSigned
-			| Action::DeprecatedTransactThroughDerivativeMultiLocation
-			| Action::DeprecatedTransactThroughDerivative
-			| Action::DeprecatedTransactThroughSignedMultiLocation
-			| Action::DeprecatedTransactThroughSigned => FunctionModifier::NonPayable,
-			_ => FunctionModifier::View,
-		})?;
+		handle.check_function_modifier(selector.modifier())?;
 
 		match selector {
 			// Check for accessor methods first. These return results immediately
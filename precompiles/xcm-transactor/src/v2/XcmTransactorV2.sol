@@ -11,13 +11,24 @@ interface XcmTransactorV2 {
         bytes[] interior;
     }
 
-    /// Get index of an account in xcm transactor
+    /// Get index of an account in xcm transactor, registered for the relay chain
     /// @custom:selector 3fdc4f36
     /// @param index The index of which we want to retrieve the account
     /// @return owner The owner of the derivative index
     ///
     function indexToAccount(uint16 index) external view returns (address owner);
 
+    /// Get index of an account in xcm transactor, registered for a given destination
+    /// @custom:selector 906480bb
+    /// @param destination The destination in which the index is registered
+    /// @param index The index of which we want to retrieve the account
+    /// @return owner The owner of the derivative index
+    ///
+    function indexToAccount(Multilocation memory destination, uint16 index)
+        external
+        view
+        returns (address owner);
+
     /// Get transact info of a multilocation
     /// @custom:selector b689e20c
     /// @param multilocation The location for which we want to know the transact info
@@ -128,4 +139,146 @@ interface XcmTransactorV2 {
         uint256 feeAmount,
         uint64 overallWeight
     ) external;
+
+    /// Get the status of a transact message sent with status reporting enabled
+    /// @custom:selector 30015ade
+    /// @dev In XCM v2 the destination chain only reports XCM execution errors. A call that is
+    /// dispatched and fails in the destination chain is still reported as succeeded
+    /// @param queryId The id of the query, as returned by the functions with options and emitted
+    /// by the TransactStatusQueried event
+    /// @return status 0 if the query is unknown or timed out, 1 if pending, 2 if succeeded and
+    /// 3 if failed
+    /// @return index The index of the failing instruction in the message
+    /// @return error The SCALE-encoded XCM error, if failed
+    ///
+    function transactStatus(uint64 queryId)
+        external
+        view
+        returns (
+            uint8 status,
+            uint32 index,
+            bytes memory error
+        );
+
+    /// Transact through XCM using fee based on its multilocation, optionally asking the
+    /// destination chain to report the outcome back
+    /// @custom:selector 13bf27c6
+    /// @dev The token transfer burns/transfers the corresponding amount before sending
+    /// @param transactor The transactor to be used
+    /// @param index The index to be used
+    /// @param feeAsset The asset in which we want to pay fees.
+    /// It has to be a reserve of the destination chain
+    /// @param transactRequiredWeightAtMost The weight we want to buy in the destination chain
+    /// @param innerCall The inner call to be executed in the destination chain
+    /// @param feeAmount Amount to be used as fee.
+    /// @param overallWeight Overall weight to be used for the xcm message.
+    /// @param reportStatus Whether the outcome of the message should be reported back. The weight
+    /// of the instructions appended to the message is added to overallWeight
+    /// @param refund Must be false, the fees are paid by the sovereign account of this chain and
+    /// cannot be refunded to the caller
+    /// @return queryId The id of the query to be passed to transactStatus, 0 if reportStatus is
+    /// false
+    function transactThroughDerivativeMultilocation(
+        uint8 transactor,
+        uint16 index,
+        Multilocation memory feeAsset,
+        uint64 transactRequiredWeightAtMost,
+        bytes memory innerCall,
+        uint256 feeAmount,
+        uint64 overallWeight,
+        bool reportStatus,
+        bool refund
+    ) external returns (uint64 queryId);
+
+    /// Transact through XCM using fee based on its currency_id, optionally asking the
+    /// destination chain to report the outcome back
+    /// @custom:selector 70683189
+    /// @dev The token transfer burns/transfers the corresponding amount before sending
+    /// @param transactor The transactor to be used
+    /// @param index The index to be used
+    /// @param currencyId Address of the currencyId of the asset to be used for fees
+    /// It has to be a reserve of the destination chain
+    /// @param transactRequiredWeightAtMost The weight we want to buy in the destination chain
+    /// @param innerCall The inner call to be executed in the destination chain
+    /// @param feeAmount Amount to be used as fee.
+    /// @param overallWeight Overall weight to be used for the xcm message.
+    /// @param reportStatus Whether the outcome of the message should be reported back. The weight
+    /// of the instructions appended to the message is added to overallWeight
+    /// @param refund Must be false, the fees are paid by the sovereign account of this chain and
+    /// cannot be refunded to the caller
+    /// @return queryId The id of the query to be passed to transactStatus, 0 if reportStatus is
+    /// false
+    function transactThroughDerivative(
+        uint8 transactor,
+        uint16 index,
+        address currencyId,
+        uint64 transactRequiredWeightAtMost,
+        bytes memory innerCall,
+        uint256 feeAmount,
+        uint64 overallWeight,
+        bool reportStatus,
+        bool refund
+    ) external returns (uint64 queryId);
+
+    /// Transact through XCM using fee based on its multilocation through signed origins,
+    /// optionally asking the destination chain to report the outcome back and to refund the
+    /// unspent fees
+    /// @custom:selector f72ef212
+    /// @dev No token is burnt before sending the message. The caller must ensure the destination
+    /// is able to undertand the DescendOrigin message, and create a unique account from which
+    /// dispatch the call
+    /// @param dest The destination chain (as multilocation) where to send the message
+    /// @param feeLocation The asset multilocation that indentifies the fee payment currency
+    /// It has to be a reserve of the destination chain
+    /// @param transactRequiredWeightAtMost The weight we want to buy in the destination chain for the call to be made
+    /// @param call The call to be executed in the destination chain
+    /// @param feeAmount Amount to be used as fee.
+    /// @param overallWeight Overall weight to be used for the xcm message.
+    /// @param reportStatus Whether the outcome of the message should be reported back. The weight
+    /// of the instructions appended to the message is added to overallWeight
+    /// @param refund Whether the unspent fees should be deposited into the account derived from
+    /// the caller in the destination chain
+    /// @return queryId The id of the query to be passed to transactStatus, 0 if reportStatus is
+    /// false
+    function transactThroughSignedMultilocation(
+        Multilocation memory dest,
+        Multilocation memory feeLocation,
+        uint64 transactRequiredWeightAtMost,
+        bytes memory call,
+        uint256 feeAmount,
+        uint64 overallWeight,
+        bool reportStatus,
+        bool refund
+    ) external returns (uint64 queryId);
+
+    /// Transact through XCM using fee based on its erc20 address through signed origins,
+    /// optionally asking the destination chain to report the outcome back and to refund the
+    /// unspent fees
+    /// @custom:selector 81cf817c
+    /// @dev No token is burnt before sending the message. The caller must ensure the destination
+    /// is able to undertand the DescendOrigin message, and create a unique account from which
+    /// dispatch the call
+    /// @param dest The destination chain (as multilocation) where to send the message
+    /// @param feeLocationAddress The ERC20 address of the token we want to use to pay for fees
+    /// only callable if such an asset has been BRIDGED to our chain
+    /// @param transactRequiredWeightAtMost The weight we want to buy in the destination chain for the call to be made
+    /// @param call The call to be executed in the destination chain
+    /// @param feeAmount Amount to be used as fee.
+    /// @param overallWeight Overall weight to be used for the xcm message.
+    /// @param reportStatus Whether the outcome of the message should be reported back. The weight
+    /// of the instructions appended to the message is added to overallWeight
+    /// @param refund Whether the unspent fees should be deposited into the account derived from
+    /// the caller in the destination chain
+    /// @return queryId The id of the query to be passed to transactStatus, 0 if reportStatus is
+    /// false
+    function transactThroughSigned(
+        Multilocation memory dest,
+        address feeLocationAddress,
+        uint64 transactRequiredWeightAtMost,
+        bytes memory call,
+        uint256 feeAmount,
+        uint64 overallWeight,
+        bool reportStatus,
+        bool refund
+    ) external returns (uint64 queryId);
 }
@@ -31,6 +31,7 @@ use xcm_primitives::AccountIdToCurrencyId;
 #[derive(Debug, PartialEq)]
 pub enum Action {
 	IndexToAccount = "indexToAccount(uint16)",
+	IndexToAccountInDestination = "indexToAccount((uint8,bytes[]),uint16)",
 	TransactInfoWithSigned = "transactInfoWithSigned((uint8,bytes[]))",
 	FeePerSecond = "feePerSecond((uint8,bytes[]))",
 	TransactThroughDerivative =
@@ -53,6 +54,48 @@ pub enum Action {
 	)",
 	TransactThroughSigned =
 		"transactThroughSigned((uint8,bytes[]),address,uint64,bytes,uint256,uint64)",
+	TransactStatus = "transactStatus(uint64)",
+	TransactThroughDerivativeWithOptions =
+		"transactThroughDerivative(uint8,uint16,address,uint64,bytes,uint256,uint64,bool,bool)",
+	TransactThroughDerivativeMultiLocationWithOptions = "transactThroughDerivativeMultilocation(\
+		uint8,\
+		uint16,\
+		(uint8,bytes[]),\
+		uint64,bytes,\
+		uint256,\
+		uint64,\
+		bool,\
+		bool\
+	)",
+	TransactThroughSignedMultiLocationWithOptions = "transactThroughSignedMultilocation(\
+		(uint8,bytes[]),\
+		(uint8,bytes[]),\
+		uint64,\
+		bytes,\
+		uint256,\
+		uint64,\
+		bool,\
+		bool\
+	)",
+	TransactThroughSignedWithOptions =
+		"transactThroughSigned((uint8,bytes[]),address,uint64,bytes,uint256,uint64,bool,bool)",
+}
+
+impl Action {
+	/// Modifier of the function called with the action.
+	pub fn modifier(&self) -> FunctionModifier {
+		match self {
+			Action::TransactThroughDerivativeMultiLocation
+			| Action::TransactThroughDerivative
+			| Action::TransactThroughSignedMultiLocation
+			| Action::TransactThroughSigned
+			| Action::TransactThroughDerivativeMultiLocationWithOptions
+			| Action::TransactThroughDerivativeWithOptions
+			| Action::TransactThroughSignedMultiLocationWithOptions
+			| Action::TransactThroughSignedWithOptions => FunctionModifier::NonPayable,
+			_ => FunctionModifier::View,
+		}
+	}
 }
 
 /// A precompile to wrap the functionality from xcm transactor
@@ -71,34 +114,44 @@ where
 	fn execute(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
 		let selector = handle.read_selector()?;
 
-		handle.check_function_modifier(match selector {
-			Action::TransactThroughDerivativeMultiLocation
-			| Action::TransactThroughDerivative
-			| Action::TransactThroughSignedMultiLocation
-			| Action::TransactThroughSigned => FunctionModifier::NonPayable,
-			_ => FunctionModifier::View,
-		})?;
+		handle.check_function_modifier(selector.modifier())?;
 
 		match selector {
 			// Check for accessor methods first. These return results immediately
 			Action::IndexToAccount => {
 				XcmTransactorWrapper::<Runtime>::account_index(handle)
 			}
+			Action::IndexToAccountInDestination => {
+				XcmTransactorWrapper::<Runtime>::account_index_in_destination(handle)
+			}
 			Action::TransactInfoWithSigned => {
 				XcmTransactorWrapper::<Runtime>::transact_info_with_signed(handle)
 			}
 			Action::FeePerSecond => XcmTransactorWrapper::<Runtime>::fee_per_second(handle),
+			Action::TransactStatus => XcmTransactorWrapper::<Runtime>::transact_status(handle),
 			Action::TransactThroughDerivativeMultiLocation => {
-				XcmTransactorWrapper::<Runtime>::transact_through_derivative_multilocation_fee_weight(handle)
+				XcmTransactorWrapper::<Runtime>::transact_through_derivative_multilocation_fee_weight(handle, false)
+			}
+			Action::TransactThroughDerivativeMultiLocationWithOptions => {
+				XcmTransactorWrapper::<Runtime>::transact_through_derivative_multilocation_fee_weight(handle, true)
 			}
 			Action::TransactThroughDerivative => {
-				XcmTransactorWrapper::<Runtime>::transact_through_derivative_fee_weight(handle)
+				XcmTransactorWrapper::<Runtime>::transact_through_derivative_fee_weight(handle, false)
+			}
+			Action::TransactThroughDerivativeWithOptions => {
+				XcmTransactorWrapper::<Runtime>::transact_through_derivative_fee_weight(handle, true)
 			}
 			Action::TransactThroughSignedMultiLocation => {
-				XcmTransactorWrapper::<Runtime>::transact_through_signed_multilocation_fee_weight(handle)
+				XcmTransactorWrapper::<Runtime>::transact_through_signed_multilocation_fee_weight(handle, false)
+			}
+			Action::TransactThroughSignedMultiLocationWithOptions => {
+				XcmTransactorWrapper::<Runtime>::transact_through_signed_multilocation_fee_weight(handle, true)
 			}
 			Action::TransactThroughSigned => {
-				XcmTransactorWrapper::<Runtime>::transact_through_signed_fee_weight(handle)
+				XcmTransactorWrapper::<Runtime>::transact_through_signed_fee_weight(handle, false)
+			}
+			Action::TransactThroughSignedWithOptions => {
+				XcmTransactorWrapper::<Runtime>::transact_through_signed_fee_weight(handle, true)
 			}
 		}
 	}
@@ -20,4 +20,66 @@ interface XcmUtils {
         external
         view
         returns (address account);
+
+    // Assets trapped for an account, as indexed by the trapped assets pallet
+    struct TrappedAssets {
+        bytes32 hash;
+        bytes assets;
+        uint32 count;
+    }
+
+    /// List the assets trapped for an account that have not been claimed yet
+    /// Only the assets trapped since the trapped assets pallet was added to the runtime are
+    /// listed, and at most the newest MaxTrapsPerOrigin of them. Older traps are not indexed but
+    /// can still be claimed with claimTrappedAssets.
+    /// @custom:selector 6f873d0d
+    /// @param account The account for which the assets were trapped
+    /// @return traps The hash under which each set of assets is trapped in pallet-xcm, the
+    /// SCALE-encoded VersionedMultiAssets and how many times they can be claimed
+    function trappedAssets(address account)
+        external
+        view
+        returns (TrappedAssets[] memory traps);
+
+    /// Claim assets trapped for the caller, depositing them into its account
+    /// @custom:selector aca23d46
+    /// @param assets The SCALE-encoded VersionedMultiAssets to claim
+    function claimTrappedAssets(bytes memory assets) external;
+
+    /// Get the weight that a message would consume in our chain
+    /// @custom:selector 25d54154
+    /// @param message The SCALE-encoded VersionedXcm message to weight
+    /// @return weight The weight the message would consume
+    function weightMessage(bytes memory message)
+        external
+        view
+        returns (uint64 weight);
+
+    /// Get the units per second charged in our chain for an asset used to pay XCM fees
+    /// @custom:selector 3f0f65db
+    /// @param multilocation The asset location for which we want to know the units per second
+    /// @return unitsPerSecond The units per second charged for the asset
+    function getUnitsPerSecond(Multilocation memory multilocation)
+        external
+        view
+        returns (uint256 unitsPerSecond);
+
+    /// Execute a message in our chain on behalf of the caller
+    /// @custom:selector 34334a02
+    /// @dev Only asset handling instructions are allowed, and the messages they send to other
+    /// chains cannot contain instructions reporting to a query. The message must also pass the
+    /// execute filter the runtime sets for this precompile, which is distinct from the one of
+    /// pallet-xcm
+    /// @param message The SCALE-encoded VersionedXcm message to execute
+    /// @param maxWeight The maximum weight the message is allowed to consume
+    function xcmExecute(bytes memory message, uint64 maxWeight) external;
+
+    /// Send a message to be executed in another chain on behalf of the caller
+    /// @custom:selector 98600e64
+    /// @dev The message is sent through pallet-xcm, which checks that the caller is allowed to
+    /// send messages and prefixes it with a DescendOrigin of the caller. Besides the instructions
+    /// allowed in xcmExecute, only Transact is allowed
+    /// @param dest The destination chain (as multilocation) where to send the message
+    /// @param message The SCALE-encoded VersionedXcm message to send
+    function xcmSend(Multilocation memory dest, bytes memory message) external;
 }
@@ -19,70 +19,83 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![feature(assert_matches)]
 
+use codec::{Decode, DecodeLimit, Encode};
 use fp_evm::PrecompileHandle;
 use frame_support::dispatch::Dispatchable;
-use frame_support::traits::OriginTrait;
-use pallet_evm::PrecompileOutput;
+use frame_support::traits::{ConstU32, Contains, EnsureOrigin, OriginTrait};
+use frame_support::weights::Weight;
+use pallet_evm::{AddressMapping, GasWeightMapping};
 use precompile_utils::prelude::*;
-use sp_core::H160;
-use sp_std::{fmt::Debug, marker::PhantomData};
-use xcm::latest::{MultiLocation, OriginKind};
-use xcm_executor::traits::ConvertOrigin;
+use sp_core::{H160, H256, U256};
+use sp_std::{fmt::Debug, marker::PhantomData, vec, vec::Vec};
+use xcm::{
+	latest::{
+		ExecuteXcm,
+		Instruction::{self, *},
+		Junction::AccountKey20,
+		Junctions::{self, X1},
+		MultiAssetFilter, MultiAssets, MultiLocation, NetworkId, OriginKind, WildMultiAsset, Xcm,
+	},
+	VersionedMultiAssets, VersionedXcm, MAX_XCM_DECODE_DEPTH,
+};
+use xcm_executor::traits::{ConvertOrigin, WeightBounds};
+use xcm_executor::XcmExecutor;
 
 pub type XcmOriginOf<XcmConfig> =
 	<<XcmConfig as xcm_executor::Config>::Call as Dispatchable>::Origin;
 pub type XcmAccountIdOf<XcmConfig> =
 	<<<XcmConfig as xcm_executor::Config>::Call as Dispatchable>::Origin as OriginTrait>::AccountId;
 
+/// Maximum size of the SCALE-encoded assets accepted by the precompile
+pub const ASSETS_DATA_LIMIT: u32 = 2u32.pow(16);
+/// Maximum size of the SCALE-encoded XCM messages accepted by the precompile
+pub const MESSAGE_DATA_LIMIT: u32 = 2u32.pow(16);
+/// Gas charged per byte of the SCALE-encoded XCM messages, which are decoded and weighed by the
+/// precompile
+pub const MESSAGE_BYTE_GAS_COST: u64 = 16;
+/// Weight charged for delivering a sent message, the same pallet-xcm charges in its send
+/// extrinsic
+pub const SEND_DELIVERY_WEIGHT: Weight = 100_000_000;
+
 #[cfg(test)]
 mod mock;
 #[cfg(test)]
 mod tests;
 
-#[generate_function_selector]
-#[derive(Debug, PartialEq)]
-pub enum Action {
-	MultiLocationToAddress = "multilocationToAddress((uint8,bytes[]))",
-}
-
 /// A precompile to wrap the functionality from xcm-utils
-pub struct XcmUtilsWrapper<Runtime, XcmConfig>(PhantomData<(Runtime, XcmConfig)>);
+///
+/// The messages executed through `xcmExecute` are restricted to asset handling instructions, and
+/// must also pass `XcmExecuteFilter`. It is distinct from the execute filter of pallet-xcm, so
+/// that a runtime can let contracts execute messages while keeping the pallet-xcm extrinsic
+/// disabled.
+pub struct XcmUtilsWrapper<Runtime, XcmConfig, XcmExecuteFilter>(
+	PhantomData<(Runtime, XcmConfig, XcmExecuteFilter)>,
+);
 
-impl<Runtime, XcmConfig> pallet_evm::Precompile for XcmUtilsWrapper<Runtime, XcmConfig>
+#[precompile]
+impl<Runtime, XcmConfig, XcmExecuteFilter> XcmUtilsWrapper<Runtime, XcmConfig, XcmExecuteFilter>
 where
-	Runtime: pallet_evm::Config + frame_system::Config,
+	Runtime: pallet_evm::Config
+		+ frame_system::Config
+		+ pallet_xcm::Config
+		+ pallet_trapped_assets::Config
+		+ pallet_asset_manager::Config,
+	<Runtime as pallet_asset_manager::Config>::ForeignAssetType: From<MultiLocation>,
 	XcmOriginOf<XcmConfig>: OriginTrait,
 	XcmAccountIdOf<XcmConfig>: Into<H160>,
 	XcmConfig: xcm_executor::Config,
+	XcmExecuteFilter: Contains<(MultiLocation, Xcm<<Runtime as frame_system::Config>::Call>)>,
 {
-	fn execute(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
-		let selector = handle.read_selector()?;
-
-		handle.check_function_modifier(match selector {
-			Action::MultiLocationToAddress => FunctionModifier::View,
-		})?;
-
-		match selector {
-			// Check for accessor methods first. These return results immediately
-			Action::MultiLocationToAddress => Self::multilocation_to_address(handle),
-		}
-	}
-}
-
-impl<Runtime, XcmConfig> XcmUtilsWrapper<Runtime, XcmConfig>
-where
-	Runtime: pallet_evm::Config + frame_system::Config,
-	XcmOriginOf<XcmConfig>: OriginTrait,
-	XcmAccountIdOf<XcmConfig>: Into<H160>,
-	XcmConfig: xcm_executor::Config,
-{
-	fn multilocation_to_address(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+	#[precompile::public("multilocationToAddress((uint8,bytes[]))")]
+	#[precompile::view]
+	fn multilocation_to_address(
+		handle: &mut impl PrecompileHandle,
+		multilocation: MultiLocation,
+	) -> EvmResult<Address> {
 		// TODO: Change once precompiles are benchmarked
 		// for now we charge a db read,
 		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
 
-		read_args!(handle, { multilocation: MultiLocation });
-
 		let origin =
 			XcmConfig::OriginConverter::convert_origin(multilocation, OriginKind::SovereignAccount)
 				.map_err(|_| {
@@ -96,8 +109,253 @@ where
 				RevertReason::custom("Failed multilocation conversion").in_field("multilocation"),
 			)?
 			.into();
-		Ok(succeed(
-			EvmDataWriter::new().write(Address(account)).build(),
-		))
+		Ok(Address(account))
+	}
+
+	#[precompile::public("trappedAssets(address)")]
+	#[precompile::view]
+	fn trapped_assets(
+		handle: &mut impl PrecompileHandle,
+		account: Address,
+	) -> EvmResult<Vec<(H256, Bytes, u32)>> {
+		// Fetching the traps indexed for the account
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		let traps =
+			pallet_trapped_assets::Pallet::<Runtime>::traps(account_location(account.into()));
+
+		// Fetching the trap count of each of them
+		handle.record_cost(
+			RuntimeHelper::<Runtime>::db_read_gas_cost().saturating_mul(traps.len() as u64),
+		)?;
+
+		Ok(traps
+			.into_iter()
+			.map(|trap| {
+				let count = pallet_xcm::Pallet::<Runtime>::asset_trap(trap.hash);
+				(trap.hash, Bytes(trap.assets.encode()), count)
+			})
+			.collect())
+	}
+
+	#[precompile::public("claimTrappedAssets(bytes)")]
+	fn claim_trapped_assets(
+		handle: &mut impl PrecompileHandle,
+		assets: BoundedBytes<ConstU32<ASSETS_DATA_LIMIT>>,
+	) -> EvmResult {
+		let assets = decode_assets(assets)?;
+		let location = account_location(handle.context().caller);
+
+		let max_assets = assets.len() as u32;
+		let mut message: Xcm<XcmConfig::Call> = Xcm(vec![
+			ClaimAsset {
+				assets,
+				ticket: MultiLocation::here(),
+			},
+			DepositAsset {
+				assets: MultiAssetFilter::Wild(WildMultiAsset::All),
+				max_assets,
+				beneficiary: location.clone(),
+			},
+		]);
+		let weight = XcmConfig::Weigher::weight(&mut message)
+			.map_err(|_| revert("Failed weighting the claim message"))?;
+		handle.record_cost(
+			<Runtime as pallet_evm::Config>::GasWeightMapping::weight_to_gas(weight),
+		)?;
+
+		// The weight was paid with gas, hence it is given as credit
+		XcmExecutor::<XcmConfig>::execute_xcm_in_credit(location, message, weight, weight)
+			.ensure_complete()
+			.map_err(|_| revert("Failed claiming the trapped assets"))?;
+
+		Ok(())
+	}
+
+	#[precompile::public("weightMessage(bytes)")]
+	#[precompile::view]
+	fn weight_message(
+		handle: &mut impl PrecompileHandle,
+		message: BoundedBytes<ConstU32<MESSAGE_DATA_LIMIT>>,
+	) -> EvmResult<u64> {
+		let mut message: Xcm<XcmConfig::Call> = decode_message(handle, message)?.into();
+
+		XcmConfig::Weigher::weight(&mut message).map_err(|_| {
+			RevertReason::custom("Failed weighting the message")
+				.in_field("message")
+				.into()
+		})
+	}
+
+	#[precompile::public("getUnitsPerSecond((uint8,bytes[]))")]
+	#[precompile::view]
+	fn get_units_per_second(
+		handle: &mut impl PrecompileHandle,
+		multilocation: MultiLocation,
+	) -> EvmResult<U256> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+		pallet_asset_manager::Pallet::<Runtime>::asset_type_units_per_second(
+			<Runtime as pallet_asset_manager::Config>::ForeignAssetType::from(multilocation),
+		)
+		.map(Into::into)
+		.ok_or_else(|| {
+			RevertReason::custom("Asset not supported as fee payment")
+				.in_field("multilocation")
+				.into()
+		})
 	}
+
+	#[precompile::public("xcmExecute(bytes,uint64)")]
+	fn xcm_execute(
+		handle: &mut impl PrecompileHandle,
+		message: BoundedBytes<ConstU32<MESSAGE_DATA_LIMIT>>,
+		max_weight: u64,
+	) -> EvmResult {
+		let message = decode_message(handle, message)?;
+		if !message.0.iter().all(is_allowed_in_execute) {
+			return Err(RevertReason::custom("Instruction not allowed")
+				.in_field("message")
+				.into());
+		}
+
+		// The message is executed on behalf of the caller, as long as the runtime allows
+		// contracts executing it
+		let location = account_location(handle.context().caller);
+		let filtered: (MultiLocation, Xcm<<Runtime as frame_system::Config>::Call>) =
+			(location, message.into());
+		if !XcmExecuteFilter::contains(&filtered) {
+			return Err(revert("Executing the message is not allowed"));
+		}
+		let (location, message) = filtered;
+
+		let mut message: Xcm<XcmConfig::Call> = message.into();
+		let weight = XcmConfig::Weigher::weight(&mut message).map_err(|_| {
+			RevertReason::custom("Failed weighting the message").in_field("message")
+		})?;
+		if weight > max_weight {
+			return Err(revert("Message weight exceeds maxWeight"));
+		}
+		handle.record_cost(
+			<Runtime as pallet_evm::Config>::GasWeightMapping::weight_to_gas(weight),
+		)?;
+
+		// The weight of the message was paid with gas
+		XcmExecutor::<XcmConfig>::execute_xcm_in_credit(location, message, max_weight, weight)
+			.ensure_complete()
+			.map_err(|_| revert("Failed executing the message"))?;
+
+		Ok(())
+	}
+
+	#[precompile::public("xcmSend((uint8,bytes[]),bytes)")]
+	fn xcm_send(
+		handle: &mut impl PrecompileHandle,
+		dest: MultiLocation,
+		message: BoundedBytes<ConstU32<MESSAGE_DATA_LIMIT>>,
+	) -> EvmResult {
+		// TODO: Change once precompiles are benchmarked
+		// for now we charge a db write, as the message is queued in storage, and the delivery
+		// as pallet-xcm does
+		handle.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost())?;
+		handle.record_cost(
+			<Runtime as pallet_evm::Config>::GasWeightMapping::weight_to_gas(SEND_DELIVERY_WEIGHT),
+		)?;
+
+		let message = decode_message(handle, message)?;
+		if !message.0.iter().all(is_allowed_in_send) {
+			return Err(RevertReason::custom("Instruction not allowed")
+				.in_field("message")
+				.into());
+		}
+
+		// The caller must be allowed to send messages through pallet-xcm, which executes them
+		// in the destination on behalf of the location of the caller
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let location = <Runtime as pallet_xcm::Config>::SendXcmOrigin::ensure_origin(
+			frame_system::RawOrigin::Signed(origin).into(),
+		)
+		.map_err(|_| revert("Origin not allowed to send messages"))?;
+		let interior: Junctions = location
+			.try_into()
+			.map_err(|_| revert("Origin not allowed to send messages"))?;
+		pallet_xcm::Pallet::<Runtime>::send_xcm(interior, dest, message)
+			.map_err(|_| revert("Failed sending the message"))?;
+
+		Ok(())
+	}
+}
+
+/// Instructions that a contract can execute locally on behalf of its account. Messages that
+/// these instructions send to other chains are checked with `is_allowed_in_send`
+fn is_allowed_in_execute<Call>(instruction: &Instruction<Call>) -> bool {
+	match instruction {
+		WithdrawAsset(..)
+		| ClaimAsset { .. }
+		| BuyExecution { .. }
+		| RefundSurplus
+		| ClearOrigin
+		| ClearError
+		| Trap(..)
+		| DepositAsset { .. }
+		| TransferAsset { .. } => true,
+		DepositReserveAsset { xcm, .. }
+		| TransferReserveAsset { xcm, .. }
+		| InitiateReserveWithdraw { xcm, .. }
+		| InitiateTeleport { xcm, .. } => xcm.0.iter().all(is_allowed_in_send),
+		SetErrorHandler(xcm) | SetAppendix(xcm) => xcm.0.iter().all(is_allowed_in_execute),
+		_ => false,
+	}
+}
+
+/// Instructions that a contract can send to be executed in another chain. Instructions that make
+/// the destination report to an arbitrary query are not allowed, as they could be used to forge
+/// responses coming from the destination
+fn is_allowed_in_send<Call>(instruction: &Instruction<Call>) -> bool {
+	match instruction {
+		Transact { .. } => true,
+		SetErrorHandler(xcm) | SetAppendix(xcm) => xcm.0.iter().all(is_allowed_in_send),
+		instruction => is_allowed_in_execute(instruction),
+	}
+}
+
+/// The location of a local account, under which assets trapped while executing XCM on its behalf
+/// are stored, as produced by the transfer pallets
+fn account_location(account: H160) -> MultiLocation {
+	MultiLocation::new(
+		0,
+		X1(AccountKey20 {
+			network: NetworkId::Any,
+			key: account.into(),
+		}),
+	)
+}
+
+fn decode_assets(assets: BoundedBytes<ConstU32<ASSETS_DATA_LIMIT>>) -> EvmResult<MultiAssets> {
+	let assets = VersionedMultiAssets::decode(&mut &assets.into_vec()[..])
+		.map_err(|_| RevertReason::custom("Invalid assets").in_field("assets"))?;
+
+	MultiAssets::try_from(assets).map_err(|_| {
+		RevertReason::custom("Unsupported assets version")
+			.in_field("assets")
+			.into()
+	})
+}
+
+/// Decode a message, charging for its size
+fn decode_message(
+	handle: &mut impl PrecompileHandle,
+	message: BoundedBytes<ConstU32<MESSAGE_DATA_LIMIT>>,
+) -> EvmResult<Xcm<()>> {
+	let message = message.into_vec();
+	handle.record_cost(MESSAGE_BYTE_GAS_COST.saturating_mul(message.len() as u64))?;
+
+	let message =
+		VersionedXcm::<()>::decode_all_with_depth_limit(MAX_XCM_DECODE_DEPTH, &mut &message[..])
+			.map_err(|_| RevertReason::custom("Invalid message").in_field("message"))?;
+
+	Xcm::try_from(message).map_err(|_| {
+		RevertReason::custom("Unsupported message version")
+			.in_field("message")
+			.into()
+	})
 }
@@ -19,9 +19,10 @@ use super::*;
 use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::{
 	construct_runtime, parameter_types,
-	traits::{EnsureOrigin, Everything, OriginTrait, PalletInfo as PalletInfoTrait},
+	traits::{Contains, EnsureOrigin, Everything, OriginTrait, PalletInfo as PalletInfoTrait},
 	weights::{RuntimeDbWeight, Weight},
 };
+use frame_system::EnsureRoot;
 use pallet_evm::{
 	AddressMapping, EnsureAddressNever, EnsureAddressRoot, GasWeightMapping, Precompile,
 	PrecompileSet,
@@ -32,9 +33,9 @@ use sp_core::{H256, U256};
 use sp_io;
 use sp_runtime::{
 	testing::Header,
-	traits::{BlakeTwo256, IdentityLookup},
+	traits::{BlakeTwo256, Hash, IdentityLookup},
 };
-use sp_std::borrow::Borrow;
+use sp_std::{borrow::Borrow, cell::RefCell};
 use xcm::latest::{
 	Error as XcmError,
 	Junction::{AccountKey20, PalletInstance, Parachain},
@@ -51,6 +52,7 @@ use xcm_executor::{
 use Junctions::Here;
 
 pub type AccountId = TestAccount;
+pub type AssetId = u128;
 pub type Balance = u128;
 pub type BlockNumber = u64;
 pub const PRECOMPILE_ADDRESS: u64 = 1;
@@ -69,6 +71,9 @@ construct_runtime!(
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 		Evm: pallet_evm::{Pallet, Call, Storage, Event<T>},
 		Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
+		PolkadotXcm: pallet_xcm::{Pallet, Call, Storage, Event<T>, Origin},
+		TrappedAssets: pallet_trapped_assets::{Pallet, Storage},
+		AssetManager: pallet_asset_manager::{Pallet, Call, Storage, Event<T>},
 	}
 );
 
@@ -285,13 +290,13 @@ pub struct TestPrecompiles<R>(PhantomData<R>);
 
 impl<R> PrecompileSet for TestPrecompiles<R>
 where
-	XcmUtilsWrapper<R, XcmConfig>: Precompile,
+	XcmUtilsWrapper<R, XcmConfig, XcmExecuteFilter>: Precompile,
 {
 	fn execute(&self, handle: &mut impl PrecompileHandle) -> Option<EvmResult<PrecompileOutput>> {
 		match handle.code_address() {
-			a if a == precompile_address() => {
-				Some(XcmUtilsWrapper::<R, XcmConfig>::execute(handle))
-			}
+			a if a == precompile_address() => Some(
+				XcmUtilsWrapper::<R, XcmConfig, XcmExecuteFilter>::execute(handle),
+			),
 			_ => None,
 		}
 	}
@@ -365,9 +370,44 @@ impl<Origin: OriginTrait> EnsureOrigin<Origin> for ConvertOriginToLocal {
 	}
 }
 
-pub struct DoNothingRouter;
-impl SendXcm for DoNothingRouter {
-	fn send_xcm(_dest: impl Into<MultiLocation>, _msg: Xcm<()>) -> SendResult {
+// Signed accounts send messages from their local location, as in the runtimes
+pub struct SignedToAccountLocation;
+impl EnsureOrigin<Origin> for SignedToAccountLocation {
+	type Success = MultiLocation;
+
+	fn try_origin(o: Origin) -> Result<MultiLocation, Origin> {
+		let o: Result<frame_system::RawOrigin<TestAccount>, Origin> = o.into();
+		o.and_then(|o| match o {
+			frame_system::RawOrigin::Signed(who) => Ok(account_location(who.into())),
+			r => Err(Origin::from(r)),
+		})
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> Origin {
+		Origin::signed(TestAccount::Alice)
+	}
+}
+
+// Every message can be executed through the precompile, except by Bob
+pub struct XcmExecuteFilter;
+impl Contains<(MultiLocation, Xcm<Call>)> for XcmExecuteFilter {
+	fn contains((origin, _): &(MultiLocation, Xcm<Call>)) -> bool {
+		origin != &account_location(TestAccount::Bob.into())
+	}
+}
+
+// Simulates sending a XCM message
+thread_local! {
+	pub static SENT_XCM: RefCell<Vec<(MultiLocation, Xcm<()>)>> = RefCell::new(Vec::new());
+}
+pub fn sent_xcm() -> Vec<(MultiLocation, Xcm<()>)> {
+	SENT_XCM.with(|q| (*q.borrow()).clone())
+}
+pub struct TestSendXcm;
+impl SendXcm for TestSendXcm {
+	fn send_xcm(dest: impl Into<MultiLocation>, msg: Xcm<()>) -> SendResult {
+		SENT_XCM.with(|q| q.borrow_mut().push((dest.into(), msg)));
 		Ok(())
 	}
 }
@@ -433,7 +473,7 @@ pub type XcmOriginToTransactDispatchOrigin = (
 pub struct XcmConfig;
 impl xcm_executor::Config for XcmConfig {
 	type Call = Call;
-	type XcmSender = DoNothingRouter;
+	type XcmSender = TestSendXcm;
 	type AssetTransactor = DummyAssetTransactor;
 	type OriginConverter = XcmOriginToTransactDispatchOrigin;
 	type IsReserve = ();
@@ -444,11 +484,114 @@ impl xcm_executor::Config for XcmConfig {
 	type Trader = DummyWeightTrader;
 	type ResponseHandler = ();
 	type SubscriptionService = ();
-	type AssetTrap = ();
-	type AssetClaims = ();
+	type AssetTrap = TrappedAssets;
+	type AssetClaims = TrappedAssets;
 	type CallDispatcher = Call;
 }
 
+impl pallet_xcm::Config for Runtime {
+	// The config types here are entirely configurable, since the only one that is sorely needed
+	// is `XcmExecutor`, which will be used in unit tests located in xcm-executor.
+	type Event = Event;
+	type ExecuteXcmOrigin = ConvertOriginToLocal;
+	type LocationInverter = InvertNothing;
+	type SendXcmOrigin = SignedToAccountLocation;
+	type Weigher = FixedWeightBounds<BaseXcmWeight, Call, MaxInstructions>;
+	type XcmRouter = TestSendXcm;
+	// The precompile has its own filter
+	type XcmExecuteFilter = frame_support::traits::Nothing;
+	type XcmExecutor = xcm_executor::XcmExecutor<XcmConfig>;
+	type XcmTeleportFilter = frame_support::traits::Nothing;
+	type XcmReserveTransferFilter = Everything;
+	type Origin = Origin;
+	type Call = Call;
+	const VERSION_DISCOVERY_QUEUE_SIZE: u32 = 100;
+	type AdvertisedXcmVersion = pallet_xcm::CurrentXcmVersion;
+}
+
+parameter_types! {
+	pub const MaxTrapsPerOrigin: u32 = 2;
+}
+
+impl pallet_trapped_assets::Config for Runtime {
+	type MaxTrapsPerOrigin = MaxTrapsPerOrigin;
+}
+
+#[derive(Clone, Eq, Debug, PartialEq, Ord, PartialOrd, Encode, Decode, TypeInfo)]
+pub enum MockAssetType {
+	Xcm(MultiLocation),
+}
+
+impl Default for MockAssetType {
+	fn default() -> Self {
+		Self::Xcm(MultiLocation::here())
+	}
+}
+
+impl From<MultiLocation> for MockAssetType {
+	fn from(location: MultiLocation) -> Self {
+		Self::Xcm(location)
+	}
+}
+
+impl Into<Option<MultiLocation>> for MockAssetType {
+	fn into(self) -> Option<MultiLocation> {
+		match self {
+			Self::Xcm(location) => Some(location),
+		}
+	}
+}
+
+impl From<MockAssetType> for AssetId {
+	fn from(asset: MockAssetType) -> AssetId {
+		match asset {
+			MockAssetType::Xcm(location) => {
+				let mut result: [u8; 16] = [0u8; 16];
+				let hash: H256 = location.using_encoded(BlakeTwo256::hash);
+				result.copy_from_slice(&hash.as_fixed_bytes()[0..16]);
+				u128::from_le_bytes(result)
+			}
+		}
+	}
+}
+
+pub struct MockAssetRegistrar;
+impl pallet_asset_manager::AssetRegistrar<Runtime> for MockAssetRegistrar {
+	fn destroy_asset_dispatch_info_weight(_asset: AssetId, _witness: u32) -> Weight {
+		0
+	}
+}
+
+pub struct MockLocalAssetIdCreator;
+impl pallet_asset_manager::LocalAssetIdCreator<Runtime> for MockLocalAssetIdCreator {
+	fn create_asset_id_from_metadata(local_asset_counter: u128) -> AssetId {
+		local_asset_counter
+	}
+}
+
+parameter_types! {
+	pub const LocalAssetDeposit: Balance = 0;
+	pub const ForeignAssetDeposit: Balance = 0;
+}
+
+impl pallet_asset_manager::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type AssetId = AssetId;
+	type AssetRegistrarMetadata = u32;
+	type ForeignAssetType = MockAssetType;
+	type AssetRegistrar = MockAssetRegistrar;
+	type ForeignAssetModifierOrigin = EnsureRoot<AccountId>;
+	type SiblingAssetRegistrarOrigin = pallet_xcm::EnsureXcm<Everything>;
+	type LocalAssetModifierOrigin = EnsureRoot<AccountId>;
+	type LocalAssetIdCreator = MockLocalAssetIdCreator;
+	type AssetDestroyWitness = u32;
+	type Currency = Balances;
+	type LocalAssetDeposit = LocalAssetDeposit;
+	type ForeignAssetDeposit = ForeignAssetDeposit;
+	type WeightInfo = ();
+}
+
 pub(crate) struct ExtBuilder {}
 
 impl Default for ExtBuilder {
@@ -14,15 +14,24 @@
 // You should have received a copy of the GNU General Public License
 // along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
 use crate::mock::{
-	ExtBuilder, PrecompilesValue, Runtime,
+	sent_xcm, ExtBuilder, MockAssetType, PolkadotXcm, PrecompilesValue, Runtime,
 	TestAccount::{self, *},
-	TestPrecompiles,
+	TestPrecompiles, TrappedAssets, XcmConfig, XcmExecuteFilter,
 };
-use crate::Action;
+use crate::{XcmUtilsWrapperCall, MESSAGE_BYTE_GAS_COST, SEND_DELIVERY_WEIGHT};
 
+use codec::Encode;
 use precompile_utils::{prelude::*, solidity, testing::*};
-use sp_core::H160;
-use xcm::latest::{Junction, Junctions, MultiLocation};
+use sp_core::{H160, H256, U256};
+use sp_runtime::traits::{BlakeTwo256, Hash};
+use xcm::latest::{
+	Instruction::*, Junction, Junctions, MultiAsset, MultiAssetFilter, MultiAssets, MultiLocation,
+	NetworkId, OriginKind, WildMultiAsset, Xcm,
+};
+use xcm::{VersionedMultiAssets, VersionedXcm};
+use xcm_executor::traits::DropAssets;
+
+type PCall = XcmUtilsWrapperCall<Runtime, XcmConfig, XcmExecuteFilter>;
 
 fn precompiles() -> TestPrecompiles<Runtime> {
 	PrecompilesValue::get()
@@ -30,15 +39,22 @@ fn precompiles() -> TestPrecompiles<Runtime> {
 
 #[test]
 fn test_selector_enum() {
-	assert_eq!(Action::MultiLocationToAddress as u32, 0x343b3e00);
+	assert_eq!(PCall::multilocation_to_address_selectors(), &[0x343b3e00]);
+	assert_eq!(PCall::trapped_assets_selectors(), &[0x6f873d0d]);
+	assert_eq!(PCall::claim_trapped_assets_selectors(), &[0xaca23d46]);
+	assert_eq!(PCall::weight_message_selectors(), &[0x25d54154]);
+	assert_eq!(PCall::get_units_per_second_selectors(), &[0x3f0f65db]);
+	assert_eq!(PCall::xcm_execute_selectors(), &[0x34334a02]);
+	assert_eq!(PCall::xcm_send_selectors(), &[0x98600e64]);
 }
 
 #[test]
 fn test_get_account_parent() {
 	ExtBuilder::default().build().execute_with(|| {
-		let input = EvmDataWriter::new_with_selector(Action::MultiLocationToAddress)
-			.write(MultiLocation::parent())
-			.build();
+		let input = PCall::multilocation_to_address {
+			multilocation: MultiLocation::parent(),
+		}
+		.into();
 
 		let expected_address: H160 = TestAccount::Parent.into();
 
@@ -57,12 +73,13 @@ fn test_get_account_parent() {
 #[test]
 fn test_get_account_sibling() {
 	ExtBuilder::default().build().execute_with(|| {
-		let input = EvmDataWriter::new_with_selector(Action::MultiLocationToAddress)
-			.write(MultiLocation {
+		let input = PCall::multilocation_to_address {
+			multilocation: MultiLocation {
 				parents: 1,
 				interior: Junctions::X1(Junction::Parachain(2000u32)),
-			})
-			.build();
+			},
+		}
+		.into();
 
 		let expected_address: H160 = TestAccount::SiblingParachain(2000u32).into();
 
@@ -78,6 +95,377 @@ fn test_get_account_sibling() {
 	});
 }
 
+fn alice_location() -> MultiLocation {
+	MultiLocation::new(
+		0,
+		Junctions::X1(Junction::AccountKey20 {
+			network: NetworkId::Any,
+			key: H160::from(Alice).into(),
+		}),
+	)
+}
+
+fn trapped_assets() -> MultiAssets {
+	let asset: MultiAsset = (MultiLocation::parent(), 100u128).into();
+	asset.into()
+}
+
+fn trapped_assets_hash() -> H256 {
+	BlakeTwo256::hash_of(&(
+		alice_location(),
+		VersionedMultiAssets::from(trapped_assets()),
+	))
+}
+
+#[test]
+fn test_trapped_assets() {
+	ExtBuilder::default().build().execute_with(|| {
+		TrappedAssets::drop_assets(&alice_location(), trapped_assets().into());
+
+		// One read for the index, one for the trap count
+		precompiles()
+			.prepare_test(
+				Bob,
+				Precompile,
+				EvmDataWriter::new_with_selector(PCall::trapped_assets_selectors()[0])
+					.write(Address(Alice.into()))
+					.build(),
+			)
+			.expect_cost(2)
+			.expect_no_logs()
+			.execute_returns(
+				EvmDataWriter::new()
+					.write(vec![(
+						trapped_assets_hash(),
+						Bytes(VersionedMultiAssets::from(trapped_assets()).encode()),
+						1u32,
+					)])
+					.build(),
+			);
+	});
+}
+
+#[test]
+fn test_trapped_assets_for_other_account() {
+	ExtBuilder::default().build().execute_with(|| {
+		TrappedAssets::drop_assets(&alice_location(), trapped_assets().into());
+
+		precompiles()
+			.prepare_test(
+				Alice,
+				Precompile,
+				EvmDataWriter::new_with_selector(PCall::trapped_assets_selectors()[0])
+					.write(Address(Bob.into()))
+					.build(),
+			)
+			.expect_cost(1)
+			.expect_no_logs()
+			.execute_returns(
+				EvmDataWriter::new()
+					.write(Vec::<(H256, Bytes, u32)>::new())
+					.build(),
+			);
+	});
+}
+
+#[test]
+fn test_claim_trapped_assets() {
+	ExtBuilder::default().build().execute_with(|| {
+		TrappedAssets::drop_assets(&alice_location(), trapped_assets().into());
+
+		// Two instructions of BaseXcmWeight
+		precompiles()
+			.prepare_test(
+				Alice,
+				Precompile,
+				EvmDataWriter::new_with_selector(PCall::claim_trapped_assets_selectors()[0])
+					.write(Bytes(VersionedMultiAssets::from(trapped_assets()).encode()))
+					.build(),
+			)
+			.expect_cost(2000)
+			.expect_no_logs()
+			.execute_returns(vec![]);
+
+		assert_eq!(PolkadotXcm::asset_trap(trapped_assets_hash()), 0);
+		assert!(TrappedAssets::traps(alice_location()).is_empty());
+	});
+}
+
+#[test]
+fn test_claim_trapped_assets_of_other_account_fails() {
+	ExtBuilder::default().build().execute_with(|| {
+		TrappedAssets::drop_assets(&alice_location(), trapped_assets().into());
+
+		precompiles()
+			.prepare_test(
+				Bob,
+				Precompile,
+				EvmDataWriter::new_with_selector(PCall::claim_trapped_assets_selectors()[0])
+					.write(Bytes(VersionedMultiAssets::from(trapped_assets()).encode()))
+					.build(),
+			)
+			.execute_reverts(|output| output == b"Failed claiming the trapped assets");
+
+		assert_eq!(PolkadotXcm::asset_trap(trapped_assets_hash()), 1);
+	});
+}
+
+#[test]
+fn test_claim_trapped_assets_invalid_assets() {
+	ExtBuilder::default().build().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				Alice,
+				Precompile,
+				EvmDataWriter::new_with_selector(PCall::claim_trapped_assets_selectors()[0])
+					.write(Bytes(vec![0xff]))
+					.build(),
+			)
+			.execute_reverts(|output| output == b"assets: Invalid assets");
+	});
+}
+
+fn encode_message(message: Xcm<()>) -> Bytes {
+	Bytes(VersionedXcm::<()>::from(message).encode())
+}
+
+// Gas charged for the size of the message
+fn message_cost(message: &Bytes) -> u64 {
+	MESSAGE_BYTE_GAS_COST * message.0.len() as u64
+}
+
+fn deposit_to_alice() -> Xcm<()> {
+	Xcm(vec![
+		WithdrawAsset(trapped_assets()),
+		DepositAsset {
+			assets: MultiAssetFilter::Wild(WildMultiAsset::All),
+			max_assets: 1,
+			beneficiary: alice_location(),
+		},
+	])
+}
+
+#[test]
+fn test_weight_message() {
+	ExtBuilder::default().build().execute_with(|| {
+		let message = encode_message(deposit_to_alice());
+
+		precompiles()
+			.prepare_test(
+				Alice,
+				Precompile,
+				EvmDataWriter::new_with_selector(PCall::weight_message_selectors()[0])
+					.write(message.clone())
+					.build(),
+			)
+			.expect_cost(message_cost(&message))
+			.expect_no_logs()
+			.execute_returns(EvmDataWriter::new().write(2000u64).build());
+	});
+}
+
+#[test]
+fn test_get_units_per_second() {
+	ExtBuilder::default().build().execute_with(|| {
+		pallet_asset_manager::AssetTypeUnitsPerSecond::<Runtime>::insert(
+			MockAssetType::from(MultiLocation::parent()),
+			500u128,
+		);
+
+		precompiles()
+			.prepare_test(
+				Alice,
+				Precompile,
+				PCall::get_units_per_second {
+					multilocation: MultiLocation::parent(),
+				}
+				.into(),
+			)
+			.expect_cost(1)
+			.expect_no_logs()
+			.execute_returns(EvmDataWriter::new().write(U256::from(500u128)).build());
+	});
+}
+
+#[test]
+fn test_get_units_per_second_unsupported_asset() {
+	ExtBuilder::default().build().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				Alice,
+				Precompile,
+				PCall::get_units_per_second {
+					multilocation: MultiLocation::parent(),
+				}
+				.into(),
+			)
+			.execute_reverts(|output| {
+				output == b"multilocation: Asset not supported as fee payment"
+			});
+	});
+}
+
+#[test]
+fn test_xcm_execute() {
+	ExtBuilder::default().build().execute_with(|| {
+		let message = encode_message(deposit_to_alice());
+
+		precompiles()
+			.prepare_test(
+				Alice,
+				Precompile,
+				EvmDataWriter::new_with_selector(PCall::xcm_execute_selectors()[0])
+					.write(message.clone())
+					.write(2000u64)
+					.build(),
+			)
+			.expect_cost(2000 + message_cost(&message))
+			.expect_no_logs()
+			.execute_returns(vec![]);
+	});
+}
+
+#[test]
+fn test_xcm_execute_filtered() {
+	ExtBuilder::default().build().execute_with(|| {
+		// The mock execute filter rejects the messages of Bob
+		precompiles()
+			.prepare_test(
+				Bob,
+				Precompile,
+				EvmDataWriter::new_with_selector(PCall::xcm_execute_selectors()[0])
+					.write(encode_message(deposit_to_alice()))
+					.write(2000u64)
+					.build(),
+			)
+			.execute_reverts(|output| output == b"Executing the message is not allowed");
+	});
+}
+
+#[test]
+fn test_xcm_execute_exceeding_max_weight() {
+	ExtBuilder::default().build().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				Alice,
+				Precompile,
+				EvmDataWriter::new_with_selector(PCall::xcm_execute_selectors()[0])
+					.write(encode_message(deposit_to_alice()))
+					.write(1000u64)
+					.build(),
+			)
+			.execute_reverts(|output| output == b"Message weight exceeds maxWeight");
+	});
+}
+
+#[test]
+fn test_xcm_execute_transact_not_allowed() {
+	ExtBuilder::default().build().execute_with(|| {
+		let message = Xcm(vec![Transact {
+			origin_type: OriginKind::SovereignAccount,
+			require_weight_at_most: 1,
+			call: vec![].into(),
+		}]);
+
+		precompiles()
+			.prepare_test(
+				Alice,
+				Precompile,
+				EvmDataWriter::new_with_selector(PCall::xcm_execute_selectors()[0])
+					.write(encode_message(message))
+					.write(2000u64)
+					.build(),
+			)
+			.execute_reverts(|output| output == b"message: Instruction not allowed");
+	});
+}
+
+#[test]
+fn test_xcm_execute_forwarding_report_not_allowed() {
+	ExtBuilder::default().build().execute_with(|| {
+		let message = Xcm(vec![
+			WithdrawAsset(trapped_assets()),
+			InitiateReserveWithdraw {
+				assets: MultiAssetFilter::Wild(WildMultiAsset::All),
+				reserve: MultiLocation::parent(),
+				xcm: Xcm(vec![ReportError {
+					query_id: 0,
+					dest: MultiLocation::parent(),
+					max_response_weight: 0,
+				}]),
+			},
+		]);
+
+		precompiles()
+			.prepare_test(
+				Alice,
+				Precompile,
+				EvmDataWriter::new_with_selector(PCall::xcm_execute_selectors()[0])
+					.write(encode_message(message))
+					.write(2000u64)
+					.build(),
+			)
+			.execute_reverts(|output| output == b"message: Instruction not allowed");
+	});
+}
+
+#[test]
+fn test_xcm_send() {
+	ExtBuilder::default().build().execute_with(|| {
+		let message = Xcm(vec![Transact {
+			origin_type: OriginKind::SovereignAccount,
+			require_weight_at_most: 1,
+			call: vec![].into(),
+		}]);
+
+		let encoded = encode_message(message.clone());
+
+		precompiles()
+			.prepare_test(
+				Alice,
+				Precompile,
+				EvmDataWriter::new_with_selector(PCall::xcm_send_selectors()[0])
+					.write(MultiLocation::parent())
+					.write(encoded.clone())
+					.build(),
+			)
+			// A db write, the delivery and the size of the message
+			.expect_cost(5 + SEND_DELIVERY_WEIGHT + message_cost(&encoded))
+			.expect_no_logs()
+			.execute_returns(vec![]);
+
+		let mut expected = message;
+		expected
+			.0
+			.insert(0, DescendOrigin(alice_location().interior));
+		assert_eq!(sent_xcm(), vec![(MultiLocation::parent(), expected)]);
+	});
+}
+
+#[test]
+fn test_xcm_send_report_not_allowed() {
+	ExtBuilder::default().build().execute_with(|| {
+		let message = Xcm(vec![SetAppendix(Xcm(vec![ReportError {
+			query_id: 0,
+			dest: MultiLocation::parent(),
+			max_response_weight: 0,
+		}]))]);
+
+		precompiles()
+			.prepare_test(
+				Alice,
+				Precompile,
+				EvmDataWriter::new_with_selector(PCall::xcm_send_selectors()[0])
+					.write(MultiLocation::parent())
+					.write(encode_message(message))
+					.build(),
+			)
+			.execute_reverts(|output| output == b"message: Instruction not allowed");
+
+		assert!(sent_xcm().is_empty());
+	});
+}
+
 #[test]
 fn test_solidity_interface_has_all_function_selectors_documented_and_implemented() {
 	for file in ["XcmUtils.sol"] {
@@ -91,9 +479,9 @@ fn test_solidity_interface_has_all_function_selectors_documented_and_implemented
 			);
 
 			let selector = solidity_fn.compute_selector();
-			if Action::try_from(selector).is_err() {
+			if !PCall::supports_selector(selector) {
 				panic!(
-					"failed decoding selector 0x{:x} => '{}' as Action for file '{}'",
+					"failed decoding selector 0x{:x} => '{}' as PCall for file '{}'",
 					selector,
 					solidity_fn.signature(),
 					file,
@@ -102,3 +490,8 @@ fn test_solidity_interface_has_all_function_selectors_documented_and_implemented
 		}
 	}
 }
+
+#[test]
+fn test_solidity_interface_matches_rust_definitions() {
+	PCall::solidity_interface().assert_matches_file("XcmUtils.sol", "XcmUtils");
+}
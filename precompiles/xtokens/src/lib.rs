@@ -389,6 +389,10 @@ impl EvmData for Currency {
 	fn has_static_size() -> bool {
 		<(Address, U256)>::has_static_size()
 	}
+
+	fn solidity_type() -> String {
+		<(Address, U256)>::solidity_type()
+	}
 }
 
 impl From<(Address, U256)> for Currency {
@@ -419,6 +423,10 @@ impl EvmData for EvmMultiAsset {
 	fn has_static_size() -> bool {
 		<(MultiLocation, U256)>::has_static_size()
 	}
+
+	fn solidity_type() -> String {
+		<(MultiLocation, U256)>::solidity_type()
+	}
 }
 
 impl From<(MultiLocation, U256)> for EvmMultiAsset {
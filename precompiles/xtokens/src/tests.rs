@@ -18,7 +18,7 @@ use crate::mock::{
 	events, CurrencyId, CurrencyIdToMultiLocation, ExtBuilder, PrecompilesValue, Runtime,
 	TestAccount::*, TestPrecompiles,
 };
-use crate::{Action, Currency, EvmMultiAsset};
+use crate::{Action, Currency, EvmMultiAsset, GetMaxAssets};
 use orml_xtokens::Event as XtokensEvent;
 use precompile_utils::{prelude::*, solidity, testing::*};
 use sp_core::U256;
@@ -851,15 +851,107 @@ fn test_solidity_interface_has_all_function_selectors_documented_and_implemented
 	}
 }
 
+#[test]
+fn test_solidity_interface_matches_rust_definitions() {
+	let function = |signature, args: &[(&str, String)]| {
+		solidity::FunctionDeclaration::from_signature(
+			signature,
+			args,
+			FunctionModifier::NonPayable,
+			&[],
+		)
+	};
+	let destination = ("destination", MultiLocation::solidity_type());
+	let weight = ("weight", u64::solidity_type());
+
+	let functions = vec![
+		function(
+			"transfer(address,uint256,(uint8,bytes[]),uint64)",
+			&[
+				("currencyAddress", Address::solidity_type()),
+				("amount", U256::solidity_type()),
+				destination.clone(),
+				weight.clone(),
+			],
+		),
+		function(
+			"transferWithFee(address,uint256,uint256,(uint8,bytes[]),uint64)",
+			&[
+				("currencyAddress", Address::solidity_type()),
+				("amount", U256::solidity_type()),
+				("fee", U256::solidity_type()),
+				destination.clone(),
+				weight.clone(),
+			],
+		),
+		function(
+			"transferMultiasset((uint8,bytes[]),uint256,(uint8,bytes[]),uint64)",
+			&[
+				("asset", MultiLocation::solidity_type()),
+				("amount", U256::solidity_type()),
+				destination.clone(),
+				weight.clone(),
+			],
+		),
+		function(
+			"transferMultiassetWithFee((uint8,bytes[]),uint256,uint256,(uint8,bytes[]),uint64)",
+			&[
+				("asset", MultiLocation::solidity_type()),
+				("amount", U256::solidity_type()),
+				("fee", U256::solidity_type()),
+				destination.clone(),
+				weight.clone(),
+			],
+		),
+		function(
+			"transferMultiCurrencies((address,uint256)[],uint32,(uint8,bytes[]),uint64)",
+			&[
+				(
+					"currencies",
+					BoundedVec::<Currency, GetMaxAssets<Runtime>>::solidity_type(),
+				),
+				("feeItem", u32::solidity_type()),
+				destination.clone(),
+				weight.clone(),
+			],
+		),
+		function(
+			"transferMultiAssets(((uint8,bytes[]),uint256)[],uint32,(uint8,bytes[]),uint64)",
+			&[
+				(
+					"assets",
+					BoundedVec::<EvmMultiAsset, GetMaxAssets<Runtime>>::solidity_type(),
+				),
+				("feeItem", u32::solidity_type()),
+				destination,
+				weight,
+			],
+		),
+	];
+
+	solidity::SolidityInterface::from_declarations(
+		functions,
+		Action::signatures()
+			.iter()
+			.map(|(_, signature)| *signature)
+			.filter(|signature| !DEPRECATED_FUNCTIONS.contains(signature)),
+	)
+	.assert_matches_file("Xtokens.sol", "Xtokens");
+}
+
+/// Deprecated functions still supported by the precompile, but not declared in the
+/// solidity file.
+const DEPRECATED_FUNCTIONS: &[&str] = &[
+	"transfer_with_fee(address,uint256,uint256,(uint8,bytes[]),uint64)",
+	"transfer_multiasset((uint8,bytes[]),uint256,(uint8,bytes[]),uint64)",
+	"transfer_multiasset_with_fee((uint8,bytes[]),uint256,uint256,(uint8,bytes[]),uint64)",
+	"transfer_multi_currencies((address,uint256)[],uint32,(uint8,bytes[]),uint64)",
+	"transfer_multi_assets(((uint8,bytes[]),uint256)[],uint32,(uint8,bytes[]),uint64)",
+];
+
 #[test]
 fn test_deprecated_solidity_selectors_are_supported() {
-	for deprecated_function in [
-		"transfer_with_fee(address,uint256,uint256,(uint8,bytes[]),uint64)",
-		"transfer_multiasset((uint8,bytes[]),uint256,(uint8,bytes[]),uint64)",
-		"transfer_multiasset_with_fee((uint8,bytes[]),uint256,uint256,(uint8,bytes[]),uint64)",
-		"transfer_multi_currencies((address,uint256)[],uint32,(uint8,bytes[]),uint64)",
-		"transfer_multi_assets(((uint8,bytes[]),uint256)[],uint32,(uint8,bytes[]),uint64)",
-	] {
+	for deprecated_function in DEPRECATED_FUNCTIONS {
 		let selector = solidity::compute_selector(deprecated_function);
 		if Action::try_from(selector).is_err() {
 			panic!(
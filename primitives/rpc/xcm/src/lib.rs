@@ -0,0 +1,116 @@
+// Copyright 2019-2022 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+// These clippy lints are disabled because the macro-generated code triggers them.
+#![allow(clippy::unnecessary_mut_passed)]
+#![allow(clippy::too_many_arguments)]
+
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_core::H160;
+use sp_runtime::DispatchError;
+use sp_std::vec::Vec;
+use xcm::{latest::Outcome, VersionedMultiLocation, VersionedXcm};
+
+/// Weight and fee that xcm-transactor would use to Transact in a destination
+#[derive(Eq, PartialEq, Clone, Encode, Decode, sp_runtime::RuntimeDebug, TypeInfo)]
+pub struct TransactFee {
+	/// Overall weight bought in the destination, including the extra weight of the Transact
+	pub weight: u64,
+	/// Amount of the fee asset charged by the destination for that weight
+	pub fee: u128,
+}
+
+/// Cost of executing an inbound message in one of the supported fee assets
+#[derive(Eq, PartialEq, Clone, Encode, Decode, sp_runtime::RuntimeDebug, TypeInfo)]
+pub struct InboundFee {
+	/// Location of the fee asset
+	pub asset: VersionedMultiLocation,
+	/// Amount of the fee asset charged for the execution
+	pub amount: u128,
+}
+
+/// Effects of executing an XCM message, none of which are persisted
+#[derive(Eq, PartialEq, Clone, Encode, Decode, sp_runtime::RuntimeDebug, TypeInfo)]
+pub struct XcmDryRunEffects {
+	/// Weight used by the execution
+	pub weight_used: u64,
+	/// Outcome of the execution
+	pub outcome: Outcome,
+	/// SCALE-encoded runtime events emitted by the execution
+	pub events: Vec<Vec<u8>>,
+	/// Messages that would have been forwarded to other consensus systems
+	pub forwarded_messages: Vec<(VersionedMultiLocation, VersionedXcm<()>)>,
+}
+
+/// An XC-20, i.e., an asset registered in asset-manager and exposed through a precompile
+#[derive(Eq, PartialEq, Clone, Encode, Decode, sp_runtime::RuntimeDebug, TypeInfo)]
+pub struct XcAsset {
+	/// Id of the asset in pallet-assets
+	pub asset_id: u128,
+	/// Location of the asset, for foreign assets
+	pub location: Option<VersionedMultiLocation>,
+	/// Address of the ERC-20 precompile of the asset
+	pub address: H160,
+	pub name: Vec<u8>,
+	pub symbol: Vec<u8>,
+	pub decimals: u8,
+	/// Whether the asset can create accounts on its own
+	pub is_sufficient: bool,
+	/// Units of the asset charged per second of execution, for foreign assets that pay fees
+	pub units_per_second: Option<u128>,
+}
+
+sp_api::decl_runtime_apis! {
+	pub trait XcmFeeApi {
+		/// Weight and fee with which xcm-transactor would Transact a call of weight
+		/// `transact_weight` in `dest`, paying with `fee_asset`. Both are taken from the
+		/// information stored in xcm-transactor, as when no overall weight or fee amount are
+		/// provided to it.
+		fn transact_fee(
+			dest: VersionedMultiLocation,
+			fee_asset: VersionedMultiLocation,
+			transact_weight: u64,
+			signed: bool,
+		) -> Result<TransactFee, DispatchError>;
+
+		/// Cost of executing an inbound message of weight `weight` in each of the supported fee
+		/// assets.
+		fn inbound_fees(weight: u64) -> Vec<InboundFee>;
+	}
+
+	pub trait XcmDryRunApi {
+		/// Executes `message` from `origin` with the runtime XCM executor, buying at most
+		/// `weight_limit`, and reverts all its changes. Outgoing messages can be dry-run by using
+		/// a local account as `origin`.
+		fn dry_run_xcm(
+			origin: VersionedMultiLocation,
+			message: VersionedXcm<()>,
+			weight_limit: u64,
+		) -> Result<XcmDryRunEffects, DispatchError>;
+	}
+
+	pub trait XcAssetRegistryApi {
+		/// Foreign assets registered in asset-manager, at most `limit` of them. Assets are
+		/// returned in storage order, starting after `start_after` if given, so that the id of
+		/// the last asset of a page can be used to request the next one.
+		fn foreign_assets(start_after: Option<u128>, limit: u32) -> Vec<XcAsset>;
+
+		/// Local assets registered in asset-manager, paginated as foreign_assets.
+		fn local_assets(start_after: Option<u128>, limit: u32) -> Vec<XcAsset>;
+	}
+}
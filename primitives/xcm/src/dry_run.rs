@@ -0,0 +1,89 @@
+// Copyright 2019-2022 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Helpers to execute an XCM message without persisting any of its effects
+
+use frame_support::{
+	storage::{with_transaction, TransactionOutcome},
+	weights::Weight,
+};
+use sp_runtime::DispatchError;
+use sp_std::{marker::PhantomData, vec::Vec};
+use xcm::latest::{ExecuteXcm, MultiLocation, Outcome, SendResult, SendXcm, Xcm};
+
+environmental::environmental!(sent_messages: Vec<(MultiLocation, Xcm<()>)>);
+
+/// Wraps an XCM router, recording every message it successfully forwards while running
+/// inside `record_sent_messages`. Outside of it, it behaves exactly like `Router`.
+/// Recorded messages are still handed to `Router`, so a dry-run only discards them if `Router`
+/// queues them in storage, as the UMP and XCMP routers do
+pub struct RecordingRouter<Router>(PhantomData<Router>);
+
+impl<Router: SendXcm> SendXcm for RecordingRouter<Router> {
+	fn send_xcm(dest: impl Into<MultiLocation>, msg: Xcm<()>) -> SendResult {
+		let dest = dest.into();
+		// Only clone the message when it is being recorded
+		let recorded = sent_messages::with(|_| (dest.clone(), msg.clone()));
+		Router::send_xcm(dest, msg)?;
+		if let Some(message) = recorded {
+			sent_messages::with(|messages| messages.push(message));
+		}
+		Ok(())
+	}
+}
+
+/// Runs `f`, returning its result together with the messages forwarded through any
+/// `RecordingRouter` in the meantime
+pub fn record_sent_messages<R>(f: impl FnOnce() -> R) -> (R, Vec<(MultiLocation, Xcm<()>)>) {
+	let mut messages = Vec::new();
+	let result = sent_messages::using(&mut messages, f);
+	(result, messages)
+}
+
+/// The effects an XCM message would have had if it was executed
+pub struct XcmExecutionEffects<Event> {
+	pub outcome: Outcome,
+	pub events: Vec<Event>,
+	pub forwarded_messages: Vec<(MultiLocation, Xcm<()>)>,
+}
+
+/// Executes `message` from `origin` with `Executor`, and reverts every storage change it made.
+/// Only the runtime events emitted by the message are returned
+pub fn dry_run_xcm<Runtime, Executor, Call>(
+	origin: MultiLocation,
+	message: Xcm<Call>,
+	weight_limit: Weight,
+) -> Result<XcmExecutionEffects<<Runtime as frame_system::Config>::Event>, DispatchError>
+where
+	Runtime: frame_system::Config,
+	Executor: ExecuteXcm<Call>,
+{
+	with_transaction(|| {
+		frame_system::Pallet::<Runtime>::reset_events();
+		let (outcome, forwarded_messages) =
+			record_sent_messages(|| Executor::execute_xcm(origin, message, weight_limit));
+		let events = frame_system::Pallet::<Runtime>::events()
+			.into_iter()
+			.map(|record| record.event)
+			.collect();
+
+		TransactionOutcome::Rollback(Ok(XcmExecutionEffects {
+			outcome,
+			events,
+			forwarded_messages,
+		}))
+	})
+}
@@ -25,7 +25,7 @@ use frame_support::{
 	weights::{constants::WEIGHT_PER_SECOND, Weight},
 };
 use sp_runtime::traits::Zero;
-use sp_std::marker::PhantomData;
+use sp_std::{marker::PhantomData, vec::Vec};
 use xcm::latest::{
 	AssetId as xcmAssetId, Error as XcmError, Fungibility, MultiAsset, MultiLocation,
 };
@@ -148,6 +148,147 @@ impl<
 	}
 }
 
+/// Weight trader buying weight with any fungible asset of the payment whose units per second
+/// are known by AssetIdInfoGetter. Weight can be bought several times, possibly with different
+/// assets, and the weight and amount paid are tracked per purchase. Weight refunds are made in the
+/// asset of the most recent purchase which can cover them, at the price paid for that purchase,
+/// and the revenue of each asset is deposited separately through R.
+pub struct MultiAssetTrader<
+	AssetType: From<MultiLocation> + Clone,
+	AssetIdInfoGetter: UnitsToWeightRatio<AssetType>,
+	R: TakeRevenue,
+>(
+	Weight,
+	Vec<(MultiLocation, Weight, u128)>, // id, weight, amount
+	PhantomData<(AssetType, AssetIdInfoGetter, R)>,
+);
+impl<
+		AssetType: From<MultiLocation> + Clone,
+		AssetIdInfoGetter: UnitsToWeightRatio<AssetType>,
+		R: TakeRevenue,
+	> WeightTrader for MultiAssetTrader<AssetType, AssetIdInfoGetter, R>
+{
+	fn new() -> Self {
+		MultiAssetTrader(0, Vec::new(), PhantomData)
+	}
+	fn buy_weight(
+		&mut self,
+		weight: Weight,
+		payment: xcm_executor::Assets,
+	) -> Result<xcm_executor::Assets, XcmError> {
+		// We pay with the first fungible asset that is supported and sufficient to pay for the
+		// requested weight.
+		for asset in payment.fungible_assets_iter() {
+			let (id, units_per_second) = match (asset.id, asset.fun) {
+				(xcmAssetId::Concrete(id), Fungibility::Fungible(_)) => {
+					let asset_type: AssetType = id.clone().into();
+					// Shortcut if we know the asset is not supported
+					// This involves the same db read per block, mitigating any attack based on
+					// non-supported assets
+					if !AssetIdInfoGetter::payment_is_supported(asset_type.clone()) {
+						continue;
+					}
+					match AssetIdInfoGetter::get_units_per_second(asset_type) {
+						Some(units_per_second) => (id, units_per_second),
+						None => continue,
+					}
+				}
+				_ => continue,
+			};
+
+			let amount =
+				units_per_second.saturating_mul(weight as u128) / (WEIGHT_PER_SECOND as u128);
+
+			// We dont need to take anything if the amount is 0
+			// For cases (specially tests) where the asset is very cheap with respect
+			// to the weight needed
+			let unused = if amount.is_zero() {
+				payment.clone()
+			} else {
+				let required = MultiAsset {
+					fun: Fungibility::Fungible(amount),
+					id: xcmAssetId::Concrete(id.clone()),
+				};
+				match payment.clone().checked_sub(required) {
+					Ok(unused) => unused,
+					// Not enough of this asset, try with the next one
+					Err(_) => continue,
+				}
+			};
+
+			self.0 = self.0.saturating_add(weight);
+			self.1.push((id, weight, amount));
+
+			return Ok(unused);
+		}
+
+		Err(XcmError::TooExpensive)
+	}
+
+	// Refund weight in a single asset, as only one can be returned. We go through the purchases
+	// from the most recent one and refund in the first that has enough weight left to cover the
+	// whole refund. If none does, we refund as much as possible with the purchase that has the
+	// most weight left. The refund is priced as the purchase was, whatever the current units per
+	// second of the asset.
+	fn refund_weight(&mut self, weight: Weight) -> Option<MultiAsset> {
+		let index = self
+			.1
+			.iter()
+			.rposition(|(_, bought_weight, _)| *bought_weight >= weight)
+			.or_else(|| {
+				self.1
+					.iter()
+					.enumerate()
+					.max_by_key(|(_, (_, bought_weight, _))| *bought_weight)
+					.map(|(index, _)| index)
+			})?;
+		let (id, bought_weight, paid_amount) = &mut self.1[index];
+
+		let weight = weight.min(*bought_weight);
+		if weight.is_zero() {
+			return None;
+		}
+
+		let amount = paid_amount.saturating_mul(weight as u128) / (*bought_weight as u128);
+		*bought_weight -= weight;
+		*paid_amount -= amount;
+		self.0 = self.0.saturating_sub(weight);
+
+		if amount.is_zero() {
+			return None;
+		}
+
+		Some(MultiAsset {
+			fun: Fungibility::Fungible(amount),
+			id: xcmAssetId::Concrete(id.clone()),
+		})
+	}
+}
+
+/// Deal with spent fees, deposit them per asset as dictated by R
+impl<
+		AssetType: From<MultiLocation> + Clone,
+		AssetIdInfoGetter: UnitsToWeightRatio<AssetType>,
+		R: TakeRevenue,
+	> Drop for MultiAssetTrader<AssetType, AssetIdInfoGetter, R>
+{
+	fn drop(&mut self) {
+		let mut revenue: Vec<(MultiLocation, u128)> = Vec::new();
+		for (id, _, amount) in self.1.drain(..) {
+			match revenue.iter_mut().find(|(prev_id, _)| prev_id == &id) {
+				Some((_, total)) => *total = total.saturating_add(amount),
+				None => revenue.push((id, amount)),
+			}
+		}
+
+		for (id, amount) in revenue {
+			if !amount.is_zero() {
+				R::take_revenue((id, amount).into());
+			}
+		}
+	}
+}
+
 /// XCM fee depositor to which we implement the TakeRevenue trait
 /// It receives a fungibles::Mutate implemented argument, a matcher to convert MultiAsset into
 /// AssetId and amount, and the fee receiver account
@@ -186,6 +327,23 @@ pub trait UnitsToWeightRatio<AssetType> {
 	fn get_units_per_second(asset_type: AssetType) -> Option<u128>;
 }
 
+// Defines the trait to update how much is charged per second of execution in a given asset
+// This is used to keep fees in line with the market price of the asset
+pub trait UpdateFeePerSecond {
+	// Update the fee per second of the asset at location, only if a fee was already set for it
+	// Returns whether the fee was updated
+	fn update_fee_per_second(location: &MultiLocation, fee_per_second: u128) -> bool;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(5)]
+impl UpdateFeePerSecond for Tuple {
+	fn update_fee_per_second(location: &MultiLocation, fee_per_second: u128) -> bool {
+		let mut updated = false;
+		for_tuples!( #( updated |= Tuple::update_fee_per_second(location, fee_per_second); )* );
+		updated
+	}
+}
+
 #[cfg(test)]
 mod test {
 	use super::*;
@@ -416,4 +574,256 @@ mod test {
 		);
 		assert_eq!(trader.0, 500); // still thinks we have unreturned weight
 	}
+
+	thread_local! {
+		static REVENUE: sp_std::cell::RefCell<Vec<MultiAsset>> = Default::default();
+	}
+
+	struct RevenueCollector;
+	impl TakeRevenue for RevenueCollector {
+		fn take_revenue(revenue: MultiAsset) {
+			REVENUE.with(|r| r.borrow_mut().push(revenue));
+		}
+	}
+
+	fn parachain_location(para_id: u32) -> MultiLocation {
+		MultiLocation {
+			parents: 1u8,
+			interior: Junctions::X1(Junction::Parachain(para_id)),
+		}
+	}
+
+	fn parachain_asset(para_id: u32, amount: u128) -> MultiAsset {
+		MultiAsset {
+			id: AssetId::Concrete(parachain_location(para_id)),
+			fun: Fungibility::Fungible(amount),
+		}
+	}
+
+	#[test]
+	fn multi_asset_trader_can_buy_weight_several_times_with_different_assets() {
+		let mut trader: MultiAssetTrader<MultiLocation, (), ()> = MultiAssetTrader::new();
+
+		let mut asset_one_payment = Assets::new();
+		asset_one_payment.subsume(parachain_asset(1000, 100));
+		let unused = trader
+			.buy_weight(100u64, asset_one_payment)
+			.expect("can buy weight with first asset");
+		assert!(unused.is_empty());
+
+		let mut asset_two_payment = Assets::new();
+		asset_two_payment.subsume(parachain_asset(1001, 10_000));
+		let unused = trader
+			.buy_weight(1_000u64, asset_two_payment)
+			.expect("can buy weight with second asset");
+		assert_eq!(unused, parachain_asset(1001, 9_000).into());
+
+		let mut asset_one_payment = Assets::new();
+		asset_one_payment.subsume(parachain_asset(1000, 50));
+		trader
+			.buy_weight(50u64, asset_one_payment)
+			.expect("can buy weight with first asset again");
+
+		assert_eq!(trader.0, 1_150u64);
+		assert_eq!(
+			trader.1,
+			vec![
+				(parachain_location(1000), 100, 100),
+				(parachain_location(1001), 1_000, 1_000),
+				(parachain_location(1000), 50, 50),
+			]
+		);
+	}
+
+	#[test]
+	fn multi_asset_trader_pays_with_first_sufficient_asset() {
+		let mut trader: MultiAssetTrader<MultiLocation, (), ()> = MultiAssetTrader::new();
+
+		let mut payment = Assets::new();
+		payment.subsume(parachain_asset(1000, 10));
+		payment.subsume(parachain_asset(1001, 1_000));
+
+		let unused = trader
+			.buy_weight(100u64, payment)
+			.expect("can buy weight with second asset");
+
+		let mut expected_unused = Assets::new();
+		expected_unused.subsume(parachain_asset(1000, 10));
+		expected_unused.subsume(parachain_asset(1001, 900));
+		assert_eq!(unused, expected_unused);
+		assert_eq!(trader.1, vec![(parachain_location(1001), 100, 100)]);
+
+		// no asset is sufficient
+		let mut payment = Assets::new();
+		payment.subsume(parachain_asset(1000, 10));
+		assert_eq!(
+			trader.buy_weight(100u64, payment),
+			Err(XcmError::TooExpensive)
+		);
+	}
+
+	#[test]
+	fn multi_asset_trader_refunds_in_most_recent_asset() {
+		let mut trader: MultiAssetTrader<MultiLocation, (), ()> = MultiAssetTrader::new();
+
+		let mut asset_one_payment = Assets::new();
+		asset_one_payment.subsume(parachain_asset(1000, 100));
+		trader
+			.buy_weight(100u64, asset_one_payment)
+			.expect("can buy weight with first asset");
+
+		let mut asset_two_payment = Assets::new();
+		asset_two_payment.subsume(parachain_asset(1001, 200));
+		trader
+			.buy_weight(200u64, asset_two_payment)
+			.expect("can buy weight with second asset");
+
+		// refund is capped to the weight bought with the most recent asset
+		assert_eq!(
+			trader.refund_weight(250u64),
+			Some(parachain_asset(1001, 200))
+		);
+		assert_eq!(trader.0, 100u64);
+
+		// then refunds in the previous asset
+		assert_eq!(trader.refund_weight(30u64), Some(parachain_asset(1000, 30)));
+		assert_eq!(trader.0, 70u64);
+		assert_eq!(
+			trader.1,
+			vec![
+				(parachain_location(1000), 70, 70),
+				(parachain_location(1001), 0, 0),
+			]
+		);
+	}
+
+	#[test]
+	fn multi_asset_trader_refunds_in_purchase_covering_the_refund() {
+		let mut trader: MultiAssetTrader<MultiLocation, (), ()> = MultiAssetTrader::new();
+
+		let mut asset_one_payment = Assets::new();
+		asset_one_payment.subsume(parachain_asset(1000, 300));
+		trader
+			.buy_weight(300u64, asset_one_payment)
+			.expect("can buy weight with first asset");
+
+		let mut asset_two_payment = Assets::new();
+		asset_two_payment.subsume(parachain_asset(1001, 10));
+		trader
+			.buy_weight(10u64, asset_two_payment)
+			.expect("can buy weight with second asset");
+
+		// the most recent purchase can't cover the refund, the previous one can
+		assert_eq!(
+			trader.refund_weight(250u64),
+			Some(parachain_asset(1000, 250))
+		);
+		assert_eq!(trader.0, 60u64);
+		assert_eq!(
+			trader.1,
+			vec![
+				(parachain_location(1000), 50, 50),
+				(parachain_location(1001), 10, 10),
+			]
+		);
+
+		// when no purchase covers it, we refund as much as possible
+		assert_eq!(
+			trader.refund_weight(100u64),
+			Some(parachain_asset(1000, 50))
+		);
+		assert_eq!(trader.0, 10u64);
+	}
+
+	thread_local! {
+		static UNITS_PER_SECOND: sp_std::cell::Cell<u128> = sp_std::cell::Cell::new(0);
+	}
+
+	struct ChangingUnitsPerSecond;
+	impl UnitsToWeightRatio<MultiLocation> for ChangingUnitsPerSecond {
+		fn payment_is_supported(_asset_type: MultiLocation) -> bool {
+			true
+		}
+		fn get_units_per_second(_asset_type: MultiLocation) -> Option<u128> {
+			Some(UNITS_PER_SECOND.with(|u| u.get()))
+		}
+	}
+
+	#[test]
+	fn multi_asset_trader_refunds_at_the_price_paid() {
+		let mut trader: MultiAssetTrader<MultiLocation, ChangingUnitsPerSecond, ()> =
+			MultiAssetTrader::new();
+
+		UNITS_PER_SECOND.with(|u| u.set(2 * WEIGHT_PER_SECOND as u128));
+		let mut payment = Assets::new();
+		payment.subsume(parachain_asset(1000, 200));
+		trader
+			.buy_weight(100u64, payment)
+			.expect("can buy weight at the first price");
+
+		UNITS_PER_SECOND.with(|u| u.set(WEIGHT_PER_SECOND as u128));
+		let mut payment = Assets::new();
+		payment.subsume(parachain_asset(1000, 100));
+		trader
+			.buy_weight(100u64, payment)
+			.expect("can buy weight at the second price");
+
+		assert_eq!(
+			trader.1,
+			vec![
+				(parachain_location(1000), 100, 200),
+				(parachain_location(1000), 100, 100),
+			]
+		);
+
+		// the price changes again, refunds are made at the price of each purchase
+		UNITS_PER_SECOND.with(|u| u.set(3 * WEIGHT_PER_SECOND as u128));
+		assert_eq!(trader.refund_weight(50u64), Some(parachain_asset(1000, 50)));
+		assert_eq!(
+			trader.refund_weight(100u64),
+			Some(parachain_asset(1000, 200))
+		);
+		assert_eq!(
+			trader.refund_weight(100u64),
+			Some(parachain_asset(1000, 50))
+		);
+		assert_eq!(trader.refund_weight(10u64), None);
+		assert_eq!(trader.0, 0u64);
+		assert_eq!(
+			trader.1,
+			vec![
+				(parachain_location(1000), 0, 0),
+				(parachain_location(1000), 0, 0),
+			]
+		);
+	}
+
+	#[test]
+	fn multi_asset_trader_takes_revenue_per_asset() {
+		REVENUE.with(|r| r.borrow_mut().clear());
+
+		{
+			let mut trader: MultiAssetTrader<MultiLocation, (), RevenueCollector> =
+				MultiAssetTrader::new();
+
+			let mut asset_one_payment = Assets::new();
+			asset_one_payment.subsume(parachain_asset(1000, 100));
+			trader
+				.buy_weight(100u64, asset_one_payment)
+				.expect("can buy weight with first asset");
+
+			let mut asset_two_payment = Assets::new();
+			asset_two_payment.subsume(parachain_asset(1001, 200));
+			trader
+				.buy_weight(200u64, asset_two_payment)
+				.expect("can buy weight with second asset");
+
+			trader.refund_weight(50u64);
+		}
+
+		assert_eq!(
+			REVENUE.with(|r| r.borrow().clone()),
+			vec![parachain_asset(1000, 100), parachain_asset(1001, 150)]
+		);
+	}
 }
@@ -36,5 +36,11 @@ pub use origin_conversion::*;
 mod transactor_traits;
 pub use transactor_traits::*;
 
+mod dry_run;
+pub use dry_run::*;
+
 mod ethereum_xcm;
 pub use ethereum_xcm::*;
+
+mod transfer_logs;
+pub use transfer_logs::*;
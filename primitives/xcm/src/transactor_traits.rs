@@ -13,8 +13,9 @@
 
 // You should have received a copy of the GNU General Public License
 
+use cumulus_primitives_core::{relay_chain::HrmpChannelId, ParaId};
 use sp_std::vec::Vec;
-use xcm::latest::MultiLocation;
+use xcm::latest::{MultiLocation, QueryId};
 
 // The utility calls that need to be implemented as part of
 // this pallet
@@ -40,3 +41,42 @@ pub trait XcmTransact: UtilityEncodeCall {
 	/// Encode call from the relay.
 	fn destination(self) -> MultiLocation;
 }
+
+// The hrmp calls that need to be implemented as part of
+// this pallet
+#[derive(Debug, PartialEq, Eq)]
+pub enum HrmpAvailableCalls {
+	InitOpenChannel(ParaId, u32, u32),
+	AcceptOpenChannel(ParaId),
+	CloseChannel(HrmpChannelId),
+}
+
+// Trait that ensures we can encode the hrmp calls of the relay chain. The calls are
+// dispatched by the sovereign account of this chain, which is the only one allowed to
+// manage its channels
+pub trait HrmpEncodeCall {
+	fn hrmp_encode_call(call: HrmpAvailableCalls) -> Vec<u8>;
+}
+
+// Trait to register queries whose response must be notified back to this chain
+// by dispatching a call. This allows the transactor to learn the outcome of the
+// messages it sends without depending on a specific response handler
+pub trait XcmQueryNotifier<Call, BlockNumber> {
+	/// Register a query expecting a response from `responder`. Once the response arrives,
+	/// `notify` is dispatched with the query id and the response as its last two arguments
+	fn new_notify_query(responder: MultiLocation, notify: Call, timeout: BlockNumber) -> QueryId;
+}
+
+impl<T, Call> XcmQueryNotifier<Call, T::BlockNumber> for pallet_xcm::Pallet<T>
+where
+	T: pallet_xcm::Config,
+	Call: Into<<T as pallet_xcm::Config>::Call>,
+{
+	fn new_notify_query(
+		responder: MultiLocation,
+		notify: Call,
+		timeout: T::BlockNumber,
+	) -> QueryId {
+		pallet_xcm::Pallet::<T>::new_notify_query(responder, notify, timeout)
+	}
+}
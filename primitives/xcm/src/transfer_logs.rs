@@ -0,0 +1,458 @@
+// Copyright 2019-2022 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+// XC-20s minted and burnt by XCM never go through the EVM, so ERC-20 indexers relying on
+// Transfer logs cannot reconcile their balances. The transactor below records a Transfer log
+// from (or to) the zero address for every deposit (or withdrawal) of a matching asset, and
+// between both accounts for every transfer of it.
+
+use ethereum::Log;
+use ethereum_types::{H160, H256, U256};
+use sp_runtime::traits::Convert;
+use sp_std::{marker::PhantomData, vec, vec::Vec};
+use xcm::latest::prelude::*;
+use xcm_executor::{
+	traits::{Convert as XcmConvert, MatchesFungibles, TransactAsset},
+	Assets,
+};
+
+/// Records Ethereum logs for state changes made outside of the EVM, so that they are part of
+/// the logs of the current Ethereum block.
+pub trait EthereumLogsRecorder {
+	fn record_logs(logs: Vec<Log>);
+}
+
+impl EthereumLogsRecorder for () {
+	fn record_logs(_logs: Vec<Log>) {}
+}
+
+/// Topic of the ERC-20 `Transfer(address,address,uint256)` event.
+pub fn erc20_transfer_topic() -> H256 {
+	H256(sp_io::hashing::keccak_256(
+		b"Transfer(address,address,uint256)",
+	))
+}
+
+/// Builds the ERC-20 `Transfer` log of `amount` tokens of the `token` contract.
+pub fn erc20_transfer_log(token: H160, from: H160, to: H160, amount: U256) -> Log {
+	let mut data = [0u8; 32];
+	amount.to_big_endian(&mut data);
+
+	Log {
+		address: token,
+		topics: vec![erc20_transfer_topic(), from.into(), to.into()],
+		data: data.to_vec(),
+	}
+}
+
+/// Wraps an asset transactor, recording the ERC-20 `Transfer` logs of the assets it mints and
+/// burns and of the transfers between accounts. Assets matched by `Matcher` are deposited from
+/// the zero address and withdrawn to it, the address of the ERC-20 contract being given by
+/// `AssetIdToAddress`.
+pub struct TransferLogsTransactor<
+	Transactor,
+	Matcher,
+	AccountIdConverter,
+	AccountId,
+	AssetId,
+	Balance,
+	AssetIdToAddress,
+	Recorder,
+>(
+	PhantomData<(
+		Transactor,
+		Matcher,
+		AccountIdConverter,
+		AccountId,
+		AssetId,
+		Balance,
+		AssetIdToAddress,
+		Recorder,
+	)>,
+);
+
+impl<
+		Transactor: TransactAsset,
+		Matcher: MatchesFungibles<AssetId, Balance>,
+		AccountIdConverter: XcmConvert<MultiLocation, AccountId>,
+		AccountId: Into<H160>,
+		AssetId,
+		Balance: Into<U256>,
+		AssetIdToAddress: Convert<AssetId, H160>,
+		Recorder: EthereumLogsRecorder,
+	>
+	TransferLogsTransactor<
+		Transactor,
+		Matcher,
+		AccountIdConverter,
+		AccountId,
+		AssetId,
+		Balance,
+		AssetIdToAddress,
+		Recorder,
+	>
+{
+	/// Records the transfer of `what` from `from` to `to`, `None` standing for the zero address
+	fn record_transfer(
+		what: &MultiAsset,
+		from: Option<&MultiLocation>,
+		to: Option<&MultiLocation>,
+	) {
+		let (asset_id, amount) = match Matcher::matches_fungibles(what) {
+			Ok(matched) => matched,
+			Err(_) => return,
+		};
+		let to_address = |location: Option<&MultiLocation>| match location {
+			Some(location) => AccountIdConverter::convert_ref(location).map(Into::into),
+			None => Ok(H160::zero()),
+		};
+		let (from, to) = match (to_address(from), to_address(to)) {
+			(Ok(from), Ok(to)) => (from, to),
+			_ => return,
+		};
+		let token = AssetIdToAddress::convert(asset_id);
+
+		Recorder::record_logs(vec![erc20_transfer_log(token, from, to, amount.into())]);
+	}
+}
+
+impl<
+		Transactor: TransactAsset,
+		Matcher: MatchesFungibles<AssetId, Balance>,
+		AccountIdConverter: XcmConvert<MultiLocation, AccountId>,
+		AccountId: Into<H160>,
+		AssetId,
+		Balance: Into<U256>,
+		AssetIdToAddress: Convert<AssetId, H160>,
+		Recorder: EthereumLogsRecorder,
+	> TransactAsset
+	for TransferLogsTransactor<
+		Transactor,
+		Matcher,
+		AccountIdConverter,
+		AccountId,
+		AssetId,
+		Balance,
+		AssetIdToAddress,
+		Recorder,
+	>
+{
+	fn can_check_in(origin: &MultiLocation, what: &MultiAsset) -> XcmResult {
+		Transactor::can_check_in(origin, what)
+	}
+
+	fn check_in(origin: &MultiLocation, what: &MultiAsset) {
+		Transactor::check_in(origin, what)
+	}
+
+	fn check_out(dest: &MultiLocation, what: &MultiAsset) {
+		Transactor::check_out(dest, what)
+	}
+
+	fn deposit_asset(what: &MultiAsset, who: &MultiLocation) -> XcmResult {
+		Transactor::deposit_asset(what, who)?;
+		Self::record_transfer(what, None, Some(who));
+		Ok(())
+	}
+
+	fn withdraw_asset(what: &MultiAsset, who: &MultiLocation) -> Result<Assets, XcmError> {
+		let withdrawn = Transactor::withdraw_asset(what, who)?;
+		Self::record_transfer(what, Some(who), None);
+		Ok(withdrawn)
+	}
+
+	fn internal_transfer_asset(
+		asset: &MultiAsset,
+		from: &MultiLocation,
+		to: &MultiLocation,
+	) -> Result<Assets, XcmError> {
+		let transferred = Transactor::internal_transfer_asset(asset, from, to)?;
+		Self::record_transfer(asset, Some(from), Some(to));
+		Ok(transferred)
+	}
+
+	fn transfer_asset(
+		asset: &MultiAsset,
+		from: &MultiLocation,
+		to: &MultiLocation,
+	) -> Result<Assets, XcmError> {
+		// Same as the default implementation, but going through the logged functions of this
+		// transactor rather than those of the inner one
+		match Self::internal_transfer_asset(asset, from, to) {
+			Err(XcmError::AssetNotFound | XcmError::Unimplemented) => {
+				let assets = Self::withdraw_asset(asset, from)?;
+				Self::deposit_asset(asset, to)?;
+				Ok(assets)
+			}
+			result => result,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use core::{borrow::Borrow, cell::RefCell};
+	use xcm_executor::traits::Error as MatchError;
+
+	const ASSET_ID: u128 = 1;
+
+	thread_local! {
+		static RECORDED: RefCell<Vec<Log>> = RefCell::new(Vec::new());
+	}
+
+	struct MockRecorder;
+	impl EthereumLogsRecorder for MockRecorder {
+		fn record_logs(logs: Vec<Log>) {
+			RECORDED.with(|recorded| recorded.borrow_mut().extend(logs));
+		}
+	}
+
+	struct MockTransactor;
+	impl TransactAsset for MockTransactor {
+		fn deposit_asset(_what: &MultiAsset, _who: &MultiLocation) -> XcmResult {
+			Ok(())
+		}
+
+		fn withdraw_asset(what: &MultiAsset, _who: &MultiLocation) -> Result<Assets, XcmError> {
+			Ok(what.clone().into())
+		}
+
+		fn internal_transfer_asset(
+			asset: &MultiAsset,
+			_from: &MultiLocation,
+			_to: &MultiLocation,
+		) -> Result<Assets, XcmError> {
+			Ok(asset.clone().into())
+		}
+	}
+
+	// Does not implement `internal_transfer_asset`
+	struct WithdrawDepositTransactor;
+	impl TransactAsset for WithdrawDepositTransactor {
+		fn deposit_asset(_what: &MultiAsset, _who: &MultiLocation) -> XcmResult {
+			Ok(())
+		}
+
+		fn withdraw_asset(what: &MultiAsset, _who: &MultiLocation) -> Result<Assets, XcmError> {
+			Ok(what.clone().into())
+		}
+	}
+
+	struct FailingTransactor;
+	impl TransactAsset for FailingTransactor {}
+
+	// Matches assets located at `GeneralIndex(ASSET_ID)`
+	struct MockMatcher;
+	impl MatchesFungibles<u128, u128> for MockMatcher {
+		fn matches_fungibles(a: &MultiAsset) -> Result<(u128, u128), MatchError> {
+			match a {
+				MultiAsset {
+					id:
+						Concrete(MultiLocation {
+							parents: 0,
+							interior: X1(GeneralIndex(id)),
+						}),
+					fun: Fungible(amount),
+				} => Ok((*id, *amount)),
+				_ => Err(MatchError::AssetNotFound),
+			}
+		}
+	}
+
+	// Converts `AccountKey20` locations
+	struct MockAccountIdConverter;
+	impl XcmConvert<MultiLocation, H160> for MockAccountIdConverter {
+		fn convert_ref(location: impl Borrow<MultiLocation>) -> Result<H160, ()> {
+			match location.borrow() {
+				MultiLocation {
+					parents: 0,
+					interior: X1(AccountKey20 { key, .. }),
+				} => Ok(H160(*key)),
+				_ => Err(()),
+			}
+		}
+	}
+
+	struct MockAssetIdToAddress;
+	impl Convert<u128, H160> for MockAssetIdToAddress {
+		fn convert(id: u128) -> H160 {
+			H160::from_low_u64_be(id as u64)
+		}
+	}
+
+	type Transactor<Inner> = TransferLogsTransactor<
+		Inner,
+		MockMatcher,
+		MockAccountIdConverter,
+		H160,
+		u128,
+		u128,
+		MockAssetIdToAddress,
+		MockRecorder,
+	>;
+
+	fn asset(id: u128, amount: u128) -> MultiAsset {
+		(MultiLocation::new(0, X1(GeneralIndex(id))), amount).into()
+	}
+
+	fn account(key: u8) -> MultiLocation {
+		MultiLocation::new(
+			0,
+			X1(AccountKey20 {
+				network: Any,
+				key: [key; 20],
+			}),
+		)
+	}
+
+	fn recorded() -> Vec<Log> {
+		RECORDED.with(|recorded| recorded.borrow_mut().drain(..).collect())
+	}
+
+	#[test]
+	fn transfer_log_matches_the_erc20_layout() {
+		let log = erc20_transfer_log(
+			H160::repeat_byte(0xff),
+			H160::zero(),
+			H160::repeat_byte(1),
+			U256::from(1000u64),
+		);
+
+		assert_eq!(
+			log.topics[0],
+			H256(
+				hex::decode("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef")
+					.unwrap()
+					.try_into()
+					.unwrap()
+			)
+		);
+		assert_eq!(log.topics[1], H256::zero());
+		assert_eq!(log.topics[2], H160::repeat_byte(1).into());
+		assert_eq!(U256::from_big_endian(&log.data), U256::from(1000u64));
+	}
+
+	#[test]
+	fn deposits_are_logged_from_the_zero_address() {
+		recorded();
+		assert_eq!(
+			Transactor::<MockTransactor>::deposit_asset(&asset(ASSET_ID, 100), &account(1)),
+			Ok(())
+		);
+
+		let token = H160::from_low_u64_be(ASSET_ID as u64);
+		assert_eq!(
+			recorded(),
+			vec![erc20_transfer_log(
+				token,
+				H160::zero(),
+				H160::repeat_byte(1),
+				100u128.into()
+			)]
+		);
+	}
+
+	#[test]
+	fn withdrawals_are_logged_to_the_zero_address() {
+		recorded();
+		assert!(
+			Transactor::<MockTransactor>::withdraw_asset(&asset(ASSET_ID, 100), &account(1))
+				.is_ok()
+		);
+
+		let token = H160::from_low_u64_be(ASSET_ID as u64);
+		assert_eq!(
+			recorded(),
+			vec![erc20_transfer_log(
+				token,
+				H160::repeat_byte(1),
+				H160::zero(),
+				100u128.into()
+			)]
+		);
+	}
+
+	#[test]
+	fn transfers_are_logged_between_both_accounts() {
+		recorded();
+		assert!(Transactor::<MockTransactor>::transfer_asset(
+			&asset(ASSET_ID, 100),
+			&account(1),
+			&account(2)
+		)
+		.is_ok());
+
+		let token = H160::from_low_u64_be(ASSET_ID as u64);
+		assert_eq!(
+			recorded(),
+			vec![erc20_transfer_log(
+				token,
+				H160::repeat_byte(1),
+				H160::repeat_byte(2),
+				100u128.into()
+			)]
+		);
+	}
+
+	#[test]
+	fn transfers_falling_back_to_withdraw_and_deposit_are_logged() {
+		recorded();
+		assert!(Transactor::<WithdrawDepositTransactor>::transfer_asset(
+			&asset(ASSET_ID, 100),
+			&account(1),
+			&account(2)
+		)
+		.is_ok());
+
+		let token = H160::from_low_u64_be(ASSET_ID as u64);
+		assert_eq!(
+			recorded(),
+			vec![
+				erc20_transfer_log(token, H160::repeat_byte(1), H160::zero(), 100u128.into()),
+				erc20_transfer_log(token, H160::zero(), H160::repeat_byte(2), 100u128.into()),
+			]
+		);
+	}
+
+	#[test]
+	fn nothing_is_logged_if_the_inner_transactor_fails() {
+		recorded();
+		assert!(
+			Transactor::<FailingTransactor>::deposit_asset(&asset(ASSET_ID, 100), &account(1))
+				.is_err()
+		);
+		assert!(Transactor::<FailingTransactor>::withdraw_asset(
+			&asset(ASSET_ID, 100),
+			&account(1)
+		)
+		.is_err());
+
+		assert_eq!(recorded(), vec![]);
+	}
+
+	#[test]
+	fn unmatched_assets_are_not_logged() {
+		recorded();
+		let native: MultiAsset = (MultiLocation::here(), 100u128).into();
+		assert_eq!(
+			Transactor::<MockTransactor>::deposit_asset(&native, &account(1)),
+			Ok(())
+		);
+
+		assert_eq!(recorded(), vec![]);
+	}
+}
@@ -189,6 +189,185 @@ macro_rules! impl_runtime_apis_plus_common {
 				}
 			}
 
+			impl moonbeam_rpc_primitives_xcm::XcmFeeApi<Block> for Runtime {
+				fn transact_fee(
+					dest: xcm::VersionedMultiLocation,
+					fee_asset: xcm::VersionedMultiLocation,
+					transact_weight: u64,
+					signed: bool,
+				) -> Result<moonbeam_rpc_primitives_xcm::TransactFee, sp_runtime::DispatchError> {
+					let bad_version = sp_runtime::DispatchError::Other("Unsupported XCM version");
+					let dest = xcm::latest::MultiLocation::try_from(dest)
+						.map_err(|()| bad_version.clone())?;
+					let fee_asset = xcm::latest::MultiLocation::try_from(fee_asset)
+						.map_err(|()| bad_version)?;
+
+					// Same weight and fee the transactor uses when they are not provided
+					let weight = if signed {
+						XcmTransactor::take_weight_from_transact_info_signed(
+							dest.clone(),
+							transact_weight,
+						)?
+					} else {
+						XcmTransactor::take_weight_from_transact_info(dest.clone(), transact_weight)?
+					};
+					let fee = XcmTransactor::take_fee_per_second_from_storage(
+						fee_asset,
+						dest,
+						weight,
+					)?;
+
+					Ok(moonbeam_rpc_primitives_xcm::TransactFee { weight, fee })
+				}
+
+				fn inbound_fees(weight: u64) -> Vec<moonbeam_rpc_primitives_xcm::InboundFee> {
+					use frame_support::weights::constants::WEIGHT_PER_SECOND;
+
+					// Inbound messages are charged as in the trader of the xcm config
+					AssetManager::supported_fee_payment_assets()
+						.into_iter()
+						.filter_map(|asset_type| {
+							let units_per_second =
+								AssetManager::asset_type_units_per_second(&asset_type)?;
+							let location: Option<xcm::latest::MultiLocation> = asset_type.into();
+
+							Some(moonbeam_rpc_primitives_xcm::InboundFee {
+								asset: xcm::VersionedMultiLocation::V1(location?),
+								amount: units_per_second.saturating_mul(weight as u128)
+									/ (WEIGHT_PER_SECOND as u128),
+							})
+						})
+						.collect()
+				}
+			}
+
+			impl moonbeam_rpc_primitives_xcm::XcmDryRunApi<Block> for Runtime {
+				fn dry_run_xcm(
+					origin: xcm::VersionedMultiLocation,
+					message: xcm::VersionedXcm<()>,
+					weight_limit: u64,
+				) -> Result<
+					moonbeam_rpc_primitives_xcm::XcmDryRunEffects,
+					sp_runtime::DispatchError,
+				> {
+					let bad_version = sp_runtime::DispatchError::Other("Unsupported XCM version");
+					let origin = xcm::latest::MultiLocation::try_from(origin)
+						.map_err(|()| bad_version.clone())?;
+					let message = xcm::latest::Xcm::<()>::try_from(message)
+						.map_err(|()| bad_version)?;
+
+					let effects = xcm_primitives::dry_run_xcm::<
+						Runtime,
+						xcm_config::XcmExecutor,
+						_,
+					>(origin, message.into(), weight_limit)?;
+
+					Ok(moonbeam_rpc_primitives_xcm::XcmDryRunEffects {
+						weight_used: effects.outcome.weight_used(),
+						outcome: effects.outcome,
+						events: effects.events.iter().map(Encode::encode).collect(),
+						forwarded_messages: effects
+							.forwarded_messages
+							.into_iter()
+							.map(|(dest, message)| (dest.into(), message.into()))
+							.collect(),
+					})
+				}
+			}
+
+			impl moonbeam_rpc_primitives_xcm::XcAssetRegistryApi<Block> for Runtime {
+				fn foreign_assets(
+					start_after: Option<u128>,
+					limit: u32,
+				) -> Vec<moonbeam_rpc_primitives_xcm::XcAsset> {
+					use frame_support::traits::tokens::fungibles::metadata::Inspect;
+					use pallet_asset_manager::AssetIdType;
+					use pallet_evm_precompileset_assets_erc20::AccountIdAssetIdConversion;
+					use xcm_primitives::UnitsToWeightRatio;
+
+					let assets = match start_after {
+						Some(asset_id) => AssetIdType::<Runtime>::iter_from(
+							AssetIdType::<Runtime>::hashed_key_for(asset_id),
+						),
+						None => AssetIdType::<Runtime>::iter(),
+					};
+
+					assets
+						.take(limit as usize)
+						.map(|(asset_id, asset_type)| {
+							let location: Option<xcm::latest::MultiLocation> =
+								asset_type.clone().into();
+							// Only assets accepted by the trader pay fees
+							let units_per_second =
+								if AssetManager::payment_is_supported(asset_type.clone()) {
+									AssetManager::get_units_per_second(asset_type)
+								} else {
+									None
+								};
+
+							moonbeam_rpc_primitives_xcm::XcAsset {
+								asset_id,
+								location: location.map(xcm::VersionedMultiLocation::V1),
+								address: Runtime::asset_id_to_account(
+									FOREIGN_ASSET_PRECOMPILE_ADDRESS_PREFIX,
+									asset_id,
+								)
+								.into(),
+								name: <Assets as Inspect<AccountId>>::name(asset_id),
+								symbol: <Assets as Inspect<AccountId>>::symbol(asset_id),
+								decimals: <Assets as Inspect<AccountId>>::decimals(asset_id),
+								is_sufficient: moonbeam_runtime_common::assets::asset_is_sufficient::<
+									Assets,
+									AccountId,
+									Balance,
+									_,
+								>(asset_id),
+								units_per_second,
+							}
+						})
+						.collect()
+				}
+
+				fn local_assets(
+					start_after: Option<u128>,
+					limit: u32,
+				) -> Vec<moonbeam_rpc_primitives_xcm::XcAsset> {
+					use frame_support::traits::tokens::fungibles::metadata::Inspect;
+					use pallet_asset_manager::LocalAssetDeposit;
+					use pallet_evm_precompileset_assets_erc20::AccountIdAssetIdConversion;
+
+					let assets = match start_after {
+						Some(asset_id) => LocalAssetDeposit::<Runtime>::iter_from(
+							LocalAssetDeposit::<Runtime>::hashed_key_for(asset_id),
+						),
+						None => LocalAssetDeposit::<Runtime>::iter(),
+					};
+
+					assets
+						.take(limit as usize)
+						.map(|(asset_id, _)| moonbeam_rpc_primitives_xcm::XcAsset {
+							asset_id,
+							location: None,
+							address: Runtime::asset_id_to_account(
+								LOCAL_ASSET_PRECOMPILE_ADDRESS_PREFIX,
+								asset_id,
+							)
+							.into(),
+							name: <LocalAssets as Inspect<AccountId>>::name(asset_id),
+							symbol: <LocalAssets as Inspect<AccountId>>::symbol(asset_id),
+							decimals: <LocalAssets as Inspect<AccountId>>::decimals(asset_id),
+							is_sufficient: moonbeam_runtime_common::assets::asset_is_sufficient::<
+								LocalAssets,
+								AccountId,
+								Balance,
+								_,
+							>(asset_id),
+							units_per_second: None,
+						})
+						.collect()
+				}
+			}
+
 			impl fp_rpc::EthereumRuntimeRPCApi<Block> for Runtime {
 				fn chain_id() -> u64 {
 					<Runtime as pallet_evm::Config>::ChainId::get()
@@ -364,16 +543,16 @@ macro_rules! impl_runtime_apis_plus_common {
 				) -> bool {
 					let block_number = parent_header.number + 1;
 
-					// The Moonbeam runtimes use an entropy source that needs to do some accounting
-					// work during block initialization. Therefore we initialize it here to match
-					// the state it will be in when the next block is being executed.
-					use frame_support::traits::OnInitialize;
+					// The Moonbeam runtimes select eligible authors with the VRF output of the
+					// previous block, which is committed during block initialization. Therefore we
+					// commit it here to match the state it will be in when the next block is being
+					// executed.
 					System::initialize(
 						&block_number,
 						&parent_header.hash(),
 						&parent_header.digest,
 					);
-					RandomnessCollectiveFlip::on_initialize(block_number);
+					pallet_randomness::Pallet::<Self>::commit_local_vrf_output();
 
 					// Because the staking solution calculates the next staking set at the beginning
 					// of the first block in the new round, the only way to accurately predict the
@@ -451,6 +630,8 @@ macro_rules! impl_runtime_apis_plus_common {
 					use pallet_asset_manager::Pallet as PalletAssetManagerBench;
 					use pallet_xcm_transactor::Pallet as XcmTransactorBench;
 					use pallet_randomness::Pallet as RandomnessBench;
+					use pallet_xcm_rate_limiter::Pallet as XcmRateLimiterBench;
+					use pallet_asset_price_feed::Pallet as AssetPriceFeedBench;
 
 					let mut list = Vec::<BenchmarkList>::new();
 
@@ -464,6 +645,8 @@ macro_rules! impl_runtime_apis_plus_common {
 					list_benchmark!(list, extra, pallet_asset_manager, PalletAssetManagerBench::<Runtime>);
 					list_benchmark!(list, extra, xcm_transactor, XcmTransactorBench::<Runtime>);
 					list_benchmark!(list, extra, pallet_randomness, RandomnessBench::<Runtime>);
+					list_benchmark!(list, extra, xcm_rate_limiter, XcmRateLimiterBench::<Runtime>);
+					list_benchmark!(list, extra, pallet_asset_price_feed, AssetPriceFeedBench::<Runtime>);
 
 					let storage_info = AllPalletsWithSystem::storage_info();
 
@@ -489,6 +672,8 @@ macro_rules! impl_runtime_apis_plus_common {
 					use pallet_asset_manager::Pallet as PalletAssetManagerBench;
 					use pallet_xcm_transactor::Pallet as XcmTransactorBench;
 					use pallet_randomness::Pallet as RandomnessBench;
+					use pallet_xcm_rate_limiter::Pallet as XcmRateLimiterBench;
+					use pallet_asset_price_feed::Pallet as AssetPriceFeedBench;
 
 					let whitelist: Vec<TrackedStorageKey> = vec![
 						// Block Number
@@ -599,6 +784,18 @@ macro_rules! impl_runtime_apis_plus_common {
 						pallet_randomness,
 						RandomnessBench::<Runtime>
 					);
+					add_benchmark!(
+						params,
+						batches,
+						xcm_rate_limiter,
+						XcmRateLimiterBench::<Runtime>
+					);
+					add_benchmark!(
+						params,
+						batches,
+						pallet_asset_price_feed,
+						AssetPriceFeedBench::<Runtime>
+					);
 
 					if batches.is_empty() {
 						return Err("Benchmark not found for this pallet.".into());
@@ -0,0 +1,122 @@
+// Copyright 2019-2022 PureStake Inc.
+// This file is part of Moonbeam.
+
+// Moonbeam is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Moonbeam is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
+
+use frame_support::{
+	storage::{storage_prefix, unhashed, KeyPrefixIterator},
+	traits::PalletInfoAccess,
+	Blake2_128Concat, ReversibleStorageHasher, StorageHasher,
+};
+use pallet_assets::DestroyWitness;
+use parity_scale_codec::{Decode, Encode};
+use sp_std::vec::Vec;
+
+/// Mirror of pallet_assets::AssetDetails, whose fields are not public
+/// The deposit is assumed to be in the same unit as the asset balances
+/// Its layout is checked against the pallet_assets version in use by the runtime tests
+#[derive(Encode, Decode)]
+pub struct AssetDetails<AccountId, Balance> {
+	pub owner: AccountId,
+	pub issuer: AccountId,
+	pub admin: AccountId,
+	pub freezer: AccountId,
+	pub supply: Balance,
+	pub deposit: Balance,
+	pub min_balance: Balance,
+	pub is_sufficient: bool,
+	pub accounts: u32,
+	pub sufficients: u32,
+	pub approvals: u32,
+	pub is_frozen: bool,
+}
+
+/// Key under which the pallet_assets instance `Assets` stores `item` of `asset_id`
+fn asset_key<Assets: PalletInfoAccess, AssetId: Encode>(item: &[u8], asset_id: AssetId) -> Vec<u8> {
+	storage_prefix(Assets::name().as_bytes(), item)
+		.into_iter()
+		.chain(Blake2_128Concat::hash(&asset_id.encode()))
+		.collect()
+}
+
+/// The details of `asset_id` in the pallet_assets instance `Assets`, if it exists
+pub fn asset_details<Assets, AccountId, Balance, AssetId>(
+	asset_id: AssetId,
+) -> Option<AssetDetails<AccountId, Balance>>
+where
+	Assets: PalletInfoAccess,
+	AccountId: Decode,
+	Balance: Decode,
+	AssetId: Encode,
+{
+	unhashed::get(&asset_key::<Assets, _>(b"Asset", asset_id))
+}
+
+/// Whether `asset_id` exists in the pallet_assets instance `Assets` and was created as
+/// sufficient, i.e., it can create accounts on its own
+pub fn asset_is_sufficient<Assets, AccountId, Balance, AssetId>(asset_id: AssetId) -> bool
+where
+	Assets: PalletInfoAccess,
+	AccountId: Decode,
+	Balance: Decode,
+	AssetId: Encode,
+{
+	asset_details::<Assets, AccountId, Balance, _>(asset_id)
+		.map_or(false, |details| details.is_sufficient)
+}
+
+/// The witness needed to destroy `asset_id` in the pallet_assets instance `Assets`
+pub fn asset_destroy_witness<Assets, AccountId, Balance, AssetId>(
+	asset_id: AssetId,
+) -> Option<DestroyWitness>
+where
+	Assets: PalletInfoAccess,
+	AccountId: Decode,
+	Balance: Decode,
+	AssetId: Encode,
+{
+	unhashed::get::<pallet_assets::AssetDetails<Balance, AccountId, Balance>>(
+		&asset_key::<Assets, _>(b"Asset", asset_id),
+	)
+	.map(|details| details.destroy_witness())
+}
+
+fn decode_account_key<AccountId: Decode>(
+	raw_key: &[u8],
+) -> Result<AccountId, parity_scale_codec::Error> {
+	AccountId::decode(&mut Blake2_128Concat::reverse(raw_key))
+}
+
+/// Iterator over the accounts holding `asset_id` in the pallet_assets instance `Assets`, starting
+/// after `start_after` if given
+pub fn asset_accounts<Assets, AccountId, AssetId>(
+	asset_id: AssetId,
+	start_after: Option<&AccountId>,
+) -> KeyPrefixIterator<AccountId>
+where
+	Assets: PalletInfoAccess,
+	AccountId: Encode + Decode,
+	AssetId: Encode,
+{
+	let prefix = asset_key::<Assets, _>(b"Account", asset_id);
+	let previous_key = match start_after {
+		Some(who) => prefix
+			.iter()
+			.copied()
+			.chain(Blake2_128Concat::hash(&who.encode()))
+			.collect(),
+		None => prefix.clone(),
+	};
+	KeyPrefixIterator::new(prefix, previous_key, decode_account_key::<AccountId>)
+}
@@ -28,7 +28,11 @@ macro_rules! impl_moonbeam_xcm_call {
 					match (call.clone(), raw_origin) {
 						(
 							Call::EthereumXcm(pallet_ethereum_xcm::Call::transact { .. }) |
-							Call::EthereumXcm(pallet_ethereum_xcm::Call::transact_through_proxy { .. }),
+							Call::EthereumXcm(pallet_ethereum_xcm::Call::transact_through_proxy { .. }) |
+							Call::EthereumXcm(pallet_ethereum_xcm::Call::transact_with_response { .. }) |
+							Call::EthereumXcm(
+								pallet_ethereum_xcm::Call::transact_through_proxy_with_response { .. }
+							),
 							RawOrigin::Signed(account_id)
 						) => {
 							return Call::dispatch(
@@ -14,6 +14,13 @@
 // You should have received a copy of the GNU General Public License
 // along with Moonbeam.  If not, see <http://www.gnu.org/licenses/>.
 
+// Accounts that chose a fee payment asset in AssetManager pay the fees of their transactions
+// with it. The fee is converted at the minimum gas price, the gas executed in a second being
+// worth the units per second charged for the asset through XCM, and goes to the account
+// receiving XCM fees. Ethereum transactions of these accounts are validated against their
+// balance of the asset rather than their native balance, and choosing a supported asset through
+// `set_fee_payment_asset_preference` is already paid with it, so that accounts holding no
+// native currency can opt in.
 #[macro_export]
 macro_rules! impl_on_charge_evm_transaction {
 	{} => {
@@ -28,6 +35,202 @@ macro_rules! impl_on_charge_evm_transaction {
 		type NegativeImbalanceFor<T> =
 			<<T as pallet_evm::Config>::Currency as CurrencyT<CurrencyAccountId<T>>>::NegativeImbalance;
 
+		/// The fee withdrawn for an Ethereum transaction, in the native currency or in the fee
+		/// payment asset of the sender
+		pub enum EvmFeeLiquidity<NI> {
+			Native(NI),
+			Asset {
+				asset_id: AssetId,
+				fee: U256,
+				amount: Balance,
+			},
+		}
+
+		/// Converts a fee in the native currency into units of a fee payment asset
+		pub fn evm_fee_in_asset(fee: U256, units_per_second: u128) -> Balance {
+			let (min_gas_price, _) =
+				<<Runtime as pallet_evm::Config>::FeeCalculator as FeeCalculator>::min_gas_price();
+			let gas_per_second =
+				<<Runtime as pallet_evm::Config>::GasWeightMapping as GasWeightMapping>::weight_to_gas(
+					WEIGHT_PER_SECOND,
+				);
+			let fee_per_second = min_gas_price.saturating_mul(U256::from(gas_per_second));
+
+			fee.saturating_mul(U256::from(units_per_second))
+				.checked_div(fee_per_second)
+				.unwrap_or_default()
+				.unique_saturated_into()
+		}
+
+		/// Refunds the share of a fee paid in a fee payment asset that was not used. A failed
+		/// refund leaves the whole fee to the fees account
+		pub fn refund_fee_in_asset(
+			who: &AccountId,
+			asset_id: AssetId,
+			fee: U256,
+			corrected_fee: U256,
+			amount: Balance,
+		) {
+			let refund: Balance = U256::from(amount)
+				.saturating_mul(fee.saturating_sub(corrected_fee))
+				.checked_div(fee)
+				.unwrap_or_default()
+				.unique_saturated_into();
+			if refund == 0 {
+				return;
+			}
+
+			if let Err(error) = <Assets as frame_support::traits::tokens::fungibles::Transfer<
+				AccountId,
+			>>::transfer(
+				asset_id,
+				&<xcm_config::XcmFeesAccount as frame_support::traits::Get<AccountId>>::get(),
+				who,
+				refund,
+				false,
+			) {
+				log::warn!(
+					"Failed to refund {} of fee payment asset {} to {:?}: {:?}",
+					refund,
+					asset_id,
+					who,
+					error,
+				);
+			}
+		}
+
+		/// Runs the Ethereum transaction `check` of `call` for `who`. When `who` pays fees in an
+		/// asset, the asset must cover the maximum fee of the transaction, and the check runs
+		/// with that fee credited to the native balance of `who` and reverted afterwards, as
+		/// it expects the native balance to cover both the fee and the transferred value.
+		/// `reapply` then persists the writes of a successful check that must outlive it
+		pub fn check_evm_transaction_with_fee_asset<R>(
+			who: &H160,
+			call: &pallet_ethereum::Call<Runtime>,
+			check: impl FnOnce() -> Option<Result<R, TransactionValidityError>>,
+			reapply: impl FnOnce() -> Result<(), TransactionValidityError>,
+		) -> Option<Result<R, TransactionValidityError>> {
+			let max_fee = match call {
+				pallet_ethereum::Call::transact { transaction } => match transaction {
+					pallet_ethereum::Transaction::Legacy(t) => {
+						t.gas_limit.saturating_mul(t.gas_price)
+					}
+					pallet_ethereum::Transaction::EIP2930(t) => {
+						t.gas_limit.saturating_mul(t.gas_price)
+					}
+					pallet_ethereum::Transaction::EIP1559(t) => {
+						t.gas_limit.saturating_mul(t.max_fee_per_gas)
+					}
+				},
+				_ => return check(),
+			};
+			let account = AccountId::from(*who);
+			let (asset_id, units_per_second) = match AssetManager::fee_payment_asset(&account) {
+				Some(fee_payment_asset) => fee_payment_asset,
+				None => return check(),
+			};
+
+			let balance = <Assets as frame_support::traits::tokens::fungibles::Inspect<
+				AccountId,
+			>>::reducible_balance(asset_id, &account, false);
+			if balance < evm_fee_in_asset(max_fee, units_per_second) {
+				return Some(Err(InvalidTransaction::Payment.into()));
+			}
+
+			frame_support::storage::with_transaction(|| {
+				let _ = <Balances as CurrencyT<AccountId>>::deposit_creating(
+					&account,
+					max_fee.unique_saturated_into(),
+				);
+				frame_support::storage::TransactionOutcome::Rollback(
+					Ok::<_, sp_runtime::DispatchError>(check()),
+				)
+			})
+			.unwrap_or_else(|_| Some(Err(InvalidTransaction::Payment.into())))
+			.map(|result| result.and_then(|checked| reapply().map(|_| checked)))
+		}
+
+		/// Charges the fees of Substrate transactions in the fee payment asset of the sender,
+		/// if any, or in the native currency through `CurrencyAdapter`
+		pub struct OnChargeSubstrateTransaction<OU>(sp_std::marker::PhantomData<OU>);
+		impl<OU> pallet_transaction_payment::OnChargeTransaction<Runtime>
+			for OnChargeSubstrateTransaction<OU>
+		where
+			OU: OnUnbalanced<<Balances as CurrencyT<AccountId>>::NegativeImbalance>,
+		{
+			type Balance = Balance;
+			type LiquidityInfo =
+				Option<EvmFeeLiquidity<<Balances as CurrencyT<AccountId>>::NegativeImbalance>>;
+
+			fn withdraw_fee(
+				who: &AccountId,
+				call: &Call,
+				dispatch_info: &DispatchInfoOf<Call>,
+				fee: Balance,
+				tip: Balance,
+			) -> Result<Self::LiquidityInfo, TransactionValidityError> {
+				if fee == 0 {
+					return Ok(None);
+				}
+
+				let fee_payment_asset = match call {
+					// Opting in is paid with the chosen asset
+					Call::AssetManager(pallet_asset_manager::Call::set_fee_payment_asset_preference {
+						asset_type: Some(asset_type),
+					}) => AssetManager::supported_fee_payment_asset(asset_type),
+					_ => AssetManager::fee_payment_asset(who),
+				};
+				if let Some((asset_id, units_per_second)) = fee_payment_asset {
+					let fee = U256::from(fee);
+					let amount = evm_fee_in_asset(fee, units_per_second);
+					<Assets as frame_support::traits::tokens::fungibles::Transfer<AccountId>>::transfer(
+						asset_id,
+						who,
+						&<xcm_config::XcmFeesAccount as frame_support::traits::Get<AccountId>>::get(),
+						amount,
+						false,
+					)
+					.map_err(|_| InvalidTransaction::Payment)?;
+
+					return Ok(Some(EvmFeeLiquidity::Asset { asset_id, fee, amount }));
+				}
+
+				<CurrencyAdapter<Balances, OU> as pallet_transaction_payment::OnChargeTransaction<
+					Runtime,
+				>>::withdraw_fee(who, call, dispatch_info, fee, tip)
+					.map(|withdrawn| withdrawn.map(EvmFeeLiquidity::Native))
+			}
+
+			fn correct_and_deposit_fee(
+				who: &AccountId,
+				dispatch_info: &DispatchInfoOf<Call>,
+				post_info: &PostDispatchInfoOf<Call>,
+				corrected_fee: Balance,
+				tip: Balance,
+				already_withdrawn: Self::LiquidityInfo,
+			) -> Result<(), TransactionValidityError> {
+				let already_withdrawn = match already_withdrawn {
+					Some(EvmFeeLiquidity::Asset { asset_id, fee, amount }) => {
+						refund_fee_in_asset(who, asset_id, fee, U256::from(corrected_fee), amount);
+						return Ok(());
+					}
+					Some(EvmFeeLiquidity::Native(withdrawn)) => Some(withdrawn),
+					None => None,
+				};
+
+				<CurrencyAdapter<Balances, OU> as pallet_transaction_payment::OnChargeTransaction<
+					Runtime,
+				>>::correct_and_deposit_fee(
+					who,
+					dispatch_info,
+					post_info,
+					corrected_fee,
+					tip,
+					already_withdrawn,
+				)
+			}
+		}
+
 		pub struct OnChargeEVMTransaction<OU>(sp_std::marker::PhantomData<OU>);
 		impl<T, OU> OnChargeEVMTransactionT<T> for OnChargeEVMTransaction<OU>
 		where
@@ -37,10 +240,26 @@ macro_rules! impl_on_charge_evm_transaction {
 			OU: OnUnbalanced<NegativeImbalanceFor<T>>,
 			U256: UniqueSaturatedInto<BalanceFor<T>>
 		{
-			type LiquidityInfo = Option<NegativeImbalanceFor<T>>;
+			type LiquidityInfo = Option<EvmFeeLiquidity<NegativeImbalanceFor<T>>>;
 
 			fn withdraw_fee(who: &H160, fee: U256) -> Result<Self::LiquidityInfo, pallet_evm::Error<T>> {
+				let account = AccountId::from(*who);
+				if let Some((asset_id, units_per_second)) = AssetManager::fee_payment_asset(&account) {
+					let amount = evm_fee_in_asset(fee, units_per_second);
+					<Assets as frame_support::traits::tokens::fungibles::Transfer<AccountId>>::transfer(
+						asset_id,
+						&account,
+						&<xcm_config::XcmFeesAccount as frame_support::traits::Get<AccountId>>::get(),
+						amount,
+						false,
+					)
+					.map_err(|_| pallet_evm::Error::<T>::BalanceLow)?;
+
+					return Ok(Some(EvmFeeLiquidity::Asset { asset_id, fee, amount }));
+				}
+
 				EVMCurrencyAdapter::<<T as pallet_evm::Config>::Currency, ()>::withdraw_fee(who, fee)
+					.map(|withdrawn| withdrawn.map(EvmFeeLiquidity::Native))
 			}
 
 			fn correct_and_deposit_fee(
@@ -49,13 +268,24 @@ macro_rules! impl_on_charge_evm_transaction {
 				base_fee: U256,
 				already_withdrawn: Self::LiquidityInfo,
 			) -> Self::LiquidityInfo {
+				let already_withdrawn = match already_withdrawn {
+					Some(EvmFeeLiquidity::Asset { asset_id, fee, amount }) => {
+						// The whole fee, tip included, is kept by the fees account
+						refund_fee_in_asset(&AccountId::from(*who), asset_id, fee, corrected_fee, amount);
+						return None;
+					}
+					Some(EvmFeeLiquidity::Native(withdrawn)) => Some(withdrawn),
+					None => None,
+				};
+
 				<EVMCurrencyAdapter<<T as pallet_evm::Config>::Currency, OU> as OnChargeEVMTransactionT<
 					T,
 				>>::correct_and_deposit_fee(who, corrected_fee, base_fee, already_withdrawn)
+					.map(EvmFeeLiquidity::Native)
 			}
 
 			fn pay_priority_fee(tip: Self::LiquidityInfo) {
-				if let Some(tip) = tip {
+				if let Some(EvmFeeLiquidity::Native(tip)) = tip {
 					OU::on_unbalanced(tip);
 				}
 			}
@@ -43,7 +43,12 @@ macro_rules! impl_self_contained_call {
 				len: usize,
 			) -> Option<TransactionValidity> {
 				match self {
-					Call::Ethereum(call) => call.validate_self_contained(signed_info, dispatch_info, len),
+					Call::Ethereum(call) => check_evm_transaction_with_fee_asset(
+						signed_info,
+						call,
+						|| call.validate_self_contained(signed_info, dispatch_info, len),
+						|| Ok(()),
+					),
 					_ => None,
 				}
 			}
@@ -55,7 +60,18 @@ macro_rules! impl_self_contained_call {
 				len: usize,
 			) -> Option<Result<(), TransactionValidityError>> {
 				match self {
-					Call::Ethereum(call) => call.pre_dispatch_self_contained(info, dispatch_info, len),
+					Call::Ethereum(call) => check_evm_transaction_with_fee_asset(
+						info,
+						call,
+						|| call.pre_dispatch_self_contained(info, dispatch_info, len),
+						// The block weight and length accounting must outlive the check
+						|| {
+							frame_system::CheckWeight::<Runtime>::do_pre_dispatch(
+								dispatch_info,
+								len,
+							)
+						},
+					),
 					_ => None,
 				}
 			}
@@ -19,6 +19,7 @@
 use sp_core::H160;
 
 mod apis;
+pub mod assets;
 mod impl_moonbeam_xcm_call;
 mod impl_on_charge_evm_transaction;
 mod impl_self_contained_call;
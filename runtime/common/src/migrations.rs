@@ -47,7 +47,8 @@ use pallet_parachain_staking::{
 	Config as ParachainStakingConfig,
 };
 use pallet_xcm_transactor::{
-	migrations::TransactSignedWeightAndFeePerSecond, Config as XcmTransactorConfig,
+	migrations::{IndexToAccountPerDestination, TransactSignedWeightAndFeePerSecond},
+	Config as XcmTransactorConfig,
 };
 use sp_runtime::Permill;
 use sp_std::{marker::PhantomData, prelude::*};
@@ -414,6 +415,30 @@ impl<T: XcmTransactorConfig> Migration for XcmTransactorTransactSignedWeightAndF
 		TransactSignedWeightAndFeePerSecond::<T>::post_upgrade()
 	}
 }
+
+pub struct XcmTransactorIndexToAccountPerDestination<T>(PhantomData<T>);
+impl<T: XcmTransactorConfig> Migration for XcmTransactorIndexToAccountPerDestination<T> {
+	fn friendly_name(&self) -> &str {
+		"MM_Xcm_Transactor_IndexToAccountPerDestination"
+	}
+
+	fn migrate(&self, _available_weight: Weight) -> Weight {
+		IndexToAccountPerDestination::<T>::on_runtime_upgrade()
+	}
+
+	/// Run a standard pre-runtime test. This works the same way as in a normal runtime upgrade.
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade(&self) -> Result<(), &'static str> {
+		IndexToAccountPerDestination::<T>::pre_upgrade()
+	}
+
+	/// Run a standard post-runtime test. This works the same way as in a normal runtime upgrade.
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(&self) -> Result<(), &'static str> {
+		IndexToAccountPerDestination::<T>::post_upgrade()
+	}
+}
+
 pub struct AssetManagerUnitsWithAssetType<T>(PhantomData<T>);
 impl<T: AssetManagerConfig> Migration for AssetManagerUnitsWithAssetType<T> {
 	fn friendly_name(&self) -> &str {
@@ -656,6 +681,7 @@ where
 	Runtime: pallet_scheduler::Config,
 	Runtime: pallet_base_fee::Config,
 	Runtime: AuthorSlotFilterConfig,
+	Runtime: XcmTransactorConfig,
 	Council: GetStorageVersion + PalletInfoAccess + 'static,
 	Tech: GetStorageVersion + PalletInfoAccess + 'static,
 {
@@ -711,6 +737,8 @@ where
 		// let xcm_supported_assets = XcmPaymentSupportedAssets::<Runtime>(Default::default());
 
 		let migration_elasticity = MigrateBaseFeeElasticity::<Runtime>(Default::default());
+		let xcm_transactor_index_to_account_per_destination =
+			XcmTransactorIndexToAccountPerDestination::<Runtime>(Default::default());
 
 		vec![
 			// completed in runtime 800
@@ -750,6 +778,7 @@ where
 			// completed in runtime 1600
 			// Box::new(xcm_transactor_transact_signed),
 			Box::new(migration_elasticity),
+			Box::new(xcm_transactor_index_to_account_per_destination),
 		]
 	}
 }
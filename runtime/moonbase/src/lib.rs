@@ -348,7 +348,7 @@ impl WeightToFeePolynomial for LengthToFee {
 
 impl pallet_transaction_payment::Config for Runtime {
 	type Event = Event;
-	type OnChargeTransaction = CurrencyAdapter<Balances, DealWithFees<Runtime>>;
+	type OnChargeTransaction = OnChargeSubstrateTransaction<DealWithFees<Runtime>>;
 	type OperationalFeeMultiplier = ConstU8<5>;
 	type WeightToFee = ConstantMultiplier<Balance, ConstU128<{ currency::WEIGHT_FEE }>>;
 	type LengthToFee = LengthToFee;
@@ -455,7 +455,7 @@ impl pallet_evm::Config for Runtime {
 	type AddressMapping = moonbeam_runtime_common::IntoAddressMapping;
 	type Currency = Balances;
 	type Event = Event;
-	type Runner = pallet_evm::runner::stack::Runner<Self>;
+	type Runner = pallet_ethereum_xcm::RecordRevertData<pallet_evm::runner::stack::Runner<Self>>;
 	type PrecompilesType = MoonbasePrecompiles<Self>;
 	type PrecompilesValue = PrecompilesValue;
 	type ChainId = EthereumChainId;
@@ -678,6 +678,8 @@ impl pallet_ethereum::Config for Runtime {
 	type StateRoot = pallet_ethereum::IntermediateStateRoot<Self>;
 }
 
+impl pallet_ethereum_logs::Config for Runtime {}
+
 pub struct EthereumXcmEnsureProxy;
 impl xcm_primitives::EnsureProxy<AccountId> for EthereumXcmEnsureProxy {
 	fn ensure_ok(delegator: AccountId, delegatee: AccountId) -> Result<(), &'static str> {
@@ -695,13 +697,28 @@ impl xcm_primitives::EnsureProxy<AccountId> for EthereumXcmEnsureProxy {
 	}
 }
 
+pub struct EthereumXcmPendingTransactions;
+impl pallet_ethereum_xcm::PendingEthereumTransactions for EthereumXcmPendingTransactions {
+	fn pending() -> Vec<(
+		pallet_ethereum_xcm::Transaction,
+		pallet_ethereum_xcm::TransactionStatus,
+		pallet_ethereum_xcm::Receipt,
+	)> {
+		Ethereum::pending()
+	}
+}
+
 impl pallet_ethereum_xcm::Config for Runtime {
+	type Event = Event;
 	type InvalidEvmTransactionError = pallet_ethereum::InvalidTransactionWrapper;
 	type ValidatedTransaction = pallet_ethereum::ValidatedTransaction<Self>;
 	type XcmEthereumOrigin = pallet_ethereum_xcm::EnsureXcmEthereumTransaction;
 	type ReservedXcmpWeight = ReservedXcmpWeight;
 	type EnsureProxy = EthereumXcmEnsureProxy;
 	type ControllerOrigin = EnsureRoot<AccountId>;
+	type PendingTransactions = EthereumXcmPendingTransactions;
+	type XcmSender = xcm_config::XcmRouter;
+	type LocationToAccountId = xcm_config::LocationToAccountId;
 }
 
 parameter_types! {
@@ -789,7 +806,7 @@ impl pallet_author_inherent::Config for Runtime {
 
 impl pallet_author_slot_filter::Config for Runtime {
 	type Event = Event;
-	type RandomnessSource = RandomnessCollectiveFlip;
+	type RandomnessSource = pallet_randomness::PreviousLocalVrfRandomness<Runtime>;
 	type PotentialAuthors = ParachainStaking;
 	type WeightInfo = pallet_author_slot_filter::weights::SubstrateWeight<Runtime>;
 }
@@ -990,9 +1007,17 @@ impl Contains<Call> for NormalFilter {
 			// We also disable destroy, as we want to route destroy through the
 			// asset-manager, which guarantees the removal both at the EVM and
 			// substrate side of things
+			// Ownership transfers go through the asset-manager too, which moves the
+			// asset deposit along with them
+			// Assets being destroyed through the asset-manager must stay frozen until their
+			// destruction is finished
 			Call::LocalAssets(method) => match method {
 				pallet_assets::Call::create { .. } => false,
 				pallet_assets::Call::destroy { .. } => false,
+				pallet_assets::Call::transfer_ownership { .. } => false,
+				pallet_assets::Call::thaw_asset { id } => {
+					AssetManager::local_asset_being_destroyed(id).is_none()
+				}
 				_ => true,
 			},
 			// We just want to enable this in case of live chains, since the default version
@@ -1199,6 +1224,12 @@ impl pallet_randomness::GetBabeData<u64, Option<Hash>> for BabeDataGetter {
 	}
 }
 
+parameter_types! {
+	/// Address of the randomness precompile, calling back the contracts of the requests
+	/// fulfilled on idle
+	pub RandomnessPrecompileAddress: H160 = H160::from_low_u64_be(2057);
+}
+
 impl pallet_randomness::Config for Runtime {
 	type Event = Event;
 	type AddressMapping = moonbeam_runtime_common::IntoAddressMapping;
@@ -1211,6 +1242,12 @@ impl pallet_randomness::Config for Runtime {
 	type MaxBlockDelay = ConstU32<2_000>;
 	type BlockExpirationDelay = ConstU32<10_000>;
 	type EpochExpirationDelay = ConstU64<10_000>;
+	type FulfillRequestsOnIdle = ConstBool<true>;
+	type FulfillmentExecutor = pallet_evm_precompile_randomness::OnIdleFulfillment<
+		Runtime,
+		RandomnessPrecompileAddress,
+		EthereumLogs,
+	>;
 }
 
 construct_runtime! {
@@ -1259,10 +1296,14 @@ construct_runtime! {
 		BaseFee: pallet_base_fee::{Pallet, Call, Storage, Config<T>, Event} = 35,
 		LocalAssets: pallet_assets::<Instance1>::{Pallet, Call, Storage, Event<T>} = 36,
 		MoonbeamOrbiters: pallet_moonbeam_orbiters::{Pallet, Call, Storage, Event<T>} = 37,
-		EthereumXcm: pallet_ethereum_xcm::{Pallet, Call, Storage, Origin} = 38,
+		EthereumXcm: pallet_ethereum_xcm::{Pallet, Call, Storage, Origin, Event<T>} = 38,
 		Randomness: pallet_randomness::{Pallet, Call, Storage, Event<T>, Inherent} = 39,
 		TreasuryCouncilCollective:
 			pallet_collective::<Instance3>::{Pallet, Call, Storage, Event<T>, Origin<T>, Config<T>} = 40,
+		XcmRateLimiter: pallet_xcm_rate_limiter::{Pallet, Call, Storage, Event<T>} = 41,
+		AssetPriceFeed: pallet_asset_price_feed::{Pallet, Call, Storage, Event<T>} = 42,
+		EthereumLogs: pallet_ethereum_logs::{Pallet, Storage} = 43,
+		TrappedAssets: pallet_trapped_assets::{Pallet, Storage} = 44,
 	}
 }
 
@@ -27,26 +27,28 @@ use frame_support::{
 	dispatch::Dispatchable,
 	traits::{
 		fungible::Inspect, fungibles::Inspect as FungiblesInspect, Currency as CurrencyT,
-		EnsureOrigin, PalletInfo, StorageInfo, StorageInfoTrait,
+		EnsureOrigin, OnFinalize, PalletInfo, StorageInfo, StorageInfoTrait,
 	},
 	weights::{DispatchClass, Weight},
-	StorageHasher, Twox128,
+	Blake2_128Concat, StorageHasher, Twox128,
 };
 use moonbase_runtime::{
-	asset_config::AssetRegistrarMetadata, asset_config::LocalAssetInstance, get,
-	xcm_config::AssetType, AccountId, AssetId, AssetManager, Assets, Balances, BaseFee, Call,
-	CrowdloanRewards, Event, LocalAssets, ParachainStaking, PolkadotXcm, Precompiles, Runtime,
+	asset_config::AssetRegistrarMetadata, asset_config::LocalAssetIdCreator,
+	asset_config::LocalAssetInstance, get, xcm_config, xcm_config::AssetType, AccountId, AssetId,
+	AssetManager, Assets, Balance, Balances, BaseFee, Call, CrowdloanRewards, Ethereum,
+	EthereumLogs, Event, LocalAssets, ParachainStaking, PolkadotXcm, Precompiles, Runtime,
 	RuntimeBlockWeights, System, TransactionPayment, XTokens, XcmTransactor,
 	FOREIGN_ASSET_PRECOMPILE_ADDRESS_PREFIX, LOCAL_ASSET_PRECOMPILE_ADDRESS_PREFIX,
 };
 use polkadot_parachain::primitives::Sibling;
 use precompile_utils::testing::MockHandle;
-use std::str::from_utf8;
+use std::str::{from_utf8, FromStr};
 use xcm_builder::{ParentIsPreset, SiblingParachainConvertsVia};
-use xcm_executor::traits::Convert as XcmConvert;
+use xcm_executor::traits::{Convert as XcmConvert, TransactAsset};
 use xcm_primitives::Account20Hash;
 
 use nimbus_primitives::NimbusId;
+use pallet_asset_manager::LocalAssetIdCreator as _;
 use pallet_evm::GasWeightMapping;
 use pallet_evm::PrecompileSet;
 use pallet_evm_precompile_author_mapping::Action as AuthorMappingAction;
@@ -59,7 +61,7 @@ use pallet_evm_precompile_randomness::{
 use pallet_evm_precompile_xcm_transactor::{
 	v1::Action as XcmTransactorActionV1, v2::Action as XcmTransactorActionV2,
 };
-use pallet_evm_precompile_xcm_utils::Action as XcmUtilsAction;
+use pallet_evm_precompile_xcm_utils::XcmUtilsWrapperCall;
 use pallet_evm_precompile_xtokens::Action as XtokensAction;
 
 use pallet_evm_precompileset_assets_erc20::{
@@ -76,6 +78,13 @@ use sp_runtime::{
 	DispatchError, ModuleError, TokenError,
 };
 use xcm::latest::prelude::*;
+
+type XcmUtilsPCall = XcmUtilsWrapperCall<
+	Runtime,
+	moonbase_runtime::xcm_config::XcmExecutorConfig,
+	moonbase_runtime::xcm_config::XcmUtilsExecuteFilter,
+>;
+
 #[test]
 fn verify_randomness_precompile_gas_constants() {
 	let weight_to_gas = |weight| {
@@ -1159,6 +1168,106 @@ fn local_assets_cannot_be_create_by_signed_origins() {
 		});
 }
 
+#[test]
+fn local_assets_being_destroyed_cannot_be_thawed() {
+	ExtBuilder::default()
+		.with_balances(vec![(AccountId::from(ALICE), 1_000 * UNIT)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(AssetManager::register_local_asset(
+				root_origin(),
+				AccountId::from(ALICE),
+				AccountId::from(ALICE),
+				true,
+				1
+			));
+			let asset_id = LocalAssetIdCreator::create_asset_id_from_metadata(0);
+
+			assert_ok!(AssetManager::start_local_asset_destruction(
+				origin_of(AccountId::from(ALICE)),
+				asset_id
+			));
+
+			assert_noop!(
+				Call::LocalAssets(
+					pallet_assets::Call::<Runtime, LocalAssetInstance>::thaw_asset { id: asset_id }
+				)
+				.dispatch(<Runtime as frame_system::Config>::Origin::signed(
+					AccountId::from(ALICE)
+				)),
+				frame_system::Error::<Runtime>::CallFiltered
+			);
+		});
+}
+
+#[test]
+fn local_asset_details_mirror_pallet_assets_storage() {
+	use frame_support::storage::{storage_prefix, unhashed};
+	use moonbeam_runtime_common::assets;
+
+	ExtBuilder::default()
+		.with_balances(vec![
+			(AccountId::from(ALICE), 1_000 * UNIT),
+			(AccountId::from(BOB), 1_000 * UNIT),
+		])
+		.build()
+		.execute_with(|| {
+			assert_ok!(AssetManager::register_local_asset(
+				root_origin(),
+				AccountId::from(ALICE),
+				AccountId::from(ALICE),
+				true,
+				1
+			));
+			let asset_id = LocalAssetIdCreator::create_asset_id_from_metadata(0);
+			assert_ok!(LocalAssets::mint(
+				origin_of(AccountId::from(ALICE)),
+				asset_id,
+				AccountId::from(BOB),
+				100
+			));
+			assert_ok!(LocalAssets::approve_transfer(
+				origin_of(AccountId::from(BOB)),
+				asset_id,
+				AccountId::from(CHARLIE),
+				10
+			));
+			assert_ok!(LocalAssets::freeze_asset(
+				origin_of(AccountId::from(ALICE)),
+				asset_id
+			));
+			assert_ok!(LocalAssets::set_team(
+				origin_of(AccountId::from(ALICE)),
+				asset_id,
+				AccountId::from(BOB),
+				AccountId::from(CHARLIE),
+				AccountId::from(DAVE)
+			));
+
+			let details =
+				assets::asset_details::<LocalAssets, AccountId, Balance, _>(asset_id).unwrap();
+			assert_eq!(details.owner, AccountId::from(ALICE));
+			assert_eq!(details.issuer, AccountId::from(BOB));
+			assert_eq!(details.admin, AccountId::from(CHARLIE));
+			assert_eq!(details.freezer, AccountId::from(DAVE));
+			assert_eq!(details.supply, LocalAssets::total_supply(asset_id));
+			assert_eq!(details.deposit, 0);
+			assert_eq!(details.min_balance, LocalAssets::minimum_balance(asset_id));
+			assert!(details.is_sufficient);
+			assert_eq!(details.accounts, 1);
+			assert_eq!(details.sufficients, 1);
+			assert_eq!(details.approvals, 1);
+			assert!(details.is_frozen);
+
+			// The mirror covers the whole of pallet_assets::AssetDetails
+			let key: Vec<u8> = storage_prefix(b"LocalAssets", b"Asset")
+				.into_iter()
+				.chain(Blake2_128Concat::hash(&asset_id.encode()))
+				.collect();
+			assert_eq!(unhashed::get_raw(&key), Some(details.encode()));
+		});
+}
+
 #[test]
 fn asset_erc20_precompiles_supply_and_balance() {
 	ExtBuilder::default()
@@ -1529,7 +1638,7 @@ fn asset_erc20_precompiles_freeze_transfer_ownership() {
 						.write(Address(BOB.into()))
 						.build(),
 				)
-				.expect_cost(6794)
+				.expect_cost(20000)
 				.expect_no_logs()
 				.execute_returns(EvmDataWriter::new().write(true).build());
 		});
@@ -1794,6 +1903,277 @@ fn xcm_asset_erc20_precompiles_approve() {
 		});
 }
 
+#[test]
+fn evm_fees_can_be_paid_with_a_fee_payment_asset() {
+	use pallet_evm::{FeeCalculator, OnChargeEVMTransaction};
+
+	ExtBuilder::default()
+		.with_xcm_assets(vec![XcmAssetInitialization {
+			asset_type: AssetType::Xcm(MultiLocation::parent()),
+			metadata: AssetRegistrarMetadata {
+				name: b"RelayToken".to_vec(),
+				symbol: b"Relay".to_vec(),
+				decimals: 12,
+				is_frozen: false,
+			},
+			balances: vec![(AccountId::from(ALICE), 1_000 * UNIT)],
+			is_sufficient: true,
+		}])
+		.with_balances(vec![(AccountId::from(ALICE), 1_000 * UNIT)])
+		.build()
+		.execute_with(|| {
+			let relay_asset_id: AssetId = AssetType::Xcm(MultiLocation::parent()).into();
+			let units_per_second = 100 * UNIT;
+			assert_ok!(AssetManager::set_asset_units_per_second(
+				root_origin(),
+				AssetType::Xcm(MultiLocation::parent()),
+				units_per_second,
+				0
+			));
+			assert_ok!(AssetManager::set_fee_payment_asset_preference(
+				origin_of(AccountId::from(ALICE)),
+				Some(AssetType::Xcm(MultiLocation::parent()))
+			));
+
+			// The fee of the gas executed in a second, at the minimum gas price
+			let (min_gas_price, _) =
+				<Runtime as pallet_evm::Config>::FeeCalculator::min_gas_price();
+			let gas_per_second = <Runtime as pallet_evm::Config>::GasWeightMapping::weight_to_gas(
+				frame_support::weights::constants::WEIGHT_PER_SECOND,
+			);
+			let fee = min_gas_price * U256::from(gas_per_second);
+			let native_balance = Balances::free_balance(AccountId::from(ALICE));
+
+			let withdrawn = <Runtime as pallet_evm::Config>::OnChargeTransaction::withdraw_fee(
+				&H160::from(ALICE),
+				fee,
+			)
+			.expect("Alice can pay the fee with the relay asset");
+			assert_eq!(
+				Assets::balance(relay_asset_id, AccountId::from(ALICE)),
+				1_000 * UNIT - units_per_second
+			);
+
+			// Only a quarter of the fee is used, the rest is refunded
+			let tip = <Runtime as pallet_evm::Config>::OnChargeTransaction::correct_and_deposit_fee(
+				&H160::from(ALICE),
+				fee / 4,
+				min_gas_price,
+				withdrawn,
+			);
+			<Runtime as pallet_evm::Config>::OnChargeTransaction::pay_priority_fee(tip);
+
+			assert_eq!(
+				Assets::balance(relay_asset_id, AccountId::from(ALICE)),
+				1_000 * UNIT - units_per_second / 4
+			);
+			assert_eq!(
+				Assets::balance(relay_asset_id, moonbase_runtime::Treasury::account_id()),
+				units_per_second / 4
+			);
+			assert_eq!(
+				Balances::free_balance(AccountId::from(ALICE)),
+				native_balance
+			);
+		});
+}
+
+#[test]
+fn ethereum_transactions_paid_with_a_fee_payment_asset_count_towards_block_weight() {
+	use fp_self_contained::SelfContainedCall;
+	use frame_support::weights::GetDispatchInfo;
+
+	// Signer of VALID_ETH_TX
+	let alith = H160::from_str("6be02d1d3665660d22ff9624b7be0551ee1ac91b")
+		.expect("internal H160 is valid; qed");
+	ExtBuilder::default()
+		.with_xcm_assets(vec![XcmAssetInitialization {
+			asset_type: AssetType::Xcm(MultiLocation::parent()),
+			metadata: AssetRegistrarMetadata {
+				name: b"RelayToken".to_vec(),
+				symbol: b"Relay".to_vec(),
+				decimals: 12,
+				is_frozen: false,
+			},
+			balances: vec![(AccountId::from(alith), 1_000 * UNIT)],
+			is_sufficient: true,
+		}])
+		.with_balances(vec![(AccountId::from(alith), UNIT)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(AssetManager::set_asset_units_per_second(
+				root_origin(),
+				AssetType::Xcm(MultiLocation::parent()),
+				100 * UNIT,
+				0
+			));
+			assert_ok!(AssetManager::set_fee_payment_asset_preference(
+				origin_of(AccountId::from(alith)),
+				Some(AssetType::Xcm(MultiLocation::parent()))
+			));
+
+			let call = Call::Ethereum(pallet_ethereum::Call::transact {
+				transaction: ethereum_transaction(VALID_ETH_TX),
+			});
+			let dispatch_info = call.get_dispatch_info();
+			let block_weight = System::block_weight().total();
+
+			assert_eq!(
+				call.pre_dispatch_self_contained(&alith, &dispatch_info, call.encode().len()),
+				Some(Ok(()))
+			);
+			assert!(System::block_weight().total() > block_weight);
+			// The fee credited to check the transaction was reverted
+			assert_eq!(Balances::free_balance(AccountId::from(alith)), UNIT);
+		});
+}
+
+#[test]
+fn fee_payment_asset_preference_can_be_set_without_native_balance() {
+	use frame_support::weights::GetDispatchInfo;
+	use pallet_evm::FeeCalculator;
+	use pallet_transaction_payment::OnChargeTransaction;
+	type FeeCharger = <Runtime as pallet_transaction_payment::Config>::OnChargeTransaction;
+
+	ExtBuilder::default()
+		.with_xcm_assets(vec![XcmAssetInitialization {
+			asset_type: AssetType::Xcm(MultiLocation::parent()),
+			metadata: AssetRegistrarMetadata {
+				name: b"RelayToken".to_vec(),
+				symbol: b"Relay".to_vec(),
+				decimals: 12,
+				is_frozen: false,
+			},
+			balances: vec![(AccountId::from(ALICE), 1_000 * UNIT)],
+			is_sufficient: true,
+		}])
+		.build()
+		.execute_with(|| {
+			let relay_asset_id: AssetId = AssetType::Xcm(MultiLocation::parent()).into();
+			let units_per_second = 100 * UNIT;
+			assert_ok!(AssetManager::set_asset_units_per_second(
+				root_origin(),
+				AssetType::Xcm(MultiLocation::parent()),
+				units_per_second,
+				0
+			));
+
+			// The fee of the gas executed in a second, at the minimum gas price
+			let (min_gas_price, _) =
+				<Runtime as pallet_evm::Config>::FeeCalculator::min_gas_price();
+			let gas_per_second = <Runtime as pallet_evm::Config>::GasWeightMapping::weight_to_gas(
+				frame_support::weights::constants::WEIGHT_PER_SECOND,
+			);
+			let fee: u128 = (min_gas_price * U256::from(gas_per_second)).low_u128();
+
+			let call = Call::AssetManager(
+				pallet_asset_manager::Call::<Runtime>::set_fee_payment_asset_preference {
+					asset_type: Some(AssetType::Xcm(MultiLocation::parent())),
+				},
+			);
+			let dispatch_info = call.get_dispatch_info();
+
+			// Alice holds no native currency, the fee is paid with the chosen asset
+			let withdrawn =
+				FeeCharger::withdraw_fee(&AccountId::from(ALICE), &call, &dispatch_info, fee, 0)
+					.expect("Alice can pay the fee with the relay asset");
+			assert_eq!(
+				Assets::balance(relay_asset_id, AccountId::from(ALICE)),
+				1_000 * UNIT - units_per_second
+			);
+
+			// Only half of the fee is used, the rest is refunded
+			assert_ok!(FeeCharger::correct_and_deposit_fee(
+				&AccountId::from(ALICE),
+				&dispatch_info,
+				&Default::default(),
+				fee / 2,
+				0,
+				withdrawn,
+			));
+			assert_eq!(
+				Assets::balance(relay_asset_id, AccountId::from(ALICE)),
+				1_000 * UNIT - units_per_second / 2
+			);
+			assert_eq!(Balances::free_balance(AccountId::from(ALICE)), 0);
+		});
+}
+
+#[test]
+fn xcm_asset_deposits_transfers_and_withdrawals_emit_transfer_logs() {
+	ExtBuilder::default()
+		.with_xcm_assets(vec![XcmAssetInitialization {
+			asset_type: AssetType::Xcm(MultiLocation::parent()),
+			metadata: AssetRegistrarMetadata {
+				name: b"RelayToken".to_vec(),
+				symbol: b"Relay".to_vec(),
+				decimals: 12,
+				is_frozen: false,
+			},
+			balances: vec![(AccountId::from(ALICE), 1_000 * UNIT)],
+			is_sufficient: true,
+		}])
+		.build()
+		.execute_with(|| {
+			let relay_asset_id: AssetId = AssetType::Xcm(MultiLocation::parent()).into();
+			let asset_precompile_address: H160 = Runtime::asset_id_to_account(
+				FOREIGN_ASSET_PRECOMPILE_ADDRESS_PREFIX,
+				relay_asset_id,
+			)
+			.into();
+			let asset: MultiAsset = (MultiLocation::parent(), 100 * UNIT).into();
+			let bob = MultiLocation::new(
+				0,
+				X1(AccountKey20 {
+					network: Any,
+					key: BOB,
+				}),
+			);
+
+			let charlie = MultiLocation::new(
+				0,
+				X1(AccountKey20 {
+					network: Any,
+					key: CHARLIE,
+				}),
+			);
+
+			assert_ok!(xcm_config::AssetTransactors::deposit_asset(&asset, &bob));
+			assert_ok!(xcm_config::AssetTransactors::transfer_asset(
+				&asset, &bob, &charlie
+			));
+			assert_ok!(xcm_config::AssetTransactors::withdraw_asset(
+				&asset, &charlie
+			));
+			assert_eq!(Assets::balance(relay_asset_id, AccountId::from(CHARLIE)), 0);
+
+			// The logs are only included in the Ethereum block once finalized
+			assert!(Ethereum::pending().is_empty());
+			EthereumLogs::on_finalize(System::block_number());
+
+			// The mint, the transfer and the burn are recorded as ERC-20 Transfer logs of the
+			// XC-20, in a single transaction
+			let pending = Ethereum::pending();
+			assert_eq!(pending.len(), 1);
+			let logs = pending[0].1.logs.clone();
+			let mut amount = [0u8; 32];
+			U256::from(100 * UNIT).to_big_endian(&mut amount);
+			assert_eq!(logs.len(), 3);
+			for (log, (from, to)) in logs.iter().zip([
+				(H160::zero(), H160::from(BOB)),
+				(H160::from(BOB), H160::from(CHARLIE)),
+				(H160::from(CHARLIE), H160::zero()),
+			]) {
+				assert_eq!(log.address, asset_precompile_address);
+				assert_eq!(
+					log.topics,
+					vec![SELECTOR_LOG_TRANSFER.into(), from.into(), to.into()]
+				);
+				assert_eq!(log.data, amount.to_vec());
+			}
+		});
+}
+
 #[test]
 fn xtokens_precompiles_transfer() {
 	ExtBuilder::default()
@@ -2383,6 +2763,7 @@ fn transactor_cannot_use_more_than_max_weight() {
 			assert_ok!(XcmTransactor::register(
 				root_origin(),
 				AccountId::from(ALICE),
+				Box::new(xcm::VersionedMultiLocation::V1(MultiLocation::parent())),
 				0,
 			));
 
@@ -2746,6 +3127,44 @@ fn author_mapping_register_and_set_keys() {
 		});
 }
 
+#[test]
+fn xcm_utils_precompile_executes_messages_of_the_caller() {
+	ExtBuilder::default()
+		.with_balances(vec![(AccountId::from(ALICE), 1_000 * UNIT)])
+		.build()
+		.execute_with(|| {
+			let xcm_utils_precompile_address = H160::from_low_u64_be(2060);
+			let amount = 100 * UNIT;
+			let message: Xcm<()> = Xcm(vec![
+				WithdrawAsset((moonbase_runtime::xcm_config::SelfReserve::get(), amount).into()),
+				DepositAsset {
+					assets: Wild(All),
+					max_assets: 1,
+					beneficiary: AccountKey20 {
+						network: Any,
+						key: BOB,
+					}
+					.into(),
+				},
+			]);
+
+			// Executing messages through pallet-xcm is disabled, but not through the precompile
+			Precompiles::new()
+				.prepare_test(
+					ALICE,
+					xcm_utils_precompile_address,
+					EvmDataWriter::new_with_selector(XcmUtilsPCall::xcm_execute_selectors()[0])
+						.write(Bytes(xcm::VersionedXcm::<()>::from(message).encode()))
+						.write(u64::MAX)
+						.build(),
+				)
+				.expect_no_logs()
+				.execute_returns(vec![]);
+
+			assert_eq!(Balances::free_balance(AccountId::from(BOB)), amount);
+		});
+}
+
 #[test]
 fn test_xcm_utils_ml_tp_account() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -2759,9 +3178,10 @@ fn test_xcm_utils_ml_tp_account() {
 			.prepare_test(
 				ALICE,
 				xcm_utils_precompile_address,
-				EvmDataWriter::new_with_selector(XcmUtilsAction::MultiLocationToAddress)
-					.write(MultiLocation::parent())
-					.build(),
+				XcmUtilsPCall::multilocation_to_address {
+					multilocation: MultiLocation::parent(),
+				}
+				.into(),
 			)
 			.expect_cost(1000)
 			.expect_no_logs()
@@ -2783,9 +3203,10 @@ fn test_xcm_utils_ml_tp_account() {
 			.prepare_test(
 				ALICE,
 				xcm_utils_precompile_address,
-				EvmDataWriter::new_with_selector(XcmUtilsAction::MultiLocationToAddress)
-					.write(parachain_2000_multilocation)
-					.build(),
+				XcmUtilsPCall::multilocation_to_address {
+					multilocation: parachain_2000_multilocation,
+				}
+				.into(),
 			)
 			.expect_cost(1000)
 			.expect_no_logs()
@@ -2814,9 +3235,10 @@ fn test_xcm_utils_ml_tp_account() {
 			.prepare_test(
 				ALICE,
 				xcm_utils_precompile_address,
-				EvmDataWriter::new_with_selector(XcmUtilsAction::MultiLocationToAddress)
-					.write(alice_in_parachain_2000_multilocation)
-					.build(),
+				XcmUtilsPCall::multilocation_to_address {
+					multilocation: alice_in_parachain_2000_multilocation,
+				}
+				.into(),
 			)
 			.expect_cost(1000)
 			.expect_no_logs()
@@ -20,12 +20,33 @@ mod common;
 use common::*;
 
 use fp_evm::GenesisAccount;
-use nimbus_primitives::NimbusId;
+use frame_support::{
+	assert_ok,
+	storage::{with_transaction, TransactionOutcome},
+	traits::OnInitialize,
+	weights::constants::WEIGHT_PER_SECOND,
+};
+use moonbase_runtime::{
+	asset_config::{AssetRegistrarMetadata, LocalAssetIdCreator},
+	xcm_config::AssetType,
+	AuthorFilter, Randomness, XcmTransactor, FOREIGN_ASSET_PRECOMPILE_ADDRESS_PREFIX,
+	LOCAL_ASSET_PRECOMPILE_ADDRESS_PREFIX,
+};
+use nimbus_primitives::{runtime_decl_for_NimbusApi::NimbusApi, NimbusId};
+use pallet_asset_manager::LocalAssetIdCreator as _;
+use pallet_author_slot_filter::EligibilityValue;
 use pallet_evm::{Account as EVMAccount, AddressMapping, FeeCalculator};
+use pallet_evm_precompileset_assets_erc20::AccountIdAssetIdConversion;
 use sp_core::{ByteArray, H160, H256, U256};
+use xcm::latest::{Junction::Parachain, Junctions::X1, MultiLocation};
 
 use fp_rpc::runtime_decl_for_EthereumRuntimeRPCApi::EthereumRuntimeRPCApi;
 use moonbeam_rpc_primitives_txpool::runtime_decl_for_TxPoolRuntimeApi::TxPoolRuntimeApi;
+use moonbeam_rpc_primitives_xcm::{
+	runtime_decl_for_XcAssetRegistryApi::XcAssetRegistryApi, runtime_decl_for_XcmFeeApi::XcmFeeApi,
+	InboundFee, TransactFee, XcAsset,
+};
+use sp_runtime::DispatchError;
 use std::{collections::BTreeMap, str::FromStr};
 
 #[test]
@@ -301,3 +322,285 @@ fn txpool_runtime_api_extrinsic_filter() {
 // 3. System remark with no tip -> calculate expected priority from gas weight mapping
 // 4. System remark with tip.
 // 5. Operational dispatch has higher priority than normal for otherwise same transactions
+
+#[test]
+fn xcm_fee_runtime_api_transact_fee() {
+	ExtBuilder::default().build().execute_with(|| {
+		let relay = xcm::VersionedMultiLocation::V1(MultiLocation::parent());
+
+		// Nothing is known about the relay yet
+		assert!(Runtime::transact_fee(relay.clone(), relay.clone(), 1000, false).is_err());
+
+		assert_ok!(XcmTransactor::set_transact_info(
+			root_origin(),
+			Box::new(relay.clone()),
+			3000,
+			20000,
+			Some(4000)
+		));
+		assert_ok!(XcmTransactor::set_fee_per_second(
+			root_origin(),
+			Box::new(relay.clone()),
+			// One unit per weight unit
+			WEIGHT_PER_SECOND as u128
+		));
+
+		assert_eq!(
+			Runtime::transact_fee(relay.clone(), relay.clone(), 1000, false),
+			Ok(TransactFee {
+				weight: 4000,
+				fee: 4000
+			})
+		);
+		assert_eq!(
+			Runtime::transact_fee(relay.clone(), relay.clone(), 1000, true),
+			Ok(TransactFee {
+				weight: 5000,
+				fee: 5000
+			})
+		);
+
+		// Over the max weight of the relay
+		assert!(Runtime::transact_fee(relay.clone(), relay, 17001, false).is_err());
+	});
+}
+
+#[test]
+fn xcm_fee_runtime_api_inbound_fees() {
+	ExtBuilder::default()
+		.with_xcm_assets(vec![XcmAssetInitialization {
+			asset_type: AssetType::Xcm(MultiLocation::parent()),
+			metadata: AssetRegistrarMetadata {
+				name: b"RelayToken".to_vec(),
+				symbol: b"Relay".to_vec(),
+				decimals: 12,
+				is_frozen: false,
+			},
+			balances: vec![],
+			is_sufficient: true,
+		}])
+		.build()
+		.execute_with(|| {
+			// Not a supported fee asset yet
+			assert_eq!(Runtime::inbound_fees(WEIGHT_PER_SECOND), vec![]);
+
+			assert_ok!(AssetManager::set_asset_units_per_second(
+				root_origin(),
+				AssetType::Xcm(MultiLocation::parent()),
+				1_000_000,
+				0
+			));
+
+			assert_eq!(
+				Runtime::inbound_fees(WEIGHT_PER_SECOND / 2),
+				vec![InboundFee {
+					asset: xcm::VersionedMultiLocation::V1(MultiLocation::parent()),
+					amount: 500_000,
+				}]
+			);
+		});
+}
+
+#[test]
+fn xc_asset_registry_runtime_api_foreign_assets() {
+	let sibling = MultiLocation::new(1, X1(Parachain(1000)));
+	ExtBuilder::default()
+		.with_xcm_assets(vec![
+			XcmAssetInitialization {
+				asset_type: AssetType::Xcm(MultiLocation::parent()),
+				metadata: AssetRegistrarMetadata {
+					name: b"RelayToken".to_vec(),
+					symbol: b"Relay".to_vec(),
+					decimals: 12,
+					is_frozen: false,
+				},
+				balances: vec![],
+				is_sufficient: true,
+			},
+			XcmAssetInitialization {
+				asset_type: AssetType::Xcm(sibling.clone()),
+				metadata: AssetRegistrarMetadata {
+					name: b"SiblingToken".to_vec(),
+					symbol: b"Sibling".to_vec(),
+					decimals: 18,
+					is_frozen: false,
+				},
+				balances: vec![],
+				is_sufficient: false,
+			},
+		])
+		.build()
+		.execute_with(|| {
+			let relay_id: AssetId = AssetType::Xcm(MultiLocation::parent()).into();
+			let sibling_id: AssetId = AssetType::Xcm(sibling.clone()).into();
+
+			assert_ok!(AssetManager::set_asset_units_per_second(
+				root_origin(),
+				AssetType::Xcm(MultiLocation::parent()),
+				1_000_000,
+				0
+			));
+
+			let mut assets = Runtime::foreign_assets(None, 10);
+			assets.sort_by_key(|asset| asset.decimals);
+			assert_eq!(
+				assets,
+				vec![
+					XcAsset {
+						asset_id: relay_id,
+						location: Some(xcm::VersionedMultiLocation::V1(MultiLocation::parent())),
+						address: Runtime::asset_id_to_account(
+							FOREIGN_ASSET_PRECOMPILE_ADDRESS_PREFIX,
+							relay_id
+						)
+						.into(),
+						name: b"RelayToken".to_vec(),
+						symbol: b"Relay".to_vec(),
+						decimals: 12,
+						is_sufficient: true,
+						units_per_second: Some(1_000_000),
+					},
+					XcAsset {
+						asset_id: sibling_id,
+						location: Some(xcm::VersionedMultiLocation::V1(sibling)),
+						address: Runtime::asset_id_to_account(
+							FOREIGN_ASSET_PRECOMPILE_ADDRESS_PREFIX,
+							sibling_id
+						)
+						.into(),
+						name: b"SiblingToken".to_vec(),
+						symbol: b"Sibling".to_vec(),
+						decimals: 18,
+						is_sufficient: false,
+						units_per_second: None,
+					},
+				]
+			);
+
+			// Pages continue after the last asset of the previous one
+			let first_page = Runtime::foreign_assets(None, 1);
+			assert_eq!(first_page.len(), 1);
+			let second_page = Runtime::foreign_assets(Some(first_page[0].asset_id), 10);
+			assert_eq!(second_page.len(), 1);
+			assert_ne!(first_page[0].asset_id, second_page[0].asset_id);
+		});
+}
+
+#[test]
+fn xc_asset_registry_runtime_api_local_assets() {
+	ExtBuilder::default()
+		.with_balances(vec![(AccountId::from(ALICE), 1_000 * UNIT)])
+		.build()
+		.execute_with(|| {
+			assert_eq!(Runtime::local_assets(None, 10), vec![]);
+
+			assert_ok!(AssetManager::register_local_asset(
+				root_origin(),
+				AccountId::from(ALICE),
+				AccountId::from(ALICE),
+				true,
+				1
+			));
+			let asset_id = LocalAssetIdCreator::create_asset_id_from_metadata(0);
+
+			assert_eq!(
+				Runtime::local_assets(None, 10),
+				vec![XcAsset {
+					asset_id,
+					location: None,
+					address: Runtime::asset_id_to_account(
+						LOCAL_ASSET_PRECOMPILE_ADDRESS_PREFIX,
+						asset_id
+					)
+					.into(),
+					name: vec![],
+					symbol: vec![],
+					decimals: 0,
+					is_sufficient: true,
+					units_per_second: None,
+				}]
+			);
+			assert_eq!(Runtime::local_assets(Some(asset_id), 10), vec![]);
+		});
+}
+
+#[test]
+fn nimbus_runtime_api_can_author_matches_author_check_of_next_block() {
+	let collators = [ALICE, BOB, CHARLIE, DAVE];
+	let nimbus_id = |account: [u8; 20]| NimbusId::from_slice(&[account[0]; 32]).unwrap();
+	// Collators eligible in each of the slots, according to `can_author`
+	let eligible_authors = |can_author: &dyn Fn(NimbusId, u32) -> bool| {
+		(0..10u32)
+			.map(|slot| {
+				collators
+					.into_iter()
+					.filter(|collator| can_author(nimbus_id(*collator), slot))
+					.collect::<Vec<_>>()
+			})
+			.collect::<Vec<_>>()
+	};
+
+	ExtBuilder::default()
+		.with_balances(
+			collators
+				.iter()
+				.map(|collator| (AccountId::from(*collator), 2_000 * UNIT))
+				.collect(),
+		)
+		.with_collators(
+			collators
+				.iter()
+				.map(|collator| (AccountId::from(*collator), 1_000 * UNIT))
+				.collect(),
+		)
+		.with_mappings(
+			collators
+				.iter()
+				.map(|collator| (nimbus_id(*collator), AccountId::from(*collator)))
+				.collect(),
+		)
+		.build()
+		.execute_with(|| {
+			set_parachain_inherent_data();
+			run_to_block(2, Some(nimbus_id(ALICE)));
+			assert_ok!(AuthorFilter::set_eligible(
+				root_origin(),
+				EligibilityValue::new_unchecked(2)
+			));
+			pallet_randomness::PreviousLocalVrfOutput::<Runtime>::put(Some(H256::repeat_byte(1)));
+			pallet_randomness::LocalVrfOutput::<Runtime>::put(Some(H256::repeat_byte(2)));
+			let parent_header = System::finalize();
+
+			// Authors of the parent block, selected with the VRF output of its own parent
+			let parent_authors =
+				eligible_authors(&|author, slot| AuthorInherent::can_author(&author, &slot));
+			// Predicted by the node on the state of the parent block, before authoring
+			let predicted_authors = eligible_authors(&|author, slot| {
+				with_transaction(|| {
+					TransactionOutcome::Rollback(Ok::<_, DispatchError>(Runtime::can_author(
+						author,
+						slot,
+						&parent_header,
+					)))
+				})
+				.unwrap()
+			});
+
+			// The VRF output of the next block can't be verified without the VRF key of its
+			// author, so it is executed as a first block, which commits the VRF output of the
+			// parent block like any other block.
+			System::initialize(
+				&(parent_header.number + 1),
+				&parent_header.hash(),
+				&Default::default(),
+			);
+			pallet_randomness::NotFirstBlock::<Runtime>::kill();
+			Randomness::on_initialize(System::block_number());
+			let authors =
+				eligible_authors(&|author, slot| AuthorInherent::can_author(&author, &slot));
+
+			assert_eq!(predicted_authors, authors);
+			assert!(authors.iter().all(|eligible| eligible.len() == 2));
+			assert_ne!(parent_authors, authors);
+		});
+}
@@ -200,3 +200,4 @@ pub type XTokens = orml_xtokens::Pallet<parachain::Runtime>;
 pub type RelayBalances = pallet_balances::Pallet<relay_chain::Runtime>;
 pub type ParaBalances = pallet_balances::Pallet<parachain::Runtime>;
 pub type XcmTransactor = pallet_xcm_transactor::Pallet<parachain::Runtime>;
+pub type XcmRateLimiter = pallet_xcm_rate_limiter::Pallet<parachain::Runtime>;
@@ -269,7 +269,7 @@ pub type AssetTransactors = (
 	LocalFungiblesTransactor,
 );
 
-pub type XcmRouter = super::ParachainXcmRouter<MsgQueue>;
+pub type XcmRouter = xcm_primitives::RecordingRouter<super::ParachainXcmRouter<MsgQueue>>;
 
 pub type Barrier = (
 	TakeWeightCredit,
@@ -344,7 +344,7 @@ pub struct XcmConfig;
 impl Config for XcmConfig {
 	type Call = Call;
 	type XcmSender = XcmRouter;
-	type AssetTransactor = AssetTransactors;
+	type AssetTransactor = XcmRateLimiter;
 	type OriginConverter = XcmOriginToTransactDispatchOrigin;
 	type IsReserve = orml_xcm_support::MultiNativeAsset<
 		xcm_primitives::AbsoluteAndRelativeReserve<SelfLocationAbsolute>,
@@ -359,7 +359,7 @@ impl Config for XcmConfig {
 	// units per second we should charge
 	type Trader = (
 		FixedRateOfFungible<ParaTokensPerSecond, ()>,
-		xcm_primitives::FirstAssetTrader<AssetType, AssetManager, XcmFeesToAccount_>,
+		xcm_primitives::MultiAssetTrader<AssetType, AssetManager, XcmFeesToAccount_>,
 	);
 
 	type ResponseHandler = PolkadotXcm;
@@ -369,9 +369,23 @@ impl Config for XcmConfig {
 	type CallDispatcher = MoonbeamCall;
 }
 
+// Track the origin of executed messages so XcmRateLimiter can enforce inflow limits
+pub type RateLimitedXcmExecutor =
+	pallet_xcm_rate_limiter::TrackInboundOrigin<XcmExecutor<XcmConfig>>;
+
+impl pallet_xcm_rate_limiter::Config for Runtime {
+	type Event = Event;
+	type AssetTransactor = AssetTransactors;
+	type AccountIdToMultiLocation = xcm_primitives::AccountIdToMultiLocation<AccountId>;
+	type LimiterOrigin = EnsureRoot<AccountId>;
+	type WeightInfo = ();
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkAsset = SelfReserve;
+}
+
 impl cumulus_pallet_xcm::Config for Runtime {
 	type Event = Event;
-	type XcmExecutor = XcmExecutor<XcmConfig>;
+	type XcmExecutor = RateLimitedXcmExecutor;
 }
 
 // Our currencyId. We distinguish for now between SelfReserve, and Others, defined by their Id.
@@ -436,7 +450,7 @@ impl orml_xtokens::Config for Runtime {
 	type AccountIdToMultiLocation = xcm_primitives::AccountIdToMultiLocation<AccountId>;
 	type CurrencyIdConvert =
 		CurrencyIdtoMultiLocation<xcm_primitives::AsAssetType<AssetId, AssetType, AssetManager>>;
-	type XcmExecutor = XcmExecutor<XcmConfig>;
+	type XcmExecutor = RateLimitedXcmExecutor;
 	type SelfLocation = SelfLocation;
 	type Weigher = xcm_builder::FixedWeightBounds<UnitWeightCost, Call, MaxInstructions>;
 	type BaseXcmWeight = BaseXcmWeight;
@@ -656,7 +670,7 @@ pub mod mock_version_changer {
 
 impl mock_msg_queue::Config for Runtime {
 	type Event = Event;
-	type XcmExecutor = XcmExecutor<XcmConfig>;
+	type XcmExecutor = RateLimitedXcmExecutor;
 }
 
 impl mock_version_changer::Config for Runtime {
@@ -672,7 +686,7 @@ impl pallet_xcm::Config for Runtime {
 	type XcmRouter = XcmRouter;
 	type ExecuteXcmOrigin = EnsureXcmOrigin<Origin, LocalOriginToLocation>;
 	type XcmExecuteFilter = frame_support::traits::Nothing;
-	type XcmExecutor = XcmExecutor<XcmConfig>;
+	type XcmExecutor = RateLimitedXcmExecutor;
 	// Do not allow teleports
 	type XcmTeleportFilter = Nothing;
 	type XcmReserveTransferFilter = Everything;
@@ -836,14 +850,21 @@ impl pallet_asset_manager::Config for Runtime {
 	type ForeignAssetType = AssetType;
 	type AssetRegistrar = AssetRegistrar;
 	type ForeignAssetModifierOrigin = EnsureRoot<AccountId>;
+	type SiblingAssetRegistrarOrigin = pallet_xcm::EnsureXcm<Everything>;
 	type LocalAssetModifierOrigin = EnsureRoot<AccountId>;
 	type LocalAssetIdCreator = LocalAssetIdCreator;
 	type AssetDestroyWitness = pallet_assets::DestroyWitness;
 	type Currency = Balances;
 	type LocalAssetDeposit = AssetDeposit;
+	type ForeignAssetDeposit = AssetDeposit;
 	type WeightInfo = ();
 }
 
+parameter_types! {
+	pub const TransactStatusTimeout: u64 = 100;
+	pub const MaxTransactStatusQueriesPerBlock: u32 = 100;
+}
+
 impl pallet_xcm_transactor::Config for Runtime {
 	type Event = Event;
 	type Balance = Balance;
@@ -861,6 +882,12 @@ impl pallet_xcm_transactor::Config for Runtime {
 	type BaseXcmWeight = BaseXcmWeight;
 	type AssetTransactor = AssetTransactors;
 	type ReserveProvider = xcm_primitives::AbsoluteAndRelativeReserve<SelfLocationAbsolute>;
+	type QueryNotifier = PolkadotXcm;
+	type ResponseOrigin = pallet_xcm::EnsureResponse<Everything>;
+	type TransactStatusTimeout = TransactStatusTimeout;
+	type MaxTransactStatusQueriesPerBlock = MaxTransactStatusQueriesPerBlock;
+	type HrmpManipulatorOrigin = EnsureRoot<AccountId>;
+	type HrmpEncoder = moonbeam_relay_encoder::westend::WestendEncoder;
 	type WeightInfo = ();
 }
 
@@ -889,7 +916,7 @@ impl pallet_evm::Config for Runtime {
 
 	type AddressMapping = moonbeam_runtime_common::IntoAddressMapping;
 	type Currency = Balances;
-	type Runner = pallet_evm::runner::stack::Runner<Self>;
+	type Runner = pallet_ethereum_xcm::RecordRevertData<pallet_evm::runner::stack::Runner<Self>>;
 
 	type Event = Event;
 	type PrecompilesType = ();
@@ -1023,13 +1050,28 @@ impl xcm_primitives::EnsureProxy<AccountId> for EthereumXcmEnsureProxy {
 	}
 }
 
+pub struct EthereumXcmPendingTransactions;
+impl pallet_ethereum_xcm::PendingEthereumTransactions for EthereumXcmPendingTransactions {
+	fn pending() -> Vec<(
+		pallet_ethereum_xcm::Transaction,
+		pallet_ethereum_xcm::TransactionStatus,
+		pallet_ethereum_xcm::Receipt,
+	)> {
+		Ethereum::pending()
+	}
+}
+
 impl pallet_ethereum_xcm::Config for Runtime {
+	type Event = Event;
 	type InvalidEvmTransactionError = pallet_ethereum::InvalidTransactionWrapper;
 	type ValidatedTransaction = pallet_ethereum::ValidatedTransaction<Self>;
 	type XcmEthereumOrigin = pallet_ethereum_xcm::EnsureXcmEthereumTransaction;
 	type ReservedXcmpWeight = ReservedXcmpWeight;
 	type EnsureProxy = EthereumXcmEnsureProxy;
 	type ControllerOrigin = EnsureRoot<AccountId>;
+	type PendingTransactions = EthereumXcmPendingTransactions;
+	type XcmSender = XcmRouter;
+	type LocationToAccountId = LocationToAccountId;
 }
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
@@ -1055,11 +1097,12 @@ construct_runtime!(
 		Treasury: pallet_treasury::{Pallet, Storage, Config, Event<T>, Call},
 		LocalAssets: pallet_assets::<Instance1>::{Pallet, Call, Storage, Event<T>},
 		Proxy: pallet_proxy::{Pallet, Call, Storage, Event<T>},
+		XcmRateLimiter: pallet_xcm_rate_limiter::{Pallet, Call, Storage, Event<T>},
 
 		Timestamp: pallet_timestamp::{Pallet, Call, Storage},
 		EVM: pallet_evm::{Pallet, Call, Storage, Config, Event<T>},
 		Ethereum: pallet_ethereum::{Pallet, Call, Storage, Event, Origin, Config},
-		EthereumXcm: pallet_ethereum_xcm::{Pallet, Call, Origin},
+		EthereumXcm: pallet_ethereum_xcm::{Pallet, Call, Origin, Event<T>},
 	}
 );
 
@@ -24,7 +24,9 @@ use frame_support::{
 	BoundedVec,
 };
 use pallet_asset_manager::LocalAssetIdCreator;
-use pallet_xcm_transactor::{Currency, CurrencyPayment, TransactWeights};
+use pallet_xcm_transactor::{
+	Currency, CurrencyPayment, TransactOptions, TransactStatus, TransactWeights,
+};
 use sp_std::boxed::Box;
 use xcm::latest::prelude::*;
 use xcm::{VersionedMultiLocation, WrapVersion};
@@ -870,6 +872,82 @@ fn send_para_a_asset_to_para_b_with_trader_and_fee() {
 	});
 }
 
+#[test]
+fn receive_asset_with_trader_buying_execution_twice() {
+	MockNet::reset();
+
+	let para_b_balances = MultiLocation::new(1, X2(Parachain(2), PalletInstance(1u8)));
+	let source_location = parachain::AssetType::Xcm(para_b_balances.clone());
+	let source_id: parachain::AssetId = source_location.clone().into();
+
+	let asset_metadata = parachain::AssetMetadata {
+		name: b"ParaBToken".to_vec(),
+		symbol: b"ParaB".to_vec(),
+		decimals: 18,
+	};
+
+	// With these units per second, 5 weight costs 10 asset units
+	ParaA::execute_with(|| {
+		assert_ok!(AssetManager::register_foreign_asset(
+			parachain::Origin::root(),
+			source_location.clone(),
+			asset_metadata,
+			1u128,
+			true
+		));
+		assert_ok!(AssetManager::set_asset_units_per_second(
+			parachain::Origin::root(),
+			source_location,
+			2000000000000u128,
+			0
+		));
+	});
+
+	let dest = MultiLocation {
+		parents: 1,
+		interior: X1(Parachain(1)),
+	};
+
+	// The message pays for its execution twice.
+	// Weight is 5 since we are executing 5 instructions with a unitweightcost of 1.
+	let message = xcm::VersionedXcm::<()>::V2(Xcm(vec![
+		ReserveAssetDeposited((para_b_balances.clone(), 100).into()),
+		ClearOrigin,
+		BuyExecution {
+			fees: (para_b_balances.clone(), 50).into(),
+			weight_limit: Limited(5),
+		},
+		BuyExecution {
+			fees: (para_b_balances, 50).into(),
+			weight_limit: Limited(5),
+		},
+		DepositAsset {
+			assets: All.into(),
+			max_assets: 1,
+			beneficiary: MultiLocation::new(
+				0,
+				X1(AccountKey20 {
+					network: Any,
+					key: PARAALICE,
+				}),
+			),
+		},
+	]));
+	ParaB::execute_with(|| {
+		assert_ok!(ParachainPalletXcm::send(
+			parachain::Origin::root(),
+			Box::new(dest.into()),
+			Box::new(message),
+		));
+	});
+
+	ParaA::execute_with(|| {
+		// Both purchases of 10 asset units went to the treasury
+		assert_eq!(Assets::balance(source_id, &PARAALICE.into()), 80);
+		assert_eq!(Assets::balance(source_id, &Treasury::account_id()), 20);
+	});
+}
+
 #[test]
 fn error_when_not_paying_enough() {
 	MockNet::reset();
@@ -999,6 +1077,7 @@ fn transact_through_derivative_multilocation() {
 		assert_ok!(XcmTransactor::register(
 			parachain::Origin::root(),
 			PARAALICE.into(),
+			Box::new(xcm::VersionedMultiLocation::V1(MultiLocation::parent())),
 			0,
 		));
 	});
@@ -1151,6 +1230,7 @@ fn transact_through_derivative_with_custom_fee_weight() {
 		assert_ok!(XcmTransactor::register(
 			parachain::Origin::root(),
 			PARAALICE.into(),
+			Box::new(xcm::VersionedMultiLocation::V1(MultiLocation::parent())),
 			0,
 		));
 	});
@@ -1318,6 +1398,7 @@ fn transact_through_sovereign() {
 		assert_ok!(XcmTransactor::register(
 			parachain::Origin::root(),
 			PARAALICE.into(),
+			Box::new(xcm::VersionedMultiLocation::V1(MultiLocation::parent())),
 			0,
 		));
 	});
@@ -1467,6 +1548,7 @@ fn transact_through_sovereign_with_custom_fee_weight() {
 		assert_ok!(XcmTransactor::register(
 			parachain::Origin::root(),
 			PARAALICE.into(),
+			Box::new(xcm::VersionedMultiLocation::V1(MultiLocation::parent())),
 			0,
 		));
 	});
@@ -2678,6 +2760,232 @@ fn transact_through_signed_multilocation_custom_fee_and_weight() {
 	});
 }
 
+#[test]
+fn transact_through_signed_multilocation_reports_status() {
+	MockNet::reset();
+	let mut ancestry = MultiLocation::parent();
+
+	ParaA::execute_with(|| {
+		ancestry = parachain::Ancestry::get();
+	});
+
+	// Let's construct the Junction that we will append with DescendOrigin
+	let signed_origin: Junctions = X1(AccountKey20 {
+		network: NetworkId::Any,
+		key: PARAALICE,
+	});
+
+	let mut descend_origin_multilocation = parachain::SelfLocation::get();
+	descend_origin_multilocation
+		.append_with(signed_origin)
+		.unwrap();
+
+	// To convert it to what the relay will see instead of us
+	descend_origin_multilocation
+		.reanchor(&MultiLocation::parent(), &ancestry)
+		.unwrap();
+
+	let derived = xcm_builder::Account32Hash::<
+		relay_chain::KusamaNetwork,
+		relay_chain::AccountId,
+	>::convert_ref(descend_origin_multilocation)
+	.unwrap();
+
+	Relay::execute_with(|| {
+		// free execution, full amount received
+		assert_ok!(RelayBalances::transfer(
+			relay_chain::Origin::signed(RELAYALICE),
+			derived.clone(),
+			4000006100u128,
+		));
+		// derived account has all funds
+		assert!(RelayBalances::free_balance(&derived) == 4000006100);
+		// sovereign account has 0 funds
+		assert!(RelayBalances::free_balance(&para_a_account()) == 0);
+	});
+
+	// Encode the call. Balances transact to para_a_account
+	// First index
+	let mut encoded: Vec<u8> = Vec::new();
+	let index = <relay_chain::Runtime as frame_system::Config>::PalletInfo::index::<
+		relay_chain::Balances,
+	>()
+	.unwrap() as u8;
+
+	encoded.push(index);
+
+	// Then call bytes
+	let mut call_bytes = pallet_balances::Call::<relay_chain::Runtime>::transfer {
+		// 100 to sovereign
+		dest: para_a_account(),
+		value: 100u32.into(),
+	}
+	.encode();
+	encoded.append(&mut call_bytes);
+
+	// 4000000000 for transfer + 6000 for XCM, as SetAppendix and ReportError are also charged
+	let total_weight = 4000006000u64;
+	// The weight of SetAppendix and ReportError is added by the transactor, which weighs them
+	// like this chain does
+	let appendix_weight = 2u64;
+	let mut query_id = 0;
+	ParaA::execute_with(|| {
+		assert_ok!(XcmTransactor::transact_through_signed_with_options(
+			parachain::Origin::signed(PARAALICE.into()),
+			Box::new(xcm::VersionedMultiLocation::V1(MultiLocation::parent())),
+			CurrencyPayment {
+				currency: Currency::AsMultiLocation(Box::new(xcm::VersionedMultiLocation::V1(
+					MultiLocation::parent()
+				))),
+				fee_amount: Some(total_weight as u128)
+			},
+			encoded,
+			TransactWeights {
+				transact_required_weight_at_most: 4000000000,
+				overall_weight: Some(total_weight - appendix_weight)
+			},
+			TransactOptions {
+				report_status: true,
+				refund: false
+			}
+		));
+
+		query_id = parachain::para_events()
+			.into_iter()
+			.find_map(|event| match event {
+				parachain::Event::XcmTransactor(
+					pallet_xcm_transactor::Event::TransactStatusQueried { query_id, .. },
+				) => Some(query_id),
+				_ => None,
+			})
+			.unwrap();
+	});
+
+	Relay::execute_with(|| {
+		assert!(RelayBalances::free_balance(&para_a_account()) == 100);
+
+		assert!(RelayBalances::free_balance(&derived) == 0);
+	});
+
+	// The relay reported back the outcome of the message
+	ParaA::execute_with(|| {
+		assert_eq!(
+			XcmTransactor::transact_status(query_id).map(|info| info.status),
+			Some(TransactStatus::Succeeded)
+		);
+
+		let expected_event = parachain::Event::XcmTransactor(
+			pallet_xcm_transactor::Event::RemoteTransactSucceeded {
+				query_id,
+				dest: MultiLocation::parent(),
+			},
+		);
+		assert!(parachain::para_events().contains(&expected_event));
+	});
+}
+
+#[test]
+fn transact_through_signed_multilocation_refunds_unspent_fees() {
+	MockNet::reset();
+	let mut ancestry = MultiLocation::parent();
+
+	ParaA::execute_with(|| {
+		ancestry = parachain::Ancestry::get();
+	});
+
+	// Let's construct the Junction that we will append with DescendOrigin
+	let signed_origin: Junctions = X1(AccountKey20 {
+		network: NetworkId::Any,
+		key: PARAALICE,
+	});
+
+	let mut descend_origin_multilocation = parachain::SelfLocation::get();
+	descend_origin_multilocation
+		.append_with(signed_origin)
+		.unwrap();
+
+	// To convert it to what the relay will see instead of us
+	descend_origin_multilocation
+		.reanchor(&MultiLocation::parent(), &ancestry)
+		.unwrap();
+
+	let derived = xcm_builder::Account32Hash::<
+		relay_chain::KusamaNetwork,
+		relay_chain::AccountId,
+	>::convert_ref(descend_origin_multilocation)
+	.unwrap();
+
+	Relay::execute_with(|| {
+		// free execution, full amount received
+		assert_ok!(RelayBalances::transfer(
+			relay_chain::Origin::signed(RELAYALICE),
+			derived.clone(),
+			4000008100u128,
+		));
+		// derived account has all funds
+		assert!(RelayBalances::free_balance(&derived) == 4000008100);
+		// sovereign account has 0 funds
+		assert!(RelayBalances::free_balance(&para_a_account()) == 0);
+	});
+
+	// Encode the call. Balances transact to para_a_account
+	// First index
+	let mut encoded: Vec<u8> = Vec::new();
+	let index = <relay_chain::Runtime as frame_system::Config>::PalletInfo::index::<
+		relay_chain::Balances,
+	>()
+	.unwrap() as u8;
+
+	encoded.push(index);
+
+	// Then call bytes
+	let mut call_bytes = pallet_balances::Call::<relay_chain::Runtime>::transfer {
+		// 100 to sovereign
+		dest: para_a_account(),
+		value: 100u32.into(),
+	}
+	.encode();
+	encoded.append(&mut call_bytes);
+
+	// 4000000000 for transfer + 7000 for XCM, as SetAppendix, RefundSurplus and DepositAsset
+	// are also charged
+	let total_weight = 4000007000u64;
+	// The weight of the appendix is added by the transactor, which weighs it like this chain does
+	let appendix_weight = 3u64;
+	// We pay 1000 more than the weight bought in the relay
+	let excess_fee = 1000u128;
+	ParaA::execute_with(|| {
+		assert_ok!(XcmTransactor::transact_through_signed_with_options(
+			parachain::Origin::signed(PARAALICE.into()),
+			Box::new(xcm::VersionedMultiLocation::V1(MultiLocation::parent())),
+			CurrencyPayment {
+				currency: Currency::AsMultiLocation(Box::new(xcm::VersionedMultiLocation::V1(
+					MultiLocation::parent()
+				))),
+				fee_amount: Some(total_weight as u128 + excess_fee)
+			},
+			encoded,
+			TransactWeights {
+				transact_required_weight_at_most: 4000000000,
+				overall_weight: Some(total_weight - appendix_weight)
+			},
+			TransactOptions {
+				report_status: false,
+				refund: true
+			}
+		));
+	});
+
+	Relay::execute_with(|| {
+		assert!(RelayBalances::free_balance(&para_a_account()) == 100);
+
+		// The fees that were not used to buy weight, plus the weight the transfer did not use,
+		// are deposited back into the derived account instead of being trapped
+		assert!(RelayBalances::free_balance(&derived) >= excess_fee);
+		assert!(RelayBalances::free_balance(&derived) < 4000008000);
+	});
+}
+
 #[test]
 fn transact_through_signed_multilocation_para_to_para() {
 	MockNet::reset();
@@ -3168,6 +3476,306 @@ fn transact_through_signed_multilocation_para_to_para_ethereum_proxy_succeeds()
 	});
 }
 
+#[test]
+fn dry_run_incoming_xcm_does_not_persist_effects() {
+	MockNet::reset();
+
+	let source_location = parachain::AssetType::Xcm(MultiLocation::parent());
+	let source_id: parachain::AssetId = source_location.clone().into();
+	let asset_metadata = parachain::AssetMetadata {
+		name: b"RelayToken".to_vec(),
+		symbol: b"Relay".to_vec(),
+		decimals: 12,
+	};
+
+	ParaA::execute_with(|| {
+		assert_ok!(AssetManager::register_foreign_asset(
+			parachain::Origin::root(),
+			source_location.clone(),
+			asset_metadata,
+			1u128,
+			true
+		));
+		assert_ok!(AssetManager::set_asset_units_per_second(
+			parachain::Origin::root(),
+			source_location,
+			0u128,
+			0
+		));
+	});
+
+	// The message the relay would send in a reserve transfer to PARAALICE
+	let message = Xcm(vec![
+		ReserveAssetDeposited((Parent, 123).into()),
+		ClearOrigin,
+		BuyExecution {
+			fees: (Parent, 123).into(),
+			weight_limit: Unlimited,
+		},
+		DepositAsset {
+			assets: All.into(),
+			max_assets: 1,
+			beneficiary: AccountKey20 {
+				network: Any,
+				key: PARAALICE,
+			}
+			.into(),
+		},
+	]);
+
+	ParaA::execute_with(|| {
+		let effects = xcm_primitives::dry_run_xcm::<
+			parachain::Runtime,
+			parachain::RateLimitedXcmExecutor,
+			_,
+		>(MultiLocation::parent(), message, 1_000_000_000)
+		.unwrap();
+
+		assert_eq!(effects.outcome, Outcome::Complete(4));
+		assert!(effects.events.iter().any(|event| matches!(
+			event,
+			parachain::Event::Assets(pallet_assets::Event::Issued {
+				total_supply: 123,
+				..
+			})
+		)));
+		assert!(effects.forwarded_messages.is_empty());
+
+		// Nothing was minted
+		assert_eq!(Assets::balance(source_id, &PARAALICE.into()), 0);
+	});
+}
+
+#[test]
+fn dry_run_outgoing_xcm_records_forwarded_messages() {
+	MockNet::reset();
+
+	let source_location = parachain::AssetType::Xcm(MultiLocation::parent());
+	let source_id: parachain::AssetId = source_location.clone().into();
+	let asset_metadata = parachain::AssetMetadata {
+		name: b"RelayToken".to_vec(),
+		symbol: b"Relay".to_vec(),
+		decimals: 12,
+	};
+
+	ParaA::execute_with(|| {
+		assert_ok!(AssetManager::register_foreign_asset(
+			parachain::Origin::root(),
+			source_location.clone(),
+			asset_metadata,
+			1u128,
+			true
+		));
+		assert_ok!(AssetManager::set_asset_units_per_second(
+			parachain::Origin::root(),
+			source_location,
+			0u128,
+			0
+		));
+	});
+
+	let dest: MultiLocation = AccountKey20 {
+		network: NetworkId::Any,
+		key: PARAALICE,
+	}
+	.into();
+	Relay::execute_with(|| {
+		assert_ok!(RelayChainPalletXcm::reserve_transfer_assets(
+			relay_chain::Origin::signed(RELAYALICE),
+			Box::new(Parachain(1).into().into()),
+			Box::new(VersionedMultiLocation::V1(dest).clone().into()),
+			Box::new((Here, 123).into()),
+			0,
+		));
+	});
+
+	// PARAALICE sends 100 relay tokens back to the relay
+	let message = Xcm(vec![
+		WithdrawAsset((Parent, 100).into()),
+		BuyExecution {
+			fees: (Parent, 100).into(),
+			weight_limit: Unlimited,
+		},
+		InitiateReserveWithdraw {
+			assets: All.into(),
+			reserve: MultiLocation::parent(),
+			xcm: Xcm(vec![DepositAsset {
+				assets: All.into(),
+				max_assets: 1,
+				beneficiary: AccountId32 {
+					network: Any,
+					id: RELAYALICE.into(),
+				}
+				.into(),
+			}]),
+		},
+	]);
+
+	ParaA::execute_with(|| {
+		let effects = xcm_primitives::dry_run_xcm::<
+			parachain::Runtime,
+			parachain::RateLimitedXcmExecutor,
+			_,
+		>(dest, message, 1_000_000_000)
+		.unwrap();
+
+		assert_eq!(effects.outcome, Outcome::Complete(3));
+		assert!(effects.events.iter().any(|event| matches!(
+			event,
+			parachain::Event::Assets(pallet_assets::Event::Burned { balance: 100, .. })
+		)));
+		assert_eq!(effects.forwarded_messages.len(), 1);
+		assert_eq!(effects.forwarded_messages[0].0, MultiLocation::parent());
+
+		// Nothing was withdrawn
+		assert_eq!(Assets::balance(source_id, &PARAALICE.into()), 123);
+	});
+}
+
+#[test]
+fn inbound_assets_exceeding_inflow_limit_are_quarantined_and_released() {
+	MockNet::reset();
+
+	let source_location = parachain::AssetType::Xcm(MultiLocation::parent());
+	let source_id: parachain::AssetId = source_location.clone().into();
+	let asset_metadata = parachain::AssetMetadata {
+		name: b"RelayToken".to_vec(),
+		symbol: b"Relay".to_vec(),
+		decimals: 12,
+	};
+	// register relay asset in parachain A and limit its inflow from the relay
+	ParaA::execute_with(|| {
+		assert_ok!(AssetManager::register_foreign_asset(
+			parachain::Origin::root(),
+			source_location.clone(),
+			asset_metadata,
+			1u128,
+			true
+		));
+		assert_ok!(AssetManager::set_asset_units_per_second(
+			parachain::Origin::root(),
+			source_location,
+			0u128,
+			0
+		));
+		assert_ok!(XcmRateLimiter::set_inflow_limit(
+			parachain::Origin::root(),
+			Box::new(MultiLocation::parent().into()),
+			Box::new(MultiLocation::parent().into()),
+			100,
+			10
+		));
+	});
+
+	let dest: MultiLocation = AccountKey20 {
+		network: NetworkId::Any,
+		key: PARAALICE,
+	}
+	.into();
+	Relay::execute_with(|| {
+		assert_ok!(RelayChainPalletXcm::reserve_transfer_assets(
+			relay_chain::Origin::signed(RELAYALICE),
+			Box::new(Parachain(1).into().into()),
+			Box::new(VersionedMultiLocation::V1(dest.clone()).clone().into()),
+			Box::new((Here, 123).into()),
+			0,
+		));
+	});
+
+	ParaA::execute_with(|| {
+		// Only the limit was received, the rest is quarantined
+		assert_eq!(Assets::balance(source_id, &PARAALICE.into()), 100);
+		assert_eq!(
+			Assets::balance(source_id, &XcmRateLimiter::account_id()),
+			23
+		);
+		assert_eq!(
+			XcmRateLimiter::quarantined(0),
+			Some(pallet_xcm_rate_limiter::QuarantinedAsset {
+				asset: (MultiLocation::parent(), 23).into(),
+				origin: MultiLocation::parent(),
+				beneficiary: dest,
+			})
+		);
+
+		assert_ok!(XcmRateLimiter::release_quarantined(
+			parachain::Origin::root(),
+			0
+		));
+
+		assert_eq!(Assets::balance(source_id, &PARAALICE.into()), 123);
+		assert_eq!(Assets::balance(source_id, &XcmRateLimiter::account_id()), 0);
+		assert_eq!(XcmRateLimiter::quarantined(0), None);
+	});
+}
+
+#[test]
+fn quarantined_inbound_assets_can_be_burned() {
+	MockNet::reset();
+
+	let source_location = parachain::AssetType::Xcm(MultiLocation::parent());
+	let source_id: parachain::AssetId = source_location.clone().into();
+	let asset_metadata = parachain::AssetMetadata {
+		name: b"RelayToken".to_vec(),
+		symbol: b"Relay".to_vec(),
+		decimals: 12,
+	};
+	// register relay asset in parachain A and limit its inflow from the relay
+	ParaA::execute_with(|| {
+		assert_ok!(AssetManager::register_foreign_asset(
+			parachain::Origin::root(),
+			source_location.clone(),
+			asset_metadata,
+			1u128,
+			true
+		));
+		assert_ok!(AssetManager::set_asset_units_per_second(
+			parachain::Origin::root(),
+			source_location,
+			0u128,
+			0
+		));
+		assert_ok!(XcmRateLimiter::set_inflow_limit(
+			parachain::Origin::root(),
+			Box::new(MultiLocation::parent().into()),
+			Box::new(MultiLocation::parent().into()),
+			100,
+			10
+		));
+	});
+
+	let dest: MultiLocation = AccountKey20 {
+		network: NetworkId::Any,
+		key: PARAALICE,
+	}
+	.into();
+	Relay::execute_with(|| {
+		assert_ok!(RelayChainPalletXcm::reserve_transfer_assets(
+			relay_chain::Origin::signed(RELAYALICE),
+			Box::new(Parachain(1).into().into()),
+			Box::new(VersionedMultiLocation::V1(dest).clone().into()),
+			Box::new((Here, 123).into()),
+			0,
+		));
+	});
+
+	ParaA::execute_with(|| {
+		assert_eq!(
+			Assets::balance(source_id, &XcmRateLimiter::account_id()),
+			23
+		);
+
+		assert_ok!(XcmRateLimiter::burn_quarantined(
+			parachain::Origin::root(),
+			0
+		));
+
+		assert_eq!(Assets::balance(source_id, &PARAALICE.into()), 100);
+		assert_eq!(Assets::balance(source_id, &XcmRateLimiter::account_id()), 0);
+		assert_eq!(Assets::total_supply(source_id), 100);
+	});
+}
+
 use parity_scale_codec::{Decode, Encode};
 use sp_io::hashing::blake2_256;
 
@@ -23,17 +23,23 @@ use super::{
 	LOCAL_ASSET_PRECOMPILE_ADDRESS_PREFIX,
 };
 
+use moonbeam_runtime_common::assets;
+use pallet_assets::WeightInfo as _;
 use pallet_evm_precompileset_assets_erc20::AccountIdAssetIdConversion;
-use sp_runtime::traits::Hash as THash;
+use sp_runtime::{
+	traits::{Hash as THash, Zero},
+	DispatchError,
+};
 
 use frame_support::{
 	parameter_types,
-	traits::{ConstU128, EitherOfDiverse},
+	traits::{tokens::fungibles, ConstU128, Contains, EitherOfDiverse},
 	weights::{GetDispatchInfo, Weight},
 };
 
 use frame_system::EnsureRoot;
 use sp_core::{H160, H256};
+use xcm::latest::{Junction::Parachain, Junctions::X1, MultiLocation};
 
 use parity_scale_codec::{Decode, Encode};
 use scale_info::TypeInfo;
@@ -143,6 +149,53 @@ impl pallet_asset_manager::AssetRegistrar<Runtime> for AssetRegistrar {
 		)
 	}
 
+	#[transactional]
+	fn update_foreign_asset(
+		asset: AssetId,
+		min_balance: Balance,
+		metadata: AssetRegistrarMetadata,
+		is_sufficient: bool,
+	) -> DispatchResult {
+		// Foreign assets are owned by AssetManager and never frozen
+		let owner = AssetManager::account_id();
+		Assets::force_asset_status(
+			Origin::root(),
+			asset,
+			owner,
+			owner,
+			owner,
+			owner,
+			min_balance,
+			is_sufficient,
+			false,
+		)?;
+
+		Assets::force_set_metadata(
+			Origin::root(),
+			asset,
+			metadata.name,
+			metadata.symbol,
+			metadata.decimals,
+			metadata.is_frozen,
+		)
+	}
+
+	fn make_foreign_asset_sufficient(asset: AssetId) -> DispatchResult {
+		// Foreign assets are owned by AssetManager and never frozen
+		let owner = AssetManager::account_id();
+		Assets::force_asset_status(
+			Origin::root(),
+			asset,
+			owner,
+			owner,
+			owner,
+			owner,
+			<Assets as fungibles::Inspect<AccountId>>::minimum_balance(asset),
+			true,
+			false,
+		)
+	}
+
 	#[transactional]
 	fn create_local_asset(
 		asset: AssetId,
@@ -225,6 +278,103 @@ impl pallet_asset_manager::AssetRegistrar<Runtime> for AssetRegistrar {
 			.weight
 			.saturating_add(<Runtime as frame_system::Config>::DbWeight::get().writes(1 as Weight))
 	}
+
+	fn local_asset_owner(asset: AssetId) -> Option<AccountId> {
+		local_asset_details(asset).map(|details| details.owner)
+	}
+
+	#[transactional]
+	fn transfer_local_asset_ownership(asset: AssetId, owner: AccountId) -> DispatchResult {
+		let details = local_asset_details(asset).ok_or(DispatchError::CannotLookup)?;
+		LocalAssets::transfer_ownership(Origin::signed(details.owner), asset, owner)
+	}
+
+	#[transactional]
+	fn freeze_local_asset(asset: AssetId) -> DispatchResult {
+		let details = local_asset_details(asset).ok_or(DispatchError::CannotLookup)?;
+		set_local_asset_frozen(asset, &details, true)
+	}
+
+	#[transactional]
+	fn burn_local_asset_accounts(
+		asset: AssetId,
+		max_accounts: u32,
+		start_after: Option<AccountId>,
+	) -> Result<(u32, Option<AccountId>), DispatchError> {
+		let details = local_asset_details(asset).ok_or(DispatchError::CannotLookup)?;
+
+		// Balances of frozen assets cannot be burnt, so we thaw the asset in the meantime
+		set_local_asset_frozen(asset, &details, false)?;
+
+		// Every scanned account is paid for, even if it has nothing to burn
+		let scanned: Vec<AccountId> =
+			assets::asset_accounts::<LocalAssets, AccountId, _>(asset, start_after.as_ref())
+				.take(max_accounts as usize)
+				.collect();
+
+		let mut burnt = 0u32;
+		for who in scanned.iter() {
+			// Accounts only holding a deposit are removed when destroying the asset
+			let balance = LocalAssets::balance(asset, who);
+			if balance.is_zero() {
+				continue;
+			}
+			LocalAssets::thaw(Origin::signed(details.admin), asset, *who)?;
+			<LocalAssets as fungibles::Mutate<AccountId>>::burn_from(asset, who, balance)?;
+			burnt += 1;
+		}
+
+		set_local_asset_frozen(asset, &details, true)?;
+
+		// The next batch starts over once every account was scanned
+		let last_scanned = if scanned.len() < max_accounts as usize {
+			None
+		} else {
+			scanned.last().copied()
+		};
+		Ok((burnt, last_scanned))
+	}
+
+	fn local_asset_destroy_witness(asset: AssetId) -> Option<pallet_assets::DestroyWitness> {
+		assets::asset_destroy_witness::<LocalAssets, AccountId, Balance, _>(asset)
+	}
+
+	fn burn_local_asset_accounts_weight(max_accounts: u32) -> Weight {
+		type LocalAssetsWeight = <Runtime as pallet_assets::Config<LocalAssetInstance>>::WeightInfo;
+
+		// Reading the asset and thawing and freezing it, plus reading, thawing and burning
+		// every account
+		let per_account = LocalAssetsWeight::thaw()
+			.saturating_add(LocalAssetsWeight::burn())
+			.saturating_add(<Runtime as frame_system::Config>::DbWeight::get().reads(1 as Weight));
+		LocalAssetsWeight::force_asset_status()
+			.saturating_mul(2)
+			.saturating_add(<Runtime as frame_system::Config>::DbWeight::get().reads(1 as Weight))
+			.saturating_add(per_account.saturating_mul(max_accounts as Weight))
+	}
+}
+
+fn local_asset_details(asset: AssetId) -> Option<assets::AssetDetails<AccountId, Balance>> {
+	assets::asset_details::<LocalAssets, AccountId, Balance, _>(asset)
+}
+
+// Freezes or thaws a local asset, keeping the rest of its details
+fn set_local_asset_frozen(
+	asset: AssetId,
+	details: &assets::AssetDetails<AccountId, Balance>,
+	is_frozen: bool,
+) -> DispatchResult {
+	LocalAssets::force_asset_status(
+		Origin::root(),
+		asset,
+		details.owner,
+		details.issuer,
+		details.admin,
+		details.freezer,
+		details.min_balance,
+		details.is_sufficient,
+		is_frozen,
+	)
 }
 
 pub struct LocalAssetIdCreator;
@@ -248,6 +398,20 @@ pub struct AssetRegistrarMetadata {
 	pub is_frozen: bool,
 }
 
+/// Sibling parachains, which are allowed to register the assets they are reserve of
+pub struct SiblingParachains;
+impl Contains<MultiLocation> for SiblingParachains {
+	fn contains(location: &MultiLocation) -> bool {
+		matches!(
+			location,
+			MultiLocation {
+				parents: 1,
+				interior: X1(Parachain(_)),
+			}
+		)
+	}
+}
+
 impl pallet_asset_manager::Config for Runtime {
 	type Event = Event;
 	type Balance = Balance;
@@ -256,11 +420,13 @@ impl pallet_asset_manager::Config for Runtime {
 	type ForeignAssetType = xcm_config::AssetType;
 	type AssetRegistrar = AssetRegistrar;
 	type ForeignAssetModifierOrigin = EnsureRoot<AccountId>;
+	type SiblingAssetRegistrarOrigin = pallet_xcm::EnsureXcm<SiblingParachains>;
 	type LocalAssetModifierOrigin = EnsureRoot<AccountId>;
 	type LocalAssetIdCreator = LocalAssetIdCreator;
 	type AssetDestroyWitness = pallet_assets::DestroyWitness;
 	type Currency = Balances;
 	type LocalAssetDeposit = AssetDeposit;
+	type ForeignAssetDeposit = AssetDeposit;
 	type WeightInfo = pallet_asset_manager::weights::SubstrateWeight<Runtime>;
 }
 
@@ -18,9 +18,10 @@
 //!
 
 use super::{
-	AccountId, AssetId, AssetManager, Assets, Balance, Balances, Call, DealWithFees, Event,
-	LocalAssets, Origin, ParachainInfo, ParachainSystem, PolkadotXcm, Runtime, Treasury, XcmpQueue,
-	FOREIGN_ASSET_PRECOMPILE_ADDRESS_PREFIX,
+	currency, AccountId, AssetId, AssetManager, Assets, Balance, Balances, BlockNumber, Call,
+	DealWithFees, EthereumLogs, Event, LocalAssets, Origin, ParachainInfo, ParachainSystem,
+	PolkadotXcm, Runtime, TrappedAssets, Treasury, XcmRateLimiter, XcmpQueue, DAYS,
+	FOREIGN_ASSET_PRECOMPILE_ADDRESS_PREFIX, HOURS, LOCAL_ASSET_PRECOMPILE_ADDRESS_PREFIX,
 };
 
 use pallet_evm_precompileset_assets_erc20::AccountIdAssetIdConversion;
@@ -28,8 +29,8 @@ use sp_runtime::traits::Hash as THash;
 
 use frame_support::{
 	parameter_types,
-	traits::{Everything, Nothing, PalletInfoAccess},
-	weights::Weight,
+	traits::{Everything, Get, Nothing, PalletInfoAccess},
+	weights::{constants::WEIGHT_PER_SECOND, Weight},
 };
 
 use frame_system::EnsureRoot;
@@ -50,7 +51,8 @@ use xcm_executor::traits::JustTry;
 use orml_xcm_support::MultiNativeAsset;
 use xcm_primitives::{
 	AbsoluteAndRelativeReserve, AccountIdToCurrencyId, AccountIdToMultiLocation, AsAssetType,
-	FirstAssetTrader, SignedToAccountId20, UtilityAvailableCalls, UtilityEncodeCall, XcmTransact,
+	MultiAssetTrader, RecordingRouter, SignedToAccountId20, TransferLogsTransactor,
+	UtilityAvailableCalls, UtilityEncodeCall, XcmTransact,
 };
 
 use parity_scale_codec::{Decode, Encode};
@@ -104,20 +106,23 @@ pub type LocationToAccountId = (
 	AccountKey20Aliases<RelayNetwork, AccountId>,
 );
 
+/// Matches foreign assets against AsAssetType
+pub type ForeignAssetsMatcher = (
+	ConvertedConcreteAssetId<
+		AssetId,
+		Balance,
+		AsAssetType<AssetId, AssetType, AssetManager>,
+		JustTry,
+	>,
+);
+
 // The non-reserve fungible transactor type
 // It will use pallet-assets, and the Id will be matched against AsAssetType
 pub type ForeignFungiblesTransactor = FungiblesAdapter<
 	// Use this fungibles implementation:
 	Assets,
 	// Use this currency when it is a fungible asset matching the given location or name:
-	(
-		ConvertedConcreteAssetId<
-			AssetId,
-			Balance,
-			AsAssetType<AssetId, AssetType, AssetManager>,
-			JustTry,
-		>,
-	),
+	ForeignAssetsMatcher,
 	// Do a simple punn to convert an AccountId20 MultiLocation into a native chain account ID:
 	LocationToAccountId,
 	// Our chain's account ID type (we can't get away without mentioning it explicitly):
@@ -143,21 +148,24 @@ pub type LocalAssetTransactor = XcmCurrencyAdapter<
 	(),
 >;
 
+/// Matches local assets by their GeneralIndex under LocalAssetsPalletLocation
+pub type LocalAssetsMatcher = (
+	ConvertedConcreteAssetId<
+		AssetId,
+		Balance,
+		// This just tells to convert an assetId into a GeneralIndex junction prepended
+		// by LocalAssetsPalletLocationNewReanchor
+		AsPrefixedGeneralIndex<LocalAssetsPalletLocation, AssetId, JustTry>,
+		JustTry,
+	>,
+);
+
 /// Means for transacting local assets besides the native currency on this chain.
 pub type LocalFungiblesTransactor = FungiblesAdapter<
 	// Use this fungibles implementation:
 	LocalAssets,
 	// Use this currency when it is a fungible asset matching the given location or name:
-	(
-		ConvertedConcreteAssetId<
-			AssetId,
-			Balance,
-			// This just tells to convert an assetId into a GeneralIndex junction prepended
-			// by LocalAssetsPalletLocationNewReanchor
-			AsPrefixedGeneralIndex<LocalAssetsPalletLocation, AssetId, JustTry>,
-			JustTry,
-		>,
-	),
+	LocalAssetsMatcher,
 	// Convert an XCM MultiLocation into a local account id:
 	LocationToAccountId,
 	// Our chain's account ID type (we can't get away without mentioning it explicitly):
@@ -171,9 +179,49 @@ pub type LocalFungiblesTransactor = FungiblesAdapter<
 // We use all transactors
 pub type AssetTransactors = (
 	LocalAssetTransactor,
+	ForeignXc20Transactor,
+	LocalXc20Transactor,
+);
+
+parameter_types! {
+	pub const ForeignAssetsPrefix: &'static [u8] = FOREIGN_ASSET_PRECOMPILE_ADDRESS_PREFIX;
+	pub const LocalAssetsPrefix: &'static [u8] = LOCAL_ASSET_PRECOMPILE_ADDRESS_PREFIX;
+}
+
+/// Converts an asset id into the address of its XC-20 precompile, given the address prefix
+pub struct AssetIdToXc20Address<Prefix>(sp_std::marker::PhantomData<Prefix>);
+impl<Prefix: Get<&'static [u8]>> sp_runtime::traits::Convert<AssetId, H160>
+	for AssetIdToXc20Address<Prefix>
+{
+	fn convert(asset_id: AssetId) -> H160 {
+		Runtime::asset_id_to_account(Prefix::get(), asset_id).into()
+	}
+}
+
+/// XC-20s minted, burnt and transferred through XCM are recorded as ERC-20 Transfer logs in the
+/// Ethereum block, so that indexers can reconcile their balances
+pub type ForeignXc20Transactor = TransferLogsTransactor<
 	ForeignFungiblesTransactor,
+	ForeignAssetsMatcher,
+	LocationToAccountId,
+	AccountId,
+	AssetId,
+	Balance,
+	AssetIdToXc20Address<ForeignAssetsPrefix>,
+	EthereumLogs,
+>;
+
+/// Same as ForeignXc20Transactor, for local assets
+pub type LocalXc20Transactor = TransferLogsTransactor<
 	LocalFungiblesTransactor,
-);
+	LocalAssetsMatcher,
+	LocationToAccountId,
+	AccountId,
+	AssetId,
+	Balance,
+	AssetIdToXc20Address<LocalAssetsPrefix>,
+	EthereumLogs,
+>;
 
 /// This is the type we use to convert an (incoming) XCM origin into a local `Origin` instance,
 /// ready for dispatching a transaction with Xcm's `Transact`. There is an `OriginKind` which can
@@ -244,7 +292,8 @@ impl xcm_executor::Config for XcmExecutorConfig {
 	type Call = Call;
 	type XcmSender = XcmRouter;
 	// How to withdraw and deposit an asset.
-	type AssetTransactor = AssetTransactors;
+	// Deposits are rate limited by XcmRateLimiter before reaching AssetTransactors
+	type AssetTransactor = XcmRateLimiter;
 	type OriginConverter = XcmOriginToTransactDispatchOrigin;
 	// Filter to the reserve withdraw operations
 	// Whenever the reserve matches the relative or absolute value
@@ -267,28 +316,77 @@ impl xcm_executor::Config for XcmExecutorConfig {
 			Balances,
 			DealWithFees<Runtime>,
 		>,
-		FirstAssetTrader<AssetType, AssetManager, XcmFeesToAccount>,
+		MultiAssetTrader<AssetType, AssetManager, XcmFeesToAccount>,
 	);
 	type ResponseHandler = PolkadotXcm;
 	type SubscriptionService = PolkadotXcm;
-	type AssetTrap = PolkadotXcm;
-	type AssetClaims = PolkadotXcm;
+	// Trapped assets are indexed by origin before being stored in PolkadotXcm
+	type AssetTrap = TrappedAssets;
+	type AssetClaims = TrappedAssets;
 	type CallDispatcher = Call;
 }
 
-type XcmExecutor = xcm_executor::XcmExecutor<XcmExecutorConfig>;
+/// The XCM executor. The origin of every executed message is tracked so that XcmRateLimiter can
+/// enforce inflow limits per origin
+pub type XcmExecutor =
+	pallet_xcm_rate_limiter::TrackInboundOrigin<xcm_executor::XcmExecutor<XcmExecutorConfig>>;
+
+parameter_types! {
+	pub const MaxTrapsPerOrigin: u32 = 20;
+}
+
+impl pallet_trapped_assets::Config for Runtime {
+	type MaxTrapsPerOrigin = MaxTrapsPerOrigin;
+}
+
+impl pallet_xcm_rate_limiter::Config for Runtime {
+	type Event = Event;
+	type AssetTransactor = AssetTransactors;
+	type AccountIdToMultiLocation = AccountIdToMultiLocation<AccountId>;
+	type LimiterOrigin = EnsureRoot<AccountId>;
+	type WeightInfo = pallet_xcm_rate_limiter::weights::SubstrateWeight<Runtime>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkAsset = SelfReserve;
+}
+
+parameter_types! {
+	// The amount of native token charged per second of execution
+	pub const NativeFeePerSecond: u128 = currency::WEIGHT_FEE * (WEIGHT_PER_SECOND as u128);
+	pub const MaxPriceFeeders: u32 = 20;
+	pub const MaxFedAssets: u32 = 50;
+	pub const MinFeedPrices: u32 = 3;
+	pub const MaxFeedPriceAge: BlockNumber = HOURS;
+}
+
+impl pallet_asset_price_feed::Config for Runtime {
+	type Event = Event;
+	type FeedOrigin = EnsureRoot<AccountId>;
+	type NativeFeePerSecond = NativeFeePerSecond;
+	// The units per second charged for incoming messages follow the fed prices
+	type FeePerSecondUpdater = AssetManager;
+	type MaxFeeders = MaxPriceFeeders;
+	type MaxFedAssets = MaxFedAssets;
+	type MinPrices = MinFeedPrices;
+	type MaxPriceAge = MaxFeedPriceAge;
+	type WeightInfo = pallet_asset_price_feed::weights::SubstrateWeight<Runtime>;
+}
 
 // Converts a Signed Local Origin into a MultiLocation
 pub type LocalOriginToLocation = SignedToAccountId20<Origin, AccountId, RelayNetwork>;
 
 /// The means for routing XCM messages which are not for local execution into the right message
-/// queues.
-pub type XcmRouter = (
+/// queues. Sent messages are recorded so they can be returned when dry-running XCM.
+pub type XcmRouter = RecordingRouter<(
 	// Two routers - use UMP to communicate with the relay chain:
 	cumulus_primitives_utility::ParentAsUmp<ParachainSystem, PolkadotXcm>,
 	// ..and XCMP to communicate with the sibling chains.
 	XcmpQueue,
-);
+)>;
+
+/// Messages that contracts can execute through the xcmExecute function of the xcm-utils
+/// precompile, which already restricts them to asset handling instructions. Executing messages
+/// through pallet-xcm is still disabled by its XcmExecuteFilter
+pub type XcmUtilsExecuteFilter = Everything;
 
 impl pallet_xcm::Config for Runtime {
 	type Event = Event;
@@ -504,6 +602,13 @@ impl XcmTransact for Transactors {
 	}
 }
 
+parameter_types! {
+	// Blocks during which the response to a transact status query is expected
+	pub const TransactStatusTimeout: BlockNumber = DAYS;
+	// Transact status queries that can time out in the same block, all removed at once
+	pub const MaxTransactStatusQueriesPerBlock: u32 = 100;
+}
+
 impl pallet_xcm_transactor::Config for Runtime {
 	type Event = Event;
 	type Balance = Balance;
@@ -521,6 +626,12 @@ impl pallet_xcm_transactor::Config for Runtime {
 	type BaseXcmWeight = BaseXcmWeight;
 	type AssetTransactor = AssetTransactors;
 	type ReserveProvider = AbsoluteAndRelativeReserve<SelfLocationAbsolute>;
+	type QueryNotifier = PolkadotXcm;
+	type ResponseOrigin = pallet_xcm::EnsureResponse<Everything>;
+	type TransactStatusTimeout = TransactStatusTimeout;
+	type MaxTransactStatusQueriesPerBlock = MaxTransactStatusQueriesPerBlock;
+	type HrmpManipulatorOrigin = EnsureRoot<AccountId>;
+	type HrmpEncoder = moonbeam_relay_encoder::polkadot::PolkadotEncoder;
 	type WeightInfo = pallet_xcm_transactor::weights::SubstrateWeight<Runtime>;
 }
 
@@ -20,12 +20,20 @@ mod common;
 use common::*;
 
 use fp_evm::GenesisAccount;
-use nimbus_primitives::NimbusId;
+use frame_support::{
+	assert_ok,
+	storage::{with_transaction, TransactionOutcome},
+	traits::OnInitialize,
+};
+use moonbeam_runtime::{AuthorFilter, Randomness};
+use nimbus_primitives::{runtime_decl_for_NimbusApi::NimbusApi, NimbusId};
+use pallet_author_slot_filter::EligibilityValue;
 use pallet_evm::{Account as EVMAccount, AddressMapping, FeeCalculator};
 use sp_core::{ByteArray, H160, H256, U256};
 
 use fp_rpc::runtime_decl_for_EthereumRuntimeRPCApi::EthereumRuntimeRPCApi;
 use moonbeam_rpc_primitives_txpool::runtime_decl_for_TxPoolRuntimeApi::TxPoolRuntimeApi;
+use sp_runtime::DispatchError;
 use std::{collections::BTreeMap, str::FromStr};
 
 #[test]
@@ -297,3 +305,84 @@ fn txpool_runtime_api_extrinsic_filter() {
 		assert_eq!(txpool.future.len(), 1);
 	});
 }
+
+#[test]
+fn nimbus_runtime_api_can_author_matches_author_check_of_next_block() {
+	let collators = [ALICE, BOB, CHARLIE, DAVE];
+	let nimbus_id = |account: [u8; 20]| NimbusId::from_slice(&[account[0]; 32]).unwrap();
+	// Collators eligible in each of the slots, according to `can_author`
+	let eligible_authors = |can_author: &dyn Fn(NimbusId, u32) -> bool| {
+		(0..10u32)
+			.map(|slot| {
+				collators
+					.into_iter()
+					.filter(|collator| can_author(nimbus_id(*collator), slot))
+					.collect::<Vec<_>>()
+			})
+			.collect::<Vec<_>>()
+	};
+
+	ExtBuilder::default()
+		.with_balances(
+			collators
+				.iter()
+				.map(|collator| (AccountId::from(*collator), 200_000 * GLMR))
+				.collect(),
+		)
+		.with_collators(
+			collators
+				.iter()
+				.map(|collator| (AccountId::from(*collator), 100_000 * GLMR))
+				.collect(),
+		)
+		.with_mappings(
+			collators
+				.iter()
+				.map(|collator| (nimbus_id(*collator), AccountId::from(*collator)))
+				.collect(),
+		)
+		.build()
+		.execute_with(|| {
+			set_parachain_inherent_data();
+			run_to_block(2, Some(nimbus_id(ALICE)));
+			assert_ok!(AuthorFilter::set_eligible(
+				root_origin(),
+				EligibilityValue::new_unchecked(2)
+			));
+			pallet_randomness::PreviousLocalVrfOutput::<Runtime>::put(Some(H256::repeat_byte(1)));
+			pallet_randomness::LocalVrfOutput::<Runtime>::put(Some(H256::repeat_byte(2)));
+			let parent_header = System::finalize();
+
+			// Authors of the parent block, selected with the VRF output of its own parent
+			let parent_authors =
+				eligible_authors(&|author, slot| AuthorInherent::can_author(&author, &slot));
+			// Predicted by the node on the state of the parent block, before authoring
+			let predicted_authors = eligible_authors(&|author, slot| {
+				with_transaction(|| {
+					TransactionOutcome::Rollback(Ok::<_, DispatchError>(Runtime::can_author(
+						author,
+						slot,
+						&parent_header,
+					)))
+				})
+				.unwrap()
+			});
+
+			// The VRF output of the next block can't be verified without the VRF key of its
+			// author, so it is executed as a first block, which commits the VRF output of the
+			// parent block like any other block.
+			System::initialize(
+				&(parent_header.number + 1),
+				&parent_header.hash(),
+				&Default::default(),
+			);
+			pallet_randomness::NotFirstBlock::<Runtime>::kill();
+			Randomness::on_initialize(System::block_number());
+			let authors =
+				eligible_authors(&|author, slot| AuthorInherent::can_author(&author, &slot));
+
+			assert_eq!(predicted_authors, authors);
+			assert!(authors.iter().all(|eligible| eligible.len() == 2));
+			assert_ne!(parent_authors, authors);
+		});
+}
@@ -181,6 +181,7 @@ pub fn relay_ext() -> sp_io::TestExternalities {
 }
 
 pub type RelayChainPalletXcm = pallet_xcm::Pallet<relay_chain::Runtime>;
+pub type ParachainPalletXcm = pallet_xcm::Pallet<parachain::Runtime>;
 
 pub type StatemintBalances = pallet_balances::Pallet<statemint_like::Runtime>;
 pub type StatemintChainPalletXcm = pallet_xcm::Pallet<statemint_like::Runtime>;
@@ -337,7 +337,7 @@ impl Config for XcmConfig {
 	// units per second we should charge
 	type Trader = (
 		FixedRateOfFungible<ParaTokensPerSecond, ()>,
-		xcm_primitives::FirstAssetTrader<AssetType, AssetManager, XcmFeesToAccount_>,
+		xcm_primitives::MultiAssetTrader<AssetType, AssetManager, XcmFeesToAccount_>,
 	);
 	type ResponseHandler = PolkadotXcm;
 	type SubscriptionService = PolkadotXcm;
@@ -809,14 +809,21 @@ impl pallet_asset_manager::Config for Runtime {
 	type ForeignAssetType = AssetType;
 	type AssetRegistrar = AssetRegistrar;
 	type ForeignAssetModifierOrigin = EnsureRoot<AccountId>;
+	type SiblingAssetRegistrarOrigin = pallet_xcm::EnsureXcm<Everything>;
 	type LocalAssetModifierOrigin = EnsureRoot<AccountId>;
 	type LocalAssetIdCreator = LocalAssetIdCreator;
 	type AssetDestroyWitness = pallet_assets::DestroyWitness;
 	type Currency = Balances;
 	type LocalAssetDeposit = AssetDeposit;
+	type ForeignAssetDeposit = AssetDeposit;
 	type WeightInfo = ();
 }
 
+parameter_types! {
+	pub const TransactStatusTimeout: u64 = 100;
+	pub const MaxTransactStatusQueriesPerBlock: u32 = 100;
+}
+
 impl pallet_xcm_transactor::Config for Runtime {
 	type Event = Event;
 	type Balance = Balance;
@@ -834,6 +841,12 @@ impl pallet_xcm_transactor::Config for Runtime {
 	type BaseXcmWeight = BaseXcmWeight;
 	type AssetTransactor = AssetTransactors;
 	type ReserveProvider = xcm_primitives::AbsoluteAndRelativeReserve<SelfLocationAbsolute>;
+	type QueryNotifier = PolkadotXcm;
+	type ResponseOrigin = pallet_xcm::EnsureResponse<Everything>;
+	type TransactStatusTimeout = TransactStatusTimeout;
+	type MaxTransactStatusQueriesPerBlock = MaxTransactStatusQueriesPerBlock;
+	type HrmpManipulatorOrigin = EnsureRoot<AccountId>;
+	type HrmpEncoder = moonbeam_relay_encoder::polkadot::PolkadotEncoder;
 	type WeightInfo = ();
 }
 
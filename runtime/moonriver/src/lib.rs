@@ -318,7 +318,7 @@ impl WeightToFeePolynomial for LengthToFee {
 
 impl pallet_transaction_payment::Config for Runtime {
 	type Event = Event;
-	type OnChargeTransaction = CurrencyAdapter<Balances, DealWithFees<Runtime>>;
+	type OnChargeTransaction = OnChargeSubstrateTransaction<DealWithFees<Runtime>>;
 	type OperationalFeeMultiplier = ConstU8<5>;
 	type WeightToFee = ConstantMultiplier<Balance, ConstU128<{ currency::WEIGHT_FEE }>>;
 	type LengthToFee = LengthToFee;
@@ -648,6 +648,8 @@ impl pallet_ethereum::Config for Runtime {
 	type StateRoot = pallet_ethereum::IntermediateStateRoot<Self>;
 }
 
+impl pallet_ethereum_logs::Config for Runtime {}
+
 parameter_types! {
 	pub DefaultBaseFeePerGas: U256 = (1 * currency::GIGAWEI * currency::SUPPLY_FACTOR).into();
 }
@@ -758,7 +760,7 @@ impl pallet_author_inherent::Config for Runtime {
 
 impl pallet_author_slot_filter::Config for Runtime {
 	type Event = Event;
-	type RandomnessSource = RandomnessCollectiveFlip;
+	type RandomnessSource = pallet_randomness::PreviousLocalVrfRandomness<Runtime>;
 	type PotentialAuthors = ParachainStaking;
 	type WeightInfo = pallet_author_slot_filter::weights::SubstrateWeight<Runtime>;
 }
@@ -954,9 +956,17 @@ impl Contains<Call> for NormalFilter {
 			// We also disable destroy, as we want to route destroy through the
 			// asset-manager, which guarantees the removal both at the EVM and
 			// substrate side of things
+			// Ownership transfers go through the asset-manager too, which moves the
+			// asset deposit along with them
+			// Assets being destroyed through the asset-manager must stay frozen until their
+			// destruction is finished
 			Call::LocalAssets(method) => match method {
 				pallet_assets::Call::create { .. } => false,
 				pallet_assets::Call::destroy { .. } => false,
+				pallet_assets::Call::transfer_ownership { .. } => false,
+				pallet_assets::Call::thaw_asset { id } => {
+					AssetManager::local_asset_being_destroyed(id).is_none()
+				}
 				_ => true,
 			},
 			// We just want to enable this in case of live chains, since the default version
@@ -975,6 +985,7 @@ impl Contains<Call> for NormalFilter {
 			// We filter for now transact through signed
 			Call::XcmTransactor(method) => match method {
 				pallet_xcm_transactor::Call::transact_through_signed { .. } => false,
+				pallet_xcm_transactor::Call::transact_through_signed_with_options { .. } => false,
 				_ => true,
 			},
 			// We filter EVM calls as allowing these calls can cause potential attack vectors
@@ -1153,6 +1164,8 @@ impl pallet_randomness::Config for Runtime {
 	type MaxBlockDelay = ConstU32<2_000>;
 	type BlockExpirationDelay = ConstU32<10_000>;
 	type EpochExpirationDelay = ConstU64<10_000>;
+	type FulfillRequestsOnIdle = ConstBool<false>;
+	type FulfillmentExecutor = ();
 }
 
 construct_runtime! {
@@ -1223,6 +1236,10 @@ construct_runtime! {
 		XTokens: orml_xtokens::{Pallet, Call, Storage, Event<T>} = 106,
 		XcmTransactor: pallet_xcm_transactor::{Pallet, Call, Storage, Event<T>} = 107,
 		LocalAssets: pallet_assets::<Instance1>::{Pallet, Call, Storage, Event<T>} = 108,
+		XcmRateLimiter: pallet_xcm_rate_limiter::{Pallet, Call, Storage, Event<T>} = 109,
+		AssetPriceFeed: pallet_asset_price_feed::{Pallet, Call, Storage, Event<T>} = 110,
+		EthereumLogs: pallet_ethereum_logs::{Pallet, Storage} = 111,
+		TrappedAssets: pallet_trapped_assets::{Pallet, Storage} = 112,
 
 		// Randomness
 		Randomness: pallet_randomness::{Pallet, Call, Storage, Event<T>, Inherent} = 120,
@@ -16,7 +16,7 @@
 
 use crate::{
 	asset_config::{ForeignAssetInstance, LocalAssetInstance},
-	xcm_config::XcmExecutorConfig,
+	xcm_config::{XcmExecutorConfig, XcmUtilsExecuteFilter},
 	CouncilInstance, TechCommitteeInstance, TreasuryCouncilInstance,
 };
 use frame_support::parameter_types;
@@ -123,7 +123,10 @@ pub type MoonriverPrecompiles<R> = PrecompileSetBuilder<
 				PrecompileAt<AddressU64<2057>, RandomnessWrapper<R>>,
 				PrecompileAt<AddressU64<2058>, CallPermitPrecompile<R>>,
 				// PrecompileAt<AddressU64<2059>, ProxyWrapper<R>>, (Moonbase only)
-				PrecompileAt<AddressU64<2060>, XcmUtilsWrapper<R, XcmExecutorConfig>>,
+				PrecompileAt<
+					AddressU64<2060>,
+					XcmUtilsWrapper<R, XcmExecutorConfig, XcmUtilsExecuteFilter>,
+				>,
 				// PrecompileAt<AddressU64<2061>, XcmTransactorWrapperV2<R>>, (Moonbase only)
 				PrecompileAt<AddressU64<2062>, CollectivePrecompile<R, CouncilInstance>>,
 				PrecompileAt<AddressU64<2063>, CollectivePrecompile<R, TechCommitteeInstance>>,
@@ -42,13 +42,13 @@ use pallet_evm::PrecompileSet;
 use pallet_evm_precompile_batch::Action as BatchAction;
 use pallet_evm_precompile_crowdloan_rewards::Action as CrowdloanAction;
 use pallet_evm_precompile_xcm_transactor::v1::Action as XcmTransactorActionV1;
-use pallet_evm_precompile_xcm_utils::Action as XcmUtilsAction;
+use pallet_evm_precompile_xcm_utils::XcmUtilsWrapperCall;
 use pallet_evm_precompile_xtokens::Action as XtokensAction;
 use pallet_evm_precompileset_assets_erc20::{
 	AccountIdAssetIdConversion, Action as AssetAction, SELECTOR_LOG_APPROVAL, SELECTOR_LOG_TRANSFER,
 };
 use pallet_transaction_payment::Multiplier;
-use pallet_xcm_transactor::{Currency, CurrencyPayment, TransactWeights};
+use pallet_xcm_transactor::{Currency, CurrencyPayment, TransactOptions, TransactWeights};
 use parity_scale_codec::Encode;
 use polkadot_parachain::primitives::Sibling;
 use precompile_utils::{prelude::*, testing::*};
@@ -64,6 +64,12 @@ use xcm::{VersionedMultiAssets, VersionedMultiLocation};
 use xcm_builder::{ParentIsPreset, SiblingParachainConvertsVia};
 use xcm_executor::traits::Convert as XcmConvert;
 
+type XcmUtilsPCall = XcmUtilsWrapperCall<
+	Runtime,
+	moonriver_runtime::xcm_config::XcmExecutorConfig,
+	moonriver_runtime::xcm_config::XcmUtilsExecuteFilter,
+>;
+
 #[test]
 fn xcmp_queue_controller_origin_is_root() {
 	// important for the XcmExecutionManager impl of PauseExecution which uses root origin
@@ -1481,6 +1487,69 @@ fn local_assets_cannot_be_create_by_signed_origins() {
 		});
 }
 
+#[test]
+fn local_asset_details_mirror_pallet_assets_storage() {
+	use frame_support::{
+		storage::{storage_prefix, unhashed},
+		Blake2_128Concat,
+	};
+	use moonbeam_runtime_common::assets;
+
+	ExtBuilder::default()
+		.with_balances(vec![
+			(AccountId::from(ALICE), 1_000 * MOVR),
+			(AccountId::from(BOB), 1_000 * MOVR),
+		])
+		.with_local_assets(vec![(
+			0u128,
+			vec![(AccountId::from(BOB), 100)],
+			AccountId::from(ALICE),
+		)])
+		.build()
+		.execute_with(|| {
+			let asset_id = 0u128;
+			assert_ok!(LocalAssets::approve_transfer(
+				origin_of(AccountId::from(BOB)),
+				asset_id,
+				AccountId::from(CHARLIE),
+				10
+			));
+			assert_ok!(LocalAssets::freeze_asset(
+				origin_of(AccountId::from(ALICE)),
+				asset_id
+			));
+			assert_ok!(LocalAssets::set_team(
+				origin_of(AccountId::from(ALICE)),
+				asset_id,
+				AccountId::from(BOB),
+				AccountId::from(CHARLIE),
+				AccountId::from(DAVE)
+			));
+
+			let details =
+				assets::asset_details::<LocalAssets, AccountId, Balance, _>(asset_id).unwrap();
+			assert_eq!(details.owner, AccountId::from(ALICE));
+			assert_eq!(details.issuer, AccountId::from(BOB));
+			assert_eq!(details.admin, AccountId::from(CHARLIE));
+			assert_eq!(details.freezer, AccountId::from(DAVE));
+			assert_eq!(details.supply, LocalAssets::total_supply(asset_id));
+			assert_eq!(details.deposit, 0);
+			assert_eq!(details.min_balance, LocalAssets::minimum_balance(asset_id));
+			assert!(details.is_sufficient);
+			assert_eq!(details.accounts, 1);
+			assert_eq!(details.sufficients, 1);
+			assert_eq!(details.approvals, 1);
+			assert!(details.is_frozen);
+
+			// The mirror covers the whole of pallet_assets::AssetDetails
+			let key: Vec<u8> = storage_prefix(b"LocalAssets", b"Asset")
+				.into_iter()
+				.chain(Blake2_128Concat::hash(&asset_id.encode()))
+				.collect();
+			assert_eq!(unhashed::get_raw(&key), Some(details.encode()));
+		});
+}
+
 #[test]
 fn asset_erc20_precompiles_supply_and_balance() {
 	ExtBuilder::default()
@@ -1859,7 +1928,7 @@ fn asset_erc20_precompiles_freeze_transfer_ownership() {
 						.write(Address(BOB.into()))
 						.build(),
 				)
-				.expect_cost(6794)
+				.expect_cost(20000)
 				.expect_no_logs()
 				.execute_returns(EvmDataWriter::new().write(true).build());
 
@@ -2311,6 +2380,7 @@ fn transactor_cannot_use_more_than_max_weight() {
 			assert_ok!(XcmTransactor::register(
 				root_origin(),
 				AccountId::from(ALICE),
+				Box::new(xcm::VersionedMultiLocation::V1(MultiLocation::parent())),
 				0,
 			));
 
@@ -2553,6 +2623,32 @@ fn transact_through_signed_not_enabled() {
 				)),
 				frame_system::Error::<Runtime>::CallFiltered
 			);
+
+			// Neither with options
+			assert_noop!(
+				Call::XcmTransactor(
+					pallet_xcm_transactor::Call::<Runtime>::transact_through_signed_with_options {
+						dest: Box::new(xcm::VersionedMultiLocation::V1(MultiLocation::parent())),
+						fee: CurrencyPayment {
+							currency: Currency::AsCurrencyId(CurrencyId::ForeignAsset(source_id)),
+							fee_amount: None
+						},
+						call: vec![],
+						weight_info: TransactWeights {
+							transact_required_weight_at_most: 11000,
+							overall_weight: None
+						},
+						options: TransactOptions {
+							report_status: false,
+							refund: false,
+						}
+					}
+				)
+				.dispatch(<Runtime as frame_system::Config>::Origin::signed(
+					AccountId::from(ALICE)
+				)),
+				frame_system::Error::<Runtime>::CallFiltered
+			);
 		})
 }
 
@@ -2605,6 +2701,44 @@ fn call_xtokens_with_fee() {
 		});
 }
 
+#[test]
+fn xcm_utils_precompile_executes_messages_of_the_caller() {
+	ExtBuilder::default()
+		.with_balances(vec![(AccountId::from(ALICE), 1_000 * MOVR)])
+		.build()
+		.execute_with(|| {
+			let xcm_utils_precompile_address = H160::from_low_u64_be(2060);
+			let amount = 100 * MOVR;
+			let message: Xcm<()> = Xcm(vec![
+				WithdrawAsset((moonriver_runtime::xcm_config::SelfReserve::get(), amount).into()),
+				DepositAsset {
+					assets: Wild(All),
+					max_assets: 1,
+					beneficiary: AccountKey20 {
+						network: Any,
+						key: BOB,
+					}
+					.into(),
+				},
+			]);
+
+			// Executing messages through pallet-xcm is disabled, but not through the precompile
+			Precompiles::new()
+				.prepare_test(
+					ALICE,
+					xcm_utils_precompile_address,
+					EvmDataWriter::new_with_selector(XcmUtilsPCall::xcm_execute_selectors()[0])
+						.write(Bytes(xcm::VersionedXcm::<()>::from(message).encode()))
+						.write(u64::MAX)
+						.build(),
+				)
+				.expect_no_logs()
+				.execute_returns(vec![]);
+
+			assert_eq!(Balances::free_balance(AccountId::from(BOB)), amount);
+		});
+}
+
 #[test]
 fn test_xcm_utils_ml_to_account() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -2618,9 +2752,10 @@ fn test_xcm_utils_ml_to_account() {
 			.prepare_test(
 				ALICE,
 				xcm_utils_precompile_address,
-				EvmDataWriter::new_with_selector(XcmUtilsAction::MultiLocationToAddress)
-					.write(MultiLocation::parent())
-					.build(),
+				XcmUtilsPCall::multilocation_to_address {
+					multilocation: MultiLocation::parent(),
+				}
+				.into(),
 			)
 			.expect_cost(1000)
 			.expect_no_logs()
@@ -2642,9 +2777,10 @@ fn test_xcm_utils_ml_to_account() {
 			.prepare_test(
 				ALICE,
 				xcm_utils_precompile_address,
-				EvmDataWriter::new_with_selector(XcmUtilsAction::MultiLocationToAddress)
-					.write(parachain_2000_multilocation)
-					.build(),
+				XcmUtilsPCall::multilocation_to_address {
+					multilocation: parachain_2000_multilocation,
+				}
+				.into(),
 			)
 			.expect_cost(1000)
 			.expect_no_logs()
@@ -2670,9 +2806,10 @@ fn test_xcm_utils_ml_to_account() {
 			.prepare_test(
 				ALICE,
 				xcm_utils_precompile_address,
-				EvmDataWriter::new_with_selector(XcmUtilsAction::MultiLocationToAddress)
-					.write(alice_in_parachain_2000_multilocation)
-					.build(),
+				XcmUtilsPCall::multilocation_to_address {
+					multilocation: alice_in_parachain_2000_multilocation,
+				}
+				.into(),
 			)
 			.expect_cost(1000)
 			.expect_no_logs()
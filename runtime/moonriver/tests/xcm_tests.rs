@@ -914,6 +914,82 @@ fn send_para_a_asset_to_para_b_with_trader_and_fee() {
 	});
 }
 
+#[test]
+fn receive_asset_with_trader_buying_execution_twice() {
+	MockNet::reset();
+
+	let para_b_balances = MultiLocation::new(1, X2(Parachain(2), PalletInstance(1u8)));
+	let source_location = parachain::AssetType::Xcm(para_b_balances.clone());
+	let source_id: parachain::AssetId = source_location.clone().into();
+
+	let asset_metadata = parachain::AssetMetadata {
+		name: b"ParaBToken".to_vec(),
+		symbol: b"ParaB".to_vec(),
+		decimals: 18,
+	};
+
+	// With these units per second, 5 weight costs 10 asset units
+	ParaA::execute_with(|| {
+		assert_ok!(AssetManager::register_foreign_asset(
+			parachain::Origin::root(),
+			source_location.clone(),
+			asset_metadata,
+			1u128,
+			true
+		));
+		assert_ok!(AssetManager::set_asset_units_per_second(
+			parachain::Origin::root(),
+			source_location,
+			2000000000000u128,
+			0
+		));
+	});
+
+	let dest = MultiLocation {
+		parents: 1,
+		interior: X1(Parachain(1)),
+	};
+
+	// The message pays for its execution twice.
+	// Weight is 5 since we are executing 5 instructions with a unitweightcost of 1.
+	let message = xcm::VersionedXcm::<()>::V2(Xcm(vec![
+		ReserveAssetDeposited((para_b_balances.clone(), 100).into()),
+		ClearOrigin,
+		BuyExecution {
+			fees: (para_b_balances.clone(), 50).into(),
+			weight_limit: Limited(5),
+		},
+		BuyExecution {
+			fees: (para_b_balances, 50).into(),
+			weight_limit: Limited(5),
+		},
+		DepositAsset {
+			assets: All.into(),
+			max_assets: 1,
+			beneficiary: MultiLocation::new(
+				0,
+				X1(AccountKey20 {
+					network: Any,
+					key: PARAALICE,
+				}),
+			),
+		},
+	]));
+	ParaB::execute_with(|| {
+		assert_ok!(ParachainPalletXcm::send(
+			parachain::Origin::root(),
+			Box::new(dest.into()),
+			Box::new(message),
+		));
+	});
+
+	ParaA::execute_with(|| {
+		// Both purchases of 10 asset units went to the treasury
+		assert_eq!(Assets::balance(source_id, &PARAALICE.into()), 80);
+		assert_eq!(Assets::balance(source_id, &Treasury::account_id()), 20);
+	});
+}
+
 #[test]
 fn error_when_not_paying_enough() {
 	MockNet::reset();
@@ -1043,6 +1119,7 @@ fn transact_through_derivative_multilocation() {
 		assert_ok!(XcmTransactor::register(
 			parachain::Origin::root(),
 			PARAALICE.into(),
+			Box::new(xcm::VersionedMultiLocation::V1(MultiLocation::parent())),
 			0,
 		));
 	});
@@ -1184,6 +1261,7 @@ fn transact_through_derivative_with_custom_fee_weight() {
 		assert_ok!(XcmTransactor::register(
 			parachain::Origin::root(),
 			PARAALICE.into(),
+			Box::new(xcm::VersionedMultiLocation::V1(MultiLocation::parent())),
 			0,
 		));
 	});
@@ -1351,6 +1429,7 @@ fn transact_through_sovereign() {
 		assert_ok!(XcmTransactor::register(
 			parachain::Origin::root(),
 			PARAALICE.into(),
+			Box::new(xcm::VersionedMultiLocation::V1(MultiLocation::parent())),
 			0,
 		));
 	});
@@ -1500,6 +1579,7 @@ fn transact_through_sovereign_with_custom_fee_weight() {
 		assert_ok!(XcmTransactor::register(
 			parachain::Origin::root(),
 			PARAALICE.into(),
+			Box::new(xcm::VersionedMultiLocation::V1(MultiLocation::parent())),
 			0,
 		));
 	});
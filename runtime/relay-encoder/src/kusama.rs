@@ -17,7 +17,11 @@
 // We want to avoid including the rococo-runtime here.
 // TODO: whenever a conclusion is taken from https://github.com/paritytech/substrate/issues/8158
 
-use parity_scale_codec::{Decode, Encode};
+use cumulus_primitives_core::{
+	relay_chain::{Balance, BlockNumber, HrmpChannelId},
+	ParaId,
+};
+use parity_scale_codec::{Compact, Decode, Encode};
 use sp_runtime::traits::{AccountIdLookup, StaticLookup};
 use sp_runtime::AccountId32;
 use sp_std::vec::Vec;
@@ -29,6 +33,14 @@ pub enum RelayCall {
 	#[codec(index = 24u8)]
 	// the index should match the position of the module in `construct_runtime!`
 	Utility(UtilityCall),
+	#[codec(index = 60u8)]
+	Hrmp(HrmpCall),
+
+	#[codec(index = 30u8)]
+	Proxy(ProxyCall),
+
+	#[codec(index = 41u8)]
+	NominationPools(NominationPoolsCall),
 }
 
 // Utility call encoding, needed for xcm transactor pallet
@@ -36,6 +48,49 @@ pub enum RelayCall {
 pub enum UtilityCall {
 	#[codec(index = 1u8)]
 	AsDerivative(u16),
+	// Only the length prefix of the calls vector, the calls are appended already encoded
+	#[codec(index = 2u8)]
+	BatchAll(Compact<u32>),
+}
+
+#[derive(Encode, Decode)]
+pub enum ProxyCall {
+	// The proxied call is appended already encoded
+	#[codec(index = 0u8)]
+	Proxy(AccountId32, Option<u8>),
+	#[codec(index = 1u8)]
+	AddProxy(AccountId32, u8, BlockNumber),
+}
+
+#[derive(Encode, Decode)]
+pub enum PoolBondExtra {
+	#[codec(index = 0u8)]
+	FreeBalance(Balance),
+	#[codec(index = 1u8)]
+	Rewards,
+}
+
+#[derive(Encode, Decode)]
+pub enum NominationPoolsCall {
+	#[codec(index = 0u8)]
+	Join(#[codec(compact)] Balance, u32),
+	#[codec(index = 1u8)]
+	BondExtra(PoolBondExtra),
+	#[codec(index = 2u8)]
+	ClaimPayout,
+	#[codec(index = 3u8)]
+	Unbond(AccountId32, #[codec(compact)] Balance),
+}
+
+// Hrmp call encoding, needed for xcm transactor pallet
+#[derive(Encode, Decode)]
+pub enum HrmpCall {
+	#[codec(index = 0u8)]
+	InitOpenChannel(ParaId, u32, u32),
+	#[codec(index = 1u8)]
+	AcceptOpenChannel(ParaId),
+	#[codec(index = 2u8)]
+	CloseChannel(HrmpChannelId),
 }
 
 #[derive(Encode, Decode)]
@@ -63,6 +118,8 @@ pub enum StakeCall {
 	SetPayee(pallet_staking::RewardDestination<AccountId32>),
 	#[codec(index = 8u16)]
 	SetController(<AccountIdLookup<AccountId32, ()> as StaticLookup>::Source),
+	#[codec(index = 18u16)]
+	PayoutStakers(AccountId32, u32),
 	#[codec(index = 19u16)]
 	Rebond(#[codec(compact)] cumulus_primitives_core::relay_chain::Balance),
 }
@@ -83,6 +140,22 @@ impl xcm_primitives::UtilityEncodeCall for KusamaEncoder {
 	}
 }
 
+impl xcm_primitives::HrmpEncodeCall for KusamaEncoder {
+	fn hrmp_encode_call(call: xcm_primitives::HrmpAvailableCalls) -> Vec<u8> {
+		match call {
+			xcm_primitives::HrmpAvailableCalls::InitOpenChannel(a, b, c) => {
+				RelayCall::Hrmp(HrmpCall::InitOpenChannel(a, b, c)).encode()
+			}
+			xcm_primitives::HrmpAvailableCalls::AcceptOpenChannel(a) => {
+				RelayCall::Hrmp(HrmpCall::AcceptOpenChannel(a)).encode()
+			}
+			xcm_primitives::HrmpAvailableCalls::CloseChannel(a) => {
+				RelayCall::Hrmp(HrmpCall::CloseChannel(a)).encode()
+			}
+		}
+	}
+}
+
 impl pallet_evm_precompile_relay_encoder::StakeEncodeCall for KusamaEncoder {
 	fn encode_call(call: pallet_evm_precompile_relay_encoder::AvailableStakeCalls) -> Vec<u8> {
 		match call {
@@ -128,17 +201,97 @@ impl pallet_evm_precompile_relay_encoder::StakeEncodeCall for KusamaEncoder {
 
 				RelayCall::Stake(StakeCall::Nominate(nominated)).encode()
 			}
+
+			pallet_evm_precompile_relay_encoder::AvailableStakeCalls::PayoutStakers(a, b) => {
+				RelayCall::Stake(StakeCall::PayoutStakers(a, b)).encode()
+			}
 		}
 	}
 }
 
+impl pallet_evm_precompile_relay_encoder::UtilityEncodeCall for KusamaEncoder {
+	fn utility_encode_call(
+		call: pallet_evm_precompile_relay_encoder::AvailableUtilityCalls,
+	) -> Vec<u8> {
+		match call {
+			pallet_evm_precompile_relay_encoder::AvailableUtilityCalls::BatchAll(calls) => {
+				let mut encoded =
+					RelayCall::Utility(UtilityCall::BatchAll((calls.len() as u32).into())).encode();
+				for mut call in calls {
+					encoded.append(&mut call);
+				}
+				encoded
+			}
+
+			pallet_evm_precompile_relay_encoder::AvailableUtilityCalls::AsDerivative(a, mut b) => {
+				let mut encoded = RelayCall::Utility(UtilityCall::AsDerivative(a)).encode();
+				encoded.append(&mut b);
+				encoded
+			}
+		}
+	}
+}
+
+impl pallet_evm_precompile_relay_encoder::ProxyEncodeCall for KusamaEncoder {
+	fn proxy_encode_call(
+		call: pallet_evm_precompile_relay_encoder::AvailableProxyCalls,
+	) -> Vec<u8> {
+		match call {
+			pallet_evm_precompile_relay_encoder::AvailableProxyCalls::AddProxy(a, b, c) => {
+				RelayCall::Proxy(ProxyCall::AddProxy(a, b, c)).encode()
+			}
+
+			pallet_evm_precompile_relay_encoder::AvailableProxyCalls::Proxy(a, b, mut c) => {
+				let mut encoded = RelayCall::Proxy(ProxyCall::Proxy(a, b)).encode();
+				encoded.append(&mut c);
+				encoded
+			}
+		}
+	}
+}
+
+impl pallet_evm_precompile_relay_encoder::NominationPoolsEncodeCall for KusamaEncoder {
+	fn nomination_pools_encode_call(
+		call: pallet_evm_precompile_relay_encoder::AvailableNominationPoolsCalls,
+	) -> Option<Vec<u8>> {
+		Some(match call {
+			pallet_evm_precompile_relay_encoder::AvailableNominationPoolsCalls::Join(a, b) => {
+				RelayCall::NominationPools(NominationPoolsCall::Join(a, b)).encode()
+			}
+
+			pallet_evm_precompile_relay_encoder::AvailableNominationPoolsCalls::BondExtraFreeBalance(
+				a,
+			) => RelayCall::NominationPools(NominationPoolsCall::BondExtra(
+				PoolBondExtra::FreeBalance(a),
+			))
+			.encode(),
+
+			pallet_evm_precompile_relay_encoder::AvailableNominationPoolsCalls::BondExtraRewards => {
+				RelayCall::NominationPools(NominationPoolsCall::BondExtra(PoolBondExtra::Rewards))
+					.encode()
+			}
+
+			pallet_evm_precompile_relay_encoder::AvailableNominationPoolsCalls::Unbond(a, b) => {
+				RelayCall::NominationPools(NominationPoolsCall::Unbond(a, b)).encode()
+			}
+
+			pallet_evm_precompile_relay_encoder::AvailableNominationPoolsCalls::ClaimPayout => {
+				RelayCall::NominationPools(NominationPoolsCall::ClaimPayout).encode()
+			}
+		})
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
 	use crate::kusama::KusamaEncoder;
 	use frame_support::traits::PalletInfo;
-	use pallet_evm_precompile_relay_encoder::StakeEncodeCall;
+	use pallet_evm_precompile_relay_encoder::{
+		NominationPoolsEncodeCall, ProxyEncodeCall, StakeEncodeCall, UtilityEncodeCall,
+	};
 	use sp_runtime::Perbill;
+	use xcm_primitives::HrmpEncodeCall;
 
 	#[test]
 	fn test_as_derivative() {
@@ -423,4 +576,369 @@ mod tests {
 			expected_encoded
 		);
 	}
+
+	#[test]
+	fn test_hrmp_init() {
+		let mut expected_encoded: Vec<u8> = Vec::new();
+
+		let index = <kusama_runtime::Runtime as frame_system::Config>::PalletInfo::index::<
+			kusama_runtime::Hrmp,
+		>()
+		.unwrap() as u8;
+		expected_encoded.push(index);
+
+		let mut expected = polkadot_runtime_parachains::hrmp::Call::<
+			kusama_runtime::Runtime
+		>::hrmp_init_open_channel {
+			recipient: 1000u32.into(),
+			proposed_max_capacity: 100u32,
+			proposed_max_message_size: 100u32,
+		}
+		.encode();
+		expected_encoded.append(&mut expected);
+
+		assert_eq!(
+			<KusamaEncoder as HrmpEncodeCall>::hrmp_encode_call(
+				xcm_primitives::HrmpAvailableCalls::InitOpenChannel(1000u32.into(), 100u32, 100u32)
+			),
+			expected_encoded
+		);
+	}
+
+	#[test]
+	fn test_hrmp_accept() {
+		let mut expected_encoded: Vec<u8> = Vec::new();
+
+		let index = <kusama_runtime::Runtime as frame_system::Config>::PalletInfo::index::<
+			kusama_runtime::Hrmp,
+		>()
+		.unwrap() as u8;
+		expected_encoded.push(index);
+
+		let mut expected = polkadot_runtime_parachains::hrmp::Call::<
+			kusama_runtime::Runtime
+		>::hrmp_accept_open_channel {
+			sender: 1000u32.into(),
+		}
+		.encode();
+		expected_encoded.append(&mut expected);
+
+		assert_eq!(
+			<KusamaEncoder as HrmpEncodeCall>::hrmp_encode_call(
+				xcm_primitives::HrmpAvailableCalls::AcceptOpenChannel(1000u32.into())
+			),
+			expected_encoded
+		);
+	}
+
+	#[test]
+	fn test_hrmp_close() {
+		let mut expected_encoded: Vec<u8> = Vec::new();
+
+		let index = <kusama_runtime::Runtime as frame_system::Config>::PalletInfo::index::<
+			kusama_runtime::Hrmp,
+		>()
+		.unwrap() as u8;
+		expected_encoded.push(index);
+
+		let channel_id = HrmpChannelId {
+			sender: 1000u32.into(),
+			recipient: 1001u32.into(),
+		};
+
+		let mut expected = polkadot_runtime_parachains::hrmp::Call::<
+			kusama_runtime::Runtime
+		>::hrmp_close_channel {
+			channel_id: channel_id.clone(),
+		}
+		.encode();
+		expected_encoded.append(&mut expected);
+
+		assert_eq!(
+			<KusamaEncoder as HrmpEncodeCall>::hrmp_encode_call(
+				xcm_primitives::HrmpAvailableCalls::CloseChannel(channel_id)
+			),
+			expected_encoded
+		);
+	}
+
+	#[test]
+	fn test_payout_stakers() {
+		let mut expected_encoded: Vec<u8> = Vec::new();
+		let relay_account: AccountId32 = [1u8; 32].into();
+
+		let index = <kusama_runtime::Runtime as frame_system::Config>::PalletInfo::index::<
+			kusama_runtime::Staking,
+		>()
+		.unwrap() as u8;
+		expected_encoded.push(index);
+
+		let mut expected = pallet_staking::Call::<kusama_runtime::Runtime>::payout_stakers {
+			validator_stash: relay_account.clone(),
+			era: 10u32,
+		}
+		.encode();
+		expected_encoded.append(&mut expected);
+
+		assert_eq!(
+			<KusamaEncoder as StakeEncodeCall>::encode_call(
+				pallet_evm_precompile_relay_encoder::AvailableStakeCalls::PayoutStakers(
+					relay_account,
+					10u32
+				)
+			),
+			expected_encoded
+		);
+	}
+
+	#[test]
+	fn test_batch_all() {
+		let mut expected_encoded: Vec<u8> = Vec::new();
+		let index = <kusama_runtime::Runtime as frame_system::Config>::PalletInfo::index::<
+			kusama_runtime::Utility,
+		>()
+		.unwrap() as u8;
+		expected_encoded.push(index);
+
+		let chill = kusama_runtime::Call::Staking(
+			pallet_staking::Call::<kusama_runtime::Runtime>::chill {},
+		);
+		let rebond = kusama_runtime::Call::Staking(
+			pallet_staking::Call::<kusama_runtime::Runtime>::rebond {
+				value: 100u32.into(),
+			},
+		);
+		let mut expected = pallet_utility::Call::<kusama_runtime::Runtime>::batch_all {
+			calls: vec![chill.clone(), rebond.clone()],
+		}
+		.encode();
+		expected_encoded.append(&mut expected);
+
+		assert_eq!(
+			<KusamaEncoder as UtilityEncodeCall>::utility_encode_call(
+				pallet_evm_precompile_relay_encoder::AvailableUtilityCalls::BatchAll(vec![
+					chill.encode(),
+					rebond.encode()
+				])
+			),
+			expected_encoded
+		);
+	}
+
+	#[test]
+	fn test_utility_as_derivative() {
+		let mut expected_encoded: Vec<u8> = Vec::new();
+		let index = <kusama_runtime::Runtime as frame_system::Config>::PalletInfo::index::<
+			kusama_runtime::Utility,
+		>()
+		.unwrap() as u8;
+		expected_encoded.push(index);
+
+		let chill = kusama_runtime::Call::Staking(
+			pallet_staking::Call::<kusama_runtime::Runtime>::chill {},
+		);
+		let mut expected = pallet_utility::Call::<kusama_runtime::Runtime>::as_derivative {
+			index: 1,
+			call: chill.clone().into(),
+		}
+		.encode();
+		expected_encoded.append(&mut expected);
+
+		assert_eq!(
+			<KusamaEncoder as UtilityEncodeCall>::utility_encode_call(
+				pallet_evm_precompile_relay_encoder::AvailableUtilityCalls::AsDerivative(
+					1,
+					chill.encode()
+				)
+			),
+			expected_encoded
+		);
+	}
+
+	#[test]
+	fn test_add_proxy() {
+		let mut expected_encoded: Vec<u8> = Vec::new();
+		let relay_account: AccountId32 = [1u8; 32].into();
+		let index = <kusama_runtime::Runtime as frame_system::Config>::PalletInfo::index::<
+			kusama_runtime::Proxy,
+		>()
+		.unwrap() as u8;
+		expected_encoded.push(index);
+
+		let mut expected = pallet_proxy::Call::<kusama_runtime::Runtime>::add_proxy {
+			delegate: relay_account.clone(),
+			proxy_type: kusama_runtime::ProxyType::Staking,
+			delay: 0u32,
+		}
+		.encode();
+		expected_encoded.append(&mut expected);
+
+		assert_eq!(
+			<KusamaEncoder as ProxyEncodeCall>::proxy_encode_call(
+				pallet_evm_precompile_relay_encoder::AvailableProxyCalls::AddProxy(
+					relay_account,
+					kusama_runtime::ProxyType::Staking.encode()[0],
+					0u32
+				)
+			),
+			expected_encoded
+		);
+	}
+
+	#[test]
+	fn test_proxy() {
+		let mut expected_encoded: Vec<u8> = Vec::new();
+		let relay_account: AccountId32 = [1u8; 32].into();
+		let index = <kusama_runtime::Runtime as frame_system::Config>::PalletInfo::index::<
+			kusama_runtime::Proxy,
+		>()
+		.unwrap() as u8;
+		expected_encoded.push(index);
+
+		let chill = kusama_runtime::Call::Staking(
+			pallet_staking::Call::<kusama_runtime::Runtime>::chill {},
+		);
+		let mut expected = pallet_proxy::Call::<kusama_runtime::Runtime>::proxy {
+			real: relay_account.clone(),
+			force_proxy_type: Some(kusama_runtime::ProxyType::Staking),
+			call: chill.clone().into(),
+		}
+		.encode();
+		expected_encoded.append(&mut expected);
+
+		assert_eq!(
+			<KusamaEncoder as ProxyEncodeCall>::proxy_encode_call(
+				pallet_evm_precompile_relay_encoder::AvailableProxyCalls::Proxy(
+					relay_account,
+					Some(kusama_runtime::ProxyType::Staking.encode()[0]),
+					chill.encode()
+				)
+			),
+			expected_encoded
+		);
+	}
+
+	#[test]
+	fn test_pool_join() {
+		let mut expected_encoded: Vec<u8> = Vec::new();
+		let index = <kusama_runtime::Runtime as frame_system::Config>::PalletInfo::index::<
+			kusama_runtime::NominationPools,
+		>()
+		.unwrap() as u8;
+		expected_encoded.push(index);
+
+		let mut expected = pallet_nomination_pools::Call::<kusama_runtime::Runtime>::join {
+			amount: 100u32.into(),
+			pool_id: 1u32,
+		}
+		.encode();
+		expected_encoded.append(&mut expected);
+
+		assert_eq!(
+			<KusamaEncoder as NominationPoolsEncodeCall>::nomination_pools_encode_call(
+				pallet_evm_precompile_relay_encoder::AvailableNominationPoolsCalls::Join(
+					100u32.into(),
+					1u32
+				)
+			),
+			Some(expected_encoded)
+		);
+	}
+
+	#[test]
+	fn test_pool_bond_extra() {
+		let mut expected_encoded: Vec<u8> = Vec::new();
+		let index = <kusama_runtime::Runtime as frame_system::Config>::PalletInfo::index::<
+			kusama_runtime::NominationPools,
+		>()
+		.unwrap() as u8;
+		expected_encoded.push(index);
+
+		let mut expected = pallet_nomination_pools::Call::<kusama_runtime::Runtime>::bond_extra {
+			extra: pallet_nomination_pools::BondExtra::FreeBalance(100u32.into()),
+		}
+		.encode();
+		expected_encoded.append(&mut expected);
+
+		assert_eq!(
+			<KusamaEncoder as NominationPoolsEncodeCall>::nomination_pools_encode_call(
+				pallet_evm_precompile_relay_encoder::AvailableNominationPoolsCalls::BondExtraFreeBalance(
+					100u32.into()
+				)
+			),
+			Some(expected_encoded)
+		);
+	}
+
+	#[test]
+	fn test_pool_bond_extra_rewards() {
+		let mut expected_encoded: Vec<u8> = Vec::new();
+		let index = <kusama_runtime::Runtime as frame_system::Config>::PalletInfo::index::<
+			kusama_runtime::NominationPools,
+		>()
+		.unwrap() as u8;
+		expected_encoded.push(index);
+
+		let mut expected = pallet_nomination_pools::Call::<kusama_runtime::Runtime>::bond_extra {
+			extra: pallet_nomination_pools::BondExtra::Rewards,
+		}
+		.encode();
+		expected_encoded.append(&mut expected);
+
+		assert_eq!(
+			<KusamaEncoder as NominationPoolsEncodeCall>::nomination_pools_encode_call(
+				pallet_evm_precompile_relay_encoder::AvailableNominationPoolsCalls::BondExtraRewards
+			),
+			Some(expected_encoded)
+		);
+	}
+
+	#[test]
+	fn test_pool_unbond() {
+		let mut expected_encoded: Vec<u8> = Vec::new();
+		let relay_account: AccountId32 = [1u8; 32].into();
+		let index = <kusama_runtime::Runtime as frame_system::Config>::PalletInfo::index::<
+			kusama_runtime::NominationPools,
+		>()
+		.unwrap() as u8;
+		expected_encoded.push(index);
+
+		let mut expected = pallet_nomination_pools::Call::<kusama_runtime::Runtime>::unbond {
+			member_account: relay_account.clone(),
+			unbonding_points: 100u32.into(),
+		}
+		.encode();
+		expected_encoded.append(&mut expected);
+
+		assert_eq!(
+			<KusamaEncoder as NominationPoolsEncodeCall>::nomination_pools_encode_call(
+				pallet_evm_precompile_relay_encoder::AvailableNominationPoolsCalls::Unbond(
+					relay_account,
+					100u32.into()
+				)
+			),
+			Some(expected_encoded)
+		);
+	}
+
+	#[test]
+	fn test_pool_claim_payout() {
+		let mut expected_encoded: Vec<u8> = Vec::new();
+		let index = <kusama_runtime::Runtime as frame_system::Config>::PalletInfo::index::<
+			kusama_runtime::NominationPools,
+		>()
+		.unwrap() as u8;
+		expected_encoded.push(index);
+
+		let mut expected =
+			pallet_nomination_pools::Call::<kusama_runtime::Runtime>::claim_payout {}.encode();
+		expected_encoded.append(&mut expected);
+
+		assert_eq!(
+			<KusamaEncoder as NominationPoolsEncodeCall>::nomination_pools_encode_call(
+				pallet_evm_precompile_relay_encoder::AvailableNominationPoolsCalls::ClaimPayout
+			),
+			Some(expected_encoded)
+		);
+	}
 }
@@ -17,7 +17,11 @@
 // We want to avoid including the rococo-runtime here.
 // TODO: whenever a conclusion is taken from https://github.com/paritytech/substrate/issues/8158
 
-use parity_scale_codec::{Decode, Encode};
+use cumulus_primitives_core::{
+	relay_chain::{Balance, BlockNumber, HrmpChannelId},
+	ParaId,
+};
+use parity_scale_codec::{Compact, Decode, Encode};
 use sp_runtime::traits::{AccountIdLookup, StaticLookup};
 use sp_runtime::AccountId32;
 use sp_std::vec::Vec;
@@ -30,6 +34,12 @@ pub enum RelayCall {
 
 	#[codec(index = 7u8)]
 	Stake(StakeCall),
+
+	#[codec(index = 60u8)]
+	Hrmp(HrmpCall),
+
+	#[codec(index = 29u8)]
+	Proxy(ProxyCall),
 }
 
 // Utility call encoding, needed for xcm transactor pallet
@@ -38,6 +48,29 @@ pub enum RelayCall {
 pub enum UtilityCall {
 	#[codec(index = 1u8)]
 	AsDerivative(u16),
+	// Only the length prefix of the calls vector, the calls are appended already encoded
+	#[codec(index = 2u8)]
+	BatchAll(Compact<u32>),
+}
+
+#[derive(Encode, Decode)]
+pub enum ProxyCall {
+	// The proxied call is appended already encoded
+	#[codec(index = 0u8)]
+	Proxy(AccountId32, Option<u8>),
+	#[codec(index = 1u8)]
+	AddProxy(AccountId32, u8, BlockNumber),
+}
+
+// Hrmp call encoding, needed for xcm transactor pallet
+#[derive(Encode, Decode)]
+pub enum HrmpCall {
+	#[codec(index = 0u8)]
+	InitOpenChannel(ParaId, u32, u32),
+	#[codec(index = 1u8)]
+	AcceptOpenChannel(ParaId),
+	#[codec(index = 2u8)]
+	CloseChannel(HrmpChannelId),
 }
 
 #[derive(Encode, Decode)]
@@ -65,6 +98,8 @@ pub enum StakeCall {
 	SetPayee(pallet_staking::RewardDestination<AccountId32>),
 	#[codec(index = 8u16)]
 	SetController(<AccountIdLookup<AccountId32, ()> as StaticLookup>::Source),
+	#[codec(index = 18u16)]
+	PayoutStakers(AccountId32, u32),
 	#[codec(index = 19u16)]
 	Rebond(#[codec(compact)] cumulus_primitives_core::relay_chain::Balance),
 }
@@ -85,6 +120,22 @@ impl xcm_primitives::UtilityEncodeCall for PolkadotEncoder {
 	}
 }
 
+impl xcm_primitives::HrmpEncodeCall for PolkadotEncoder {
+	fn hrmp_encode_call(call: xcm_primitives::HrmpAvailableCalls) -> Vec<u8> {
+		match call {
+			xcm_primitives::HrmpAvailableCalls::InitOpenChannel(a, b, c) => {
+				RelayCall::Hrmp(HrmpCall::InitOpenChannel(a, b, c)).encode()
+			}
+			xcm_primitives::HrmpAvailableCalls::AcceptOpenChannel(a) => {
+				RelayCall::Hrmp(HrmpCall::AcceptOpenChannel(a)).encode()
+			}
+			xcm_primitives::HrmpAvailableCalls::CloseChannel(a) => {
+				RelayCall::Hrmp(HrmpCall::CloseChannel(a)).encode()
+			}
+		}
+	}
+}
+
 impl pallet_evm_precompile_relay_encoder::StakeEncodeCall for PolkadotEncoder {
 	fn encode_call(call: pallet_evm_precompile_relay_encoder::AvailableStakeCalls) -> Vec<u8> {
 		match call {
@@ -130,17 +181,74 @@ impl pallet_evm_precompile_relay_encoder::StakeEncodeCall for PolkadotEncoder {
 
 				RelayCall::Stake(StakeCall::Nominate(nominated)).encode()
 			}
+
+			pallet_evm_precompile_relay_encoder::AvailableStakeCalls::PayoutStakers(a, b) => {
+				RelayCall::Stake(StakeCall::PayoutStakers(a, b)).encode()
+			}
 		}
 	}
 }
 
+impl pallet_evm_precompile_relay_encoder::UtilityEncodeCall for PolkadotEncoder {
+	fn utility_encode_call(
+		call: pallet_evm_precompile_relay_encoder::AvailableUtilityCalls,
+	) -> Vec<u8> {
+		match call {
+			pallet_evm_precompile_relay_encoder::AvailableUtilityCalls::BatchAll(calls) => {
+				let mut encoded =
+					RelayCall::Utility(UtilityCall::BatchAll((calls.len() as u32).into())).encode();
+				for mut call in calls {
+					encoded.append(&mut call);
+				}
+				encoded
+			}
+
+			pallet_evm_precompile_relay_encoder::AvailableUtilityCalls::AsDerivative(a, mut b) => {
+				let mut encoded = RelayCall::Utility(UtilityCall::AsDerivative(a)).encode();
+				encoded.append(&mut b);
+				encoded
+			}
+		}
+	}
+}
+
+impl pallet_evm_precompile_relay_encoder::ProxyEncodeCall for PolkadotEncoder {
+	fn proxy_encode_call(
+		call: pallet_evm_precompile_relay_encoder::AvailableProxyCalls,
+	) -> Vec<u8> {
+		match call {
+			pallet_evm_precompile_relay_encoder::AvailableProxyCalls::AddProxy(a, b, c) => {
+				RelayCall::Proxy(ProxyCall::AddProxy(a, b, c)).encode()
+			}
+
+			pallet_evm_precompile_relay_encoder::AvailableProxyCalls::Proxy(a, b, mut c) => {
+				let mut encoded = RelayCall::Proxy(ProxyCall::Proxy(a, b)).encode();
+				encoded.append(&mut c);
+				encoded
+			}
+		}
+	}
+}
+
+impl pallet_evm_precompile_relay_encoder::NominationPoolsEncodeCall for PolkadotEncoder {
+	// Nomination pools are not yet available in Polkadot
+	fn nomination_pools_encode_call(
+		_call: pallet_evm_precompile_relay_encoder::AvailableNominationPoolsCalls,
+	) -> Option<Vec<u8>> {
+		None
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
 	use crate::polkadot::PolkadotEncoder;
 	use frame_support::traits::PalletInfo;
-	use pallet_evm_precompile_relay_encoder::StakeEncodeCall;
+	use pallet_evm_precompile_relay_encoder::{
+		NominationPoolsEncodeCall, ProxyEncodeCall, StakeEncodeCall, UtilityEncodeCall,
+	};
 	use sp_runtime::Perbill;
+	use xcm_primitives::HrmpEncodeCall;
 
 	#[test]
 	fn test_as_derivative() {
@@ -425,4 +533,255 @@ mod tests {
 			expected_encoded
 		);
 	}
+
+	#[test]
+	fn test_hrmp_init() {
+		let mut expected_encoded: Vec<u8> = Vec::new();
+
+		let index = <polkadot_runtime::Runtime as frame_system::Config>::PalletInfo::index::<
+			polkadot_runtime::Hrmp,
+		>()
+		.unwrap() as u8;
+		expected_encoded.push(index);
+
+		let mut expected = polkadot_runtime_parachains::hrmp::Call::<
+			polkadot_runtime::Runtime
+		>::hrmp_init_open_channel {
+			recipient: 1000u32.into(),
+			proposed_max_capacity: 100u32,
+			proposed_max_message_size: 100u32,
+		}
+		.encode();
+		expected_encoded.append(&mut expected);
+
+		assert_eq!(
+			<PolkadotEncoder as HrmpEncodeCall>::hrmp_encode_call(
+				xcm_primitives::HrmpAvailableCalls::InitOpenChannel(1000u32.into(), 100u32, 100u32)
+			),
+			expected_encoded
+		);
+	}
+
+	#[test]
+	fn test_hrmp_accept() {
+		let mut expected_encoded: Vec<u8> = Vec::new();
+
+		let index = <polkadot_runtime::Runtime as frame_system::Config>::PalletInfo::index::<
+			polkadot_runtime::Hrmp,
+		>()
+		.unwrap() as u8;
+		expected_encoded.push(index);
+
+		let mut expected = polkadot_runtime_parachains::hrmp::Call::<
+			polkadot_runtime::Runtime
+		>::hrmp_accept_open_channel {
+			sender: 1000u32.into(),
+		}
+		.encode();
+		expected_encoded.append(&mut expected);
+
+		assert_eq!(
+			<PolkadotEncoder as HrmpEncodeCall>::hrmp_encode_call(
+				xcm_primitives::HrmpAvailableCalls::AcceptOpenChannel(1000u32.into())
+			),
+			expected_encoded
+		);
+	}
+
+	#[test]
+	fn test_hrmp_close() {
+		let mut expected_encoded: Vec<u8> = Vec::new();
+
+		let index = <polkadot_runtime::Runtime as frame_system::Config>::PalletInfo::index::<
+			polkadot_runtime::Hrmp,
+		>()
+		.unwrap() as u8;
+		expected_encoded.push(index);
+
+		let channel_id = HrmpChannelId {
+			sender: 1000u32.into(),
+			recipient: 1001u32.into(),
+		};
+
+		let mut expected = polkadot_runtime_parachains::hrmp::Call::<
+			polkadot_runtime::Runtime
+		>::hrmp_close_channel {
+			channel_id: channel_id.clone(),
+		}
+		.encode();
+		expected_encoded.append(&mut expected);
+
+		assert_eq!(
+			<PolkadotEncoder as HrmpEncodeCall>::hrmp_encode_call(
+				xcm_primitives::HrmpAvailableCalls::CloseChannel(channel_id)
+			),
+			expected_encoded
+		);
+	}
+
+	#[test]
+	fn test_payout_stakers() {
+		let mut expected_encoded: Vec<u8> = Vec::new();
+		let relay_account: AccountId32 = [1u8; 32].into();
+
+		let index = <polkadot_runtime::Runtime as frame_system::Config>::PalletInfo::index::<
+			polkadot_runtime::Staking,
+		>()
+		.unwrap() as u8;
+		expected_encoded.push(index);
+
+		let mut expected = pallet_staking::Call::<polkadot_runtime::Runtime>::payout_stakers {
+			validator_stash: relay_account.clone(),
+			era: 10u32,
+		}
+		.encode();
+		expected_encoded.append(&mut expected);
+
+		assert_eq!(
+			<PolkadotEncoder as StakeEncodeCall>::encode_call(
+				pallet_evm_precompile_relay_encoder::AvailableStakeCalls::PayoutStakers(
+					relay_account,
+					10u32
+				)
+			),
+			expected_encoded
+		);
+	}
+
+	#[test]
+	fn test_batch_all() {
+		let mut expected_encoded: Vec<u8> = Vec::new();
+		let index = <polkadot_runtime::Runtime as frame_system::Config>::PalletInfo::index::<
+			polkadot_runtime::Utility,
+		>()
+		.unwrap() as u8;
+		expected_encoded.push(index);
+
+		let chill = polkadot_runtime::Call::Staking(pallet_staking::Call::<
+			polkadot_runtime::Runtime,
+		>::chill {});
+		let rebond = polkadot_runtime::Call::Staking(pallet_staking::Call::<
+			polkadot_runtime::Runtime,
+		>::rebond {
+			value: 100u32.into(),
+		});
+		let mut expected = pallet_utility::Call::<polkadot_runtime::Runtime>::batch_all {
+			calls: vec![chill.clone(), rebond.clone()],
+		}
+		.encode();
+		expected_encoded.append(&mut expected);
+
+		assert_eq!(
+			<PolkadotEncoder as UtilityEncodeCall>::utility_encode_call(
+				pallet_evm_precompile_relay_encoder::AvailableUtilityCalls::BatchAll(vec![
+					chill.encode(),
+					rebond.encode()
+				])
+			),
+			expected_encoded
+		);
+	}
+
+	#[test]
+	fn test_utility_as_derivative() {
+		let mut expected_encoded: Vec<u8> = Vec::new();
+		let index = <polkadot_runtime::Runtime as frame_system::Config>::PalletInfo::index::<
+			polkadot_runtime::Utility,
+		>()
+		.unwrap() as u8;
+		expected_encoded.push(index);
+
+		let chill = polkadot_runtime::Call::Staking(pallet_staking::Call::<
+			polkadot_runtime::Runtime,
+		>::chill {});
+		let mut expected = pallet_utility::Call::<polkadot_runtime::Runtime>::as_derivative {
+			index: 1,
+			call: chill.clone().into(),
+		}
+		.encode();
+		expected_encoded.append(&mut expected);
+
+		assert_eq!(
+			<PolkadotEncoder as UtilityEncodeCall>::utility_encode_call(
+				pallet_evm_precompile_relay_encoder::AvailableUtilityCalls::AsDerivative(
+					1,
+					chill.encode()
+				)
+			),
+			expected_encoded
+		);
+	}
+
+	#[test]
+	fn test_add_proxy() {
+		let mut expected_encoded: Vec<u8> = Vec::new();
+		let relay_account: AccountId32 = [1u8; 32].into();
+		let index = <polkadot_runtime::Runtime as frame_system::Config>::PalletInfo::index::<
+			polkadot_runtime::Proxy,
+		>()
+		.unwrap() as u8;
+		expected_encoded.push(index);
+
+		let mut expected = pallet_proxy::Call::<polkadot_runtime::Runtime>::add_proxy {
+			delegate: relay_account.clone(),
+			proxy_type: polkadot_runtime::ProxyType::Staking,
+			delay: 0u32,
+		}
+		.encode();
+		expected_encoded.append(&mut expected);
+
+		assert_eq!(
+			<PolkadotEncoder as ProxyEncodeCall>::proxy_encode_call(
+				pallet_evm_precompile_relay_encoder::AvailableProxyCalls::AddProxy(
+					relay_account,
+					polkadot_runtime::ProxyType::Staking.encode()[0],
+					0u32
+				)
+			),
+			expected_encoded
+		);
+	}
+
+	#[test]
+	fn test_proxy() {
+		let mut expected_encoded: Vec<u8> = Vec::new();
+		let relay_account: AccountId32 = [1u8; 32].into();
+		let index = <polkadot_runtime::Runtime as frame_system::Config>::PalletInfo::index::<
+			polkadot_runtime::Proxy,
+		>()
+		.unwrap() as u8;
+		expected_encoded.push(index);
+
+		let chill = polkadot_runtime::Call::Staking(pallet_staking::Call::<
+			polkadot_runtime::Runtime,
+		>::chill {});
+		let mut expected = pallet_proxy::Call::<polkadot_runtime::Runtime>::proxy {
+			real: relay_account.clone(),
+			force_proxy_type: Some(polkadot_runtime::ProxyType::Staking),
+			call: chill.clone().into(),
+		}
+		.encode();
+		expected_encoded.append(&mut expected);
+
+		assert_eq!(
+			<PolkadotEncoder as ProxyEncodeCall>::proxy_encode_call(
+				pallet_evm_precompile_relay_encoder::AvailableProxyCalls::Proxy(
+					relay_account,
+					Some(polkadot_runtime::ProxyType::Staking.encode()[0]),
+					chill.encode()
+				)
+			),
+			expected_encoded
+		);
+	}
+
+	#[test]
+	fn test_pool_calls_are_not_encoded() {
+		assert_eq!(
+			<PolkadotEncoder as NominationPoolsEncodeCall>::nomination_pools_encode_call(
+				pallet_evm_precompile_relay_encoder::AvailableNominationPoolsCalls::ClaimPayout
+			),
+			None
+		);
+	}
 }
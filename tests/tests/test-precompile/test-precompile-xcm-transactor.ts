@@ -33,7 +33,7 @@ const XCM_TRANSACTOR_INTERFACE_V2 = new ethers.utils.Interface(
 const registerXcmTransactorAndContract = async (context: DevTestContext) => {
   await context.createBlock(
     context.polkadotApi.tx.sudo.sudo(
-      context.polkadotApi.tx.xcmTransactor.register(alith.address, 0)
+      context.polkadotApi.tx.xcmTransactor.register(alith.address, RELAY_V1_SOURCE_LOCATION, 0)
     )
   );
 
@@ -64,7 +64,7 @@ const registerXcmTransactorAndContract = async (context: DevTestContext) => {
 const registerXcmTransactorDerivativeIndex = async (context: DevTestContext) => {
   await context.createBlock(
     context.polkadotApi.tx.sudo.sudo(
-      context.polkadotApi.tx.xcmTransactor.register(alith.address, 0)
+      context.polkadotApi.tx.xcmTransactor.register(alith.address, RELAY_V1_SOURCE_LOCATION, 0)
     )
   );
 };
@@ -3,6 +3,7 @@ import "@moonbeam-network/api-augment";
 import { expect } from "chai";
 
 import { alith } from "../../util/accounts";
+import { RELAY_V1_SOURCE_LOCATION } from "../../util/assets";
 import { describeDevMoonbeam } from "../../util/setup-dev-tests";
 
 describeDevMoonbeam("Precompiles - xcm transactor", (context) => {
@@ -10,13 +11,16 @@ describeDevMoonbeam("Precompiles - xcm transactor", (context) => {
     // register index 0 for Alith
     await context.createBlock(
       context.polkadotApi.tx.sudo.sudo(
-        context.polkadotApi.tx.xcmTransactor.register(alith.address, 0)
+        context.polkadotApi.tx.xcmTransactor.register(alith.address, RELAY_V1_SOURCE_LOCATION, 0)
       )
     );
   });
 
   it("allows to retrieve index through precompiles", async function () {
-    const resp = await context.polkadotApi.query.xcmTransactor.indexToAccount(0);
+    const resp = await context.polkadotApi.query.xcmTransactor.destinationIndexToAccount(
+      { parents: 1, interior: "Here" },
+      0
+    );
     expect(resp.toString()).to.eq(alith.address);
   });
 });